@@ -16,17 +16,42 @@ func keyComboString(_ targetKey: UInt16, _ ctrl: Bool, _ alt: Bool, _ cmd: Bool,
     return s
 }
 
+/// "⌃⌥J"-style label for a global hotkey trigger (macOS menu glyph order).
+func hotkeyLabel(_ key: UInt16, _ ctrl: Bool, _ alt: Bool, _ cmd: Bool, _ shift: Bool) -> String {
+    (ctrl ? "⌃" : "") + (alt ? "⌥" : "") + (shift ? "⇧" : "") + (cmd ? "⌘" : "") + keyCodeDisplay(key)
+}
+
+/// "Caps+Ctrl+Shift+H": a Caps chord with its extra modifiers, `keyName`
+/// already rendered (log names or UI glyphs, depending on the caller).
+func capsChordLabel(_ keyName: String, shift: Bool, ctrl: Bool = false, alt: Bool = false, cmd: Bool = false) -> String {
+    "Caps+" + (ctrl ? "Ctrl+" : "") + (alt ? "Opt+" : "") + (cmd ? "Cmd+" : "") + (shift ? "Shift+" : "") + keyName
+}
+
 func describeAction(_ action: ActionConfig) -> String {
     switch action {
     case .keyCombo(let k, let ctrl, let alt, let cmd, let shift):
         return keyComboString(k, ctrl, alt, cmd, shift)
     case .directional(let a): return "directional \(a.rawValue)"
-    case .jump(let dir, let count): return "jump \(dir.rawValue) x\(count)"
+    case .jump(let dir, let count, let unit):
+        switch unit {
+        case .line: return "jump \(dir.rawValue) x\(count)"
+        case .page: return "jump \(dir.rawValue) \(count) page(s)"
+        case .paragraph: return "jump \(dir.rawValue) \(count) paragraph(s)"
+        }
     case .independent(let a): return "independent \(a.rawValue)"
     case .inputSource(let id): return "input source \(id)"
-    case .command(let cmd): return "command: \(cmd)"
+    case .command(let cmd, let capture): return "command\(capture ? " (capture)" : ""): \(cmd)"
     case .openApp(let bid, let name): return "open app \(name) (\(bid))"
     case .modifierKey(let m): return "hold modifier \(m.rawValue)"
+    case .wrapPair(let open, let close, let spaces): return "wrap pair \(open)…\(close) (spaces=\(spaces))"
+    case .appControl(let cmd): return "app control \(cmd.rawValue)"
+    case .script(let lang, let source): return "\(lang.rawValue) script: \(source)"
+    case .notify(let title, let body): return "notify: \(title) — \(body)"
+    case .transformSelection(let op): return "transform selection \(op.rawValue)"
+    case .goTo(let line, let column, let shortcut):
+        let target = line.map { l in column.map { "\(l):\($0)" } ?? "\(l)" } ?? "(count)"
+        return "go to \(target) via \(keyComboString(shortcut.targetKey, shortcut.withCtrl, shortcut.withAlt, shortcut.withCmd, shortcut.withShift))"
+    case .insertDateTime(let format): return "insert date/time \(format)"
     }
 }
 
@@ -43,30 +68,68 @@ func hudParts(_ action: ActionConfig) -> (String, String) {
             .up: ("↑", "Move Up"), .down: ("↓", "Move Down"),
             .wordForward: ("⌥→", "Word Forward"), .wordBack: ("⌥←", "Word Back"),
             .home: ("↖", "Line Start"), .end: ("↘", "Line End"),
+            .docStart: ("⌘↑", "Document Start"), .docEnd: ("⌘↓", "Document End"),
         ]
         let (sym, name) = map[a]!
         return (sym, name)
-    case .jump(let dir, let count):
+    case .jump(let dir, let count, let unit):
+        if unit == .page {
+            let sym = dir == .up ? "⇞" : "⇟"
+            return ("\(sym)×\(count)", "Page \(dir.rawValue)")
+        }
+        if unit == .paragraph {
+            let sym = dir == .up ? "¶↑" : "¶↓"
+            return ("\(sym)×\(count)", "Paragraph \(dir.rawValue)")
+        }
         let sym = dir == .up ? "↑" : "↓"
         return ("\(sym)×\(count)", "Jump \(dir.rawValue)")
     case .independent(let a):
         let map: [IndependentActionKind: (String, String)] = [
             .backspace: ("⌫", "Backspace"), .nextLine: ("↵", "New Line"),
+            .openLineAbove: ("\u{21B0}", "Open Line Above"),
             .insertQuotes: ("\u{201C}\u{201D}", "Insert Quotes"),
             .toggleCapsLock: ("\u{21EA}", "Toggle Caps Lock"),
             .switchInputSource: ("\u{2328}", "Switch Input Source"),
             .noop: ("\u{2298}", "Do Nothing"),
+            .releaseModifiers: ("\u{238B}", "Release Modifiers"),
+            .repeatLast: ("\u{21BA}", "Repeat Last"),
+            .stickyShift: ("\u{21E7}", "Sticky Shift"),
         ]
         let (sym, name) = map[a]!
         return (sym, name)
     case .inputSource(let id):
         return ("\u{2328}", id)
-    case .command(let cmd):
+    case .command(let cmd, _):
         return ("Shell", cmd)
+    case .script(let lang, let source):
+        return (lang == .shell ? "Shell" : "AppleScript", source)
+    case .notify(let title, let body):
+        return ("Notify", title.isEmpty ? body : title)
+    case .transformSelection(let op):
+        let map: [SelectionTransform: (String, String)] = [
+            .uppercase: ("AA", "Uppercase Selection"), .lowercase: ("aa", "Lowercase Selection"),
+            .trim: ("\u{2702}", "Trim Selection"),
+        ]
+        let (sym, name) = map[op]!
+        return (sym, name)
+    case .goTo(let line, let column, _):
+        return (ActionExecutor.goToText(line: line, column: column) ?? "\u{2192}#", "Go to Line")
+    case .insertDateTime(let format):
+        return (DateTimeText.render(format, at: Date()) ?? format, "Insert Date/Time")
     case .openApp(_, let name):
         return ("App", name)
     case .modifierKey(let m):
         return (modifierHudLabel(m), "Hold Modifier")
+    case .wrapPair(let open, let close, _):
+        return ("\(open)\(close)", "Wrap Pair")
+    case .appControl(let cmd):
+        let map: [AppCommand: (String, String)] = [
+            .togglePause: ("\u{23EF}", "Toggle Pause"), .reloadConfig: ("\u{21BB}", "Reload Config"),
+            .showWindow: ("\u{2750}", "Show Window"), .quit: ("\u{23FB}", "Quit"),
+            .showPalette: ("\u{2630}", "Command Palette"),
+        ]
+        let (sym, name) = map[cmd]!
+        return (sym, name)
     }
 }
 
@@ -93,7 +156,8 @@ enum ActionExecutor {
     /// modifier intent).
     static func allowShiftFallback(_ action: ActionConfig) -> Bool {
         switch action {
-        case .inputSource, .command, .keyCombo, .openApp, .modifierKey: return false
+        case .inputSource, .command, .script, .notify, .keyCombo, .openApp, .modifierKey, .appControl,
+             .transformSelection, .goTo, .insertDateTime: return false
         case .independent(.noop): return false  // a disabled key shouldn't disable its shifted variant too
         default: return true
         }
@@ -110,39 +174,123 @@ enum ActionExecutor {
     /// the default `actionId`/inline; otherwise nil (caller decides
     /// swallow-vs-passthrough). An orphaned matching binding is skipped.
     static func effectiveAction(_ entry: ActionMappingEntry, _ ctx: RuntimeContext) -> ActionConfig? {
+        effectiveChoice(entry, ctx).action
+    }
+
+    /// `effectiveAction`, plus whether a per-app binding supplied it.
+    static func effectiveChoice(_ entry: ActionMappingEntry, _ ctx: RuntimeContext) -> (action: ActionConfig?, viaAppRule: Bool) {
         for binding in entry.bindings where binding.matches(ctx) {
-            if let cfg = ActionsRegistry.shared.resolve(binding) { return cfg }
+            if let cfg = ActionsRegistry.shared.resolve(binding) { return (cfg, true) }
+        }
+        return (ActionsRegistry.shared.resolve(entry), false)
+    }
+
+    /// One mapping as it behaves under a context: the action it runs (nil =
+    /// swallowed), whether a per-app rule chose it, and whether Caps+key is on
+    /// the passthrough list there (the key then reaches the app untouched).
+    struct EffectiveMapping: Equatable {
+        var entry: ActionMappingEntry
+        var action: ActionConfig?
+        var viaAppRule: Bool
+        var passthrough: Bool
+    }
+
+    /// Every mapping resolved under `ctx` with the same rules the hook applies
+    /// per key, for "what's active in this app?" (the socket's
+    /// `get_effective_mappings`). App exclusion and pause are the caller's to
+    /// report: they bypass mappings wholesale.
+    static func effectiveMappings(_ ctx: RuntimeContext,
+                                  passthrough: [PassthroughKey] = EngineState.shared.passthroughKeys) -> [EffectiveMapping] {
+        MappingsRegistry.shared.withMappings { mappings in
+            mappings.filter(\.enabled).map { entry in
+                let choice = effectiveChoice(entry, ctx)
+                var passes = false
+                if case .hyperPlusKey(let key, _, _, _, _) = entry.trigger { passes = isPassthrough(key, ctx, passthrough) }
+                return EffectiveMapping(entry: entry, action: choice.action, viaAppRule: choice.viaAppRule, passthrough: passes)
+            }
         }
-        return ActionsRegistry.shared.resolve(entry)
+    }
+
+    /// True when Caps+`jsKeycode` is on the passthrough list under `ctx`.
+    static func isPassthrough(_ jsKeycode: UInt16, _ ctx: RuntimeContext,
+                              _ keys: [PassthroughKey] = EngineState.shared.passthroughKeys) -> Bool {
+        keys.contains { $0.key == jsKeycode && $0.applies(ctx) }
+    }
+
+    /// The Ctrl/Option/Cmd a Caps chord is bound with, as event flags. The
+    /// trigger consumes them, so they're stripped before its action runs.
+    static func capsChordModifiers(_ trigger: Trigger) -> CGEventFlags {
+        guard case .hyperPlusKey(_, _, let ctrl, let alt, let cmd) = trigger else { return [] }
+        return hotkeyFlags(ctrl: ctrl, alt: alt, cmd: cmd, shift: false)
+    }
+
+    private static func isCapsChord(_ trigger: Trigger, key: UInt16, shift: Bool, modifiers: CGEventFlags) -> Bool {
+        guard case .hyperPlusKey(let k, let withShift, _, _, _) = trigger else { return false }
+        return k == key && withShift == shift && capsChordModifiers(trigger) == modifiers
     }
 
     /// Stage 1: find the trigger group for a Caps+key chord, applying the
     /// shift-fallback — Caps+Shift+K with no exact group falls back to the
-    /// Caps+K group when *its effective action under `ctx`* allows it.
-    static func resolveEntry(jsKeycode: UInt16, shiftHeld: Bool, ctx: RuntimeContext) -> ActionMappingEntry? {
-        MappingsRegistry.shared.withMappings { mappings in
-            if let exact = mappings.first(where: {
-                if case .hyperPlusKey(let key, let withShift) = $0.trigger {
-                    return key == jsKeycode && withShift == shiftHeld
+    /// Caps+K group when *its effective action under `ctx`* allows it. With
+    /// `shiftLayers == .strict` there is no fallback: the layers are separate.
+    /// Held Ctrl/Option/Cmd first look for a chord bound with exactly those;
+    /// failing that, the plain chord takes the press (and gets them forwarded).
+    /// A passthrough key resolves to nothing, whatever is bound to it, and so
+    /// does a chord whose exact entry is switched off — no fallback for it.
+    static func resolveEntry(jsKeycode: UInt16, shiftHeld: Bool, modifiers: CGEventFlags = [], ctx: RuntimeContext,
+                             shiftLayers: ShiftLayerMode = EngineState.shared.shiftLayerMode,
+                             passthrough: [PassthroughKey] = EngineState.shared.passthroughKeys) -> ActionMappingEntry? {
+        guard !isPassthrough(jsKeycode, ctx, passthrough) else { return nil }
+        let held = modifiers.intersection([.maskControl, .maskAlternate, .maskCommand])
+        return MappingsRegistry.shared.withMappings { mappings in
+            var switchedOff = false
+            func find(_ chordModifiers: CGEventFlags) -> ActionMappingEntry? {
+                if let exact = mappings.first(where: {
+                    isCapsChord($0.trigger, key: jsKeycode, shift: shiftHeld, modifiers: chordModifiers)
+                }) {
+                    switchedOff = !exact.enabled
+                    return exact.enabled ? exact : nil
                 }
-                return false
-            }) { return exact }
-
-            if shiftHeld {
-                if let fallback = mappings.first(where: { entry in
-                    guard case .hyperPlusKey(let key, let withShift) = entry.trigger,
-                          key == jsKeycode, withShift == false,
+                guard shiftHeld && shiftLayers == .fallback else { return nil }
+                return mappings.first { entry in
+                    guard entry.enabled, isCapsChord(entry.trigger, key: jsKeycode, shift: false, modifiers: chordModifiers),
                           let cfg = effectiveAction(entry, ctx) else { return false }
                     return allowShiftFallback(cfg)
-                }) { return fallback }
+                }
+            }
+            if !held.isEmpty {
+                if let modified = find(held) { return modified }
+                if switchedOff { return nil }
             }
-            return nil
+            return find([])
         }
     }
 
+    /// What a fresh Caps+key press resolves to: the matched entry, the action
+    /// it runs under `ctx` (nil = swallowed), and whether it was reached by the
+    /// Shift fallback — the action then runs with Shift held.
+    struct ChordResolution: Equatable {
+        let entry: ActionMappingEntry
+        let action: ActionConfig?
+        let viaShiftFallback: Bool
+    }
+
+    /// Stages 1 and 2 together — exactly what the hook runs on a fresh press,
+    /// so the editor's "what does this key do" hint and the scripting socket's
+    /// `resolve` can't disagree with it. Nil = not ours; the key passes through.
+    static func resolveChord(jsKeycode: UInt16, shiftHeld: Bool, modifiers: CGEventFlags = [], ctx: RuntimeContext,
+                             shiftLayers: ShiftLayerMode = EngineState.shared.shiftLayerMode,
+                             passthrough: [PassthroughKey] = EngineState.shared.passthroughKeys) -> ChordResolution? {
+        guard let entry = resolveEntry(jsKeycode: jsKeycode, shiftHeld: shiftHeld, modifiers: modifiers, ctx: ctx,
+                                       shiftLayers: shiftLayers, passthrough: passthrough) else { return nil }
+        var viaFallback = false
+        if case .hyperPlusKey(_, let withShift, _, _, _) = entry.trigger { viaFallback = shiftHeld && !withShift }
+        return ChordResolution(entry: entry, action: effectiveAction(entry, ctx), viaShiftFallback: viaFallback)
+    }
+
     static func findSingleTapAction(_ ctx: RuntimeContext) -> ActionConfig? {
         MappingsRegistry.shared.withMappings { m in
-            guard let entry = m.first(where: { if case .singleTapHyper = $0.trigger { return true }; return false })
+            guard let entry = m.first(where: { if case .singleTapHyper = $0.trigger { return $0.enabled }; return false })
             else { return nil }
             return effectiveAction(entry, ctx)
         }
@@ -150,33 +298,27 @@ enum ActionExecutor {
 
     static func findDoubleTapAction(_ ctx: RuntimeContext) -> ActionConfig? {
         MappingsRegistry.shared.withMappings { m in
-            guard let entry = m.first(where: { if case .doubleTapHyper = $0.trigger { return true }; return false })
+            guard let entry = m.first(where: { if case .doubleTapHyper = $0.trigger { return $0.enabled }; return false })
             else { return nil }
             return effectiveAction(entry, ctx)
         }
     }
 
-    static func execute(_ action: ActionConfig, keyDown: Bool, activeModifiers: CGEventFlags) {
+    /// Run `action`'s key-down or key-up half. `trigger` names the mapping that
+    /// fired it, for the `ActionResults` of kinds that can fail. Keys go to
+    /// `emitter` — the real event stream except in tests.
+    static func execute(_ action: ActionConfig, keyDown: Bool, activeModifiers: CGEventFlags, trigger: Trigger? = nil,
+                        emitter: KeyEmitter = LiveKeyEmitter()) {
+        if keyDown && isRepeatable(action) { EngineState.shared.lastAction = action }
         switch action {
         case .directional(let a):
-            switch a {
-            case .left: KeyPoster.post(KeyCodes.left, keyDown: keyDown, flags: activeModifiers)
-            case .right: KeyPoster.post(KeyCodes.right, keyDown: keyDown, flags: activeModifiers)
-            case .up: KeyPoster.post(KeyCodes.up, keyDown: keyDown, flags: activeModifiers)
-            case .down: KeyPoster.post(KeyCodes.down, keyDown: keyDown, flags: activeModifiers)
-            case .wordForward:
-                KeyPoster.post(KeyCodes.right, keyDown: keyDown, flags: activeModifiers.union(.maskAlternate))
-            case .wordBack:
-                KeyPoster.post(KeyCodes.left, keyDown: keyDown, flags: activeModifiers.union(.maskAlternate))
-            case .home:
-                KeyPoster.post(KeyCodes.left, keyDown: keyDown, flags: activeModifiers.union(.maskCommand))
-            case .end:
-                KeyPoster.post(KeyCodes.right, keyDown: keyDown, flags: activeModifiers.union(.maskCommand))
-            }
-        case .jump(let direction, let count):
+            let stroke = directionalKeystroke(a, activeModifiers: activeModifiers,
+                                              wordMotion: EngineState.shared.wordMotionStyle)
+            emitter.post(stroke.keycode, keyDown: keyDown, flags: stroke.flags)
+        case .jump(let direction, let count, let unit):
             if keyDown && count > 0 {
-                let kc = direction == .up ? KeyCodes.up : KeyCodes.down
-                for _ in 0..<count { KeyPoster.postTap(kc, flags: activeModifiers) }
+                let stroke = jumpKeystroke(direction, unit: unit, activeModifiers: activeModifiers)
+                PostQueue.enqueue { emitter.postTaps(stroke.keycode, count: count, flags: stroke.flags) }
             }
         case .independent(let a):
             // Modifier-forwarding decision is per sub-action (not a blanket win):
@@ -184,45 +326,82 @@ enum ActionExecutor {
             //    cleanly (Option+Delete = delete word).
             //  - .nextLine: NO forward — a fixed compound macro (go to line end +
             //    Return); a stray held Cmd/Option has no coherent meaning here and
-            //    could corrupt either step.
+            //    could corrupt either step. Same for .openLineAbove.
             //  - .insertQuotes: NO forward — emits literal text + cursor taps, not a
             //    modified keystroke; forwarding would be meaningless / harmful.
             //  - .toggleCapsLock / .switchInputSource / .noop: no target key.
+            //  - .releaseModifiers: NO forward — its whole point is clearing them.
+            //  - .repeatLast: NO forward — it replays a finished action as a tap.
+            //  - .stickyShift: posts nothing; it latches Shift for later motions.
             switch a {
             case .backspace:
-                KeyPoster.post(KeyCodes.delete, keyDown: keyDown, flags: activeModifiers)
+                emitter.post(KeyCodes.delete, keyDown: keyDown, flags: activeModifiers)
             case .nextLine:
                 if keyDown {
-                    KeyPoster.postTap(KeyCodes.right, flags: .maskCommand)
-                    KeyPoster.postTap(KeyCodes.return, flags: [])
+                    let edge = lineEdgeKeystroke(end: true, style: EngineState.shared.lineEdgeStyle)
+                    PostQueue.enqueue {
+                        emitter.postTap(edge.keycode, flags: edge.flags)
+                        emitter.postTap(KeyCodes.return, flags: [])
+                    }
                 }
-            case .insertQuotes:
+            case .openLineAbove:
+                // Line start + Return pushes the line down; Up lands on the new
+                // empty line above it.
                 if keyDown {
-                    for _ in 0..<6 { KeyPoster.insertString("\"") }
-                    for _ in 0..<3 { KeyPoster.postTap(KeyCodes.left, flags: []) }
+                    let edge = lineEdgeKeystroke(end: false, style: EngineState.shared.lineEdgeStyle)
+                    PostQueue.enqueue {
+                        emitter.postTap(edge.keycode, flags: edge.flags)
+                        emitter.postTap(KeyCodes.return, flags: [])
+                        emitter.postTap(KeyCodes.up, flags: [])
+                    }
                 }
+            case .insertQuotes:
+                if keyDown { typeWrapPair(open: "\"\"\"", close: "\"\"\"", innerSpaces: 0, emitter: emitter) }
             case .toggleCapsLock:
                 if keyDown { _ = toggleCapsLock() }
+            case .releaseModifiers:
+                if keyDown { releaseAllModifiers() }
+            case .repeatLast:
+                // Straight back through execute, so an input-source switch still
+                // goes through its repeat suppression and a command is logged.
+                guard keyDown else { break }
+                guard let last = EngineState.shared.lastAction else {
+                    FileLog.shared.info("Repeat last: nothing has fired yet.")
+                    break
+                }
+                execute(last, keyDown: true, activeModifiers: [], emitter: emitter)
+                execute(last, keyDown: false, activeModifiers: [], emitter: emitter)
+            case .stickyShift:
+                if keyDown {
+                    let latched = EngineState.shared.toggleStickyShift(now: nowMillis())
+                    FileLog.shared.info("Sticky Shift \(latched ? "latched" : "released").")
+                }
             case .switchInputSource, .noop:
                 break   // intentionally does nothing (the chord is still swallowed).
                         // `.switchInputSource` is a retired tombstone — see ActionModel.swift.
             }
         case .inputSource(let id):
-            if keyDown { InputSourceController.queueSwitch(toID: id) }
-        case .command(let cmd):
             if keyDown {
-                FileLog.shared.info("Shell mapping triggered: command=\(cmd)")
-                DispatchQueue.global().async {
-                    let proc = Process()
-                    proc.executableURL = URL(fileURLWithPath: "/bin/sh")
-                    proc.arguments = ["-c", cmd]
-                    do { try proc.run() }
-                    catch { FileLog.shared.error("Failed to spawn shell mapping: \(error.localizedDescription)") }
+                InputSourceController.queueSwitch(toID: id) { error in ActionResults.report(trigger, action, error: error) }
+            }
+        case .command(let cmd, let capture):
+            if keyDown {
+                FileLog.shared.info("Shell mapping triggered: command=\(cmd)\(capture ? " (capturing output)" : "")")
+                if capture {
+                    runCapturing(scriptInvocation(.shell, cmd), trigger: trigger, action: action)
+                } else {
+                    spawn(scriptInvocation(.shell, cmd), trigger: trigger, action: action)
                 }
             }
+        case .script(let lang, let source):
+            if keyDown {
+                FileLog.shared.info("Script mapping triggered (\(lang.rawValue)): \(source)")
+                spawn(scriptInvocation(lang, source), trigger: trigger, action: action)
+            }
         case .keyCombo(let targetKey, let ctrl, let alt, let cmd, let shift):
             guard let mac = KeyCodes.jsToMac(targetKey) else {
                 FileLog.shared.warn("KeyCombo: unknown JS keycode \(targetKey), cannot map to macOS")
+                if keyDown { ActionResults.report(trigger, action, error: "Unknown key code \(targetKey)") }
                 return
             }
             // Forward the user's live-held modifiers so a held Shift/Option/etc.
@@ -234,7 +413,7 @@ enum ActionExecutor {
             if alt { flags.insert(.maskAlternate) }
             if cmd { flags.insert(.maskCommand) }
             if shift { flags.insert(.maskShift) }
-            KeyPoster.post(mac, keyDown: keyDown, flags: flags)
+            emitter.post(mac, keyDown: keyDown, flags: flags)
         case .openApp(let bundleID, _):
             if keyDown {
                 FileLog.shared.info("Open-app mapping triggered: bundleID=\(bundleID)")
@@ -243,12 +422,14 @@ enum ActionExecutor {
                 DispatchQueue.global().async {
                     guard let url = NSWorkspace.shared.urlForApplication(withBundleIdentifier: bundleID) else {
                         FileLog.shared.error("Open-app: no application found for bundle id \(bundleID)")
+                        ActionResults.report(trigger, action, error: "No application found for \(bundleID)")
                         return
                     }
                     let cfg = NSWorkspace.OpenConfiguration()
                     cfg.activates = true
                     NSWorkspace.shared.openApplication(at: url, configuration: cfg) { _, error in
                         if let error { FileLog.shared.error("Open-app failed for \(bundleID): \(error.localizedDescription)") }
+                        ActionResults.report(trigger, action, error: error?.localizedDescription)
                     }
                 }
             }
@@ -261,13 +442,287 @@ enum ActionExecutor {
             // handleCapsRemap), `[]` is the true post-release state, never a
             // desync. `.fn` has no synthesizable keycode, so it's a no-op.
             if let (kc, flag) = KeyCodes.modifierKeyAndFlag(m) {
-                KeyPoster.post(kc, keyDown: keyDown, flags: keyDown ? flag : [])
+                emitter.post(kc, keyDown: keyDown, flags: keyDown ? flag : [])
             }
             // Dismiss the until-dismissed HUD exactly when the modifier is released. Every
             // release path (normal chord key-up AND releaseAllInFlightChords)
             // funnels through this `keyDown == false` call, so HUD visibility is
             // perfectly aligned with how long the modifier is actually held.
             if !keyDown { HudCenter.shared.dismiss() }
+        case .wrapPair(let open, let close, let spaces):
+            // Like .insertQuotes: literal text + cursor taps, no modifier forwarding.
+            if keyDown { typeWrapPair(open: open, close: close, innerSpaces: spaces, emitter: emitter) }
+        case .appControl(let cmd):
+            if keyDown { AppCommands.post(cmd) }
+        case .notify(let title, let body):
+            // Notifications belong to the app side; hop off the tap thread.
+            if keyDown {
+                DispatchQueue.main.async {
+                    Notifier.shared.post(title: title, body: body, trigger: trigger, action: action)
+                }
+            }
+        case .transformSelection(let op):
+            // AX calls wait on the focused app; keep them off the tap thread.
+            if keyDown {
+                DispatchQueue.global(qos: .userInitiated).async { SelectionEditor.apply(op) }
+            }
+        case .insertDateTime(let format):
+            // The time of the press, typed like a wrap pair: one character per event.
+            guard keyDown else { break }
+            guard let text = DateTimeText.render(format, at: Date()) else {
+                FileLog.shared.warn("Insert date/time: format \"\(format)\" produced no text")
+                ActionResults.report(trigger, action, error: "Date format produced no text: \(format)")
+                return
+            }
+            PostQueue.enqueue {
+                for ch in text { emitter.insertString(String(ch)) }
+            }
+        case .goTo(let line, let column, let shortcut):
+            guard keyDown else { break }
+            guard let mac = KeyCodes.jsToMac(shortcut.targetKey) else {
+                FileLog.shared.warn("Go to: unknown JS keycode \(shortcut.targetKey), cannot map to macOS")
+                ActionResults.report(trigger, action, error: "Unknown key code \(shortcut.targetKey)")
+                return
+            }
+            var flags: CGEventFlags = []
+            if shortcut.withCtrl { flags.insert(.maskControl) }
+            if shortcut.withAlt { flags.insert(.maskAlternate) }
+            if shortcut.withCmd { flags.insert(.maskCommand) }
+            if shortcut.withShift { flags.insert(.maskShift) }
+            // No line and no count: open the field and leave the typing to the user.
+            let text = goToText(line: line, column: column)
+            PostQueue.enqueue {
+                emitter.postTap(mac, flags: flags)
+                guard let text else { return }
+                // Give the field time to open before typing into it; a budgeted
+                // pause like the injection delay (see `PostQueue.pause`).
+                PostQueue.pause(goToFieldDelayMs)
+                for ch in text { emitter.insertString(String(ch)) }
+                emitter.postTap(KeyCodes.return, flags: [])
+            }
+        }
+    }
+
+    /// Whether `.repeatLast` may replay `action`. Not itself (it would loop),
+    /// not a held modifier (replayed as a tap it would hold nothing), and not
+    /// a no-op or Sticky Shift, so neither wipes what there is to repeat.
+    static func isRepeatable(_ action: ActionConfig) -> Bool {
+        switch action {
+        case .independent(.repeatLast), .independent(.noop), .independent(.stickyShift), .modifierKey: return false
+        default: return true
+        }
+    }
+
+    /// The keystroke a directional action posts. Held modifiers are forwarded
+    /// on top of the action's own, which is what makes the Shift fallback
+    /// select: Caps+Shift+H borrows Caps+H and posts Shift+Left.
+    static func directionalKeystroke(_ a: DirectionalActionKind, activeModifiers: CGEventFlags,
+                                     wordMotion: WordMotionStyle) -> (keycode: UInt16, flags: CGEventFlags) {
+        switch a {
+        case .left: return (KeyCodes.left, activeModifiers)
+        case .right: return (KeyCodes.right, activeModifiers)
+        case .up: return (KeyCodes.up, activeModifiers)
+        case .down: return (KeyCodes.down, activeModifiers)
+        case .wordForward: return (KeyCodes.right, activeModifiers.union(wordMotionFlags(wordMotion)))
+        case .wordBack: return (KeyCodes.left, activeModifiers.union(wordMotionFlags(wordMotion)))
+        case .home: return (KeyCodes.left, activeModifiers.union(.maskCommand))
+        case .end: return (KeyCodes.right, activeModifiers.union(.maskCommand))
+        case .docStart: return (KeyCodes.up, activeModifiers.union(.maskCommand))
+        case .docEnd: return (KeyCodes.down, activeModifiers.union(.maskCommand))
+        }
+    }
+
+    /// The key one jump step taps.
+    static func jumpKeystroke(_ direction: JumpDirection, unit: JumpUnit,
+                              activeModifiers: CGEventFlags) -> (keycode: UInt16, flags: CGEventFlags) {
+        switch unit {
+        case .line: return (direction == .up ? KeyCodes.up : KeyCodes.down, activeModifiers)
+        case .page: return (direction == .up ? KeyCodes.pageUp : KeyCodes.pageDown, activeModifiers)
+        case .paragraph:
+            // Option+Up/Down: the Cocoa text system's paragraph move.
+            return (direction == .up ? KeyCodes.up : KeyCodes.down, activeModifiers.union(.maskAlternate))
+        }
+    }
+
+    /// The tap that moves to the line's end (or start) before New Line / Open
+    /// Line Above press Return.
+    static func lineEdgeKeystroke(end: Bool, style: LineEdgeStyle) -> (keycode: UInt16, flags: CGEventFlags) {
+        switch style {
+        case .visual: return (end ? KeyCodes.right : KeyCodes.left, .maskCommand)
+        case .logical: return (end ? KeyCodes.e : KeyCodes.a, .maskControl)
+        }
+    }
+
+    /// Interpreter and arguments that run `source` as `lang`. The source is one
+    /// argument either way, so nothing in it needs quoting.
+    static func scriptInvocation(_ lang: ScriptLanguage, _ source: String) -> (executable: String, arguments: [String]) {
+        switch lang {
+        case .shell: return ("/bin/sh", ["-c", source])
+        case .applescript: return ("/usr/bin/osascript", ["-e", source])
+        }
+    }
+
+    /// Start a command/script process off the tap thread and report whether it
+    /// spawned. Fire-and-forget: its exit status isn't awaited.
+    private static func spawn(_ invocation: (executable: String, arguments: [String]), trigger: Trigger?,
+                              action: ActionConfig) {
+        DispatchQueue.global().async {
+            let proc = Process()
+            proc.executableURL = URL(fileURLWithPath: invocation.executable)
+            proc.arguments = invocation.arguments
+            do {
+                try proc.run()
+                ActionResults.report(trigger, action, error: nil)
+            } catch {
+                FileLog.shared.error("Failed to spawn \(action.kindTag) mapping: \(error.localizedDescription)")
+                ActionResults.report(trigger, action, error: error.localizedDescription)
+            }
+        }
+    }
+
+    /// Per-stream cap on captured command output; the rest is read and dropped
+    /// so the child never blocks on a full pipe.
+    static let maxCapturedBytes = 16 * 1024
+
+    /// A `capture` command still running after this many seconds is terminated
+    /// and reported as timed out, so a hung command can't pin a thread forever.
+    static let captureTimeout: TimeInterval = 30
+
+    /// What a captured run left behind. A timed-out run was terminated, so its
+    /// status is the signal's rather than the command's.
+    struct CapturedRun {
+        var stdout: Data
+        var stderr: Data
+        var status: Int32
+        var timedOut: Bool
+    }
+
+    /// Run a `capture` command to completion on a background queue, then report
+    /// its stdout and exit status (a non-zero exit or a timeout is a failure
+    /// carrying stderr) and show the output's first line on the HUD.
+    private static func runCapturing(_ invocation: (executable: String, arguments: [String]), trigger: Trigger?,
+                                     action: ActionConfig) {
+        DispatchQueue.global().async {
+            let run: CapturedRun
+            do {
+                run = try capture(invocation)
+            } catch {
+                FileLog.shared.error("Failed to spawn command mapping: \(error.localizedDescription)")
+                ActionResults.report(trigger, action, error: error.localizedDescription)
+                return
+            }
+            let status = run.status
+            let stdout = String(decoding: run.stdout, as: UTF8.self).trimmingCharacters(in: .whitespacesAndNewlines)
+            let stderr = String(decoding: run.stderr, as: UTF8.self).trimmingCharacters(in: .whitespacesAndNewlines)
+            if run.timedOut {
+                FileLog.shared.warn("Command mapping timed out after \(Int(captureTimeout))s; terminated.")
+                ActionResults.report(ActionResult(triggerID: trigger.map(triggerUniqueID), kind: action.kindTag, ok: false,
+                                                  error: "Timed out after \(Int(captureTimeout))s", output: stdout))
+                HudCenter.shared.emit(trigger: "Shell", combo: "timeout", caption: stdout.split(separator: "\n").first.map(String.init) ?? "")
+                return
+            }
+            FileLog.shared.info("Command mapping exited \(status) (\(run.stdout.count)B stdout, \(run.stderr.count)B stderr)")
+            let ok = status == 0
+            ActionResults.report(ActionResult(triggerID: trigger.map(triggerUniqueID), kind: action.kindTag, ok: ok,
+                                              error: ok ? nil : (stderr.isEmpty ? "Exit status \(status)" : stderr),
+                                              output: stdout, exitCode: status))
+            if let line = stdout.split(separator: "\n").first {
+                HudCenter.shared.emit(trigger: "Shell", combo: ok ? "\u{2713}" : "exit \(status)", caption: String(line))
+            }
+        }
+    }
+
+    /// Run `invocation`, collecting both streams, and wait at most `timeout`
+    /// for it to exit and close them. Past that it's terminated (then killed
+    /// if it ignores SIGTERM), and whatever was read by then is returned.
+    /// Blocks the calling thread; throws only when the process can't start.
+    static func capture(_ invocation: (executable: String, arguments: [String]),
+                        timeout: TimeInterval = captureTimeout) throws -> CapturedRun {
+        let proc = Process()
+        proc.executableURL = URL(fileURLWithPath: invocation.executable)
+        proc.arguments = invocation.arguments
+        let out = Pipe(), err = Pipe()
+        proc.standardOutput = out
+        proc.standardError = err
+        let group = DispatchGroup()
+        group.enter()
+        proc.terminationHandler = { _ in group.leave() }
+        do {
+            try proc.run()
+        } catch {
+            group.leave()
+            throw error
+        }
+        // Drain both pipes at once: a chatty stderr must not stall stdout.
+        let outData = OSAllocatedUnfairLock(initialState: Data())
+        let errData = OSAllocatedUnfairLock(initialState: Data())
+        DispatchQueue.global().async(group: group) {
+            let data = drain(out.fileHandleForReading)
+            outData.withLock { $0 = data }
+        }
+        DispatchQueue.global().async(group: group) {
+            let data = drain(err.fileHandleForReading)
+            errData.withLock { $0 = data }
+        }
+        var timedOut = false
+        if group.wait(timeout: .now() + timeout) == .timedOut {
+            timedOut = true
+            proc.terminate()
+            // A child that outlives the shell may keep the pipes open; don't
+            // wait on it past a short grace.
+            if group.wait(timeout: .now() + 1) == .timedOut, proc.isRunning {
+                kill(proc.processIdentifier, SIGKILL)
+            }
+        }
+        proc.waitUntilExit()
+        return CapturedRun(stdout: outData.withLock { $0 }, stderr: errData.withLock { $0 },
+                           status: proc.terminationStatus, timedOut: timedOut)
+    }
+
+    /// Read `handle` to EOF, keeping at most `limit` bytes.
+    static func drain(_ handle: FileHandle, limit: Int = maxCapturedBytes) -> Data {
+        var kept = Data()
+        while true {
+            let chunk = handle.availableData
+            if chunk.isEmpty { return kept }
+            if kept.count < limit { kept.append(chunk.prefix(limit - kept.count)) }
+        }
+    }
+
+    /// Modifiers a word motion adds to its arrow key.
+    static func wordMotionFlags(_ style: WordMotionStyle) -> CGEventFlags {
+        switch style {
+        case .word: return .maskAlternate
+        case .subword: return [.maskControl, .maskAlternate]
+        }
+    }
+
+    /// What a wrap pair types and how far the caret then steps back: `open`,
+    /// `innerSpaces` spaces on each side of the caret, `close`. The step-back is
+    /// counted in Characters, which is what a Left arrow moves over in a text view.
+    static func wrapPairPlan(open: String, close: String, innerSpaces: Int) -> (text: String, leftArrows: Int) {
+        let pad = String(repeating: " ", count: max(innerSpaces, 0))
+        return (open + pad + pad + close, close.count + pad.count)
+    }
+
+    /// How long a go-to waits between its shortcut and the number, for the
+    /// editor's field to appear. Fits well inside a sequence's pause budget.
+    static let goToFieldDelayMs = 150
+
+    /// What a go-to types into the editor's field: `line`, or `line:column`
+    /// (the form Xcode, VS Code and Sublime Text all accept). Nil without a line.
+    static func goToText(line: Int?, column: Int?) -> String? {
+        guard let line else { return nil }
+        return column.map { "\(line):\($0)" } ?? "\(line)"
+    }
+
+    private static func typeWrapPair(open: String, close: String, innerSpaces: Int, emitter: KeyEmitter) {
+        let plan = wrapPairPlan(open: open, close: close, innerSpaces: innerSpaces)
+        // One character per event: a keyboard event carries a short Unicode string
+        // only, and per-character posts keep IMEs from regrouping the text.
+        PostQueue.enqueue {
+            for ch in plan.text { emitter.insertString(String(ch)) }
+            emitter.postTaps(KeyCodes.left, count: plan.leftArrows, flags: [])
         }
     }
 
@@ -281,7 +736,11 @@ enum ActionExecutor {
             FileLog.shared.error("toggle_caps_lock: could not read current CapsLock state; aborting toggle.")
             return false
         }
-        let newState = !current
+        let newState = CapsLockState.toggleTarget(from: current, pinnedOff: EngineState.shared.capsLockPinnedOff)
+        if newState == current {
+            FileLog.shared.info("toggle_caps_lock: Caps Lock is pinned off; leaving it off.")
+            return false
+        }
         if CapsLockState.set(newState) {
             FileLog.shared.info("CapsLock toggled via IOKit: previous_state=\(current) new_state=\(newState)")
             return true
@@ -302,18 +761,49 @@ enum ActionExecutor {
             if case .independent(.toggleCapsLock) = action {
                 return toggleCapsLock()
             }
-            execute(action, keyDown: true, activeModifiers: [])
+            execute(action, keyDown: true, activeModifiers: [], trigger: .singleTapHyper)
             execute(action, keyDown: false, activeModifiers: [])
             return false
+        } else if !EngineState.shared.capsToggleEnabled {
+            FileLog.shared.info("Caps short tap: Caps Lock toggle disabled in settings; doing nothing.")
+            return false
+        } else if EngineState.shared.layerKey != .capsLock {
+            // Caps Lock is still a real key then; a tap of the layer key shouldn't flip it.
+            FileLog.shared.info("Caps short tap: layer key is \(EngineState.shared.layerKey.rawValue), not Caps Lock; doing nothing.")
+            return false
         } else {
             return toggleCapsLock()
         }
     }
 
+    /// What releasing Caps means, given how long it was held and whether the
+    /// layer was used while it was down.
+    enum CapsRelease: Equatable {
+        /// A short tap: run the tap logic (`handleShortTap`).
+        case shortTap
+        /// Held past the tap threshold with nothing pressed: do nothing.
+        case suppress
+        /// The layer was used (a chord, sequence, raw escape): the release just
+        /// ends it, however short the hold.
+        case afterRemap
+    }
+
+    /// Classify a Caps release. A hold of exactly `thresholdMs` still counts as
+    /// a tap; a remap during the hold wins over any duration. Pure, for tests.
+    static func classifyCapsRelease(heldMs: UInt64, didRemap: Bool,
+                                    thresholdMs: UInt64 = EngineConstants.capsTapMaxMs) -> CapsRelease {
+        if didRemap { return .afterRemap }
+        return heldMs <= thresholdMs ? .shortTap : .suppress
+    }
+
     /// State machine for a confirmed short Caps tap (held ≤ capsTapMax, no remap).
     /// Defers the CapsLock toggle by `doubleTapWindow` when a DoubleTapHyper
     /// mapping exists, so a 2nd tap can convert it into the configured action.
     static func handleShortTap() {
+        if EngineState.shared.capsLayerMode.allowsOneShot {
+            handleOneShotTap()
+            return
+        }
         let now = nowMillis()
         let prevTap = EngineState.shared.swapLastTapAtMs(0)
         let dtAction = findDoubleTapAction(currentContext())
@@ -324,7 +814,7 @@ enum ActionExecutor {
             UsageStats.shared.record(triggerUniqueID(.doubleTapHyper))
             let (combo, caption) = hudParts(action)
             HudCenter.shared.emit(trigger: "Caps ×2", combo: combo, caption: caption)
-            execute(action, keyDown: true, activeModifiers: [])
+            execute(action, keyDown: true, activeModifiers: [], trigger: .doubleTapHyper)
             execute(action, keyDown: false, activeModifiers: [])
             return
         }
@@ -356,15 +846,59 @@ enum ActionExecutor {
         }
     }
 
+    /// Short tap with the one-shot layer on: arm it, or — if it's already
+    /// armed — treat this as the second tap. A quick second tap fires the
+    /// double-tap mapping when there is one; otherwise it just cancels. Never
+    /// toggles CapsLock (bind Toggle Caps Lock to a chord for that).
+    private static func handleOneShotTap() {
+        let now = nowMillis()
+        let armedAt = EngineState.shared.swapOneShotArmedAtMs(0)
+        if armedAt > 0, now &- armedAt <= EngineConstants.oneShotTimeoutMs {
+            if now &- armedAt <= EngineConstants.doubleTapWindowMs, let action = findDoubleTapAction(currentContext()) {
+                FileLog.shared.info("Caps(F18) DOUBLE-TAP detected (\(now &- armedAt)ms gap, one-shot). Firing action.")
+                UsageStats.shared.record(triggerUniqueID(.doubleTapHyper))
+                let (combo, caption) = hudParts(action)
+                HudCenter.shared.emit(trigger: "Caps ×2", combo: combo, caption: caption)
+                execute(action, keyDown: true, activeModifiers: [], trigger: .doubleTapHyper)
+                execute(action, keyDown: false, activeModifiers: [])
+            } else {
+                FileLog.shared.info("Caps(F18) tapped again; one-shot layer cancelled.")
+            }
+            return
+        }
+        _ = EngineState.shared.swapOneShotArmedAtMs(now)
+        FileLog.shared.info("Caps(F18) short tap; one-shot layer armed for the next key (\(EngineConstants.oneShotTimeoutMs)ms).")
+        HudCenter.shared.emit(trigger: "Caps", combo: "…", caption: "")
+    }
+
     // MARK: - Caps + key chord
 
     /// Action latched at key-DOWN so key-UP releases the SAME synthesized key
     /// even if the frontmost app changes mid-chord. With per-app bindings the
     /// resolved action is context-dependent, so re-resolving on key-up could
-    /// post the up of a *different* key (or none) and strand the down. The
-    /// stored value is `ActionConfig?`: a present entry means "we handled the
-    /// down" (nil inner = swallowed, no action posted); absent means we didn't.
-    private static let inFlightChord = OSAllocatedUnfairLock<[UInt16: ActionConfig?]>(initialState: [:])
+    /// post the up of a *different* key (or none) and strand the down. A present
+    /// entry means "we handled the down" (nil `action` = swallowed, no action
+    /// posted); absent means we didn't.
+    private static let inFlightChord = OSAllocatedUnfairLock<[UInt16: LatchedChord]>(initialState: [:])
+
+    /// One latched chord: the action posted at key-down and the mapping's
+    /// auto-repeat mode, both fixed for the whole hold.
+    private struct LatchedChord {
+        let action: ActionConfig?
+        var repeatMode: RepeatMode = .auto
+        /// Modifiers that were part of the trigger (a hotkey's ⌘⌥…, a Caps
+        /// chord's ⌃⌥⌘), stripped from the held modifiers forwarded on repeat /
+        /// key-up.
+        var triggerModifiers: CGEventFlags = []
+        /// Modifiers added on top of the held ones for the whole hold (a
+        /// latched Sticky Shift).
+        var addedModifiers: CGEventFlags = []
+
+        /// What the chord's repeats and key-up forward, given what's held now.
+        func forwarded(_ held: CGEventFlags) -> CGEventFlags {
+            held.subtracting(triggerModifiers).union(addedModifiers)
+        }
+    }
 
     /// Force-release every in-flight chord (post each latched action's key-up)
     /// and clear the latch. Called whenever a chord can no longer be ended the
@@ -375,7 +909,7 @@ enum ActionExecutor {
     /// you release Caps first" bug for ordinary chords. Idempotent.
     static func releaseAllInFlightChords() {
         let pending = inFlightChord.withLock { latch -> [ActionConfig] in
-            let actions = latch.values.compactMap { $0 }
+            let actions = latch.values.compactMap(\.action)
             latch.removeAll()
             return actions
         }
@@ -389,6 +923,26 @@ enum ActionExecutor {
     /// key-up for a modifier that isn't actually down is a harmless no-op. `.fn`
     /// has no synthesizable keycode and is skipped.
     static func normalizeSyntheticModifiersAtStartup() {
+        postModifierKeyUps()
+    }
+
+    /// The panic button (Caps+Esc by default, also in the tray menu): release
+    /// every latched chord — so a held `.modifierKey` lets go through its normal
+    /// path — then post a clean key-up for every modifier, which clears one
+    /// stuck by any injected combo. Idempotent and safe to run any time.
+    static func releaseAllModifiers() {
+        releaseAllInFlightChords()
+        releaseModifierKeys()
+    }
+
+    /// `releaseAllModifiers` without the chord half, for callers off the tap
+    /// thread: they release the chords via `KeyboardHook.releaseHeldChordsSerialized`.
+    static func releaseModifierKeys() {
+        postModifierKeyUps()
+        FileLog.shared.info("Released all modifiers (panic release).")
+    }
+
+    private static func postModifierKeyUps() {
         for m in ModifierKey.allCases {
             if let (kc, _) = KeyCodes.modifierKeyAndFlag(m) {
                 KeyPoster.post(kc, keyDown: false, flags: [])
@@ -396,6 +950,46 @@ enum ActionExecutor {
         }
     }
 
+    /// The flags to strip when a fresh Caps+key press should go through raw —
+    /// the escape modifier is held — or nil to handle it as a chord. A key
+    /// already latched as a chord (the modifier pressed mid-hold) stays a
+    /// chord until its key-up. Fn only counts on keys that don't carry the
+    /// Fn flag by themselves (arrows, F-keys, …).
+    static func rawEscapeFlags(keycode: UInt16, activeModifiers: CGEventFlags,
+                               escape: RawEscapeModifier = EngineState.shared.rawEscapeModifier) -> CGEventFlags? {
+        guard let flag = escape.flag, activeModifiers.contains(flag) else { return nil }
+        if escape == .fn && KeyCodes.implicitFnKeys.contains(keycode) { return nil }
+        if let js = KeyCodes.macToJs(keycode), inFlightChord.withLock({ $0[js] != nil }) { return nil }
+        return flag
+    }
+
+    /// The Shift a latched Sticky Shift adds to `action`: the motions (arrows,
+    /// word and line moves, jumps) extend the selection and keep it latched;
+    /// any other chord ends it. The Sticky Shift chord itself is left alone.
+    static func stickyShiftFlags(for action: ActionConfig?, now: UInt64 = nowMillis(),
+                                 state: EngineState = .shared) -> CGEventFlags {
+        switch action {
+        case .independent(.stickyShift)?:
+            return []
+        case .directional?, .jump?:
+            return state.useStickyShift(now: now) ? .maskShift : []
+        default:
+            state.clearStickyShift()
+            return []
+        }
+    }
+
+    /// Whether an OS auto-repeat of a held chord re-fires `action` under `mode`.
+    static func refiresOnRepeat(_ action: ActionConfig, _ mode: RepeatMode) -> Bool {
+        switch mode {
+        case .auto:
+            if case .inputSource = action { return false }
+            return !action.isHeldModifier
+        case .never: return false
+        case .onHold: return true
+        }
+    }
+
     /// Returns true if the chord was handled (and the original key should be
     /// swallowed). Logs a readable "Caps remap: <trigger> -> <action>" on keyDown.
     static func handleCapsRemap(keycode: UInt16, keyDown: Bool, activeModifiers: CGEventFlags) -> Bool {
@@ -406,7 +1000,9 @@ enum ActionExecutor {
         // pair up, regardless of any app switch in between.
         if !keyDown {
             if let latched = inFlightChord.withLock({ $0.removeValue(forKey: jsKeycode) }) {
-                if let action = latched { execute(action, keyDown: false, activeModifiers: activeModifiers) }
+                if let action = latched.action {
+                    execute(action, keyDown: false, activeModifiers: latched.forwarded(activeModifiers))
+                }
                 return true   // handled the down (executed or swallowed) → swallow the up too
             }
             return false       // we passed the down through → pass the up through
@@ -418,27 +1014,58 @@ enum ActionExecutor {
         if let cached = inFlightChord.withLock({ $0[jsKeycode] }) {
             // A held modifier is pressed once and held (real modifiers don't
             // autorepeat); re-posting its down on every OS repeat is wrong. Other
-            // actions re-fire normally.
-            if let action = cached, !action.isHeldModifier {
-                execute(action, keyDown: true, activeModifiers: activeModifiers)
+            // actions re-fire per the mapping's repeat mode.
+            if let action = cached.action, !action.isHeldModifier, refiresOnRepeat(action, cached.repeatMode) {
+                execute(action, keyDown: true, activeModifiers: cached.forwarded(activeModifiers))
             }
             return true   // already our chord (autorepeat) → swallow
         }
 
-        // Fresh press. Stage 1: trigger group. No group → not ours; pass through.
+        // Fresh press of a passthrough key: not ours at all — no mapping, no
+        // count digit, no sequence. The app gets Caps+key unchanged.
         let ctx = currentContext()
-        guard let mapping = resolveEntry(jsKeycode: jsKeycode, shiftHeld: shiftHeld, ctx: ctx) else { return false }
+        if isPassthrough(jsKeycode, ctx) { return false }
+
+        // Fresh press of a sequence leader: arm the pending sequence instead of
+        // firing a chord. Latch nil so autorepeat and the key-up are swallowed.
+        // Caps+Shift+<leader> keeps its normal chord resolution.
+        if !shiftHeld && KeySequences.shared.isLeader(jsKeycode) {
+            inFlightChord.withLock { $0[jsKeycode] = LatchedChord(action: nil) }
+            KeySequences.shared.begin(leader: jsKeycode)
+            FileLog.shared.info("Caps remap: Caps+\(KeyCodes.name(jsKeycode)) armed sequence — waiting for follow-up.")
+            HudCenter.shared.emit(trigger: "Caps+\(KeyCodes.name(jsKeycode))", combo: "…", caption: "")
+            return true
+        }
+
+        // Fresh press. Stage 1: trigger group. No group → not ours; pass through.
+        guard let resolved = resolveChord(jsKeycode: jsKeycode, shiftHeld: shiftHeld, modifiers: activeModifiers, ctx: ctx) else {
+            // An unbound Caps+digit feeds the count prefix for the next motion.
+            // Latch nil so its autorepeat and key-up are swallowed too.
+            guard !shiftHeld, let digit = CountPrefix.digit(jsKeycode),
+                  let count = EngineState.shared.pushCountDigit(digit, now: nowMillis()) else { return false }
+            inFlightChord.withLock { $0[jsKeycode] = LatchedChord(action: nil) }
+            HudCenter.shared.emit(trigger: "Caps+\(KeyCodes.name(jsKeycode))", combo: "\(count)×", caption: "")
+            return true
+        }
+        let mapping = resolved.entry
+        // A chord bound with Ctrl/Option/Cmd consumes them; a plain chord
+        // forwards whatever is held (Caps+Cmd+L = Cmd+→).
+        let triggerModifiers = capsChordModifiers(mapping.trigger)
+        let sticky = stickyShiftFlags(for: resolved.action)
+        let forwarded = activeModifiers.subtracting(triggerModifiers).union(sticky)
+        // Any configured chord spends the pending count; only motions use it.
+        let count = EngineState.shared.takeCountPrefix(now: nowMillis())
         // Usage stat: one count per fresh physical press of a configured chord.
         // Reached only on a fresh press — OS auto-repeat returns at the `cached`
         // branch above, so a held key counts once. Counts the trigger that fired
         // (the shift-fallback resolves `mapping` to the Caps+key it borrowed),
         // including swallow/no-op resolutions (the key is still configured).
         UsageStats.shared.record(triggerUniqueID(mapping.trigger))
-        // Stage 2: effective action under the frontmost app. Latch it. Use
-        // updateValue, not subscript-assign: for a `[Key: Optional]` dictionary,
-        // `dict[key] = nil` REMOVES the entry, but we need to store an explicit
-        // nil meaning "we handled the down by swallowing".
-        let action = effectiveAction(mapping, ctx)
+        // Stage 2 (resolved above): effective action under the frontmost app.
+        // Latch it (a nil action still latches, meaning "we handled the down by
+        // swallowing").
+        // A go-to without its own line takes the count as the line number.
+        let action = resolved.action.map { CountPrefix.fillingLine($0, count: count) }
         // Single hold-modifier at a time: if this chord wants to hold a modifier
         // but another hold-modifier chord is already active, neutralize it
         // (swallow, hold nothing) so two synthesized modifiers never fight over
@@ -446,11 +1073,12 @@ enum ActionExecutor {
         // so a concurrent fresh press can't slip a second modifier in between.
         let suppressedHeldModifier = inFlightChord.withLock { latch -> Bool in
             if let a = action, a.isHeldModifier,
-               latch.values.contains(where: { $0?.isHeldModifier ?? false }) {
-                latch.updateValue(nil, forKey: jsKeycode)   // claim the chord, post nothing
+               latch.values.contains(where: { $0.action?.isHeldModifier ?? false }) {
+                latch[jsKeycode] = LatchedChord(action: nil)   // claim the chord, post nothing
                 return true
             }
-            latch.updateValue(action, forKey: jsKeycode)
+            latch[jsKeycode] = LatchedChord(action: action, repeatMode: mapping.repeatMode,
+                                            triggerModifiers: triggerModifiers, addedModifiers: sticky)
             return false
         }
         if suppressedHeldModifier {
@@ -458,7 +1086,10 @@ enum ActionExecutor {
             return true
         }
 
-        let trigger = shiftHeld ? "Caps+Shift+\(KeyCodes.name(jsKeycode))" : "Caps+\(KeyCodes.name(jsKeycode))"
+        var trigger = capsChordLabel(KeyCodes.name(jsKeycode), shift: shiftHeld,
+                                     ctrl: triggerModifiers.contains(.maskControl),
+                                     alt: triggerModifiers.contains(.maskAlternate),
+                                     cmd: triggerModifiers.contains(.maskCommand))
         guard let action else {
             // Group matched but no applicable binding and no resolvable default.
             // The user claimed this chord → swallow it (no-op), do NOT pass the
@@ -471,6 +1102,12 @@ enum ActionExecutor {
             }
             return true
         }
+        // A count multiplies motions only; anything else (a command, a script,
+        // text) runs once, so Caps+999 can't fire a side effect 999 times.
+        let motion = CountPrefix.countedTaps(action, count: count, last: EngineState.shared.lastAction,
+                                             activeModifiers: forwarded, wordMotion: EngineState.shared.wordMotionStyle)
+        if motion != nil { trigger = "\(count)× " + trigger }
+        if !sticky.isEmpty { trigger = "\u{21E7} " + trigger }
         FileLog.shared.info("Caps remap: \(trigger) -> \(describeAction(action))")
         let (combo, caption) = hudParts(action)
         // A hold-modifier keeps the HUD up for the whole hold: show it
@@ -479,7 +1116,109 @@ enum ActionExecutor {
         // action uses the normal timed HUD.
         HudCenter.shared.emit(trigger: trigger, combo: combo, caption: caption,
                               duration: action.isHeldModifier ? .untilDismissed : .timed(ms: 0))
-        execute(action, keyDown: true, activeModifiers: activeModifiers)
+        if let motion {
+            // The whole count as one queued sequence, capped per press. It's a
+            // finished burst, so the chord's autorepeat and key-up are swallowed.
+            if isRepeatable(action) { EngineState.shared.lastAction = action }
+            inFlightChord.withLock { $0[jsKeycode] = LatchedChord(action: nil) }
+            PostQueue.enqueue { LiveKeyEmitter().postTaps(motion.keycode, count: motion.taps, flags: motion.flags) }
+            return true
+        }
+        execute(action, keyDown: true, activeModifiers: forwarded, trigger: mapping.trigger)
+        return true
+    }
+
+    /// A key event with Caps NOT acting as a held layer. Routes it through the
+    /// Caps layer when a one-shot is armed (the press consumes it) or when this
+    /// key is a chord still latched — one-shot or hotkey — so its autorepeat /
+    /// key-up pair with the latched down. Returns true if the event should be swallowed. An armed
+    /// key with no mapping is spent and passes through.
+    static func handleOneShotKey(keycode: UInt16, keyDown: Bool, isRepeat: Bool, activeModifiers: CGEventFlags) -> Bool {
+        guard let jsKeycode = KeyCodes.macToJs(keycode) else { return false }
+        if inFlightChord.withLock({ $0[jsKeycode] != nil }) {
+            return handleCapsRemap(keycode: keycode, keyDown: keyDown, activeModifiers: activeModifiers)
+        }
+        guard keyDown, !isRepeat, EngineState.shared.consumeOneShot(now: nowMillis()) else { return false }
+        if handleCapsRemap(keycode: keycode, keyDown: true, activeModifiers: activeModifiers) { return true }
+        FileLog.shared.info("One-shot Caps + \(KeyCodes.name(jsKeycode)) had NO mapping — passing through.")
+        return false
+    }
+
+    // MARK: - Global hotkeys
+
+    /// The hotkey mapping for `jsKeycode` with exactly `modifiers` held (Fn is
+    /// ignored — macOS sets it on arrows and F-keys). Hand-edited combos that
+    /// `hotkeyAllowed` rejects never match.
+    static func resolveHotkey(jsKeycode: UInt16, modifiers: CGEventFlags) -> ActionMappingEntry? {
+        let mods = modifiers.subtracting(.maskSecondaryFn)
+        return MappingsRegistry.shared.withMappings { mappings in
+            mappings.first { entry in
+                guard entry.enabled, case .hotkey(let key, let ctrl, let alt, let cmd, let shift) = entry.trigger,
+                      key == jsKeycode, Trigger.hotkeyAllowed(key: key, ctrl: ctrl, alt: alt, cmd: cmd)
+                else { return false }
+                return mods == hotkeyFlags(ctrl: ctrl, alt: alt, cmd: cmd, shift: shift)
+            }
+        }
+    }
+
+    static func hotkeyFlags(ctrl: Bool, alt: Bool, cmd: Bool, shift: Bool) -> CGEventFlags {
+        var flags: CGEventFlags = []
+        if ctrl { flags.insert(.maskControl) }
+        if alt { flags.insert(.maskAlternate) }
+        if cmd { flags.insert(.maskCommand) }
+        if shift { flags.insert(.maskShift) }
+        return flags
+    }
+
+    /// A fresh key-down with Caps not held as the layer: fire a matching global
+    /// hotkey and latch it like a chord, so its autorepeat and key-up are
+    /// swallowed and paired through `handleOneShotKey`. Returns true if handled.
+    /// Anything short of an exact modifier match passes through untouched.
+    static func handleHotkey(keycode: UInt16, activeModifiers: CGEventFlags) -> Bool {
+        guard let jsKeycode = KeyCodes.macToJs(keycode),
+              let mapping = resolveHotkey(jsKeycode: jsKeycode, modifiers: activeModifiers),
+              case .hotkey(_, let ctrl, let alt, let cmd, let shift) = mapping.trigger
+        else { return false }
+        UsageStats.shared.record(triggerUniqueID(mapping.trigger))
+        let action = effectiveAction(mapping, currentContext())
+        let triggerModifiers = hotkeyFlags(ctrl: ctrl, alt: alt, cmd: cmd, shift: shift)
+        inFlightChord.withLock {
+            $0[jsKeycode] = LatchedChord(action: action, repeatMode: mapping.repeatMode, triggerModifiers: triggerModifiers)
+        }
+        let trigger = hotkeyLabel(jsKeycode, ctrl, alt, cmd, shift)
+        guard let action else {
+            FileLog.shared.info("Hotkey \(trigger) matched but no applicable action — swallowing.")
+            return true
+        }
+        FileLog.shared.info("Hotkey: \(trigger) -> \(describeAction(action))")
+        let (combo, caption) = hudParts(action)
+        HudCenter.shared.emit(trigger: trigger, combo: combo, caption: caption,
+                              duration: action.isHeldModifier ? .untilDismissed : .timed(ms: 0))
+        execute(action, keyDown: true, activeModifiers: activeModifiers.subtracting(triggerModifiers), trigger: mapping.trigger)
+        return true
+    }
+
+    // MARK: - Resume while paused
+
+    /// While paused the tap passes everything through, so the chord that paused
+    /// the engine could never resume it. This is the one exception: a fresh
+    /// key-down whose Caps chord (`capsHeld`) or hotkey resolves to Toggle Pause
+    /// still fires it. Returns true if the key should be swallowed.
+    static func handlePausedKey(keycode: UInt16, capsHeld: Bool, activeModifiers: CGEventFlags) -> Bool {
+        guard let jsKeycode = KeyCodes.macToJs(keycode) else { return false }
+        let ctx = currentContext()
+        let mapping = capsHeld
+            ? resolveEntry(jsKeycode: jsKeycode, shiftHeld: activeModifiers.contains(.maskShift),
+                           modifiers: activeModifiers, ctx: ctx)
+            : resolveHotkey(jsKeycode: jsKeycode, modifiers: activeModifiers)
+        guard let mapping, effectiveAction(mapping, ctx) == .appControl(.togglePause) else { return false }
+        var trigger = "Caps+\(KeyCodes.name(jsKeycode))"
+        if case .hotkey(let key, let ctrl, let alt, let cmd, let shift) = mapping.trigger {
+            trigger = hotkeyLabel(key, ctrl, alt, cmd, shift)
+        }
+        let (combo, caption) = hudParts(.appControl(.togglePause))
+        HudCenter.shared.emit(trigger: trigger, combo: combo, caption: caption)
+        AppCommands.post(.togglePause)
         return true
     }
 
@@ -490,10 +1229,12 @@ enum ActionExecutor {
     /// release for Carbon/global-hotkey matching, so we defer ~50ms then
     /// synthesize an explicit modifier-down → target → modifier-up sequence with
     /// cumulative flags. Other actions keep the plain down+up behavior.
-    static func fireDoubleTapModifierAction(_ action: ActionConfig) {
+    static func fireDoubleTapModifierAction(_ action: ActionConfig, modifier: ModifierKey) {
+        let trigger = Trigger.doubleTapModifier(modifier)
         if case .keyCombo(let targetKey, let ctrl, let alt, let cmd, let shift) = action {
             guard let mac = KeyCodes.jsToMac(targetKey) else {
                 FileLog.shared.warn("double-tap KeyCombo: unknown JS keycode \(targetKey), cannot map to macOS")
+                ActionResults.report(trigger, action, error: "Unknown key code \(targetKey)")
                 return
             }
             FileLog.shared.info("double-tap KeyCombo synthesizing: \(keyComboString(targetKey, ctrl, alt, cmd, shift))")
@@ -519,7 +1260,7 @@ enum ActionExecutor {
             }
             return
         }
-        execute(action, keyDown: true, activeModifiers: [])
+        execute(action, keyDown: true, activeModifiers: [], trigger: trigger)
         execute(action, keyDown: false, activeModifiers: [])
     }
 }
@@ -0,0 +1,41 @@
+import Foundation
+
+/// Outcome of one executed action that can fail at runtime — an input source
+/// that isn't installed, a shell command that won't spawn, an app that can't be
+/// found or opened, a key combo with an unmappable key. Actions that can't fail
+/// (arrows, text, …) report nothing, so the hot path stays quiet.
+struct ActionResult: Equatable {
+  /// `triggerUniqueID` of the mapping that fired, or nil when the action didn't
+  /// come from a mapping (a leader-key sequence follow-up).
+  var triggerID: String?
+  /// The action's wire `kind` (`input_source`, `command`, …).
+  var kind: String
+  var ok: Bool
+  var error: String?
+  /// Captured stdout of a `.command` run with `capture`; nil otherwise.
+  var output: String? = nil
+  var exitCode: Int32? = nil
+}
+
+/// Fan-out of `ActionResult`s to the UI, so failures that used to live only in
+/// the log show up next to the binding. Reports may come from any thread (the
+/// tap, a spawn queue, a Launch Services callback); they're always posted on
+/// the main queue.
+enum ActionResults {
+  static let didReportNotification = Notification.Name("me.xueshi.hypercapslock.actionResult")
+  /// userInfo key carrying the `ActionResult`.
+  static let resultKey = "result"
+
+  static func report(_ result: ActionResult) {
+    if !result.ok {
+      FileLog.shared.warn("Action failed (\(result.kind), trigger=\(result.triggerID ?? "-")): \(result.error ?? "unknown error")")
+    }
+    DispatchQueue.main.async {
+      NotificationCenter.default.post(name: didReportNotification, object: nil, userInfo: [resultKey: result])
+    }
+  }
+
+  static func report(_ trigger: Trigger?, _ action: ActionConfig, error: String?) {
+    report(ActionResult(triggerID: trigger.map(triggerUniqueID), kind: action.kindTag, ok: error == nil, error: error))
+  }
+}
@@ -0,0 +1,17 @@
+import Foundation
+
+/// Hand-off for `ActionConfig.appControl`: the tap thread can't touch the app
+/// (pause state, window, config store are all main-actor), so it posts the
+/// command and `AppState` carries it out on the main queue.
+enum AppCommands {
+  static let didRequestNotification = Notification.Name("me.xueshi.hypercapslock.appCommand")
+  /// userInfo key carrying the `AppCommand`.
+  static let commandKey = "command"
+
+  static func post(_ command: AppCommand) {
+    FileLog.shared.info("App command requested from the keyboard: \(command.rawValue)")
+    DispatchQueue.main.async {
+      NotificationCenter.default.post(name: didRequestNotification, object: nil, userInfo: [commandKey: command])
+    }
+  }
+}
@@ -7,6 +7,19 @@ import os
 protocol CapsHoldObserver: AnyObject {
     func capsHoldBegan()
     func capsHoldEnded()
+    /// A mouse button went down during the hold. Only fires with the opt-in
+    /// mouse tap (Settings ▸ Caps + mouse click); the click itself still
+    /// reaches the app.
+    func capsHoldClicked(_ button: CapsMouseButton)
+}
+
+extension CapsHoldObserver {
+    func capsHoldClicked(_ button: CapsMouseButton) {}
+}
+
+/// Which mouse button a Caps + click used.
+enum CapsMouseButton: String {
+    case left, right, other
 }
 
 /// In-process event hub for the CapsLock-hold lifecycle. Observer membership IS
@@ -62,6 +75,9 @@ final class CapsHoldCenter {
     /// Lifecycle: a hold ended.
     func notifyEnded() { fanOut(isHeld: false) { $0.capsHoldEnded() } }
 
+    /// A click during the hold. The hold itself continues.
+    func notifyClicked(_ button: CapsMouseButton) { fanOut(isHeld: true) { $0.capsHoldClicked(button) } }
+
     private func fanOut(isHeld: Bool, _ body: (CapsHoldObserver) -> Void) {
         let live: [CapsHoldObserver] = state.withLock { st in
             st.isHeld = isHeld
@@ -93,6 +109,20 @@ func beginCapsHold() {
 @discardableResult
 func endCapsHold() -> Bool {
     let wasDown = EngineState.shared.swapCapsDown(false)
+    EngineState.shared.clearStickyShift()
     if wasDown { CapsHoldCenter.shared.notifyEnded() }
+    if EngineState.shared.capsLockPinnedOff, CapsLockState.forceOff() {
+        FileLog.shared.info("Caps Lock is pinned off; cleared it on layer release.")
+    }
     return wasDown
 }
+
+/// A mouse button went down while Caps is held as the layer. Reported only —
+/// log, HUD and the hub — so Caps + click features can be built on top; it
+/// also counts as using the layer, so releasing Caps won't toggle Caps Lock.
+func capsHoldClicked(_ button: CapsMouseButton) {
+    EngineState.shared.didRemap = true
+    FileLog.shared.info("Caps HELD + \(button.rawValue) click.")
+    HudCenter.shared.emit(trigger: "Caps", combo: "\u{1F5B1} \(button.rawValue.capitalized)", caption: "Click")
+    CapsHoldCenter.shared.notifyClicked(button)
+}
@@ -36,6 +36,20 @@ enum CapsLockState {
         return true
     }
 
+    /// The state a toggle lands on from `current`: the opposite, unless Caps
+    /// Lock is pinned off, in which case it can only go (or stay) off.
+    static func toggleTarget(from current: Bool, pinnedOff: Bool) -> Bool {
+        pinnedOff ? false : !current
+    }
+
+    /// Clear the lock if it's on, for the pin-off mode. Returns `true` only when
+    /// it was on and got cleared.
+    @discardableResult
+    static func forceOff() -> Bool {
+        guard read() == true else { return false }
+        return set(false)
+    }
+
     private static func openHIDSystem() -> io_connect_t? {
         let matching = IOServiceMatching("IOHIDSystem")
         let service = IOServiceGetMatchingService(kIOMainPortDefault, matching)
@@ -6,6 +6,25 @@ enum EngineConstants {
     static let capsTapMaxMs: UInt64 = 200
     /// Window within which a 2nd tap counts as a double-tap.
     static let doubleTapWindowMs: UInt64 = 200
+    /// How long an armed one-shot layer waits for its key before lapsing.
+    static let oneShotTimeoutMs: UInt64 = 3000
+    /// How long a Caps+digit count prefix waits for its motion before lapsing.
+    static let countPrefixTimeoutMs: UInt64 = 2000
+    /// How long a latched Sticky Shift survives without a motion using it.
+    static let stickyShiftTimeoutMs: UInt64 = 3000
+    /// How long the layer may stay on with no key activity, and the layer key
+    /// physically up, before `LayerWatchdog` decides its key-up was lost.
+    static let layerStuckTimeoutMs: UInt64 = 30_000
+    /// Largest per-key injection delay: at 200 keys (the jump cap) that's
+    /// already 10s of posting.
+    static let maxInjectDelayMs = 50
+    /// Most one queued sequence may spend in injection delays; the rest of its
+    /// keys go back-to-back. The tap drains the queue before every key, so this
+    /// bounds how long a keystroke can wait — well under the tap timeout.
+    static let maxInjectPausePerSequenceMs = 250
+    /// Largest key debounce window. Past this it starts eating deliberate
+    /// double presses.
+    static let maxDebounceMs = 100
 }
 
 /// The subset of `flags` that are real modifier flags (Shift/Ctrl/Alt/Cmd/Fn).
@@ -0,0 +1,83 @@
+import Foundation
+import CoreGraphics
+
+/// Vim-style count prefix for the Caps layer: `Caps+3` then `Caps+J` moves down
+/// three lines. Digits only count when that Caps+digit isn't bound to anything,
+/// so an existing digit mapping always wins. The pending count lives in
+/// `EngineState`; everything here is pure so it can be tested without a tap.
+enum CountPrefix {
+  /// Largest count we accumulate; more digits are ignored past it, so a
+  /// mistyped `Caps+9999…` can't post thousands of arrow keys.
+  static let maxCount = 999
+  /// Most taps one counted press posts, however the count and a jump's own
+  /// step multiply — the same ceiling as a jump's count (`ConfigStore.maxJumpCount`).
+  static let maxTaps = 200
+
+  /// The digit a JS keycode stands for (top-row 0–9), or nil.
+  static func digit(_ jsKeycode: UInt16) -> Int? {
+    (48...57).contains(jsKeycode) ? Int(jsKeycode) - 48 : nil
+  }
+
+  /// Append `digit` to a pending count (0 = none pending). Returns the new
+  /// count, or nil when the digit doesn't start a count — a leading 0, as in
+  /// Vim. Clamped to `maxCount`.
+  static func accumulate(_ pending: Int, digit: Int) -> Int? {
+    if pending == 0 && digit == 0 { return nil }
+    return min(pending * 10 + digit, maxCount)
+  }
+
+  /// Whether a count multiplies `action`: the stepwise motions (arrows, word
+  /// motions, jumps) and Repeat Last. Everything else just clears the pending
+  /// count.
+  static func isCountable(_ action: ActionConfig) -> Bool {
+    switch action {
+    case .directional(let a):
+      switch a {
+      case .left, .right, .up, .down, .wordForward, .wordBack: return true
+      case .home, .end, .docStart, .docEnd: return false
+      }
+    case .jump, .independent(.repeatLast): return true
+    default: return false
+    }
+  }
+
+  /// The taps a counted motion posts, as one queued sequence: `count` steps
+  /// of an arrow or word motion, or `count` × the jump's own count, capped at
+  /// `maxTaps`. Repeat Last folds the same way when `last` (what it would
+  /// replay) is a motion. Nil for anything else, which then runs once: a
+  /// count never repeats a command, script or other side effect.
+  static func countedTaps(_ action: ActionConfig, count: Int, last: ActionConfig?, activeModifiers: CGEventFlags,
+                          wordMotion: WordMotionStyle) -> (keycode: UInt16, flags: CGEventFlags, taps: Int)? {
+    guard count > 1 else { return nil }
+    let stroke: (keycode: UInt16, flags: CGEventFlags)
+    let steps: Int
+    switch action {
+    case .independent(.repeatLast):
+      // Replayed with no held modifiers, as `execute` does.
+      guard let last, !isRepeatLast(last) else { return nil }
+      return countedTaps(last, count: count, last: nil, activeModifiers: [], wordMotion: wordMotion)
+    case .directional(let a) where isCountable(action):
+      stroke = ActionExecutor.directionalKeystroke(a, activeModifiers: activeModifiers, wordMotion: wordMotion)
+      steps = count
+    case .jump(let direction, let n, let unit) where n > 0:
+      stroke = ActionExecutor.jumpKeystroke(direction, unit: unit, activeModifiers: activeModifiers)
+      steps = count * n
+    default:
+      return nil
+    }
+    return (stroke.keycode, stroke.flags, min(steps, maxTaps))
+  }
+
+  private static func isRepeatLast(_ action: ActionConfig) -> Bool {
+    if case .independent(.repeatLast) = action { return true }
+    return false
+  }
+
+  /// `action` with a go-to's missing line supplied by `count`. A count of 1
+  /// is indistinguishable from none, so it leaves the go-to unchanged (the
+  /// field just opens); every other action is returned as is.
+  static func fillingLine(_ action: ActionConfig, count: Int) -> ActionConfig {
+    guard count > 1, case .goTo(nil, let column, let shortcut) = action else { return action }
+    return .goTo(line: count, column: column, shortcut: shortcut)
+  }
+}
@@ -0,0 +1,50 @@
+import Foundation
+
+/// The text `.insertDateTime` types: `strftime(3)` formatting of the current
+/// local time, e.g. `%Y-%m-%d %H:%M` → `2026-10-16 19:30`. Names (`%A`,
+/// `%b`) come out in English whatever the system language, since the app
+/// formats in the C locale.
+enum DateTimeText {
+  static let defaultFormat = "%Y-%m-%d %H:%M"
+
+  /// Offered as one-click choices in the action editor.
+  static let presets = ["%Y-%m-%d", "%Y-%m-%d %H:%M", "%H:%M:%S", "%Y-%m-%dT%H:%M:%S%z", "%a, %d %b %Y"]
+
+  /// Conversions `strftime` understands here; `E` and `O` may prefix one.
+  private static let conversions = Set("aAbBcCdDeFgGhHIjklmMnprRsStTuUvVwWxXyYzZ+%")
+
+  /// Longest text a format may produce.
+  static let maxLength = 256
+
+  /// Why `format` can't be used, or nil if it can: it must be non-empty, and
+  /// every `%` must start a conversion `strftime` knows (a literal percent
+  /// sign is `%%`), so a typo fails at save instead of typing garbage.
+  static func problem(_ format: String) -> String? {
+    if format.isEmpty { return "format cannot be empty" }
+    var chars = format.makeIterator()
+    while let ch = chars.next() {
+      guard ch == "%" else { continue }
+      var spec = chars.next()
+      if spec == "E" || spec == "O" { spec = chars.next() }
+      guard let spec, conversions.contains(spec) else {
+        return "unknown conversion %\(spec.map(String.init) ?? "") (write %% for a percent sign)"
+      }
+    }
+    guard render(format, at: Date()) != nil else { return "format produces more than \(maxLength) characters" }
+    return nil
+  }
+
+  /// `format` applied to `date`, in local time or (for tests) UTC. Nil when the
+  /// result would be longer than `maxLength` or empty.
+  static func render(_ format: String, at date: Date, utc: Bool = false) -> String? {
+    var t = time_t(date.timeIntervalSince1970.rounded(.down))
+    var parts = tm()
+    guard (utc ? gmtime_r(&t, &parts) : localtime_r(&t, &parts)) != nil else { return nil }
+    // Room for multi-byte output; the length check below is in characters.
+    var buffer = [CChar](repeating: 0, count: maxLength * 4 + 1)
+    let count = strftime(&buffer, buffer.count, format, &parts)
+    guard count > 0 else { return nil }
+    let text = String(decoding: buffer.prefix(count).map { UInt8(bitPattern: $0) }, as: UTF8.self)
+    return text.count <= maxLength ? text : nil
+  }
+}
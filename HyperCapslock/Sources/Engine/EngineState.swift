@@ -12,16 +12,217 @@ final class EngineState {
     private let _isPaused = OSAllocatedUnfairLock(initialState: false)
     private let _capsDown = OSAllocatedUnfairLock(initialState: false)
     private let _capsPressedAtMs = OSAllocatedUnfairLock<UInt64>(initialState: 0)
+    /// Last key event seen while the layer was on; `LayerWatchdog`'s idle clock.
+    private let _layerActivityAtMs = OSAllocatedUnfairLock<UInt64>(initialState: 0)
     private let _didRemap = OSAllocatedUnfairLock(initialState: false)
     /// Timestamp of the last short tap pending a possible 2nd tap (cancellation
     /// token for the deferred CapsLock toggle). 0 = none pending.
     private let _lastTapAtMs = OSAllocatedUnfairLock<UInt64>(initialState: 0)
+    /// Bundle ids (lowercased) where the engine passes everything through, as if
+    /// paused. Written by `AppState`, read per event by the tap.
+    private let _excludedBundleIDs = OSAllocatedUnfairLock<Set<String>>(initialState: [])
+    private let _wordMotionStyle = OSAllocatedUnfairLock<WordMotionStyle>(initialState: .word)
+    private let _lineEdgeStyle = OSAllocatedUnfairLock<LineEdgeStyle>(initialState: .visual)
+    private let _capsLayerMode = OSAllocatedUnfairLock<CapsLayerMode>(initialState: .hold)
+    private let _capsToggleEnabled = OSAllocatedUnfairLock(initialState: true)
+    private let _capsLockPinnedOff = OSAllocatedUnfairLock(initialState: false)
+    private let _layerKey = OSAllocatedUnfairLock<LayerKey>(initialState: .capsLock)
+    private let _shiftLayerMode = OSAllocatedUnfairLock<ShiftLayerMode>(initialState: .fallback)
+    private let _tapLocation = OSAllocatedUnfairLock<EventTapLocation>(initialState: .hid)
+    private let _postLocation = OSAllocatedUnfairLock<EventTapLocation>(initialState: .hid)
+    private let _injectDelayMs = OSAllocatedUnfairLock(initialState: 0)
+    private let _capsMouseEvents = OSAllocatedUnfairLock(initialState: false)
+    private let _rawCapsFallback = OSAllocatedUnfairLock(initialState: false)
+    private let _debounceMs = OSAllocatedUnfairLock(initialState: 0)
+    private let _passthroughKeys = OSAllocatedUnfairLock<[PassthroughKey]>(initialState: [])
+    private let _rawEscapeModifier = OSAllocatedUnfairLock<RawEscapeModifier>(initialState: .fn)
+    /// When a short tap armed the one-shot layer. 0 = not armed.
+    private let _oneShotArmedAtMs = OSAllocatedUnfairLock<UInt64>(initialState: 0)
+    /// Caps physically held while paused — tracked apart from `capsDown` so a
+    /// paused engine fires no hold lifecycle, only the resume chord.
+    private let _pausedCapsDown = OSAllocatedUnfairLock(initialState: false)
+    /// Pending Caps+digit count and when its last digit was typed. 0 = none.
+    private let _countPrefix = OSAllocatedUnfairLock<(count: Int, atMs: UInt64)>(initialState: (0, 0))
+    /// What `.repeatLast` replays. Nil until something has fired.
+    private let _lastAction = OSAllocatedUnfairLock<ActionConfig?>(initialState: nil)
+    /// When Sticky Shift was latched or last used. 0 = not latched.
+    private let _stickyShiftAtMs = OSAllocatedUnfairLock<UInt64>(initialState: 0)
 
     var isPaused: Bool {
         get { _isPaused.withLock { $0 } }
         set { _isPaused.withLock { $0 = newValue } }
     }
 
+    func setExcludedBundleIDs(_ ids: [String]) {
+        let set = Set(ids.map { $0.lowercased() })
+        _excludedBundleIDs.withLock { $0 = set }
+    }
+
+    /// True if `bundleID` is on the exclusion list (case-insensitive, like
+    /// per-app binding conditions). A nil bundle id is never excluded.
+    func isExcluded(_ bundleID: String?) -> Bool {
+        guard let bundleID else { return false }
+        return _excludedBundleIDs.withLock { !$0.isEmpty && $0.contains(bundleID.lowercased()) }
+    }
+
+    /// Which shortcut the word-motion directional actions send.
+    var wordMotionStyle: WordMotionStyle {
+        get { _wordMotionStyle.withLock { $0 } }
+        set { _wordMotionStyle.withLock { $0 = newValue } }
+    }
+
+    /// How New Line / Open Line Above find the line's end or start.
+    var lineEdgeStyle: LineEdgeStyle {
+        get { _lineEdgeStyle.withLock { $0 } }
+        set { _lineEdgeStyle.withLock { $0 = newValue } }
+    }
+
+    /// How Caps enters the layer (hold, one-shot tap, or both).
+    var capsLayerMode: CapsLayerMode {
+        get { _capsLayerMode.withLock { $0 } }
+        set { _capsLayerMode.withLock { $0 = newValue } }
+    }
+
+    /// Whether an unmapped short Caps tap toggles Caps Lock.
+    var capsToggleEnabled: Bool {
+        get { _capsToggleEnabled.withLock { $0 } }
+        set { _capsToggleEnabled.withLock { $0 = newValue } }
+    }
+
+    /// Whether Caps Lock is pinned off (see `AppConfig.capsLockPinnedOff`).
+    var capsLockPinnedOff: Bool {
+        get { _capsLockPinnedOff.withLock { $0 } }
+        set { _capsLockPinnedOff.withLock { $0 = newValue } }
+    }
+
+    /// Which physical key arrives as F18. When it isn't Caps Lock, Caps Lock
+    /// events are the user's own and pass through untouched.
+    var layerKey: LayerKey {
+        get { _layerKey.withLock { $0 } }
+        set { _layerKey.withLock { $0 = newValue } }
+    }
+
+    /// Whether Caps+Shift+key may fall back to the Caps+key mapping.
+    var shiftLayerMode: ShiftLayerMode {
+        get { _shiftLayerMode.withLock { $0 } }
+        set { _shiftLayerMode.withLock { $0 = newValue } }
+    }
+
+    /// Where the next tap is created (read once per install by the tap thread).
+    var tapLocation: EventTapLocation {
+        get { _tapLocation.withLock { $0 } }
+        set { _tapLocation.withLock { $0 = newValue } }
+    }
+
+    /// Where `KeyPoster` posts synthesized keys.
+    var postLocation: EventTapLocation {
+        get { _postLocation.withLock { $0 } }
+        set { _postLocation.withLock { $0 = newValue } }
+    }
+
+    /// Pause after each key of a queued multi-key sequence (see `KeyPoster`).
+    var injectDelayMs: Int {
+        get { _injectDelayMs.withLock { $0 } }
+        set { _injectDelayMs.withLock { $0 = newValue } }
+    }
+
+    /// Window within which a repeated key-down is dropped (see `KeyDebounce`).
+    var debounceMs: Int {
+        get { _debounceMs.withLock { $0 } }
+        set { _debounceMs.withLock { $0 = newValue } }
+    }
+
+    /// Whether the next tap install also taps mouse-button downs.
+    var capsMouseEvents: Bool {
+        get { _capsMouseEvents.withLock { $0 } }
+        set { _capsMouseEvents.withLock { $0 = newValue } }
+    }
+
+    /// hidutil couldn't put the CapsLock → F18 remap in place, so the tap
+    /// reads Caps from the raw Caps Lock flag instead (see `KeyboardHook`).
+    var rawCapsFallback: Bool {
+        get { _rawCapsFallback.withLock { $0 } }
+        set { _rawCapsFallback.withLock { $0 = newValue } }
+    }
+
+    /// Caps+key chords the layer leaves alone (see `PassthroughKey`).
+    var passthroughKeys: [PassthroughKey] {
+        get { _passthroughKeys.withLock { $0 } }
+        set { _passthroughKeys.withLock { $0 = newValue } }
+    }
+
+    /// Held with Caps, sends the chord key through unmapped.
+    var rawEscapeModifier: RawEscapeModifier {
+        get { _rawEscapeModifier.withLock { $0 } }
+        set { _rawEscapeModifier.withLock { $0 = newValue } }
+    }
+
+    /// Atomic swap of the one-shot arm timestamp. Returns the previous value.
+    func swapOneShotArmedAtMs(_ newValue: UInt64) -> UInt64 {
+        _oneShotArmedAtMs.withLock { old in let prev = old; old = newValue; return prev }
+    }
+
+    /// Disarm the one-shot layer and report whether it was armed and still
+    /// within `oneShotTimeoutMs` — i.e. whether the caller gets to use it.
+    func consumeOneShot(now: UInt64) -> Bool {
+        let armedAt = swapOneShotArmedAtMs(0)
+        return armedAt > 0 && now &- armedAt <= EngineConstants.oneShotTimeoutMs
+    }
+
+    /// Add a digit to the pending count prefix (a lapsed one starts over).
+    /// Returns the new count, or nil if the digit doesn't start a count.
+    func pushCountDigit(_ digit: Int, now: UInt64) -> Int? {
+        _countPrefix.withLock { st in
+            let pending = now &- st.atMs <= EngineConstants.countPrefixTimeoutMs ? st.count : 0
+            guard let next = CountPrefix.accumulate(pending, digit: digit) else { return nil }
+            st = (next, now)
+            return next
+        }
+    }
+
+    /// Clear the pending count prefix and return it if still fresh, else 1.
+    func takeCountPrefix(now: UInt64) -> Int {
+        _countPrefix.withLock { st in
+            let fresh = st.count > 0 && now &- st.atMs <= EngineConstants.countPrefixTimeoutMs
+            let count = fresh ? st.count : 1
+            st = (0, 0)
+            return count
+        }
+    }
+
+    /// Flip Sticky Shift. Returns whether it is now latched.
+    func toggleStickyShift(now: UInt64) -> Bool {
+        _stickyShiftAtMs.withLock { at in
+            let latched = at > 0 && now &- at <= EngineConstants.stickyShiftTimeoutMs
+            at = latched ? 0 : now
+            return !latched
+        }
+    }
+
+    /// Whether Sticky Shift is latched and fresh; a fresh one is kept alive
+    /// for another timeout, a lapsed one is cleared.
+    func useStickyShift(now: UInt64) -> Bool {
+        _stickyShiftAtMs.withLock { at in
+            guard at > 0, now &- at <= EngineConstants.stickyShiftTimeoutMs else { at = 0; return false }
+            at = now
+            return true
+        }
+    }
+
+    func clearStickyShift() {
+        _stickyShiftAtMs.withLock { $0 = 0 }
+    }
+
+    var lastAction: ActionConfig? {
+        get { _lastAction.withLock { $0 } }
+        set { _lastAction.withLock { $0 = newValue } }
+    }
+
+    var pausedCapsDown: Bool {
+        get { _pausedCapsDown.withLock { $0 } }
+        set { _pausedCapsDown.withLock { $0 = newValue } }
+    }
+
     var capsDown: Bool {
         get { _capsDown.withLock { $0 } }
         set { _capsDown.withLock { $0 = newValue } }
@@ -37,6 +238,11 @@ final class EngineState {
         set { _capsPressedAtMs.withLock { $0 = newValue } }
     }
 
+    var layerActivityAtMs: UInt64 {
+        get { _layerActivityAtMs.withLock { $0 } }
+        set { _layerActivityAtMs.withLock { $0 = newValue } }
+    }
+
     func swapCapsPressedAtMs(_ newValue: UInt64) -> UInt64 {
         _capsPressedAtMs.withLock { old in let prev = old; old = newValue; return prev }
     }
@@ -22,6 +22,9 @@ final class FrontmostAppTracker {
   static let shared = FrontmostAppTracker()
 
   private let _bundleID = OSAllocatedUnfairLock<String?>(initialState: nil)
+  /// Main-thread only: the most recent frontmost app that wasn't
+  /// HyperCapslock itself.
+  private var _lastOtherApp: FrontmostApp?
   private var observers: [NSObjectProtocol] = []
 
   /// Diagnostic hook, invoked on the main thread whenever the frontmost app
@@ -29,11 +32,31 @@ final class FrontmostAppTracker {
   /// production hot path reads `currentBundleID()`, not this callback.
   var onChange: ((FrontmostApp) -> Void)?
 
+  /// Posted on the main thread after the cached frontmost app changes. For UI
+  /// state that follows the frontmost app (the excluded-app auto-pause).
+  static let didChangeNotification = Notification.Name("me.xueshi.hypercapslock.frontmostAppDidChange")
+
   /// Hot-path safe: read the cached frontmost bundle id from any thread.
   func currentBundleID() -> String? {
     _bundleID.withLock { $0 }
   }
 
+  /// The most recent frontmost app other than HyperCapslock, or nil until one
+  /// has been seen. While our own settings window is in front this is the app
+  /// the user just came from — the one they most likely want to target with a
+  /// rule or exclusion.
+  @MainActor
+  func lastOtherApp() -> FrontmostApp? { _lastOtherApp }
+
+  /// What `lastOtherApp` becomes once `app` is frontmost: `app` itself, unless
+  /// it's us or has no bundle id to target, in which case the previous one
+  /// (possibly still nil) is kept.
+  static func lastOtherApp(after app: FrontmostApp, previous: FrontmostApp?,
+                           ownBundleID: String? = Bundle.main.bundleIdentifier) -> FrontmostApp? {
+    guard let bundleID = app.bundleID, bundleID != ownBundleID else { return previous }
+    return app
+  }
+
   @MainActor
   func start() {
     guard observers.isEmpty else { return }  // idempotent: never double-register
@@ -91,7 +114,9 @@ final class FrontmostAppTracker {
     }
     guard changed else { return }
     let info = FrontmostApp(name: app.localizedName ?? "(unknown)", bundleID: bundleID)
+    _lastOtherApp = Self.lastOtherApp(after: info, previous: _lastOtherApp)
     FileLog.shared.info("Frontmost app → \(info.name) [\(bundleID ?? "no bundle id")]")
     onChange?(info)
+    NotificationCenter.default.post(name: Self.didChangeNotification, object: self)
   }
 }
@@ -2,30 +2,35 @@ import Foundation
 
 /// CapsLock → F18 remap via `hidutil`. This gives proper KeyDown/KeyUp events
 /// for CapsLock instead of the unreliable FlagsChanged toggle macOS sends
-/// natively, which is what makes Caps usable as a hyper modifier.
+/// natively, which is what makes Caps usable as a hyper modifier. When it
+/// can't be applied, the tap falls back to that toggle (see
+/// `EngineState.rawCapsFallback`). With another `LayerKey` chosen, that key is
+/// remapped to F18 instead and CapsLock is left alone.
 enum HidUtil {
-    // 0x700000039 = CapsLock usage, 0x70000006D = F18 usage. This base remap is
-    // always present — it's what makes CapsLock usable as the hyper modifier.
-    private static let capsLockUsage: UInt64 = 0x700000039
+    // 0x700000039 = CapsLock usage, 0x70000006D = F18 usage. The base remap
+    // (layer key → F18) is always present — it's what makes the layer key
+    // usable as the hyper modifier.
+    static let capsLockUsage: UInt64 = 0x700000039
     private static let f18Usage: UInt64 = 0x70000006D
     private static let clearPayload = #"{"UserKeyMapping":[]}"#
 
-    /// Apply the base CapsLock→F18 remap plus any user-configured remaps, as a
+    /// Apply the base layer-key→F18 remap plus any user-configured remaps, as a
     /// single `--set` (hidutil replaces the whole `UserKeyMapping`, so everything
     /// must go in one call). Idempotent — safe to call again whenever the list
     /// changes.
     @discardableResult
-    static func setupRemap(extra: [KeyRemap] = []) -> Bool {
-        run(["property", "--set", buildPayload(extra: extra)],
-            onSuccess: "hidutil remap applied (CapsLock→F18 + \(extra.count) user remap(s)).",
+    static func setupRemap(layerKey: LayerKey = .capsLock, extra: [KeyRemap] = []) -> Bool {
+        run(["property", "--set", buildPayload(layerKey: layerKey, extra: extra)],
+            onSuccess: "hidutil remap applied (\(layerKey.rawValue)→F18 + \(extra.count) user remap(s)).",
             onFail: "hidutil remap failed")
     }
 
     /// Build the `UserKeyMapping` JSON: the base remap first, then the user
-    /// remaps (deduped by source, so a hand-edited config can't emit a key twice).
-    private static func buildPayload(extra: [KeyRemap]) -> String {
-        var pairs: [(src: UInt64, dst: UInt64)] = [(capsLockUsage, f18Usage)]
-        var seenSrc: Set<UInt64> = [capsLockUsage]
+    /// remaps (deduped by source, so a hand-edited config can't emit a key twice
+    /// or take the layer key).
+    static func buildPayload(layerKey: LayerKey, extra: [KeyRemap]) -> String {
+        var pairs: [(src: UInt64, dst: UInt64)] = [(layerKey.hidUsage, f18Usage)]
+        var seenSrc: Set<UInt64> = [layerKey.hidUsage]
         for r in extra where seenSrc.insert(r.source.hidUsage).inserted {
             pairs.append((r.source.hidUsage, r.destination.hidUsage))
         }
@@ -41,6 +46,52 @@ enum HidUtil {
                 onFail: "Failed to remove hidutil remap")
     }
 
+    /// Whether the tap should fall back to raw Caps Lock events: only when Caps
+    /// Lock is the layer key, our apply failed AND the probe finds no
+    /// CapsLock→F18 — a rejected user remap leaves the earlier one in place, and
+    /// then F18 still arrives. Any other layer key has no raw events to read.
+    static func needsRawCapsFallback(applied: Bool, remapActive: Bool?, layerKey: LayerKey = .capsLock) -> Bool {
+        layerKey == .capsLock && !applied && remapActive != true
+    }
+
+    /// Whether the layer-key→F18 remap is in effect right now, read back from
+    /// `hidutil property --get UserKeyMapping`. Another tool (or a reboot without
+    /// us running) can drop it, so Settings probes rather than trusting our own
+    /// last `--set`.
+    static func isCapsRemapActive(layerKey: LayerKey = .capsLock) -> Bool {
+        let proc = Process()
+        proc.executableURL = URL(fileURLWithPath: "/usr/bin/hidutil")
+        proc.arguments = ["property", "--get", "UserKeyMapping"]
+        let outPipe = Pipe()
+        proc.standardOutput = outPipe
+        proc.standardError = Pipe()
+        do {
+            try proc.run()
+            let data = outPipe.fileHandleForReading.readDataToEndOfFile()
+            proc.waitUntilExit()
+            guard proc.terminationStatus == 0 else { return false }
+            return capsRemapPresent(in: String(data: data, encoding: .utf8) ?? "", layerKey: layerKey)
+        } catch {
+            FileLog.shared.error("Failed to execute hidutil: \(error.localizedDescription)")
+            return false
+        }
+    }
+
+    /// Scan `--get UserKeyMapping` output (a plist-style array of
+    /// `{ HIDKeyboardModifierMappingDst = …; HIDKeyboardModifierMappingSrc = …; }`
+    /// dictionaries, values in decimal) for the layer-key→F18 pair.
+    static func capsRemapPresent(in output: String, layerKey: LayerKey = .capsLock) -> Bool {
+        func value(_ key: String, in entry: Substring) -> UInt64? {
+            guard let range = entry.range(of: key) else { return nil }
+            let digits = entry[range.upperBound...].drop { !$0.isNumber }.prefix { $0.isNumber }
+            return UInt64(digits)
+        }
+        return output.split(separator: "{").contains { entry in
+            value("HIDKeyboardModifierMappingSrc", in: entry) == layerKey.hidUsage
+                && value("HIDKeyboardModifierMappingDst", in: entry) == f18Usage
+        }
+    }
+
     @discardableResult
     private static func run(_ args: [String], onSuccess: String, onFail: String) -> Bool {
         let proc = Process()
@@ -4,7 +4,10 @@ import Carbon
 /// macOS input-source (keyboard layout / IME) control via Carbon TIS.
 ///
 /// TIS APIs assert main-queue affinity, so the switch runs on the main queue:
-///   • `queueSwitch(toID:)` — async to main (fire-and-forget mapping switch).
+///   • `queueSwitch(toID:onResult:)` — async to main (mapping switch; the
+///     outcome is reported back, not awaited).
+///   • `current()` — read-back of the selected source (main thread), refreshed
+///     on `selectionDidChangeNotification`.
 ///
 /// When the configured `CJKVFixStrategy` is non-`.none` and the target is a CJKV
 /// IME, the switch is handled by `InputSourceFix` instead of a plain select.
@@ -52,12 +55,71 @@ enum InputSourceController {
         return nil
     }
 
+    // MARK: - Read-back
+
+    /// The selected keyboard input source, as shown in the tray and used to
+    /// mark the mapping that switches to it.
+    struct InputSourceInfo: Equatable {
+        let id: String
+        let name: String
+    }
+
+    /// Posted on `DistributedNotificationCenter` whenever the selected keyboard
+    /// source changes — by us, the menu-bar input menu, or another app.
+    static let selectionDidChangeNotification =
+        Notification.Name(kTISNotifySelectedKeyboardInputSourceChanged as String)
+
+    /// The currently selected keyboard input source. Main thread.
+    static func current() -> InputSourceInfo? {
+        guard let source = TISCopyCurrentKeyboardInputSource()?.takeRetainedValue(),
+              let id = property(source, kTISPropertyInputSourceID) else { return nil }
+        return InputSourceInfo(id: id, name: property(source, kTISPropertyLocalizedName) ?? id)
+    }
+
+    private static func property(_ source: TISInputSource, _ key: CFString) -> String? {
+        guard let ptr = TISGetInputSourceProperty(source, key) else { return nil }
+        return Unmanaged<CFString>.fromOpaque(ptr).takeUnretainedValue() as String
+    }
+
+    // MARK: - Repeat suppression
+
+    /// A request for the source we last switched to, arriving within this long
+    /// of the previous request for it, is dropped. Catches a held chord whose
+    /// mapping re-fires on autorepeat (`RepeatMode.onHold`) and a quick double
+    /// press, which would otherwise queue one TIS switch — and one flicker of
+    /// the menu-bar indicator — per repeat.
+    static let repeatWindow: TimeInterval = 0.5
+
+    private static let _lastRequest = NSLock()
+    private static var _lastRequestValue: (id: String, at: TimeInterval)?
+
+    /// Whether a request for `id` at `now` repeats `last`. Pure, for tests.
+    static func isRepeat(_ id: String, at now: TimeInterval, last: (id: String, at: TimeInterval)?) -> Bool {
+        guard let last, last.id == id else { return false }
+        return now - last.at < repeatWindow
+    }
+
+    /// Record the request and report whether it should be dropped. Repeats
+    /// slide the window forward, so a key held for seconds stays suppressed.
+    private static func suppressRepeat(of id: String) -> Bool {
+        let now = ProcessInfo.processInfo.systemUptime
+        _lastRequest.lock(); defer { _lastRequest.unlock() }
+        let repeated = isRepeat(id, at: now, last: _lastRequestValue)
+        _lastRequestValue = (id, now)
+        return repeated
+    }
+
     // MARK: - Mapping switch (async to main)
 
-    static func queueSwitch(toID id: String) {
+    /// `onResult` gets nil on success or an error message, on the main queue.
+    /// A repeat of the previous request (see `repeatWindow`) is dropped before
+    /// it reaches the main queue, and reports nothing.
+    static func queueSwitch(toID id: String, onResult: ((String?) -> Void)? = nil) {
+        guard !suppressRepeat(of: id) else { return }
         let strategy = currentFixStrategy()
         DispatchQueue.main.async {
-            InputSourceFix.switchToSource(id: id, strategy: strategy)
+            let error = InputSourceFix.switchToSource(id: id, strategy: strategy)
+            onResult?(error)
         }
     }
 }
@@ -24,7 +24,8 @@ enum InputSourceFix {
     /// `KeyPoster.injectedMagic` (which tags the high-frequency nav/edit
     /// injections): a separate value lets the tap *positively* log that it
     /// recognized and passed through THESE events, without flooding the log on
-    /// every Caps+key navigation. Value spells "ISFX".
+    /// every Caps+key navigation. Value spells "ISFX"; fixed, since the tap also
+    /// checks the poster's pid (`KeyPoster.isOwnEvent`).
     static let syntheticEventUserData: Int64 = 0x4953_4658
 
     // MARK: - Tunables (mirror ISP timings)
@@ -49,7 +50,10 @@ enum InputSourceFix {
 
     // MARK: - Entry point (called on the main queue from InputSourceController)
 
-    static func switchToSource(id: String, strategy: CJKVFixStrategy) {
+    /// Returns nil once the switch is under way, or why it couldn't be made (the
+    /// source isn't installed / enabled, or a plain select was refused).
+    @discardableResult
+    static func switchToSource(id: String, strategy: CJKVFixStrategy) -> String? {
         dispatchPrecondition(condition: .onQueue(.main))
         cancelPendingWorkItems()
 
@@ -57,7 +61,7 @@ enum InputSourceFix {
             FileLog.shared.warn("InputSourceFix: no selectable input source for id=\(id) — switch aborted.")
             // A just-cancelled Switching-Focus grab must still hand focus back.
             restoreFocusIfNeeded()
-            return
+            return "Input source not found: \(id)"
         }
         let cjkv = isCJKV(target)
         let willGrabFocus = (strategy == .switchingFocus && cjkv)
@@ -72,7 +76,7 @@ enum InputSourceFix {
         guard strategy != .none, cjkv else {
             let status = tisSelect(target, reason: cjkv ? "plain (strategy=none)" : "plain (non-CJKV target)")
             FileLog.shared.info("InputSourceFix: plain select id=\(id) status=\(status) now=\(currentSourceID() ?? "nil")")
-            return
+            return status == noErr ? nil : "TISSelectInputSource failed with status \(status)"
         }
 
         switch strategy {
@@ -83,6 +87,7 @@ enum InputSourceFix {
         case .shortcutSimulation:
             applyShortcutSimulation(target: target, id: id)
         }
+        return nil
     }
 
     // MARK: - Strategy: Switching Focus (temporary input window, from macism)
@@ -379,6 +384,36 @@ enum InputSourceFix {
         }
     }
 
+    /// Where a stored source id stands on this Mac, for the editor's warning.
+    enum SourceAvailability: Equatable {
+        /// Enabled and selectable — the switch will work.
+        case available
+        /// Installed but not enabled in System Settings — `TISSelectInputSource`
+        /// can't reach it until the user adds it.
+        case installedNotEnabled
+        /// Not installed at all (typo, or the IME was removed).
+        case missing
+    }
+
+    /// Classify `id` against the enabled sources and then against every
+    /// installed one (`TISCreateInputSourceList(_, true)`). Main thread.
+    static func availability(ofSourceID id: String) -> SourceAvailability {
+        availability(ofSourceID: id, enabledIDs: Set(availableSourcesByID().keys), isInstalled: isInstalledSource)
+    }
+
+    /// The classification itself, with the TIS lookups passed in (testable).
+    static func availability(ofSourceID id: String, enabledIDs: Set<String>,
+                             isInstalled: (String) -> Bool) -> SourceAvailability {
+        if enabledIDs.contains(id) { return .available }
+        return isInstalled(id) ? .installedNotEnabled : .missing
+    }
+
+    private static func isInstalledSource(_ id: String) -> Bool {
+        let filter = [kTISPropertyInputSourceID as String: id] as CFDictionary
+        guard let cf = TISCreateInputSourceList(filter, true)?.takeRetainedValue() else { return false }
+        return CFArrayGetCount(cf) > 0
+    }
+
     /// Cached id→source map for the mappings list (cheap across many rows and
     /// view re-inits, and carries name + icon for display). Lazily computed; call
     /// `refreshAvailableSourcesByID` (e.g. on page appear) to pick up changes.
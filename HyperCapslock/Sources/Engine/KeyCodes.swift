@@ -18,6 +18,10 @@ enum KeyCodes {
     static let right: UInt16 = 0x7C
     static let down: UInt16 = 0x7D
     static let up: UInt16 = 0x7E
+    static let pageUp: UInt16 = 0x74
+    static let pageDown: UInt16 = 0x79
+    static let a: UInt16 = 0x00        // ⌃A / ⌃E: start / end of paragraph
+    static let e: UInt16 = 0x0E
 
     // Side-specific modifier keycodes for double-tap-modifier triggers.
     static let lShift: UInt16 = 56
@@ -30,6 +34,17 @@ enum KeyCodes {
     static let rCommand: UInt16 = 54
     static let fn: UInt16 = 63
 
+    /// Keys the system posts with the Fn flag set whether or not Fn is held:
+    /// arrows, the navigation block and the F-keys. Their Fn flag says nothing
+    /// about the Fn key.
+    static let implicitFnKeys: Set<UInt16> = [
+        0x7B, 0x7C, 0x7D, 0x7E,                          // arrows
+        0x72, 0x73, 0x74, 0x75, 0x77, 0x79,              // help, home, page up, fwd delete, end, page down
+        0x7A, 0x78, 0x63, 0x76, 0x60, 0x61, 0x62, 0x64,  // F1–F8
+        0x65, 0x6D, 0x67, 0x6F, 0x69, 0x6B, 0x71, 0x6A,  // F9–F16
+        0x40, 0x4F, 0x50, 0x5A,                          // F17–F20
+    ]
+
     // Device-dependent modifier flag bits (the private NX_DEVICE*KEYMASK values).
     // CGEventFlags exposes only the side-agnostic masks (.maskShift, …), so to
     // synthesize a *specific* side (e.g. right Option) we OR the generic mask
@@ -146,6 +161,18 @@ enum KeyCodes {
         }
     }
 
+    /// Every key the UI may offer for binding, in keycode order: each JS keyCode
+    /// that `name` knows or `jsToMac` translates. `supported` is false where
+    /// there's a name but no macOS keycode (F18, F20–F24) — a binding on one of
+    /// those saves but never fires. Derived from the tables, so it grows with them.
+    static let bindableKeys: [BindableKey] = (0...255).compactMap { js in
+        let key = UInt16(js)
+        let supported = jsToMac(key) != nil
+        let display = name(key)
+        guard supported || display != "Key\(key)" else { return nil }
+        return BindableKey(jsKeycode: key, displayName: display, supported: supported)
+    }
+
     /// Human-readable name for a JS keyCode (used in logs and the YAML
     /// `# comment`). Mirrors `js_keycode_name` in the Rust original.
     static func name(_ key: UInt16) -> String {
@@ -182,3 +209,18 @@ enum KeyCodes {
         }
     }
 }
+
+/// One entry of `KeyCodes.bindableKeys`; also the `get_bindable_keys` wire format.
+struct BindableKey: Encodable, Equatable, Identifiable {
+    let jsKeycode: UInt16
+    let displayName: String
+    let supported: Bool
+
+    var id: UInt16 { jsKeycode }
+
+    enum CodingKeys: String, CodingKey {
+        case supported
+        case jsKeycode = "js_keycode"
+        case displayName = "display_name"
+    }
+}
@@ -0,0 +1,55 @@
+import Foundation
+import os
+
+/// Drops the extra key-downs a chattering switch sends: a second down of the
+/// same key within `debounce_ms` of the last accepted one is swallowed, so a
+/// bouncy Caps + key can't run a shell command or jump twice. OS auto-repeat
+/// is marked as such and never counts. Off (0 ms) by default.
+///
+/// A bounce usually comes as down-up-down-up; the extra up is swallowed with
+/// its down. A down with no up in between only loses the down, so the real
+/// release still reaches the app and nothing is left held.
+final class KeyDebounce {
+  static let shared = KeyDebounce()
+
+  private struct State {
+    var lastDownMs: [UInt16: UInt64] = [:]
+    /// Keys whose accepted down hasn't been released yet.
+    var held: Set<UInt16> = []
+    /// Keys whose next up belongs to a dropped down.
+    var swallowUp: Set<UInt16> = []
+  }
+
+  private let state = OSAllocatedUnfairLock(initialState: State())
+
+  /// Whether the tap should drop this key event. Call for every key-down and
+  /// key-up the layer would otherwise see.
+  func shouldDrop(keycode: UInt16, keyDown: Bool, isRepeat: Bool, now: UInt64,
+                  windowMs: UInt64 = UInt64(EngineState.shared.debounceMs)) -> Bool {
+    guard windowMs > 0 else { return false }
+    if keyDown && isRepeat { return false }
+    let dropped = state.withLock { st -> Bool in
+      guard keyDown else {
+        if st.swallowUp.remove(keycode) != nil { return true }
+        st.held.remove(keycode)
+        return false
+      }
+      if let last = st.lastDownMs[keycode], now &- last < windowMs {
+        if !st.held.contains(keycode) { st.swallowUp.insert(keycode) }
+        return true
+      }
+      st.lastDownMs[keycode] = now
+      st.held.insert(keycode)
+      return false
+    }
+    if dropped && keyDown {
+      FileLog.shared.info("Debounce: dropped a repeat key-down (mac=\(keycode)) within \(windowMs)ms.")
+    }
+    return dropped
+  }
+
+  /// Forget every key, for when the window changes.
+  func reset() {
+    state.withLock { $0 = State() }
+  }
+}
@@ -0,0 +1,35 @@
+import CoreGraphics
+
+/// Where `ActionExecutor.execute` sends the keys an action produces. The app
+/// always uses `LiveKeyEmitter`, which posts through `KeyPoster`; tests pass a
+/// recording emitter to check exactly what an action would type without
+/// touching the event system.
+///
+/// Sequences (`postTap`, `postTaps`, `insertString`) are called on the
+/// `PostQueue` worker, so an emitter must tolerate being called off the
+/// thread that ran `execute`.
+protocol KeyEmitter {
+  func post(_ keycode: UInt16, keyDown: Bool, flags: CGEventFlags)
+  func postTap(_ keycode: UInt16, flags: CGEventFlags)
+  func postTaps(_ keycode: UInt16, count: Int, flags: CGEventFlags)
+  func insertString(_ string: String)
+}
+
+/// The real emitter: tagged CGEvents at the configured injection location.
+struct LiveKeyEmitter: KeyEmitter {
+  func post(_ keycode: UInt16, keyDown: Bool, flags: CGEventFlags) {
+    KeyPoster.post(keycode, keyDown: keyDown, flags: flags)
+  }
+
+  func postTap(_ keycode: UInt16, flags: CGEventFlags) {
+    KeyPoster.postTap(keycode, flags: flags)
+  }
+
+  func postTaps(_ keycode: UInt16, count: Int, flags: CGEventFlags) {
+    KeyPoster.postTaps(keycode, count: count, flags: flags)
+  }
+
+  func insertString(_ string: String) {
+    KeyPoster.insertString(string)
+  }
+}
@@ -1,35 +1,123 @@
 import Foundation
 import CoreGraphics
+import os
 
 /// Synthesizes keyboard events. Every posted event is stamped with
 /// `injectedMagic` in `EVENT_SOURCE_USER_DATA` so the tap callback skips it
 /// (no feedback loop / re-detection). Mirrors `post_key` / `post_key_tap`.
+///
+/// The user-data field is shared by every app that posts events, so the tag
+/// alone can collide: a second HyperCapslock, or another tool that picked the
+/// same value, would have its events passed through unprocessed (or ours
+/// treated as real input if we matched theirs). `isOwnEvent` therefore also
+/// requires the poster's pid, which the window server fills in and a sender
+/// can't forge, to be ours.
 enum KeyPoster {
-    /// Magic value stamped on injected events ("GVLN").
-    static let injectedMagic: Int64 = 0x4756_4C4E
+    /// Magic value stamped on injected events. The high half spells "GVLN";
+    /// the low half is random per launch (logged when the hook starts), so
+    /// no other process carries this exact tag.
+    static let injectedMagic: Int64 = 0x4756_4C4E << 32 | Int64(UInt32.random(in: 1...UInt32.max))
+
+    /// Tag on a real key the tap re-posts behind a busy `PostQueue`: the copy
+    /// is handled as input when it comes back, just not deferred again.
+    static let deferredMagic: Int64 = injectedMagic ^ 0x0000_0001_0000_0000
+
+    static let ownPID = Int64(getpid())
+    /// Whether a tagged event from another process has been logged yet.
+    private static let loggedForeignTag = OSAllocatedUnfairLock(initialState: false)
+
+    /// True when `event` was posted by this process with `magic`. Logs once if
+    /// another process's event carries our tag — it's then handled as input.
+    static func isOwnEvent(_ event: CGEvent, magic: Int64 = injectedMagic) -> Bool {
+        let userData = event.getIntegerValueField(.eventSourceUserData)
+        let sourcePID = event.getIntegerValueField(.eventSourceUnixProcessID)
+        let own = isOwn(userData: userData, sourcePID: sourcePID, magic: magic)
+        if userData == magic && !own {
+            let first = loggedForeignTag.withLock { logged -> Bool in
+                defer { logged = true }
+                return !logged
+            }
+            if first {
+                FileLog.shared.warn("Tap: pid \(sourcePID) posted an event with our injected tag 0x\(String(magic, radix: 16)); treating it as real input.")
+            }
+        }
+        return own
+    }
+
+    /// The tag check itself: our magic AND our pid. Pure, for tests.
+    static func isOwn(userData: Int64, sourcePID: Int64, magic: Int64, ownPID: Int64 = KeyPoster.ownPID) -> Bool {
+        userData == magic && sourcePID == ownPID
+    }
+
+    /// One private-state source for every injected event. Creating a source per
+    /// event cost a round trip to the window server on each post (six of them
+    /// for InsertQuotes, 20 for a 10-line jump). A static `let` is initialized
+    /// once, thread-safely, and the tap thread and main thread can share it:
+    /// the source only seeds event fields, it isn't mutated by posting.
+    private static let source = CGEventSource(stateID: .privateState)
+
+    /// Where keys are posted (Settings ▸ Injection location); HID by default.
+    private static var location: CGEventTapLocation { EngineState.shared.postLocation.cgLocation }
 
     static func post(_ keycode: UInt16, keyDown: Bool, flags: CGEventFlags) {
-        guard let source = CGEventSource(stateID: .privateState),
-              let event = CGEvent(keyboardEventSource: source, virtualKey: keycode, keyDown: keyDown) else { return }
+        guard let event = CGEvent(keyboardEventSource: source, virtualKey: keycode, keyDown: keyDown) else { return }
         event.flags = flags
         event.setIntegerValueField(.eventSourceUserData, value: injectedMagic)
-        event.post(tap: .cghidEventTap)
+        event.post(tap: location)
+    }
+
+    /// Re-post a copy of a real key, tagged `deferredMagic`, where the tap
+    /// listens, so the tap sees it again as if just typed.
+    static func repostDeferred(_ event: CGEvent) {
+        event.setIntegerValueField(.eventSourceUserData, value: deferredMagic)
+        event.post(tap: EngineState.shared.tapLocation.cgLocation)
     }
 
+    /// The taps and characters below make up multi-key sequences and run on
+    /// the `PostQueue` worker, so each may wait out Settings ▸ Injection delay
+    /// after it — never on the tap thread.
     static func postTap(_ keycode: UInt16, flags: CGEventFlags) {
         post(keycode, keyDown: true, flags: flags)
         post(keycode, keyDown: false, flags: flags)
+        interKeyPause()
+    }
+
+    /// Sleep for the configured injection delay, if any, as far as the running
+    /// sequence's budget allows (see `PostQueue.pause`).
+    private static func interKeyPause(_ ms: Int = EngineState.shared.injectDelayMs) {
+        PostQueue.pause(ms)
+    }
+
+    /// Post `count` taps of one key as a batch: one stamped down/up event
+    /// pair, re-posted `count` times. macOS has no "move N lines"
+    /// event, so a jump is still N taps — but this skips re-creating 2N events
+    /// in the hot loop, which is what made long jumps visibly lag.
+    static func postTaps(_ keycode: UInt16, count: Int, flags: CGEventFlags) {
+        guard count > 0,
+              let down = CGEvent(keyboardEventSource: source, virtualKey: keycode, keyDown: true),
+              let up = CGEvent(keyboardEventSource: source, virtualKey: keycode, keyDown: false) else { return }
+        for event in [down, up] {
+            event.flags = flags
+            event.setIntegerValueField(.eventSourceUserData, value: injectedMagic)
+        }
+        let location = location
+        let delay = EngineState.shared.injectDelayMs
+        for _ in 0..<count {
+            down.post(tap: location)
+            up.post(tap: location)
+            interKeyPause(delay)
+        }
     }
 
     /// Insert a literal string, bypassing the IME (posted at the annotated
     /// session level) so Chinese input methods don't convert ASCII quotes into
     /// smart quotes. Used by the InsertQuotes action.
     static func insertString(_ string: String) {
-        guard let source = CGEventSource(stateID: .privateState),
-              let event = CGEvent(keyboardEventSource: source, virtualKey: 0, keyDown: true) else { return }
+        guard let event = CGEvent(keyboardEventSource: source, virtualKey: 0, keyDown: true) else { return }
         let utf16 = Array(string.utf16)
         event.keyboardSetUnicodeString(stringLength: utf16.count, unicodeString: utf16)
         event.setIntegerValueField(.eventSourceUserData, value: injectedMagic)
         event.post(tap: .cgAnnotatedSessionEventTap)
+        interKeyPause()
     }
 }
@@ -0,0 +1,127 @@
+import Foundation
+import os
+
+/// Pure leader-key state machine: at most one pending leader, resolved by the
+/// next key press. Holds no config and reads no clock — the caller passes both
+/// in — so it's unit-testable and any hook can drive it.
+struct SequenceState: Equatable {
+  private(set) var pendingLeader: UInt16?
+  private(set) var startedAtMs: UInt64 = 0
+
+  enum Resolution: Equatable {
+    /// Nothing pending — handle the key normally.
+    case idle
+    /// A leader was pending but the timeout had passed. Cleared; handle normally.
+    case expired
+    /// The follow-up completed a sequence.
+    case matched(ActionConfig)
+    /// The follow-up isn't in the leader's map. Cancelled; handle normally.
+    case unmatched
+  }
+
+  var isPending: Bool { pendingLeader != nil }
+
+  mutating func begin(leader: UInt16, at now: UInt64) {
+    pendingLeader = leader
+    startedAtMs = now
+  }
+
+  mutating func cancel() {
+    pendingLeader = nil
+    startedAtMs = 0
+  }
+
+  /// Consume the pending leader (if any) with `key`. Always leaves the state
+  /// idle: a sequence is exactly two keys, so any follow-up ends it.
+  mutating func resolve(followUp key: UInt16, at now: UInt64, table: SequenceTable, timeoutMs: UInt64) -> Resolution {
+    guard let leader = pendingLeader else { return .idle }
+    let started = startedAtMs
+    cancel()
+    if now &- started > timeoutMs { return .expired }
+    guard let action = table.action(leader: leader, followUp: key) else { return .unmatched }
+    return .matched(action)
+  }
+}
+
+/// Live leader-key sequence runtime for the event tap: the configured table, the
+/// timeout, and the pending `SequenceState`, all behind one lock (the tap thread
+/// drives it; `ConfigStore` / `AppState` write the table and timeout).
+final class KeySequences {
+  static let shared = KeySequences()
+
+  static let defaultTimeoutMs: UInt64 = 800
+
+  private struct Runtime {
+    var table = SequenceTable()
+    var timeoutMs = KeySequences.defaultTimeoutMs
+    var state = SequenceState()
+    /// macOS keycodes whose key-down completed a sequence; their key-up is
+    /// swallowed too so the app never sees an unpaired up.
+    var swallowedUps: Set<UInt16> = []
+  }
+
+  private let runtime = OSAllocatedUnfairLock(initialState: Runtime())
+
+  func setTable(_ table: SequenceTable) {
+    runtime.withLock {
+      $0.table = table
+      $0.state.cancel()
+    }
+  }
+
+  func setTimeout(ms: Int) {
+    runtime.withLock { $0.timeoutMs = UInt64(max(ms, 0)) }
+  }
+
+  /// True if `jsKeycode` starts a sequence (Caps+<it> arms the pending state).
+  func isLeader(_ jsKeycode: UInt16) -> Bool {
+    runtime.withLock { $0.table.isLeader(jsKeycode) }
+  }
+
+  /// Arm the pending state for `leader`. Replaces any previous pending leader.
+  func begin(leader jsKeycode: UInt16) {
+    runtime.withLock { $0.state.begin(leader: jsKeycode, at: nowMillis()) }
+  }
+
+  func cancel() {
+    runtime.withLock { $0.state.cancel() }
+  }
+
+  /// Offer a key event to a pending sequence. Returns true if the event
+  /// completed a sequence (or is the key-up of one) and must be swallowed.
+  /// Auto-repeat never resolves: holding the leader key must not count as the
+  /// follow-up. An unmatched or expired follow-up returns false so the key
+  /// passes through the normal path untouched.
+  func handleFollowUp(macKeycode: UInt16, keyDown: Bool, isRepeat: Bool) -> Bool {
+    if !keyDown {
+      return runtime.withLock { $0.swallowedUps.remove(macKeycode) != nil }
+    }
+    if isRepeat { return false }
+    let js = KeyCodes.macToJs(macKeycode)
+    let resolution: SequenceState.Resolution = runtime.withLock { rt in
+      guard rt.state.isPending else { return .idle }
+      guard let js else { rt.state.cancel(); return .unmatched }
+      let r = rt.state.resolve(followUp: js, at: nowMillis(), table: rt.table, timeoutMs: rt.timeoutMs)
+      if case .matched = r { rt.swallowedUps.insert(macKeycode) }
+      return r
+    }
+    switch resolution {
+    case .idle:
+      return false
+    case .expired:
+      FileLog.shared.info("Sequence: pending leader expired before follow-up (mac=\(macKeycode)) — passing through.")
+      return false
+    case .unmatched:
+      FileLog.shared.info("Sequence: follow-up mac=\(macKeycode) not mapped — cancelled, passing through.")
+      return false
+    case .matched(let action):
+      let name = js.map(KeyCodes.name) ?? "?"
+      FileLog.shared.info("Sequence: follow-up \(name) -> \(describeAction(action))")
+      let (combo, caption) = hudParts(action)
+      HudCenter.shared.emit(trigger: "… \(name)", combo: combo, caption: caption)
+      ActionExecutor.execute(action, keyDown: true, activeModifiers: [])
+      ActionExecutor.execute(action, keyDown: false, activeModifiers: [])
+      return true
+    }
+  }
+}
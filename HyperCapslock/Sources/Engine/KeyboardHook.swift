@@ -1,5 +1,6 @@
 import Foundation
 import CoreGraphics
+import os
 
 /// The CGEventTap callback. Must be a bare C function (captures nothing); all
 /// state lives in singletons, exactly like the Rust globals.
@@ -30,24 +31,67 @@ private func hcTapCallback(
         return pass
     }
 
-    // Skip our own injected events (no feedback loop / re-detection).
-    let injectedUserData = event.getIntegerValueField(.eventSourceUserData)
-    if injectedUserData == KeyPoster.injectedMagic {
+    // Skip our own injected events (no feedback loop / re-detection). Tag and
+    // pid must both match — see `KeyPoster.isOwnEvent`.
+    if KeyPoster.isOwnEvent(event) {
         return pass
     }
     // Same idea for the input-source-fix synthetic events (⌃Space / ⌘ reset),
     // but with a distinct tag we log explicitly — positive proof the tap saw them
     // as ours and did NOT re-enter the F18/chord/modifier-double-tap logic.
-    if injectedUserData == InputSourceFix.syntheticEventUserData {
+    if KeyPoster.isOwnEvent(event, magic: InputSourceFix.syntheticEventUserData) {
         FileLog.shared.info("Tap: passing through input-source-fix synthetic event (keycode=\(UInt16(event.getIntegerValueField(.keyboardEventKeycode))) type=\(type.rawValue)) — not re-processed.")
         return pass
     }
 
-    // If paused, pass everything through.
-    if state.isPaused { return pass }
+    FileLog.shared.debug("Tap: type=\(type.rawValue) keycode=\(event.getIntegerValueField(.keyboardEventKeycode)) "
+                         + "flags=0x\(String(event.flags.rawValue, radix: 16)) caps=\(state.capsDown) paused=\(state.isPaused)")
+
+    // A chord's multi-key sequence may still be posting on the worker. Waiting
+    // for it here could get the tap disabled, so swallow the key and re-post it
+    // behind the sequence; the copy comes back tagged and is handled then.
+    if (type == .keyDown || type == .keyUp) && !KeyPoster.isOwnEvent(event, magic: KeyPoster.deferredMagic)
+        && KeyboardHook.deferBehindPostQueue(event) {
+        return nil
+    }
+
+    // If paused, pass everything through — except the Toggle Pause chord, so
+    // the key that paused the engine can resume it too.
+    if state.isPaused {
+        let keycode = UInt16(event.getIntegerValueField(.keyboardEventKeycode))
+        if keycode == KeyCodes.f18 {
+            if type == .keyDown || type == .keyUp { state.pausedCapsDown = type == .keyDown }
+        } else if type == .keyDown && event.getIntegerValueField(.keyboardEventAutorepeat) == 0
+                    && ActionExecutor.handlePausedKey(keycode: keycode, capsHeld: state.pausedCapsDown,
+                                                      activeModifiers: activeModifierFlags(event.flags)) {
+            return nil
+        }
+        return pass
+    }
+    // Same for an excluded frontmost app (it has its own CapsLock handling).
+    // AppState releases any in-flight chord when such an app comes forward.
+    if state.isExcluded(FrontmostAppTracker.shared.currentBundleID()) { return pass }
+
+    // ─── Caps + mouse button (opt-in mouse tap) ───
+    // Reported, never swallowed: every click reaches the app.
+    if let button = CapsMouseButton(type) {
+        if state.capsDown && state.capsLayerMode.allowsHold { capsHoldClicked(button) }
+        return pass
+    }
 
     let keycode = UInt16(event.getIntegerValueField(.keyboardEventKeycode))
     let flags = event.flags
+    // Anything typed while the layer is on (F18's own autorepeat included)
+    // shows the hold is live — see `LayerWatchdog`.
+    if state.capsDown { state.layerActivityAtMs = nowMillis() }
+
+    // ─── Key chatter: a bounced second press never reaches the layer ───
+    if (type == .keyDown || type == .keyUp)
+        && KeyDebounce.shared.shouldDrop(keycode: keycode, keyDown: type == .keyDown,
+                                         isRepeat: event.getIntegerValueField(.keyboardEventAutorepeat) != 0,
+                                         now: nowMillis()) {
+        return nil
+    }
 
     // F18 = physical CapsLock (remapped via hidutil) → proper KeyDown/KeyUp.
     if keycode == KeyCodes.f18 {
@@ -61,21 +105,38 @@ private func hcTapCallback(
             ActionExecutor.releaseAllInFlightChords()
             let pressedAt = state.swapCapsPressedAtMs(0)
             let held = nowMillis() &- pressedAt
-            if wasDown && !state.didRemap {
-                if held <= EngineConstants.capsTapMaxMs {
+            if wasDown {
+                switch ActionExecutor.classifyCapsRelease(heldMs: held, didRemap: state.didRemap) {
+                case .shortTap:
                     ActionExecutor.handleShortTap()
-                } else {
+                case .suppress:
                     FileLog.shared.info("Caps(F18) held \(held)ms (> \(EngineConstants.capsTapMaxMs)ms). Suppressing native CapsLock toggle.")
+                case .afterRemap:
+                    FileLog.shared.info("Caps(F18) up after remap sequence.")
+                    // The layer was used as a hold; a one-shot armed before it is moot.
+                    _ = state.swapOneShotArmedAtMs(0)
                 }
-            } else if wasDown {
-                FileLog.shared.info("Caps(F18) up after remap sequence.")
             }
         }
         return nil  // swallow F18
     }
 
-    // Raw CapsLock FlagsChanged (in case hidutil isn't active) → swallow.
-    if type == .flagsChanged && keycode == KeyCodes.capsLock {
+    // Raw CapsLock FlagsChanged. With the F18 remap in place these are only
+    // echoes of our own lock-state changes → swallow. Without it (hidutil
+    // failed, see `EngineState.rawCapsFallback`) they're all there is of Caps,
+    // and macOS sends one per lock toggle, not per press: the lock turning on
+    // starts the hold and turning off ends it, so the layer latches — press
+    // Caps, chord, press Caps again. No tap classification: the lock itself
+    // is the toggle. With another layer key, Caps Lock is native: pass it on.
+    if type == .flagsChanged && keycode == KeyCodes.capsLock && state.layerKey == .capsLock {
+        if state.rawCapsFallback {
+            if flags.contains(.maskAlphaShift) {
+                beginCapsHold()
+            } else if endCapsHold() {
+                ActionExecutor.releaseAllInFlightChords()
+                _ = state.swapCapsPressedAtMs(0)
+            }
+        }
         return nil
     }
 
@@ -90,7 +151,7 @@ private func hcTapCallback(
                 UsageStats.shared.record(triggerUniqueID(.doubleTapModifier(modifier)))
                 let (combo, caption) = hudParts(action)
                 HudCenter.shared.emit(trigger: "\(modifierHudLabel(modifier)) ×2", combo: combo, caption: caption)
-                ActionExecutor.fireDoubleTapModifierAction(action)
+                ActionExecutor.fireDoubleTapModifierAction(action, modifier: modifier)
             }
         } else if type == .keyDown {
             // A regular key press means any in-progress modifier tap is a chord.
@@ -119,8 +180,53 @@ private func hcTapCallback(
         }
     }
 
+    // ─── Leader-key sequence follow-up ───
+    // A prior Caps+<leader> armed a pending sequence; the next key press (with
+    // or without Caps held) resolves it. Unmatched/expired keys fall through.
+    if type == .keyDown || type == .keyUp {
+        let isRepeat = event.getIntegerValueField(.keyboardEventAutorepeat) != 0
+        if KeySequences.shared.handleFollowUp(macKeycode: keycode, keyDown: type == .keyDown, isRepeat: isRepeat) {
+            if state.capsDown { state.didRemap = true }
+            return nil
+        }
+    }
+
+    let capsHeldAsLayer = state.capsDown && state.capsLayerMode.allowsHold
+    let isRepeat = event.getIntegerValueField(.keyboardEventAutorepeat) != 0
+
+    // ─── Global hotkeys (no Caps) ───
+    // Only an exact modifier match is consumed; everything else is typing and
+    // passes on. While Caps is held the layer wins.
+    if !capsHeldAsLayer && type == .keyDown && !isRepeat
+        && ActionExecutor.handleHotkey(keycode: keycode, activeModifiers: activeModifierFlags(flags)) {
+        return nil
+    }
+
+    // ─── One-shot Caps layer ───
+    // A short tap armed the layer for the next key (see handleShortTap). Also
+    // carries that key's autorepeat / key-up (and a hotkey's), which arrive
+    // with Caps up.
+    if !capsHeldAsLayer && (type == .keyDown || type == .keyUp) {
+        if ActionExecutor.handleOneShotKey(keycode: keycode, keyDown: type == .keyDown, isRepeat: isRepeat,
+                                           activeModifiers: activeModifierFlags(flags)) {
+            return nil
+        }
+    }
+
+    // ─── Raw escape: Caps+<escape modifier>+key types the key itself ───
+    // Skips the whole layer for this press (mapping, passthrough list, count,
+    // sequence) and strips the escape modifier so the app sees the bare key.
+    // Its key-up finds no latched chord below and passes through too.
+    if capsHeldAsLayer && type == .keyDown,
+       let strip = ActionExecutor.rawEscapeFlags(keycode: keycode, activeModifiers: activeModifierFlags(flags)) {
+        state.didRemap = true   // Caps was used as a layer; releasing it mustn't toggle
+        event.flags = flags.subtracting(strip)
+        FileLog.shared.info("Caps chord RAW ESCAPE (mac=\(keycode)) — passing the key through unmapped.")
+        return pass
+    }
+
     // ─── Caps + key chord ───
-    if state.capsDown {
+    if capsHeldAsLayer {
         let keyDown = (type == .keyDown)
         let activeMods = activeModifierFlags(flags)
         let js = KeyCodes.macToJs(keycode)
@@ -147,6 +253,21 @@ final class KeyboardHook {
     /// cross-thread style as `eventTap`). Used to run chord-release on the tap
     /// thread so it serializes with chord handling.
     private var tapRunLoop: CFRunLoop?
+    /// Signalled when the tap thread exits; non-nil while a tap thread exists.
+    /// Main-thread owned (start/stop/restart are called from the main thread).
+    private var tapThreadDone: DispatchSemaphore?
+    /// Set by `stop()`; the tap loop checks it instead of rebuilding the tap.
+    private let stopRequested = OSAllocatedUnfairLock(initialState: false)
+    /// True while a tap is actually installed (tapCreate succeeded and the run
+    /// loop is up). Written on the tap thread, read from anywhere.
+    private let tapInstalled = OSAllocatedUnfairLock(initialState: false)
+    /// Signalled to cut a tapCreate retry sleep short (a grant just landed).
+    private let retryWake = DispatchSemaphore(value: 0)
+
+    /// True while the tap thread is alive (installing, retrying, or running).
+    var isRunning: Bool { tapThreadDone != nil }
+    /// True while the tap is installed and intercepting keys.
+    var isInstalled: Bool { tapInstalled.withLock { $0 } }
 
     /// Release every in-flight chord, but **on the tap thread's run loop** so it
     /// can't race a fresh chord key-down being processed there (which would post
@@ -179,9 +300,26 @@ final class KeyboardHook {
         }
     }
 
-    /// Install hidutil remap + the event tap. Call once at launch.
+    /// Run `work` on the tap thread's run loop, like the chord release above, for
+    /// off-tap-thread code that changes state the tap's Caps handling owns (the
+    /// layer watchdog). Inline when already there, or when no tap is running.
+    func performOnTapThread(_ work: @escaping () -> Void) {
+        Self.perform(on: tapRunLoop, work)
+    }
+
+    static func perform(on rl: CFRunLoop?, _ work: @escaping () -> Void) {
+        guard let rl, CFRunLoopGetCurrent() !== rl else {
+            work()
+            return
+        }
+        CFRunLoopPerformBlock(rl, CFRunLoopMode.commonModes.rawValue, work)
+        CFRunLoopWakeUp(rl)
+    }
+
+    /// Prompt for Accessibility if needed and spawn the event-tap thread. Call
+    /// once at launch; after a `stop()`, call `start()` or `restart()` again.
     func start() {
-        FileLog.shared.info("Starting macOS keyboard hook.")
+        FileLog.shared.info("Starting macOS keyboard hook (tap at \(EngineState.shared.tapLocation.rawValue), posting at \(EngineState.shared.postLocation.rawValue), injected-event tag 0x\(String(KeyPoster.injectedMagic, radix: 16)), pid \(KeyPoster.ownPID)).")
 
         // The event tap is an ACTIVE (.defaultTap) tap, which macOS gates on
         // Accessibility — NOT Input Monitoring (that's for .listenOnly taps).
@@ -196,9 +334,72 @@ final class KeyboardHook {
         // hidutil remaps (CapsLock→F18 + any user key remaps) are applied by
         // AppState.applyKeyRemaps() during bootstrap(), which runs before this —
         // keeping a single owner of the UserKeyMapping so the two never clobber
-        // each other.
+        // each other. Which Caps input that left us with is logged here, once
+        // per start, so a log shows at a glance why Caps latches.
+        if EngineState.shared.rawCapsFallback {
+            FileLog.shared.warn("Caps input: raw Caps Lock fallback (no CapsLock→F18 remap) — Caps latches the layer on and off, no key repeat or tap actions.")
+        } else {
+            FileLog.shared.info("Caps input: F18 via hidutil remap (layer key: \(EngineState.shared.layerKey.rawValue)).")
+        }
 
-        let thread = Thread { [weak self] in self?.runTapLoop() }
+        spawnTapThread()
+        LayerWatchdog.shared.start()
+    }
+
+    /// Tear the tap down completely: disable it, stop the tap thread's run loop
+    /// and wait (bounded) for the thread to exit. Held chords and a Caps hold are
+    /// released on the way out, on the tap thread. Unlike pause, nothing is
+    /// intercepted afterwards. The hidutil remap is left alone — that's the
+    /// caller's call (see `AppState.setEnabled`). Idempotent.
+    func stop() {
+        guard let done = tapThreadDone else { return }
+        stopRequested.withLock { $0 = true }
+        if let tap = eventTap { CGEvent.tapEnable(tap: tap, enable: false) }
+        if let rl = tapRunLoop {
+            // Queued as a block rather than a bare CFRunLoopStop: if the thread
+            // hasn't entered CFRunLoopRun yet, a direct stop would be lost.
+            CFRunLoopPerformBlock(rl, CFRunLoopMode.commonModes.rawValue) { CFRunLoopStop(CFRunLoopGetCurrent()) }
+            CFRunLoopWakeUp(rl)
+        }
+        // A thread still retrying tapCreate notices the flag once woken.
+        retryWake.signal()
+        if done.wait(timeout: .now() + 2.0) == .timedOut {
+            FileLog.shared.warn("Event tap thread did not exit within 2s of stop().")
+        }
+        tapThreadDone = nil
+        FileLog.shared.info("macOS keyboard hook stopped.")
+    }
+
+    /// Accessibility just flipped to granted (from `PermissionWatcher`). Make sure
+    /// a tap gets installed without a relaunch: spawn the tap thread if none is
+    /// alive, or wake a thread that's sleeping between tapCreate retries so it
+    /// installs now instead of up to a second later. Main thread only.
+    func accessibilityGranted() {
+        if !isRunning {
+            FileLog.shared.info("Accessibility granted and no tap thread alive — installing the event tap automatically.")
+            spawnTapThread()
+        } else if !isInstalled {
+            FileLog.shared.info("Accessibility granted — retrying event tap install now.")
+            retryWake.signal()
+        }
+    }
+
+    /// Stop and reinstall the tap, for settings that need a fresh tap.
+    func restart() {
+        stop()
+        spawnTapThread()
+    }
+
+    private func spawnTapThread() {
+        guard tapThreadDone == nil else { return }   // never run two tap threads
+        stopRequested.withLock { $0 = false }
+        while retryWake.wait(timeout: .now()) == .success {}   // drop stale wakes
+        let done = DispatchSemaphore(value: 0)
+        tapThreadDone = done
+        let thread = Thread { [weak self] in
+            self?.runTapLoop()
+            done.signal()
+        }
         thread.name = "me.xueshi.hypercapslock.eventtap"
         thread.start()
     }
@@ -215,23 +416,47 @@ final class KeyboardHook {
         HidUtil.cleanupRemap()
     }
 
-    private func runTapLoop() {
-        FileLog.shared.info("macOS hook thread spawned. AXIsProcessTrusted=\(Permissions.isAccessibilityGranted)")
-        let mask: CGEventMask =
+    /// While a `PostQueue` sequence is posting, queue a copy of `event` to be
+    /// re-posted after it and return true (the tap then swallows the original);
+    /// false when the queue is idle. Never waits, whatever is queued.
+    static func deferBehindPostQueue(_ event: CGEvent,
+                                     repost: @escaping (CGEvent) -> Void = KeyPoster.repostDeferred) -> Bool {
+        guard PostQueue.isBusy, let copy = event.copy() else { return false }
+        PostQueue.enqueue { repost(copy) }
+        return true
+    }
+
+    /// Events the tap asks for: keys and modifiers, plus mouse-button downs
+    /// when Caps + mouse click is on.
+    static func eventMask(includeMouse: Bool) -> CGEventMask {
+        var mask: CGEventMask =
             (1 << CGEventType.keyDown.rawValue) |
             (1 << CGEventType.keyUp.rawValue) |
             (1 << CGEventType.flagsChanged.rawValue)
+        if includeMouse {
+            mask |= (1 << CGEventType.leftMouseDown.rawValue) |
+                (1 << CGEventType.rightMouseDown.rawValue) |
+                (1 << CGEventType.otherMouseDown.rawValue)
+        }
+        return mask
+    }
+
+    private func runTapLoop() {
+        FileLog.shared.info("macOS hook thread spawned. AXIsProcessTrusted=\(Permissions.isAccessibilityGranted)")
+        let withMouse = EngineState.shared.capsMouseEvents
+        let mask = Self.eventMask(includeMouse: withMouse)
 
         // Retry tapCreate until it succeeds. An active tap requires Accessibility;
         // creation fails (returns nil) until it's granted. Retrying tapCreate
         // itself — rather than polling AXIsProcessTrusted(), which can return a
         // stale cached value within a process — lets the tap auto-install the
         // moment the user grants Accessibility, with no relaunch.
+        let location = EngineState.shared.tapLocation
         var attempt = 0
-        while true {
+        while !stopRequested.withLock({ $0 }) {
             attempt += 1
             guard let tap = CGEvent.tapCreate(
-                tap: .cghidEventTap,
+                tap: location.cgLocation,
                 place: .headInsertEventTap,
                 options: .defaultTap,
                 eventsOfInterest: mask,
@@ -241,7 +466,7 @@ final class KeyboardHook {
                 if attempt == 1 || attempt % 5 == 0 {
                     FileLog.shared.warn("⏳ CGEventTap creation FAILED (attempt \(attempt)). Accessibility likely not granted yet (AXIsProcessTrusted=\(Permissions.isAccessibilityGranted)). Retrying every 1s — grant Accessibility and the tap will auto-install with NO relaunch.")
                 }
-                Thread.sleep(forTimeInterval: 1.0)
+                _ = retryWake.wait(timeout: .now() + 1.0)
                 continue
             }
 
@@ -255,19 +480,63 @@ final class KeyboardHook {
             CFRunLoopAddSource(CFRunLoopGetCurrent(), source, .commonModes)
             tapRunLoop = CFRunLoopGetCurrent()
             CGEvent.tapEnable(tap: tap, enable: true)
-            FileLog.shared.info("✅ macOS keyboard event tap INSTALLED and enabled (attempt \(attempt)). mappings=\(MappingsRegistry.shared.snapshot().count) isPaused=\(EngineState.shared.isPaused)")
+            tapInstalled.withLock { $0 = true }
+            FileLog.shared.info("✅ macOS keyboard event tap INSTALLED and enabled at \(location.rawValue)\(withMouse ? " with mouse buttons" : "") (attempt \(attempt)). mappings=\(MappingsRegistry.shared.snapshot().count) isPaused=\(EngineState.shared.isPaused)")
             // Recover from a prior crash/kill that left a hold-modifier stuck down.
             ActionExecutor.normalizeSyntheticModifiersAtStartup()
             CFRunLoopRun()   // blocks while the tap is alive
-            FileLog.shared.warn("CFRunLoopRun returned; tap loop will rebuild the tap.")
-            // The tap died for some reason other than the handled tap-disabled
-            // callback; we'll miss any pending key-up, so force-release now (on
-            // the tap thread, tap already dead → no concurrent callback to race).
+            tapInstalled.withLock { $0 = false }
+            let stopping = stopRequested.withLock { $0 }
+            if !stopping {
+                FileLog.shared.warn("CFRunLoopRun returned; tap loop will rebuild the tap.")
+            }
+            // Stopped on purpose, or the tap died for some reason other than the
+            // handled tap-disabled callback. Either way we'll miss any pending
+            // key-up, so force-release now (on the tap thread, tap already dead →
+            // no concurrent callback to race).
             ActionExecutor.releaseAllInFlightChords()
             // Same reasoning for a CapsLock hold — end it so a missed F18 key-up
             // can't leave a hold latched (mirrors the tap-disabled branch).
             endCapsHold()
+            CFRunLoopRemoveSource(CFRunLoopGetCurrent(), source, .commonModes)
+            CFMachPortInvalidate(tap)
             eventTap = nil
+            tapRunLoop = nil
+        }
+    }
+}
+
+extension EventTapLocation {
+    var cgLocation: CGEventTapLocation {
+        switch self {
+        case .hid: return .cghidEventTap
+        case .session: return .cgSessionEventTap
+        case .annotatedSession: return .cgAnnotatedSessionEventTap
+        }
+    }
+}
+
+extension CapsMouseButton {
+    /// The button a mouse-down event is for; nil for every other event type.
+    init?(_ type: CGEventType) {
+        switch type {
+        case .leftMouseDown: self = .left
+        case .rightMouseDown: self = .right
+        case .otherMouseDown: self = .other
+        default: return nil
+        }
+    }
+}
+
+extension RawEscapeModifier {
+    /// The event flag that marks the modifier as held; nil when off.
+    var flag: CGEventFlags? {
+        switch self {
+        case .off: return nil
+        case .fn: return .maskSecondaryFn
+        case .control: return .maskControl
+        case .option: return .maskAlternate
+        case .command: return .maskCommand
         }
     }
 }
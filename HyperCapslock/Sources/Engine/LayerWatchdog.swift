@@ -0,0 +1,58 @@
+import CoreGraphics
+import Foundation
+
+/// Recovers a layer stuck on. If the F18 key-up is lost (a busy system, focus
+/// changing mid-hold), `capsDown` stays true and every key keeps going through
+/// the layer until Caps is pressed again. Every few seconds this looks for a
+/// hold with no key activity for `EngineConstants.layerStuckTimeoutMs`; if the
+/// key isn't physically down either, it ends the hold and releases held
+/// chords, and logs the recovery. The raw Caps Lock fallback is left alone —
+/// there the layer latches on purpose.
+final class LayerWatchdog {
+  static let shared = LayerWatchdog()
+
+  private let queue = DispatchQueue(label: "me.xueshi.hypercapslock.layer-watchdog", qos: .utility)
+  private var timer: DispatchSourceTimer?
+
+  private init() {}
+
+  /// Start checking (idempotent).
+  func start(interval: TimeInterval = 5.0) {
+    queue.async { [self] in
+      guard timer == nil else { return }
+      let t = DispatchSource.makeTimerSource(queue: queue)
+      t.schedule(deadline: .now() + interval, repeating: interval, leeway: .seconds(1))
+      t.setEventHandler { [weak self] in self?.check() }
+      timer = t
+      t.resume()
+    }
+  }
+
+  /// Whether a hold whose last key activity was at `lastActivityMs` is stuck
+  /// at `now`. A key still physically down is a real (if long) hold.
+  static func isStuck(capsDown: Bool, lastActivityMs: UInt64, now: UInt64, physicallyDown: Bool,
+                      rawFallback: Bool) -> Bool {
+    capsDown && !rawFallback && !physicallyDown && now &- lastActivityMs > EngineConstants.layerStuckTimeoutMs
+  }
+
+  private func check() {
+    let state = EngineState.shared
+    guard state.capsDown else { return }
+    let now = nowMillis()
+    let last = max(state.capsPressedAtMs, state.layerActivityAtMs)
+    // hidutil remaps below the window server, so its key state reports F18.
+    let physicallyDown = CGEventSource.keyState(.hidSystemState, key: CGKeyCode(KeyCodes.f18))
+    guard Self.isStuck(capsDown: true, lastActivityMs: last, now: now, physicallyDown: physicallyDown,
+                       rawFallback: state.rawCapsFallback) else { return }
+    FileLog.shared.warn("[RECOVERY] Caps layer on for \((now &- last) / 1000)s with no key activity and the layer key up — "
+                        + "its key-up was lost. Releasing the layer and held chords.")
+    // End the hold where Caps events are handled, so it can't interleave with
+    // a Caps press or chord the tap is processing right now. One that landed
+    // since the check above means the layer is live again: leave it.
+    KeyboardHook.shared.performOnTapThread {
+      guard state.capsDown, max(state.capsPressedAtMs, state.layerActivityAtMs) == last else { return }
+      ActionExecutor.releaseAllInFlightChords()
+      if endCapsHold() { _ = state.swapCapsPressedAtMs(0) }
+    }
+  }
+}
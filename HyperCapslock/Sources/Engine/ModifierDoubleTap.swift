@@ -80,14 +80,14 @@ final class ModifierDoubleTap {
 
     static func anyConfigured() -> Bool {
         MappingsRegistry.shared.withMappings { mappings in
-            mappings.contains { if case .doubleTapModifier = $0.trigger { return true }; return false }
+            mappings.contains { if case .doubleTapModifier = $0.trigger { return $0.enabled }; return false }
         }
     }
 
     private static func configuredAction(_ m: ModifierKey) -> ActionConfig? {
         let ctx = ActionExecutor.currentContext()
         return MappingsRegistry.shared.withMappings { mappings in
-            guard let entry = mappings.first(where: { if case .doubleTapModifier(let cfg) = $0.trigger { return cfg == m }; return false })
+            guard let entry = mappings.first(where: { if case .doubleTapModifier(let cfg) = $0.trigger { return $0.enabled && cfg == m }; return false })
             else { return nil }
             return ActionExecutor.effectiveAction(entry, ctx)
         }
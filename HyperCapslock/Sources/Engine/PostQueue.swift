@@ -0,0 +1,64 @@
+import Foundation
+import os
+
+/// Serial worker for multi-event keystroke sequences (a jump's N taps, a wrap
+/// pair's characters, the new-line macros), so the tap callback only queues
+/// them and returns. A callback that loops over hundreds of posts is what gets
+/// the tap disabled for timeout.
+///
+/// Ordering: sequences run one at a time in the order queued. A key typed
+/// while one is still posting isn't handled then: the tap swallows it and
+/// queues a re-post behind the sequence (`KeyboardHook.deferBehindPostQueue`),
+/// so it still lands after everything the chord posted and the callback never
+/// waits. Single keystrokes (arrows, backspace, combos) are still posted
+/// inline — they're one event and must pair with their key-up.
+enum PostQueue {
+  private static let queue = DispatchQueue(label: "me.xueshi.hypercapslock.post-queue", qos: .userInteractive)
+  /// Sequences queued and not yet finished; lets `drain()` skip the queue
+  /// hop when idle, which is nearly always.
+  private static let pending = OSAllocatedUnfairLock(initialState: 0)
+  /// Injection delay the running sequence may still sleep, in ms; 0 between
+  /// sequences, so a pause outside one (i.e. on the tap thread) never sleeps.
+  private static let pauseBudgetMs = OSAllocatedUnfairLock(initialState: 0)
+
+  /// Queue `work` behind any sequence still running. Callable from any thread.
+  static func enqueue(_ work: @escaping () -> Void) {
+    pending.withLock { $0 += 1 }
+    queue.async {
+      pauseBudgetMs.withLock { $0 = EngineConstants.maxInjectPausePerSequenceMs }
+      work()
+      pauseBudgetMs.withLock { $0 = 0 }
+      pending.withLock { $0 -= 1 }
+    }
+  }
+
+  /// Sleep up to `ms` inside a sequence, as far as its budget allows, and
+  /// return how long that was. Every wait in a sequence goes through here, so
+  /// none holds the queue past `maxInjectPausePerSequenceMs` in total.
+  @discardableResult
+  static func pause(_ ms: Int) -> Int {
+    let granted = allowedPause(ms)
+    if granted > 0 { usleep(useconds_t(granted) * 1000) }
+    return granted
+  }
+
+  /// How much of a wanted `ms` pause the running sequence may still take,
+  /// deducted from its budget (see `maxInjectPausePerSequenceMs`).
+  static func allowedPause(_ ms: Int) -> Int {
+    pauseBudgetMs.withLock { budget in
+      let granted = min(max(ms, 0), budget)
+      budget -= granted
+      return granted
+    }
+  }
+
+  /// True while a sequence is queued or still posting.
+  static var isBusy: Bool { pending.withLock { $0 > 0 } }
+
+  /// Block until every queued sequence has been posted. Never from inside a
+  /// sequence, and not on the tap thread (it defers keys instead).
+  static func drain() {
+    guard pending.withLock({ $0 > 0 }) else { return }
+    queue.sync {}
+  }
+}
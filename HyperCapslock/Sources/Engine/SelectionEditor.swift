@@ -0,0 +1,71 @@
+import ApplicationServices
+import Foundation
+
+/// Runs `.transformSelection` actions through the Accessibility API instead of
+/// synthesized keys: read the focused element's selected text, transform it,
+/// write it back as the new selection, and re-select the result. Works in any
+/// field that exposes `AXSelectedText` as settable — native text views, most
+/// browsers' inputs — and does nothing (logged, not reported as a failure) in
+/// one that doesn't, or when nothing is selected.
+///
+/// AX calls block on the target app, so callers run this off the tap thread.
+enum SelectionEditor {
+  /// Apply `op` to the current selection. Returns false when nothing changed.
+  @discardableResult
+  static func apply(_ op: SelectionTransform) -> Bool {
+    let system = AXUIElementCreateSystemWide()
+    guard let focused = copyAttribute(system, kAXFocusedUIElementAttribute).map({ $0 as! AXUIElement }) else {
+      FileLog.shared.info("Selection \(op.rawValue): no focused element")
+      return false
+    }
+    guard let selected = copyAttribute(focused, kAXSelectedTextAttribute) as? String, !selected.isEmpty else {
+      FileLog.shared.info("Selection \(op.rawValue): no selected text in the focused element")
+      return false
+    }
+    let replacement = transform(selected, op)
+    guard replacement != selected else { return false }
+    var settable: DarwinBoolean = false
+    guard AXUIElementIsAttributeSettable(focused, kAXSelectedTextAttribute as CFString, &settable) == .success,
+          settable.boolValue else {
+      FileLog.shared.info("Selection \(op.rawValue): the focused element's selection is read-only")
+      return false
+    }
+    let range = selectedRange(focused)
+    let err = AXUIElementSetAttributeValue(focused, kAXSelectedTextAttribute as CFString, replacement as CFString)
+    guard err == .success else {
+      FileLog.shared.warn("Selection \(op.rawValue): writing the selection failed (AXError \(err.rawValue))")
+      return false
+    }
+    // Setting the selected text leaves a caret after it; select the result
+    // again so a second transform (or Cmd+C) applies to the same text.
+    if let range {
+      var result = CFRange(location: range.location, length: (replacement as NSString).length)
+      if let value = AXValueCreate(.cfRange, &result) {
+        AXUIElementSetAttributeValue(focused, kAXSelectedTextRangeAttribute as CFString, value)
+      }
+    }
+    return true
+  }
+
+  /// The text `op` turns `text` into. Pure, for tests.
+  static func transform(_ text: String, _ op: SelectionTransform) -> String {
+    switch op {
+    case .uppercase: return text.uppercased()
+    case .lowercase: return text.lowercased()
+    case .trim: return text.trimmingCharacters(in: .whitespacesAndNewlines)
+    }
+  }
+
+  private static func copyAttribute(_ element: AXUIElement, _ attribute: String) -> CFTypeRef? {
+    var value: CFTypeRef?
+    guard AXUIElementCopyAttributeValue(element, attribute as CFString, &value) == .success else { return nil }
+    return value
+  }
+
+  private static func selectedRange(_ element: AXUIElement) -> CFRange? {
+    guard let value = copyAttribute(element, kAXSelectedTextRangeAttribute),
+          CFGetTypeID(value) == AXValueGetTypeID() else { return nil }
+    var range = CFRange()
+    return AXValueGetValue(value as! AXValue, .cfRange, &range) ? range : nil
+  }
+}
@@ -87,12 +87,19 @@ final class LocalizationManager: ObservableObject {
             "status.label": "Status", "status.initializing": "Initializing...",
             "status.running": "Running", "status.paused": "Paused", "status.error": "Error",
             "status.pause": "Pause", "status.resume": "Resume",
+            "status.disabled": "Disabled", "tray.disable": "Disable Completely", "tray.enable": "Enable",
+            "status.auto_paused_excluded": "Auto-paused (excluded app)",
+            "status.secure_input": "Paused: Secure Input active", "status.secure_input_app": "Paused: Secure Input active ({app})",
+            "status.resumes_in": "resumes in {minutes} min",
             "settings.label": "Options", "settings.autostart": "Start at Login",
+            "settings.autostart_approval": "Waiting for approval in System Settings ▸ Login Items.", "settings.autostart_open": "Open Login Items…",
             "settings.hide_dock": "Hide Dock Icon", "settings.show_hud": "Show On-screen HUD",
+            "settings.tray_style": "Menu Bar", "tray_style.icon": "Icon", "tray_style.title": "Text", "tray_style.both": "Both",
             "settings.show_window_on_launch": "Show window on launch",
             "settings.show_window_on_launch_hint": "When off, the app starts quietly in the menu bar. Open the window from the menu-bar icon or the Dock.",
             "settings.anydrag_caps_hold": "Hold CapsLock to drag windows (works with AnyDrag)",
             "settings.anydrag_caps_hold_hint": "Requires AnyDrag with “CapsLock (via HyperCapslock)” enabled.",
+            "settings.control_socket": "Scripting socket", "settings.control_socket_hint": "Lets local scripts drive HyperCapslock over a Unix socket at {path}. Each request needs the token.", "settings.control_socket_copy_token": "Copy Token",
             "remap.label": "Key Remapping",
             "remap.hint": "Turn a spare right-side modifier into a free function key (F13–F19) you can bind as a global shortcut in any app — including HyperCapslock itself. ⚠️ The remapped key loses its normal function (its left-side twin keeps working).",
             "remap.add": "Add Remap",
@@ -111,6 +118,8 @@ final class LocalizationManager: ObservableObject {
             "mappings.title": "Action Mappings (Caps+Key)", "mappings.add": "Add",
             "mappings.add_title": "Add Mapping", "mappings.edit": "Edit", "mappings.edit_title": "Edit Mapping",
             "mappings.delete": "Delete", "mappings.save": "Save", "mappings.empty": "No action mappings yet",
+            "mappings.reset_one": "Reset to Default", "mappings.reset_one_hint": "Restore this trigger's shipped binding", "mappings.reset_one_removes": "This trigger has no default binding — resetting removes it", "toast.mapping_reset": "Mapping reset to default", "tray.reset_mappings": "Reset Mappings to Defaults…", "mappings.reset_all_title": "Reset all mappings?", "mappings.reset_all_prompt": "Every mapping is replaced with the defaults. Custom actions are kept. You can undo this from the Mappings page.", "mappings.reset_all_confirm": "Reset", "toast.mappings_reset": "Mappings reset to defaults",
+            "tray.presets": "Presets", "tray.presets_none": "No saved presets", "tray.presets_save": "Save Current as Preset…", "presets.save_title": "Save Preset", "presets.save_prompt": "Save the current mappings and custom actions under this name. A preset with the same name is replaced.", "presets.save_confirm": "Save", "toast.preset_saved": "Preset \"{name}\" saved", "toast.preset_loaded": "Preset \"{name}\" loaded", "toast.preset_failed": "Preset failed: {error}",
             "mappings.press_key": "Press Key", "mappings.caps": "Caps + …", "mappings.caps_shift": "Caps + Shift + …",
             "trigger.hyper_plus_key": "Caps + Key", "trigger.single_tap_hyper": "Single-tap Caps",
             "trigger.double_tap_hyper": "Double-tap Caps", "trigger.double_tap_prefix": "Double-tap",
@@ -118,15 +127,25 @@ final class LocalizationManager: ObservableObject {
             "trigger.double_tap_modifier": "Double-tap Modifier", "side.left": "Left", "side.right": "Right",
             "group.directional": "Directional", "group.jump": "Jump", "group.independent": "Independent",
             "group.input_source": "Input Source", "group.command": "Command", "group.key_combo": "Key Combo",
+            "group.script": "Script", "script.language": "Language", "script.shell": "Shell script", "script.applescript": "AppleScript", "script.source": "Script",
+            "group.notify": "Notification", "actions.notify_title": "Title", "actions.notify_body": "Message",
             "group.open_app": "Open App", "actions.app": "App", "actions.choose_app": "Choose…", "actions.no_app": "No app selected",
             "group.hold_modifier": "Hold Modifier",
             "actions.hold_modifier_hint": "Hold the trigger to keep this modifier pressed; release to let go (e.g. push-to-talk). Use with a Caps+key trigger.",
+            "actions.command_capture": "Capture output", "actions.command_capture_hint": "Wait for the command and show what it prints (first line on the HUD); a non-zero exit marks the mapping as failed. Output past 16 KB is dropped.",
             "action.left": "Left", "action.right": "Right", "action.up": "Up", "action.down": "Down",
             "action.word_forward": "Word Forward", "action.word_back": "Word Back",
-            "action.home": "Home", "action.end": "End", "action.backspace": "Backspace",
+            "action.home": "Line Start", "action.end": "Line End", "action.doc_start": "Document Start", "action.doc_end": "Document End", "action.backspace": "Backspace",
             "action.next_line": "Next Line", "action.insert_quotes": "Insert Quotes",
             "action.toggle_caps_lock": "Toggle Caps Lock", "action.switch_input_source": "Switch Input Source",
             "action.noop": "Do Nothing",
+            "action.release_modifiers": "Release All Modifiers",
+            "action.repeat_last": "Repeat Last Action",
+            "action.sticky_shift": "Sticky Shift",
+            "action.uppercase": "Uppercase Selection", "action.lowercase": "Lowercase Selection", "action.trim": "Trim Selection", "group.selection": "Selection",
+            "action.toggle_pause": "Pause / Resume HyperCapslock", "action.reload_config": "Reload Config", "action.show_window": "Show Window", "action.quit": "Quit HyperCapslock",
+            "action.show_palette": "Command Palette", "palette.search": "Search bindings…", "palette.empty": "No matching bindings",
+            "action.open_line_above": "Open Line Above",
             "action.unknown": "Unknown",
             "theme.light": "Switch to Light Mode", "theme.dark": "Switch to Dark Mode",
             "toast.perm_refreshed": "Permissions refreshed", "toast.perm_failed": "Failed to refresh permissions",
@@ -144,9 +163,11 @@ final class LocalizationManager: ObservableObject {
             "toast.anydrag_caps_hold_enabled": "AnyDrag CapsLock-hold broadcast enabled",
             "toast.anydrag_caps_hold_disabled": "AnyDrag CapsLock-hold broadcast disabled",
             "toast.anydrag_caps_hold_failed": "Failed to change AnyDrag CapsLock-hold setting",
+            "toast.control_socket_enabled": "Scripting socket on", "toast.control_socket_disabled": "Scripting socket off", "toast.control_socket_failed": "Failed to start the scripting socket", "toast.control_token_copied": "Token copied",
             "toast.show_hud_failed": "Failed to change HUD setting",
             "config.export": "Export", "config.import": "Import", "config.import_title": "Import Configuration",
-            "config.import_prompt": "This will replace your current mappings. Continue?",
+            "mappings.undo": "Undo mapping change", "mappings.redo": "Redo mapping change",
+            "config.import_prompt": "This will replace your current mappings. Continue?", "config.import_diff": "{added} new, {changed} changed, {removed} removed.",
             "config.import_confirm": "Replace", "config.overwrite_title": "File Already Exists",
             "config.overwrite_prompt": "{path} already exists. Overwrite?", "config.overwrite_confirm": "Overwrite",
             "toast.config_exported": "Configuration exported", "toast.config_export_failed": "Failed to export configuration",
@@ -160,6 +181,13 @@ final class LocalizationManager: ObservableObject {
             "footer.by": "By", "footer.github": "GitHub Repository",
             "footer.more_apps_desc": "More Apps by Xueshi:",
             "tray.open": "Open Window", "tray.quit": "Quit HyperCapslock", "tray.more_apps": "More Apps by Author…",
+            "tray.release_modifiers": "Release Stuck Modifiers",
+            "tray.input_source": "Input Source: {name}", "is.current": "Current input source", "mappings.input_source_active": "This input source is active now",
+            "tray.grant_accessibility": "Grant Accessibility Permission…",
+            "excluded.label": "Excluded apps", "excluded.add": "Add App…", "excluded.hint": "HyperCapslock passes every key through while one of these apps is frontmost — useful for remote desktops, VMs and games with their own CapsLock handling.", "toast.excluded_apps_failed": "Failed to save excluded apps",
+            "passthrough.label": "Passthrough keys", "passthrough.add": "Add key", "passthrough.capture": "Press a key", "passthrough.everywhere": "All apps", "passthrough.limit_app": "Limit to App…", "passthrough.hint": "Caps + these keys reach the app unchanged, even if a mapping is bound to them — the mapping stays, just switched off here. Limit a key to some apps to punch the hole only there.", "toast.passthrough_failed": "Failed to save passthrough keys",
+            "excluded.recent": "Recently used: {app} ({id})", "excluded.add_recent": "Exclude",
+            "tray.pause_for": "Pause For", "tray.pause_minutes": "{minutes} min",
             "nav.settings": "Settings", "nav.mappings": "Mappings", "nav.actions": "Actions", "nav.about": "About", "nav.toggle_sidebar": "Toggle Sidebar",
             "nav.input_source": "Input Source",
             "is.fix_title": "Fix Input Source Switching",
@@ -174,17 +202,33 @@ final class LocalizationManager: ObservableObject {
             "is.attribution": "Input-source fix methods inspired by Input Source Pro.",
             "toast.is_strategy_failed": "Failed to change input-source fix method",
             "is.source_unavailable_suffix": "(unavailable)",
+            "is.source_not_enabled_warning": "This input source is installed but not enabled — add it in System Settings ▸ Keyboard ▸ Input Sources, or the mapping won't switch.",
+            "is.source_missing_warning": "This input source isn't installed on this Mac — the mapping won't switch.",
             "mappings.switch_input_source": "Switch Input Source",
             "mappings.inline_section": "Action Type",
             "is.refresh": "Re-check input source settings",
             "appearance.label": "Appearance", "settings.language": "Language", "settings.language_system": "Follow System", "settings.theme": "Theme",
             "theme.light_opt": "Light", "theme.dark_opt": "Dark", "theme.system_opt": "System",
             "perm.refresh_label": "Re-check after granting",
+            "selftest.label": "Self-test", "selftest.hint": "Checks permissions and Secure Input, then types one key into a test window to confirm injected keys arrive.", "selftest.run": "Run Self-Test", "selftest.ok": "Self-test passed: injected keys arrive", "selftest.title": "Self-test found problems", "selftest.done": "OK", "selftest.open_accessibility": "Open Accessibility Settings", "selftest.unknown_app": "another app", "selftest.accessibility_missing": "Accessibility isn't granted. Allow HyperCapslock in System Settings ▸ Privacy & Security ▸ Accessibility.", "selftest.tap_not_installed": "The keyboard tap isn't installed. Make sure HyperCapslock isn't disabled, or turn it off and on from the menu bar.", "selftest.secure_input": "Secure Input is on, held by {app}. While a password field is focused, macOS hides keys from HyperCapslock — leave that field or quit {app}.", "selftest.injection_blocked": "An injected key didn't arrive. Another tool may be filtering synthetic keys; try a different Injection location in Settings.", "selftest.injection_untested": "The injection check couldn't run. Keep the test window focused and try again.",
+            "perm.caps_remap": "CapsLock remap", "perm.caps_remap_hint": "CapsLock → F18 via hidutil, so Caps works as a layer key", "perm.caps_remap_active": "Active", "perm.caps_remap_inactive": "Not active", "perm.caps_remap_fallback": "Fallback", "perm.caps_remap_fallback_hint": "hidutil couldn't remap CapsLock, so Caps now latches the layer: press it once to turn the layer on and again to turn it off.", "onboarding.label": "Getting started", "onboarding.intro": "Two things before Caps does anything. Both are checked live — come back here after granting.", "onboarding.accessibility": "Grant Accessibility access", "onboarding.grant": "Grant…", "onboarding.caps_remap": "CapsLock is remapped", "onboarding.done": "Done", "toast.onboarding_failed": "Failed to save onboarding state",
+            "safe_mode.title": "Safe mode", "safe_mode.body": "Started with --safe-mode: default settings, no keyboard hook and no CapsLock remap. Your config files weren't read, and nothing changed here is saved. Fix or remove the broken file in the config folder, then quit and relaunch normally.", "safe_mode.reveal": "Show Config Folder", "toast.safe_mode_engine_off": "The engine stays off in safe mode — relaunch normally to enable it",
+            "logs.label": "Logs", "logs.copy": "Copy Recent Logs", "logs.open": "Open Log", "logs.hint": "Copy puts the last {lines} lines of {path} on the clipboard — attach them when reporting a bug.", "toast.logs_copied": "Recent logs copied", "toast.logs_empty": "Nothing has been logged yet",
+            "logs.debug": "Debug Capture", "logs.debug_start": "Record {seconds}s", "logs.debug_stop": "Stop", "logs.debug_hint": "Logs every key event for a short while, then turns itself off. Reproduce the problem, then copy the logs.", "tray.debug_logging": "Recording Debug Log — Stop",
+            "files.label": "Files", "files.hint": "Back these up or keep them under version control to carry your setup to another Mac. Files marked not created yet appear once there is something to save.", "files.reveal": "Show in Finder", "files.missing": "{path} (not created yet)", "files.mappings": "Mappings and custom actions", "files.settings": "Settings", "files.presets": "Presets", "files.backups": "Config backups", "files.usage_stats": "Usage statistics", "files.log": "Log",
+            "logs.max_size": "Log size limit", "logs.max_size_hint": "Past the limit the log is rotated to .1 and .2, so at most three times the limit is kept.",
             "mappings.action": "Action", "mappings.action_hint": "Pick an action from the library, or create a new one at the bottom of the list.",
+            "mappings.repeat": "While held", "mappings.repeat.auto": "Automatic", "mappings.repeat.never": "Fire once", "mappings.repeat.on_hold": "Repeat",
+            "mappings.label": "Label", "mappings.label_placeholder": "e.g. Save", "mappings.note": "Note",
+            "mappings.enabled_help": "Switch off to let this key through natively; the mapping is kept",
             "mappings.default_action": "Default action", "mappings.default_action_hint": "Runs everywhere unless a per-app rule below matches the frontmost app.",
             "mappings.app_rules": "Per-app rules", "mappings.app_rules_hint": "Checked top to bottom; the first rule whose apps include the frontmost app wins, otherwise the default action runs.",
             "mappings.style": "View style", "mappings.style.grouped": "Grouped by trigger", "mappings.style.keyboard": "Keyboard map",
             "mappings.group.caps_key": "Caps + Key", "mappings.group.caps_shift_key": "Caps + Shift + Key", "mappings.group.single_tap": "Single-tap Caps", "mappings.group.double_tap": "Double-tap Caps", "mappings.group.double_tap_modifier": "Double-tap Modifier",
+            "mappings.group.hotkey": "Hotkeys (no Caps)", "trigger.hotkey": "Hotkey (no Caps)", "mappings.hotkey_modifiers": "Modifiers", "mappings.hotkey_hint": "Fires anywhere without holding Caps. Needs ⌃, ⌥ or ⌘ (Shift alone would swallow typing) — or use a function key. Only the exact combination is taken; while Caps is held, the Caps layer wins.",
+            "mappings.resolves_passthrough": "Unbound today: {chord} passes through to the app.", "mappings.resolves_fallback": "Unbound today: {chord} falls back to {action} (with Shift).",
+            "mappings.resolves_modified": "Unbound today: {chord} runs {action} with the extra modifiers added.", "mappings.caps_modifiers": "Also hold", "mappings.caps_modifiers_hint": "With ⌃/⌥/⌘ set, this mapping fires only while exactly those are held. A mapping without them also fires with them held and adds them to its keystroke (Caps + ⌘ + L → ⌘→).",
+            "mappings.action_failed": "This action failed the last time it ran.",
             "mappings.kb.hint": "Click any key to view or edit its mapping.", "mappings.kb.other": "Other triggers", "mappings.kb.empty_hint": "Click a key to map Caps + that key.",
             "mappings.add_app_rule": "Add app rule", "mappings.applies_in": "Applies in", "mappings.add_app": "Add app", "mappings.rule_action": "Action", "mappings.advanced_rule": "Advanced rule (edit in YAML)",
             "toast.rule_needs_app": "Each app rule needs at least one app",
@@ -193,15 +237,24 @@ final class LocalizationManager: ObservableObject {
             "actions.add": "Add Action", "actions.custom": "Custom", "actions.builtin": "Built-in",
             "actions.none_custom": "No custom actions yet", "actions.builtin_hint": "Built-in actions can't be edited or deleted, but you can bind any of them to a trigger.",
             "actions.used_by": "{count} mapping(s)", "actions.delete_blocked": "Can't delete — used by: {triggers}",
+            "actions.try": "Try in the sandbox window", "toast.sandbox_failed": "Sandbox run failed: {error}",
+            "mappings.key_unsupported": "This key can't be bound on macOS, so the mapping never fires",
             "actions.edit_title": "Edit Action", "actions.add_title": "New Action",
             "actions.name": "Name", "actions.name_placeholder": "e.g. Open Calculator", "actions.type": "Type",
             "about.version": "Version", "about.links": "Links",
             "mappings.trigger": "Trigger", "mappings.key": "Key", "actions.count": "Count",
+            "group.wrap_pair": "Wrap Pair", "actions.wrap_open": "Open", "actions.wrap_close": "Close", "actions.wrap_spaces": "Inner spaces",
+            "group.go_to": "Go to Line", "actions.go_to_line": "Line", "actions.go_to_column": "Column", "actions.go_to_shortcut": "Editor shortcut",
+            "group.insert_date_time": "Insert Date/Time", "actions.date_format": "Format", "actions.date_presets": "Presets", "actions.date_preview": "Preview", "actions.date_hint": "strftime codes: %Y year, %m month, %d day, %H:%M:%S time, %a / %b day and month names, %z time-zone offset; %% for a literal percent sign.",
+            "actions.go_to_count": "From count", "actions.go_to_hint": "Opens the editor's go-to field and types the line. Leave Line empty to take it from a count prefix (Caps+123 first). Xcode uses ⌘L; VS Code and Sublime Text use ⌃G — set that in a per-app rule.",
+            "group.app_control": "App Control", "toast.config_reloaded": "Config reloaded ({count} mappings)",
+            "actions.jump_unit": "Step", "actions.jump_unit.line": "Line", "actions.jump_unit.page": "Page", "actions.jump_unit.paragraph": "Paragraph",
             "toast.action_saved": "Action saved", "toast.action_removed": "Action removed", "toast.action_remove_failed": "Failed to remove action",
             "toast.app_no_bundle_id": "Couldn't read that app's identifier.",
             "nav.statistics": "Statistics",
             "stats.range.today": "Today", "stats.range.7d": "7 Days", "stats.range.30d": "30 Days", "stats.range.all": "All Time",
             "stats.total_label": "Total presses", "stats.ranking": "Most used",
+            "stats.unused": "Never used", "stats.unused_hint": "Mappings with no presses in this range — candidates to remove or rebind to something you'd actually use.",
             "stats.empty": "No presses recorded in this range yet.",
             "stats.empty_all": "No presses recorded yet — start using your mappings!",
             "stats.reset": "Reset Statistics", "stats.reset_title": "Reset all statistics?",
@@ -209,6 +262,22 @@ final class LocalizationManager: ObservableObject {
             "stats.reset_ok": "Reset", "toast.stats_reset": "Statistics reset", "stats.removed": "removed",
             "stats.page_hint": "How many times each mapping has fired. Holding a key to repeat counts as one press.",
             "settings.stats_inline": "Show press counts on mapping rows", "stats.inline_help": "Times triggered (all time)",
+            "settings.layer_key": "Layer key", "layer_key.caps_lock": "Caps Lock", "settings.layer_key_hint": "The key you hold for the layer. Pick a spare right-side modifier to keep Caps Lock working normally; that key then stops doing its own job (its left-side twin still does).",
+            "settings.caps_layer": "Caps layer", "caps_layer.hold": "Hold", "caps_layer.one_shot": "One-shot tap", "caps_layer.both": "Hold or tap", "settings.caps_layer_hint": "One-shot: tap Caps, then press the next key — it goes through the layer as if Caps were held, then the layer turns off (after 3s unused, too). Tapping Caps again cancels, so a tap no longer toggles Caps Lock; bind Toggle Caps Lock to a key if you need it.", "toast.caps_layer_mode_failed": "Failed to save the Caps layer setting",
+            "settings.caps_tap": "Tap Caps", "caps_tap.toggle_caps": "Toggle Caps Lock", "caps_tap.escape": "Escape", "caps_tap.nothing": "Nothing", "caps_tap.custom": "Custom (see Mappings)", "settings.caps_tap_hint": "What a quick tap of Caps does; holding it is still the layer. Escape is the classic Vim setup; Nothing makes Caps purely the layer key. Any other single-tap Caps mapping shows as Custom.", "toast.caps_toggle_failed": "Failed to save the Caps Lock toggle setting",
+            "settings.caps_pin_off": "Keep Caps Lock off", "settings.caps_pin_off_hint": "Caps Lock can't be turned on: a toggle only clears it, and it's cleared whenever Caps is released.", "toast.caps_pin_off_failed": "Failed to save the Keep Caps Lock off setting",
+            "settings.shift_layer": "Caps + Shift layer", "shift_layer.fallback": "Falls back to Caps + Key", "shift_layer.strict": "Separate layer", "settings.shift_layer_hint": "Falls back: an unbound Caps+Shift+key runs its Caps+key motion with Shift held, so Caps+Shift+H selects left. Separate: Caps+Shift+key only does what is bound to it — for keymaps that give Shift its own meaning.", "toast.shift_layer_failed": "Failed to save the Caps + Shift layer setting",
+            "settings.raw_escape": "Raw key escape", "raw_escape.off": "Off", "raw_escape.fn": "Fn", "raw_escape.control": "Control", "raw_escape.option": "Option", "raw_escape.command": "Command", "settings.raw_escape_hint": "Hold this with Caps to type a mapped key as itself, e.g. Caps + Fn + H types h. Caps chords bound with the same modifier can't fire while it is set.", "toast.raw_escape_failed": "Failed to save the raw key escape",
+            "settings.inject_delay": "Injection delay", "settings.inject_delay_off": "None", "settings.inject_delay_hint": "Pause between the keys of a jump or inserted text, for apps (Electron editors, remote desktops) that drop keys sent back-to-back. Each millisecond slows long sequences: a 10-line jump at 5 ms takes 50 ms.", "toast.inject_delay_failed": "Failed to save the injection delay",
+            "settings.debounce": "Key debounce", "settings.debounce_off": "Off", "settings.debounce_hint": "Ignore a second press of the same key this soon after the first — for worn keyboards that register one press twice (a command running twice, a double jump). Key repeat from holding a key is unaffected.", "toast.debounce_failed": "Failed to save the key debounce",
+            "settings.caps_mouse": "Caps + mouse click", "settings.caps_mouse_hint": "Also watch mouse buttons, so a click while Caps is held shows on the HUD and in the log. The click still goes through. Off unless you need it: every click passes through the app when on.", "toast.caps_mouse_failed": "Failed to save the mouse click setting",
+            "settings.default_jump": "Default jump", "settings.default_jump_hint": "Lines Caps + U / Caps + D move in new and reset mappings (up to 200).", "settings.default_jump_reset": "Reset Caps + U / D", "toast.default_jump_failed": "Failed to save the default jump", "toast.jump_mappings_reset": "Jump mappings reset",
+            "settings.tap_location": "Keyboard tap location", "settings.post_location": "Injection location",
+            "settings.event_location_hint": "Advanced. Keep HID unless another input tool conflicts; Session or Annotated session moves HyperCapslock further down the event chain.",
+            "event_location.hid": "HID", "event_location.session": "Session", "event_location.annotated_session": "Annotated session",
+            "toast.event_location_failed": "Failed to save event location",
+            "settings.word_motion": "Word motion", "word_motion.word": "Whole word (⌥←/→)", "word_motion.subword": "Subword (⌃⌥←/→)", "settings.word_motion_hint": "What Word Forward / Word Back send. Subword stops at camelCase and snake_case parts in editors that support it. To pin a different shortcut on one key, map it to a Key Combo instead.", "toast.word_motion_failed": "Failed to save the word motion setting",
+            "settings.line_edge": "Line end for Next Line", "line_edge.visual": "Displayed line (⌘→)", "line_edge.logical": "Whole paragraph (⌃E)", "settings.line_edge_hint": "Where Next Line and Open Line Above go before pressing Return. With soft-wrapped text, the displayed line ends mid-paragraph; the whole-paragraph option skips past the wrapping.", "toast.line_edge_failed": "Failed to save the line end setting",
             "common.cancel": "Cancel",
         ],
         .zh: [
@@ -216,12 +285,19 @@ final class LocalizationManager: ObservableObject {
             "status.label": "状态", "status.initializing": "初始化中...",
             "status.running": "运行中", "status.paused": "已暂停", "status.error": "错误",
             "status.pause": "暂停", "status.resume": "恢复",
+            "status.disabled": "已停用", "tray.disable": "完全停用", "tray.enable": "启用",
+            "status.auto_paused_excluded": "已自动暂停（排除的 App）",
+            "status.secure_input": "已暂停：安全输入已开启", "status.secure_input_app": "已暂停：安全输入已开启（{app}）",
+            "status.resumes_in": "{minutes} 分钟后恢复",
             "settings.label": "选项", "settings.autostart": "开机启动",
+            "settings.autostart_approval": "需要在 系统设置 ▸ 登录项 中允许。", "settings.autostart_open": "打开登录项…",
             "settings.hide_dock": "隐藏 Dock 图标", "settings.show_hud": "显示屏幕提示",
+            "settings.tray_style": "菜单栏", "tray_style.icon": "图标", "tray_style.title": "文字", "tray_style.both": "两者",
             "settings.show_window_on_launch": "启动时显示主窗口",
             "settings.show_window_on_launch_hint": "关闭后，App 启动时只在菜单栏静默运行，不再自动弹出窗口。可从菜单栏图标或 Dock 图标打开。",
             "settings.anydrag_caps_hold": "按住 CapsLock 拖动窗口（联动 AnyDrag）",
             "settings.anydrag_caps_hold_hint": "需在 AnyDrag 中启用“CapsLock（通过 HyperCapslock）”。",
+            "settings.control_socket": "脚本控制套接字", "settings.control_socket_hint": "允许本机脚本通过 Unix 套接字 {path} 控制 HyperCapslock。每个请求都需要附带令牌。", "settings.control_socket_copy_token": "复制令牌",
             "remap.label": "键位重映射",
             "remap.hint": "把一个不常用的右侧修饰键改造成空闲功能键（F13–F19），即可在任意 App（包括 HyperCapslock 本身）里把它绑定为全局快捷键。⚠️ 被映射的键会失去原本的功能（左侧的同名键不受影响）。",
             "remap.add": "添加映射",
@@ -239,6 +315,8 @@ final class LocalizationManager: ObservableObject {
             "mappings.title": "按键映射 (Caps+按键)", "mappings.add": "添加",
             "mappings.add_title": "添加映射", "mappings.edit": "编辑", "mappings.edit_title": "编辑映射",
             "mappings.delete": "删除", "mappings.save": "保存", "mappings.empty": "还没有映射配置",
+            "mappings.reset_one": "恢复默认", "mappings.reset_one_hint": "恢复此触发键的默认映射", "mappings.reset_one_removes": "此触发键没有默认映射，恢复默认会将其删除", "toast.mapping_reset": "已恢复默认映射", "tray.reset_mappings": "将映射恢复为默认…", "mappings.reset_all_title": "恢复所有映射为默认？", "mappings.reset_all_prompt": "所有映射都将替换为默认设置，自定义动作会保留。可在映射页面撤销。", "mappings.reset_all_confirm": "恢复", "toast.mappings_reset": "映射已恢复为默认",
+            "tray.presets": "预设", "tray.presets_none": "没有已保存的预设", "tray.presets_save": "将当前映射存为预设…", "presets.save_title": "保存预设", "presets.save_prompt": "以此名称保存当前映射和自定义动作。同名预设将被替换。", "presets.save_confirm": "保存", "toast.preset_saved": "已保存预设「{name}」", "toast.preset_loaded": "已载入预设「{name}」", "toast.preset_failed": "预设操作失败：{error}",
             "mappings.press_key": "按下按键", "mappings.caps": "Caps + …", "mappings.caps_shift": "Caps + Shift + …",
             "trigger.hyper_plus_key": "Caps + 按键", "trigger.single_tap_hyper": "单击 Caps",
             "trigger.double_tap_hyper": "双击 Caps", "trigger.double_tap_prefix": "双击",
@@ -246,15 +324,25 @@ final class LocalizationManager: ObservableObject {
             "trigger.double_tap_modifier": "双击修饰键", "side.left": "左", "side.right": "右",
             "group.directional": "方向", "group.jump": "跳转", "group.independent": "独立",
             "group.input_source": "输入法", "group.command": "命令", "group.key_combo": "组合键",
+            "group.script": "脚本", "script.language": "语言", "script.shell": "Shell 脚本", "script.applescript": "AppleScript", "script.source": "脚本",
+            "group.notify": "通知", "actions.notify_title": "标题", "actions.notify_body": "内容",
             "group.open_app": "打开应用", "actions.app": "应用", "actions.choose_app": "选择…", "actions.no_app": "未选择应用",
             "group.hold_modifier": "按住修饰键",
             "actions.hold_modifier_hint": "按住触发键即保持该修饰键按下,松开即释放(如按住说话)。请配合 Caps+键 这类按住型触发器使用。",
+            "actions.command_capture": "捕获输出", "actions.command_capture_hint": "等待命令结束并显示其输出（第一行显示在 HUD 上）；退出码非零时该映射会标记为失败。超过 16 KB 的输出会被丢弃。",
             "action.left": "左", "action.right": "右", "action.up": "上", "action.down": "下",
             "action.word_forward": "下一个词", "action.word_back": "上一个词",
-            "action.home": "行首", "action.end": "行尾", "action.backspace": "退格",
+            "action.home": "行首", "action.end": "行尾", "action.doc_start": "文档开头", "action.doc_end": "文档末尾", "action.backspace": "退格",
             "action.next_line": "下一行", "action.insert_quotes": "插入引号",
             "action.toggle_caps_lock": "大小写切换", "action.switch_input_source": "输入法切换",
             "action.noop": "什么都不做",
+            "action.release_modifiers": "释放所有修饰键",
+            "action.repeat_last": "重复上一个动作",
+            "action.sticky_shift": "粘滞 Shift",
+            "action.uppercase": "选中文本转大写", "action.lowercase": "选中文本转小写", "action.trim": "去除选中文本首尾空白", "group.selection": "选中文本",
+            "action.toggle_pause": "暂停 / 恢复 HyperCapslock", "action.reload_config": "重新加载配置", "action.show_window": "显示窗口", "action.quit": "退出 HyperCapslock",
+            "action.show_palette": "命令面板", "palette.search": "搜索按键绑定…", "palette.empty": "没有匹配的绑定",
+            "action.open_line_above": "在上方新建一行",
             "action.unknown": "未知",
             "theme.light": "切换到浅色模式", "theme.dark": "切换到深色模式",
             "toast.perm_refreshed": "权限已刷新", "toast.perm_failed": "刷新权限失败",
@@ -272,9 +360,11 @@ final class LocalizationManager: ObservableObject {
             "toast.anydrag_caps_hold_enabled": "已开启 AnyDrag CapsLock 按住广播",
             "toast.anydrag_caps_hold_disabled": "已关闭 AnyDrag CapsLock 按住广播",
             "toast.anydrag_caps_hold_failed": "修改 AnyDrag CapsLock 按住设置失败",
+            "toast.control_socket_enabled": "已开启脚本控制套接字", "toast.control_socket_disabled": "已关闭脚本控制套接字", "toast.control_socket_failed": "脚本控制套接字启动失败", "toast.control_token_copied": "已复制令牌",
             "toast.show_hud_failed": "屏幕提示设置修改失败",
             "config.export": "导出", "config.import": "导入", "config.import_title": "导入配置",
-            "config.import_prompt": "这将替换当前所有的映射，是否继续？", "config.import_confirm": "替换",
+            "mappings.undo": "撤销映射修改", "mappings.redo": "重做映射修改",
+            "config.import_prompt": "这将替换当前所有的映射，是否继续？", "config.import_diff": "新增 {added} 个，修改 {changed} 个，移除 {removed} 个。", "config.import_confirm": "替换",
             "config.overwrite_title": "文件已存在", "config.overwrite_prompt": "{path} 已存在，是否覆盖？",
             "config.overwrite_confirm": "覆盖",
             "toast.config_exported": "配置已导出", "toast.config_export_failed": "导出配置失败",
@@ -286,6 +376,13 @@ final class LocalizationManager: ObservableObject {
             "update.failed": "检查更新失败：{error}", "update.error": "错误", "update.check": "检查更新",
             "footer.by": "By", "footer.github": "GitHub 仓库", "footer.more_apps_desc": "Xueshi 的更多应用：",
             "tray.open": "打开窗口", "tray.quit": "退出 HyperCapslock", "tray.more_apps": "作者的更多应用…",
+            "tray.release_modifiers": "释放卡住的修饰键",
+            "tray.input_source": "输入法：{name}", "is.current": "当前输入法", "mappings.input_source_active": "当前正在使用此输入法",
+            "tray.grant_accessibility": "授予辅助功能权限…",
+            "excluded.label": "排除的 App", "excluded.add": "添加 App…", "excluded.hint": "当这些 App 位于前台时，HyperCapslock 放行所有按键——适用于远程桌面、虚拟机以及自带 CapsLock 处理的游戏。", "toast.excluded_apps_failed": "保存排除的 App 失败",
+            "passthrough.label": "直通按键", "passthrough.add": "添加按键", "passthrough.capture": "按下一个键", "passthrough.everywhere": "所有应用", "passthrough.limit_app": "仅限应用…", "passthrough.hint": "Caps + 这些按键会原样传给应用，即使已绑定映射——映射仍保留，只是在这里不生效。可将按键限定在某些应用中，只在那里开洞。", "toast.passthrough_failed": "保存直通按键失败",
+            "excluded.recent": "最近使用：{app}（{id}）", "excluded.add_recent": "排除",
+            "tray.pause_for": "暂停一段时间", "tray.pause_minutes": "{minutes} 分钟",
             "nav.settings": "设置", "nav.mappings": "按键映射", "nav.actions": "动作", "nav.about": "关于", "nav.toggle_sidebar": "切换边栏",
             "nav.input_source": "输入法",
             "is.fix_title": "修复输入法切换问题",
@@ -300,17 +397,33 @@ final class LocalizationManager: ObservableObject {
             "is.attribution": "输入法修复方法借鉴自 Input Source Pro。",
             "toast.is_strategy_failed": "修改输入法修复方法失败",
             "is.source_unavailable_suffix": "（不可用）",
+            "is.source_not_enabled_warning": "此输入法已安装但未启用——请在系统设置 ▸ 键盘 ▸ 输入法中添加，否则映射无法切换。",
+            "is.source_missing_warning": "此 Mac 未安装该输入法——映射无法切换。",
             "mappings.switch_input_source": "切换输入法",
             "mappings.inline_section": "动作类型",
             "is.refresh": "重新检查输入法设置",
             "appearance.label": "外观", "settings.language": "语言", "settings.language_system": "跟随系统", "settings.theme": "主题",
             "theme.light_opt": "浅色", "theme.dark_opt": "深色", "theme.system_opt": "跟随系统",
             "perm.refresh_label": "授权后重新检查",
+            "selftest.label": "自检", "selftest.hint": "检查权限和安全输入，再向测试窗口输入一个按键，确认注入的按键能够到达。", "selftest.run": "运行自检", "selftest.ok": "自检通过：注入的按键可以到达", "selftest.title": "自检发现问题", "selftest.done": "好", "selftest.open_accessibility": "打开辅助功能设置", "selftest.unknown_app": "其他应用", "selftest.accessibility_missing": "未授予辅助功能权限。请在 系统设置 ▸ 隐私与安全性 ▸ 辅助功能 中允许 HyperCapslock。", "selftest.tap_not_installed": "键盘监听未安装。请确认 HyperCapslock 未被停用，或在菜单栏中关闭再开启。", "selftest.secure_input": "安全输入已开启，由 {app} 占用。密码输入框获得焦点时，macOS 会对 HyperCapslock 隐藏按键——请离开该输入框或退出 {app}。", "selftest.injection_blocked": "注入的按键未能到达。可能有其他工具在过滤合成按键；请在设置中尝试更换「按键注入位置」。", "selftest.injection_untested": "无法完成注入检查。请保持测试窗口处于焦点后重试。",
+            "perm.caps_remap": "CapsLock 重映射", "perm.caps_remap_hint": "通过 hidutil 将 CapsLock 映射为 F18，使 Caps 可作为层键使用", "perm.caps_remap_active": "已生效", "perm.caps_remap_inactive": "未生效", "perm.caps_remap_fallback": "备用模式", "perm.caps_remap_fallback_hint": "hidutil 无法重映射 CapsLock，Caps 改为锁定式切换层：按一次开启，再按一次关闭。", "onboarding.label": "快速上手", "onboarding.intro": "Caps 生效前需要完成两件事。状态会实时检查——授权后回到这里即可。", "onboarding.accessibility": "授予辅助功能权限", "onboarding.grant": "授权…", "onboarding.caps_remap": "CapsLock 已重映射", "onboarding.done": "完成", "toast.onboarding_failed": "保存引导状态失败",
+            "safe_mode.title": "安全模式", "safe_mode.body": "以 --safe-mode 启动：使用默认设置，不安装键盘钩子，也不重映射 CapsLock。未读取你的配置文件，在此所做的更改也不会保存。请在配置文件夹中修复或删除出错的文件，然后退出并正常重新启动。", "safe_mode.reveal": "显示配置文件夹", "toast.safe_mode_engine_off": "安全模式下引擎保持关闭——请正常重新启动以启用",
+            "logs.label": "日志", "logs.copy": "复制最近日志", "logs.open": "打开日志", "logs.hint": "复制会把 {path} 的最后 {lines} 行放到剪贴板——报告问题时请附上。", "toast.logs_copied": "已复制最近日志", "toast.logs_empty": "还没有任何日志",
+            "logs.debug": "调试记录", "logs.debug_start": "记录 {seconds} 秒", "logs.debug_stop": "停止", "logs.debug_hint": "在短时间内记录每个按键事件，随后自动关闭。先复现问题，再复制日志。", "tray.debug_logging": "正在记录调试日志——停止",
+            "files.label": "文件", "files.hint": "备份这些文件或将其纳入版本管理，即可把配置带到另一台 Mac。标记为尚未创建的文件会在有内容需要保存时出现。", "files.reveal": "在访达中显示", "files.missing": "{path}（尚未创建）", "files.mappings": "映射与自定义动作", "files.settings": "设置", "files.presets": "预设", "files.backups": "配置备份", "files.usage_stats": "使用统计", "files.log": "日志",
+            "logs.max_size": "日志大小上限", "logs.max_size_hint": "超过上限后日志会轮换为 .1 和 .2，因此最多保留上限的三倍。",
             "mappings.action": "动作", "mappings.action_hint": "从动作库中选择一个动作，或在列表底部新建。",
+            "mappings.repeat": "按住时", "mappings.repeat.auto": "自动", "mappings.repeat.never": "只触发一次", "mappings.repeat.on_hold": "重复",
+            "mappings.label": "标签", "mappings.label_placeholder": "例如：保存", "mappings.note": "备注",
+            "mappings.enabled_help": "关闭后该按键按原生方式输入；映射会保留",
             "mappings.default_action": "默认动作", "mappings.default_action_hint": "在所有 App 中生效，除非下方某条按 App 规则匹配当前前台 App。",
             "mappings.app_rules": "按 App 规则", "mappings.app_rules_hint": "自上而下匹配；第一条包含当前前台 App 的规则生效，否则执行默认动作。",
             "mappings.style": "显示样式", "mappings.style.grouped": "按触发方式分组", "mappings.style.keyboard": "键盘图",
             "mappings.group.caps_key": "Caps + 按键", "mappings.group.caps_shift_key": "Caps + Shift + 按键", "mappings.group.single_tap": "单击 Caps", "mappings.group.double_tap": "双击 Caps", "mappings.group.double_tap_modifier": "双击修饰键",
+            "mappings.group.hotkey": "快捷键（无需 Caps）", "trigger.hotkey": "快捷键（无需 Caps）", "mappings.hotkey_modifiers": "修饰键", "mappings.hotkey_hint": "无需按住 Caps，随处可用。需要包含 ⌃、⌥ 或 ⌘（仅 Shift 会吞掉正常输入），或使用功能键。只拦截完全一致的组合；按住 Caps 时以 Caps 层为准。",
+            "mappings.resolves_passthrough": "当前未绑定：{chord} 会原样传给应用。", "mappings.resolves_fallback": "当前未绑定：{chord} 会回退为 {action}（带 Shift）。",
+            "mappings.resolves_modified": "当前未绑定：{chord} 会执行 {action}，并附加额外的修饰键。", "mappings.caps_modifiers": "同时按住", "mappings.caps_modifiers_hint": "选中 ⌃/⌥/⌘ 后，此映射仅在恰好按住这些键时触发。未设置它们的映射在按住这些键时也会触发，并把它们附加到按键上（Caps + ⌘ + L → ⌘→）。",
+            "mappings.action_failed": "此动作上次执行失败。",
             "mappings.kb.hint": "点击任意键即可查看或编辑它的映射。", "mappings.kb.other": "其它触发方式", "mappings.kb.empty_hint": "点击一个键即可为「Caps + 该键」添加映射。",
             "mappings.add_app_rule": "添加 App 规则", "mappings.applies_in": "适用于", "mappings.add_app": "添加 App", "mappings.rule_action": "动作", "mappings.advanced_rule": "高级规则（在 YAML 中编辑）",
             "toast.rule_needs_app": "每条 App 规则至少需要一个 App",
@@ -319,15 +432,24 @@ final class LocalizationManager: ObservableObject {
             "actions.add": "添加动作", "actions.custom": "自定义", "actions.builtin": "内置",
             "actions.none_custom": "还没有自定义动作", "actions.builtin_hint": "内置动作不可编辑或删除,但可以绑定到任意触发键。",
             "actions.used_by": "{count} 个映射", "actions.delete_blocked": "无法删除 — 被以下触发键引用:{triggers}",
+            "actions.try": "在沙盒窗口中试用", "toast.sandbox_failed": "沙盒运行失败：{error}",
+            "mappings.key_unsupported": "该按键在 macOS 上无法绑定，此映射永远不会触发",
             "actions.edit_title": "编辑动作", "actions.add_title": "新建动作",
             "actions.name": "名称", "actions.name_placeholder": "例如 打开计算器", "actions.type": "类型",
             "about.version": "版本", "about.links": "链接",
             "mappings.trigger": "触发", "mappings.key": "按键", "actions.count": "次数",
+            "group.wrap_pair": "成对包裹", "actions.wrap_open": "左侧", "actions.wrap_close": "右侧", "actions.wrap_spaces": "内侧空格",
+            "group.go_to": "跳转到行", "actions.go_to_line": "行", "actions.go_to_column": "列", "actions.go_to_shortcut": "编辑器快捷键",
+            "group.insert_date_time": "插入日期/时间", "actions.date_format": "格式", "actions.date_presets": "预设", "actions.date_preview": "预览", "actions.date_hint": "strftime 代码：%Y 年、%m 月、%d 日、%H:%M:%S 时间、%a / %b 星期与月份名称、%z 时区偏移；%% 表示百分号本身。",
+            "actions.go_to_count": "取自计数", "actions.go_to_hint": "打开编辑器的跳转输入框并输入行号。行留空时取计数前缀（先按 Caps+123）。Xcode 用 ⌘L；VS Code 和 Sublime Text 用 ⌃G——可在按应用规则中设置。",
+            "group.app_control": "应用控制", "toast.config_reloaded": "已重新加载配置（{count} 个映射）",
+            "actions.jump_unit": "步长", "actions.jump_unit.line": "行", "actions.jump_unit.page": "页", "actions.jump_unit.paragraph": "段落",
             "toast.action_saved": "动作已保存", "toast.action_removed": "动作已删除", "toast.action_remove_failed": "删除动作失败",
             "toast.app_no_bundle_id": "无法读取该应用的标识符。",
             "nav.statistics": "统计",
             "stats.range.today": "今天", "stats.range.7d": "近 7 天", "stats.range.30d": "近 30 天", "stats.range.all": "全部",
             "stats.total_label": "总触发次数", "stats.ranking": "使用排行",
+            "stats.unused": "从未使用", "stats.unused_hint": "在此时间范围内没有触发过的映射——可以考虑删除，或改绑成更常用的动作。",
             "stats.empty": "该时间范围内还没有触发记录。",
             "stats.empty_all": "还没有任何触发记录 —— 开始使用你的映射吧！",
             "stats.reset": "重置统计", "stats.reset_title": "重置所有统计？",
@@ -335,6 +457,22 @@ final class LocalizationManager: ObservableObject {
             "stats.reset_ok": "重置", "toast.stats_reset": "统计已重置", "stats.removed": "已删除",
             "stats.page_hint": "每个映射被触发的次数。按住某个键持续重复只计为一次。",
             "settings.stats_inline": "在映射行上显示触发次数", "stats.inline_help": "触发次数（全部时间）",
+            "settings.layer_key": "层键", "layer_key.caps_lock": "Caps Lock", "settings.layer_key_hint": "按住以进入映射层的按键。选择一个不常用的右侧修饰键即可让 Caps Lock 保持原有功能；该键随之失去本来的作用（左侧同名键不受影响）。",
+            "settings.caps_layer": "Caps 层", "caps_layer.hold": "按住", "caps_layer.one_shot": "单次轻按", "caps_layer.both": "按住或轻按", "settings.caps_layer_hint": "单次轻按：轻按一下 Caps，再按下一个键——它会像按住 Caps 一样经过映射层，之后自动退出（3 秒未使用也会退出）。再次轻按 Caps 可取消，因此轻按不再切换大写锁定；如有需要，可把“大小写切换”绑定到某个按键。", "toast.caps_layer_mode_failed": "保存 Caps 层设置失败",
+            "settings.caps_tap": "轻按 Caps", "caps_tap.toggle_caps": "切换大小写", "caps_tap.escape": "Esc", "caps_tap.nothing": "无操作", "caps_tap.custom": "自定义（见映射页）", "settings.caps_tap_hint": "快速轻按 Caps 时执行的操作，按住 Caps 仍然是层键。Esc 是经典的 Vim 用法；无操作则让 Caps 只作为层键。其他单击 Caps 映射会显示为自定义。", "toast.caps_toggle_failed": "保存大小写切换设置失败",
+            "settings.caps_pin_off": "始终关闭大写锁定", "settings.caps_pin_off_hint": "大写锁定无法打开：切换只会关闭它，每次松开 Caps 时也会将其关闭。", "toast.caps_pin_off_failed": "保存“始终关闭大写锁定”设置失败",
+            "settings.shift_layer": "Caps + Shift 层", "shift_layer.fallback": "回退到 Caps + 按键", "shift_layer.strict": "独立的层", "settings.shift_layer_hint": "回退：未绑定的 Caps+Shift+按键会带着 Shift 执行对应 Caps+按键的移动，因此 Caps+Shift+H 向左选择。独立：Caps+Shift+按键只执行绑定给它的操作——适合让 Shift 有独立含义的键位。", "toast.shift_layer_failed": "保存 Caps + Shift 层设置失败",
+            "settings.raw_escape": "原样输入按键", "raw_escape.off": "关闭", "raw_escape.fn": "Fn", "raw_escape.control": "Control", "raw_escape.option": "Option", "raw_escape.command": "Command", "settings.raw_escape_hint": "与 Caps 一起按住此键，可原样输入已映射的按键，例如 Caps + Fn + H 输入 h。设置后，绑定了同一修饰键的 Caps 组合键将不会触发。", "toast.raw_escape_failed": "保存原样输入按键失败",
+            "settings.inject_delay": "注入间隔", "settings.inject_delay_off": "无", "settings.inject_delay_hint": "跳转或插入文本时在各按键之间停顿，适用于会丢失连续模拟按键的应用（Electron 编辑器、远程桌面）。每毫秒都会让长序列变慢：5 ms 时跳 10 行需要 50 ms。", "toast.inject_delay_failed": "保存注入间隔失败",
+            "settings.debounce": "按键防抖", "settings.debounce_off": "关闭", "settings.debounce_hint": "忽略在此时间内对同一按键的第二次按下——适用于按一次却被识别为两次的老化键盘（命令执行两次、跳转两次）。按住按键产生的连发不受影响。", "toast.debounce_failed": "保存按键防抖失败",
+            "settings.caps_mouse": "Caps + 鼠标点击", "settings.caps_mouse_hint": "同时监听鼠标按键：按住 Caps 时点击会显示在 HUD 和日志中，点击本身照常生效。开启后每次点击都会经过本应用，不需要时请保持关闭。", "toast.caps_mouse_failed": "保存鼠标点击设置失败",
+            "settings.default_jump": "默认跳转行数", "settings.default_jump_hint": "新建或重置映射时 Caps + U / Caps + D 移动的行数（最多 200）。", "settings.default_jump_reset": "重置 Caps + U / D", "toast.default_jump_failed": "保存默认跳转行数失败", "toast.jump_mappings_reset": "已重置跳转映射",
+            "settings.tap_location": "键盘监听位置", "settings.post_location": "按键注入位置",
+            "settings.event_location_hint": "高级选项。除非与其他输入工具冲突，否则保持 HID；选择 Session 或 Annotated session 会让 HyperCapslock 位于事件链更靠后的位置。",
+            "event_location.hid": "HID", "event_location.session": "Session", "event_location.annotated_session": "Annotated session",
+            "toast.event_location_failed": "保存事件位置失败",
+            "settings.word_motion": "按词移动", "word_motion.word": "整词（⌥←/→）", "word_motion.subword": "子词（⌃⌥←/→）", "settings.word_motion_hint": "“下一个词 / 上一个词”发送的快捷键。子词会在支持的编辑器中停在驼峰和下划线分隔处。若想让某个按键使用别的快捷键，可改为映射到组合键。", "toast.word_motion_failed": "保存按词移动设置失败",
+            "settings.line_edge": "“下一行”的行尾", "line_edge.visual": "显示行（⌘→）", "line_edge.logical": "整段（⌃E）", "settings.line_edge_hint": "“下一行 / 在上方新建一行”按回车前移动到的位置。文本自动换行时，显示行会在段落中间结束；选择整段则跳过自动换行。", "toast.line_edge_failed": "保存行尾设置失败",
             "common.cancel": "取消",
         ],
         .ja: [
@@ -342,12 +480,19 @@ final class LocalizationManager: ObservableObject {
             "status.label": "ステータス", "status.initializing": "初期化中...",
             "status.running": "実行中", "status.paused": "一時停止", "status.error": "エラー",
             "status.pause": "一時停止", "status.resume": "再開",
+            "status.disabled": "無効", "tray.disable": "完全に無効にする", "tray.enable": "有効にする",
+            "status.auto_paused_excluded": "自動停止中（除外アプリ）",
+            "status.secure_input": "一時停止：セキュア入力がオン", "status.secure_input_app": "一時停止：セキュア入力がオン（{app}）",
+            "status.resumes_in": "{minutes} 分後に再開",
             "settings.label": "オプション", "settings.autostart": "ログイン時に起動",
+            "settings.autostart_approval": "システム設定 ▸ ログイン項目で許可が必要です。", "settings.autostart_open": "ログイン項目を開く…",
             "settings.hide_dock": "Dock アイコンを非表示", "settings.show_hud": "画面 HUD を表示",
+            "settings.tray_style": "メニューバー", "tray_style.icon": "アイコン", "tray_style.title": "テキスト", "tray_style.both": "両方",
             "settings.show_window_on_launch": "起動時にウィンドウを表示",
             "settings.show_window_on_launch_hint": "オフにすると、メニューバーで静かに起動します。ウィンドウはメニューバーのアイコンまたは Dock から開けます。",
             "settings.anydrag_caps_hold": "CapsLock を押しながらウィンドウをドラッグ（AnyDrag 連携）",
             "settings.anydrag_caps_hold_hint": "AnyDrag で「CapsLock（HyperCapslock 経由）」を有効にしてください。",
+            "settings.control_socket": "スクリプト用ソケット", "settings.control_socket_hint": "ローカルのスクリプトから Unix ソケット {path} 経由で HyperCapslock を操作できます。各リクエストにはトークンが必要です。", "settings.control_socket_copy_token": "トークンをコピー",
             "remap.label": "キーの再マッピング",
             "remap.hint": "使っていない右側の修飾キーを空きファンクションキー（F13–F19）に変換し、任意のアプリ（HyperCapslock 自身を含む）でグローバルショートカットとして割り当てられます。⚠️ 変換したキーは本来の機能を失います（左側の同名キーは影響を受けません）。",
             "remap.add": "マッピングを追加",
@@ -365,6 +510,8 @@ final class LocalizationManager: ObservableObject {
             "mappings.title": "キーマッピング (Caps+キー)", "mappings.add": "追加",
             "mappings.add_title": "マッピングを追加", "mappings.edit": "編集", "mappings.edit_title": "マッピングを編集",
             "mappings.delete": "削除", "mappings.save": "保存", "mappings.empty": "マッピングがまだありません",
+            "mappings.reset_one": "デフォルトに戻す", "mappings.reset_one_hint": "このトリガーを既定の割り当てに戻す", "mappings.reset_one_removes": "このトリガーには既定の割り当てがないため、戻すと削除されます", "toast.mapping_reset": "マッピングをデフォルトに戻しました", "tray.reset_mappings": "マッピングをデフォルトに戻す…", "mappings.reset_all_title": "すべてのマッピングをデフォルトに戻しますか？", "mappings.reset_all_prompt": "すべてのマッピングが既定値に置き換わります。カスタムアクションは残ります。マッピングページから取り消せます。", "mappings.reset_all_confirm": "戻す", "toast.mappings_reset": "マッピングをデフォルトに戻しました",
+            "tray.presets": "プリセット", "tray.presets_none": "保存済みのプリセットはありません", "tray.presets_save": "現在のマッピングをプリセットとして保存…", "presets.save_title": "プリセットを保存", "presets.save_prompt": "現在のマッピングとカスタムアクションをこの名前で保存します。同名のプリセットは置き換えられます。", "presets.save_confirm": "保存", "toast.preset_saved": "プリセット「{name}」を保存しました", "toast.preset_loaded": "プリセット「{name}」を読み込みました", "toast.preset_failed": "プリセットの操作に失敗：{error}",
             "mappings.press_key": "キーを押す", "mappings.caps": "Caps + …", "mappings.caps_shift": "Caps + Shift + …",
             "trigger.hyper_plus_key": "Caps + キー", "trigger.single_tap_hyper": "Caps をシングルタップ",
             "trigger.double_tap_hyper": "Caps をダブルタップ", "trigger.double_tap_prefix": "ダブルタップ",
@@ -372,15 +519,25 @@ final class LocalizationManager: ObservableObject {
             "trigger.double_tap_modifier": "修飾キーをダブルタップ", "side.left": "左", "side.right": "右",
             "group.directional": "方向", "group.jump": "ジャンプ", "group.independent": "独立",
             "group.input_source": "入力ソース", "group.command": "コマンド", "group.key_combo": "キーコンボ",
+            "group.script": "スクリプト", "script.language": "言語", "script.shell": "シェルスクリプト", "script.applescript": "AppleScript", "script.source": "スクリプト",
+            "group.notify": "通知", "actions.notify_title": "タイトル", "actions.notify_body": "本文",
             "group.open_app": "アプリを開く", "actions.app": "アプリ", "actions.choose_app": "選択…", "actions.no_app": "アプリ未選択",
             "group.hold_modifier": "修飾キーを押し続ける",
             "actions.hold_modifier_hint": "トリガーを押している間この修飾キーを押し続け、離すと解除します(プッシュトゥトークなど)。Caps+キーのような押し続けるトリガーで使ってください。",
+            "actions.command_capture": "出力を取得", "actions.command_capture_hint": "コマンドの終了を待ち、出力を表示します（1 行目を HUD に表示）。終了コードが 0 以外ならマッピングを失敗として表示します。16 KB を超える出力は破棄されます。",
             "action.left": "左", "action.right": "右", "action.up": "上", "action.down": "下",
             "action.word_forward": "次の単語", "action.word_back": "前の単語",
-            "action.home": "行頭", "action.end": "行末", "action.backspace": "バックスペース",
+            "action.home": "行頭", "action.end": "行末", "action.doc_start": "文書の先頭", "action.doc_end": "文書の末尾", "action.backspace": "バックスペース",
             "action.next_line": "次の行", "action.insert_quotes": "引用符を挿入",
             "action.toggle_caps_lock": "Caps Lock 切り替え", "action.switch_input_source": "入力ソース切り替え",
             "action.noop": "何もしない",
+            "action.release_modifiers": "すべての修飾キーを解放",
+            "action.repeat_last": "直前のアクションを繰り返す",
+            "action.sticky_shift": "スティッキー Shift",
+            "action.uppercase": "選択範囲を大文字に", "action.lowercase": "選択範囲を小文字に", "action.trim": "選択範囲の前後の空白を削除", "group.selection": "選択範囲",
+            "action.toggle_pause": "HyperCapslock を一時停止 / 再開", "action.reload_config": "設定を再読み込み", "action.show_window": "ウィンドウを表示", "action.quit": "HyperCapslock を終了",
+            "action.show_palette": "コマンドパレット", "palette.search": "割り当てを検索…", "palette.empty": "一致する割り当てはありません",
+            "action.open_line_above": "上に新しい行を作る",
             "action.unknown": "不明",
             "theme.light": "ライトモードに切替", "theme.dark": "ダークモードに切替",
             "toast.perm_refreshed": "権限を更新しました", "toast.perm_failed": "権限の更新に失敗",
@@ -398,9 +555,11 @@ final class LocalizationManager: ObservableObject {
             "toast.anydrag_caps_hold_enabled": "AnyDrag CapsLock 押下ブロードキャストを有効化",
             "toast.anydrag_caps_hold_disabled": "AnyDrag CapsLock 押下ブロードキャストを無効化",
             "toast.anydrag_caps_hold_failed": "AnyDrag CapsLock 押下設定の変更に失敗しました",
+            "toast.control_socket_enabled": "スクリプト用ソケットを有効化", "toast.control_socket_disabled": "スクリプト用ソケットを無効化", "toast.control_socket_failed": "スクリプト用ソケットを開始できませんでした", "toast.control_token_copied": "トークンをコピーしました",
             "toast.show_hud_failed": "HUD 設定の変更に失敗",
             "config.export": "エクスポート", "config.import": "インポート", "config.import_title": "設定をインポート",
-            "config.import_prompt": "現在のマッピングを置き換えます。続行しますか？", "config.import_confirm": "置き換え",
+            "mappings.undo": "マッピングの変更を取り消す", "mappings.redo": "マッピングの変更をやり直す",
+            "config.import_prompt": "現在のマッピングを置き換えます。続行しますか？", "config.import_diff": "追加 {added} 件、変更 {changed} 件、削除 {removed} 件。", "config.import_confirm": "置き換え",
             "config.overwrite_title": "ファイルは既に存在します", "config.overwrite_prompt": "{path} は既に存在します。上書きしますか？",
             "config.overwrite_confirm": "上書き",
             "toast.config_exported": "設定をエクスポートしました", "toast.config_export_failed": "エクスポートに失敗しました",
@@ -412,6 +571,13 @@ final class LocalizationManager: ObservableObject {
             "update.failed": "アップデートの確認に失敗: {error}", "update.error": "エラー", "update.check": "アップデートを確認",
             "footer.by": "By", "footer.github": "GitHub リポジトリ", "footer.more_apps_desc": "Xueshi の他のアプリ：",
             "tray.open": "ウィンドウを開く", "tray.quit": "HyperCapslock を終了", "tray.more_apps": "作者の他のアプリ…",
+            "tray.release_modifiers": "押したままの修飾キーを解放",
+            "tray.input_source": "入力ソース: {name}", "is.current": "現在の入力ソース", "mappings.input_source_active": "この入力ソースが現在有効です",
+            "tray.grant_accessibility": "アクセシビリティ権限を許可…",
+            "excluded.label": "除外アプリ", "excluded.add": "アプリを追加…", "excluded.hint": "これらのアプリが前面にある間、HyperCapslock はすべてのキーをそのまま通します。リモートデスクトップ、仮想マシン、独自の CapsLock 処理を持つゲームに便利です。", "toast.excluded_apps_failed": "除外アプリを保存できませんでした",
+            "passthrough.label": "パススルーキー", "passthrough.add": "キーを追加", "passthrough.capture": "キーを押す", "passthrough.everywhere": "すべてのアプリ", "passthrough.limit_app": "アプリに限定…", "passthrough.hint": "Caps + これらのキーは、マッピングが割り当てられていてもそのままアプリに届きます（マッピングは残り、ここでだけ無効になります）。キーを特定のアプリに限定すると、そのアプリでだけ穴を開けられます。", "toast.passthrough_failed": "パススルーキーの保存に失敗しました",
+            "excluded.recent": "最近使ったアプリ：{app}（{id}）", "excluded.add_recent": "除外",
+            "tray.pause_for": "一定時間停止", "tray.pause_minutes": "{minutes} 分",
             "nav.settings": "設定", "nav.mappings": "マッピング", "nav.actions": "アクション", "nav.about": "情報", "nav.toggle_sidebar": "サイドバーの表示切り替え",
             "nav.input_source": "入力ソース",
             "is.fix_title": "入力ソース切り替えの修正",
@@ -426,17 +592,33 @@ final class LocalizationManager: ObservableObject {
             "is.attribution": "入力ソース修正方法は Input Source Pro を参考にしています。",
             "toast.is_strategy_failed": "入力ソース修正方法の変更に失敗",
             "is.source_unavailable_suffix": "（利用不可）",
+            "is.source_not_enabled_warning": "この入力ソースはインストール済みですが有効ではありません。システム設定 ▸ キーボード ▸ 入力ソースで追加しないと切り替わりません。",
+            "is.source_missing_warning": "この入力ソースはこの Mac にインストールされていません。マッピングは切り替わりません。",
             "mappings.switch_input_source": "入力ソースを切り替え",
             "mappings.inline_section": "アクションの種類",
             "is.refresh": "入力ソース設定を再確認",
             "appearance.label": "外観", "settings.language": "言語", "settings.language_system": "システムに従う", "settings.theme": "テーマ",
             "theme.light_opt": "ライト", "theme.dark_opt": "ダーク", "theme.system_opt": "システム",
             "perm.refresh_label": "許可後に再確認",
+            "selftest.label": "セルフテスト", "selftest.hint": "権限とセキュア入力を確認し、テストウィンドウに 1 キー入力して、注入したキーが届くか確かめます。", "selftest.run": "セルフテストを実行", "selftest.ok": "セルフテスト合格：注入したキーが届いています", "selftest.title": "セルフテストで問題が見つかりました", "selftest.done": "OK", "selftest.open_accessibility": "アクセシビリティ設定を開く", "selftest.unknown_app": "他のアプリ", "selftest.accessibility_missing": "アクセシビリティが許可されていません。システム設定 ▸ プライバシーとセキュリティ ▸ アクセシビリティ で HyperCapslock を許可してください。", "selftest.tap_not_installed": "キーボードタップがインストールされていません。HyperCapslock が無効になっていないか確認するか、メニューバーでオフ／オンしてください。", "selftest.secure_input": "セキュア入力がオンです（{app} が使用中）。パスワード欄にフォーカスがある間、macOS は HyperCapslock からキーを隠します。その欄から離れるか {app} を終了してください。", "selftest.injection_blocked": "注入したキーが届きませんでした。他のツールが合成キーを遮断している可能性があります。設定で「キー注入の位置」を変えてみてください。", "selftest.injection_untested": "注入チェックを実行できませんでした。テストウィンドウにフォーカスを保ったまま再試行してください。",
+            "perm.caps_remap": "CapsLock のリマップ", "perm.caps_remap_hint": "hidutil で CapsLock を F18 に割り当て、Caps をレイヤーキーとして使えるようにします", "perm.caps_remap_active": "有効", "perm.caps_remap_inactive": "無効", "perm.caps_remap_fallback": "代替モード", "perm.caps_remap_fallback_hint": "hidutil で CapsLock をリマップできないため、Caps はレイヤーの切り替え式になります。1 回押すとオン、もう 1 回押すとオフです。", "onboarding.label": "はじめに", "onboarding.intro": "Caps を使う前に 2 つの準備が必要です。状態はその場で確認されるので、許可したらここに戻ってください。", "onboarding.accessibility": "アクセシビリティを許可する", "onboarding.grant": "許可…", "onboarding.caps_remap": "CapsLock がリマップされている", "onboarding.done": "完了", "toast.onboarding_failed": "初回設定の状態を保存できませんでした",
+            "safe_mode.title": "セーフモード", "safe_mode.body": "--safe-mode で起動しました：既定の設定で動作し、キーボードフックも CapsLock のリマップも行いません。設定ファイルは読み込まれず、ここでの変更も保存されません。設定フォルダ内の壊れたファイルを修正または削除してから、終了して通常どおり再起動してください。", "safe_mode.reveal": "設定フォルダを表示", "toast.safe_mode_engine_off": "セーフモードではエンジンはオフのままです。有効にするには通常どおり再起動してください",
+            "logs.label": "ログ", "logs.copy": "最近のログをコピー", "logs.open": "ログを開く", "logs.hint": "コピーすると {path} の最後の {lines} 行をクリップボードに入れます。不具合を報告するときに添付してください。", "toast.logs_copied": "最近のログをコピーしました", "toast.logs_empty": "まだログがありません",
+            "logs.debug": "デバッグ記録", "logs.debug_start": "{seconds} 秒記録", "logs.debug_stop": "停止", "logs.debug_hint": "しばらくの間すべてのキーイベントを記録し、その後自動でオフになります。問題を再現してからログをコピーしてください。", "tray.debug_logging": "デバッグログを記録中 — 停止",
+            "files.label": "ファイル", "files.hint": "これらをバックアップするかバージョン管理しておけば、設定を別の Mac に持ち込めます。未作成のファイルは保存する内容ができた時点で作られます。", "files.reveal": "Finder で表示", "files.missing": "{path}（未作成）", "files.mappings": "マッピングとカスタムアクション", "files.settings": "設定", "files.presets": "プリセット", "files.backups": "設定のバックアップ", "files.usage_stats": "使用統計", "files.log": "ログ",
+            "logs.max_size": "ログサイズの上限", "logs.max_size_hint": "上限を超えるとログは .1 と .2 にローテーションされるため、保持されるのは最大で上限の 3 倍です。",
             "mappings.action": "アクション", "mappings.action_hint": "ライブラリからアクションを選択するか、リスト下部で新規作成できます。",
+            "mappings.repeat": "長押し時", "mappings.repeat.auto": "自動", "mappings.repeat.never": "1回だけ", "mappings.repeat.on_hold": "繰り返す",
+            "mappings.label": "ラベル", "mappings.label_placeholder": "例：保存", "mappings.note": "メモ",
+            "mappings.enabled_help": "オフにするとキーは通常どおり入力されます。マッピングは保持されます",
             "mappings.default_action": "デフォルトのアクション", "mappings.default_action_hint": "下のアプリ別ルールが前面のアプリに一致しない限り、すべてのアプリで動作します。",
             "mappings.app_rules": "アプリ別ルール", "mappings.app_rules_hint": "上から順に評価し、前面のアプリを含む最初のルールが優先されます。一致しなければデフォルトのアクションが実行されます。",
             "mappings.style": "表示スタイル", "mappings.style.grouped": "トリガー別にグループ化", "mappings.style.keyboard": "キーボードマップ",
             "mappings.group.caps_key": "Caps + キー", "mappings.group.caps_shift_key": "Caps + Shift + キー", "mappings.group.single_tap": "Caps シングルタップ", "mappings.group.double_tap": "Caps ダブルタップ", "mappings.group.double_tap_modifier": "修飾キーのダブルタップ",
+            "mappings.group.hotkey": "ホットキー（Caps なし）", "trigger.hotkey": "ホットキー（Caps なし）", "mappings.hotkey_modifiers": "修飾キー", "mappings.hotkey_hint": "Caps を押さずにどこでも発動します。⌃・⌥・⌘ のいずれかが必要です（Shift だけだと通常の入力を奪ってしまうため）。ファンクションキーなら単独でも可。完全に一致する組み合わせだけを受け取り、Caps を押している間は Caps レイヤーが優先されます。",
+            "mappings.resolves_passthrough": "現在は未割り当て：{chord} はそのままアプリに渡ります。", "mappings.resolves_fallback": "現在は未割り当て：{chord} は {action}（Shift 付き）にフォールバックします。",
+            "mappings.resolves_modified": "現在は未割り当て：{chord} は {action} に追加の修飾キーを加えて実行します。", "mappings.caps_modifiers": "同時に押す", "mappings.caps_modifiers_hint": "⌃/⌥/⌘ を指定すると、このマッピングはちょうどそれらを押しているときだけ発動します。指定のないマッピングはそれらを押していても発動し、キー入力に付け加えます（Caps + ⌘ + L → ⌘→）。",
+            "mappings.action_failed": "このアクションは前回の実行で失敗しました。",
             "mappings.kb.hint": "キーをクリックするとマッピングを表示・編集できます。", "mappings.kb.other": "その他のトリガー", "mappings.kb.empty_hint": "キーをクリックすると「Caps + そのキー」を割り当てられます。",
             "mappings.add_app_rule": "アプリ別ルールを追加", "mappings.applies_in": "適用先", "mappings.add_app": "アプリを追加", "mappings.rule_action": "アクション", "mappings.advanced_rule": "詳細ルール（YAML で編集）",
             "toast.rule_needs_app": "各アプリ別ルールには少なくとも 1 つのアプリが必要です",
@@ -445,15 +627,24 @@ final class LocalizationManager: ObservableObject {
             "actions.add": "アクションを追加", "actions.custom": "カスタム", "actions.builtin": "組み込み",
             "actions.none_custom": "カスタムアクションはまだありません", "actions.builtin_hint": "組み込みアクションは編集・削除できませんが、任意のトリガーに割り当てられます。",
             "actions.used_by": "{count} 個のマッピング", "actions.delete_blocked": "削除できません — 使用中:{triggers}",
+            "actions.try": "サンドボックスウィンドウで試す", "toast.sandbox_failed": "サンドボックスでの実行に失敗しました：{error}",
+            "mappings.key_unsupported": "このキーは macOS では割り当てられないため、このマッピングは発動しません",
             "actions.edit_title": "アクションを編集", "actions.add_title": "新規アクション",
             "actions.name": "名前", "actions.name_placeholder": "例: 電卓を開く", "actions.type": "種類",
             "about.version": "バージョン", "about.links": "リンク",
             "mappings.trigger": "トリガー", "mappings.key": "キー", "actions.count": "回数",
+            "group.wrap_pair": "ペアで囲む", "actions.wrap_open": "開き", "actions.wrap_close": "閉じ", "actions.wrap_spaces": "内側のスペース",
+            "group.go_to": "行へ移動", "actions.go_to_line": "行", "actions.go_to_column": "列", "actions.go_to_shortcut": "エディタのショートカット",
+            "group.insert_date_time": "日付/時刻を入力", "actions.date_format": "書式", "actions.date_presets": "プリセット", "actions.date_preview": "プレビュー", "actions.date_hint": "strftime の記法：%Y 年、%m 月、%d 日、%H:%M:%S 時刻、%a / %b 曜日と月の名前、%z タイムゾーンのオフセット。% そのものは %% と書きます。",
+            "actions.go_to_count": "カウントから", "actions.go_to_hint": "エディタの移動欄を開いて行番号を入力します。行を空にするとカウント接頭辞（先に Caps+123）を使います。Xcode は ⌘L、VS Code と Sublime Text は ⌃G です（アプリ別ルールで設定）。",
+            "group.app_control": "アプリ操作", "toast.config_reloaded": "設定を再読み込みしました（{count} 件のマッピング）",
+            "actions.jump_unit": "単位", "actions.jump_unit.line": "行", "actions.jump_unit.page": "ページ", "actions.jump_unit.paragraph": "段落",
             "toast.action_saved": "アクションを保存しました", "toast.action_removed": "アクションを削除しました", "toast.action_remove_failed": "アクションの削除に失敗",
             "toast.app_no_bundle_id": "アプリの識別子を取得できませんでした。",
             "nav.statistics": "統計",
             "stats.range.today": "今日", "stats.range.7d": "7日間", "stats.range.30d": "30日間", "stats.range.all": "全期間",
             "stats.total_label": "総トリガー回数", "stats.ranking": "使用ランキング",
+            "stats.unused": "未使用", "stats.unused_hint": "この期間に一度も押されていないマッピングです。削除するか、よく使う動作に割り当て直す候補です。",
             "stats.empty": "この期間にはまだ記録がありません。",
             "stats.empty_all": "まだ記録がありません — マッピングを使ってみましょう！",
             "stats.reset": "統計をリセット", "stats.reset_title": "すべての統計をリセットしますか？",
@@ -461,6 +652,22 @@ final class LocalizationManager: ObservableObject {
             "stats.reset_ok": "リセット", "toast.stats_reset": "統計をリセットしました", "stats.removed": "削除済み",
             "stats.page_hint": "各マッピングが発動した回数です。キーを押し続けて連続発動しても1回として数えます。",
             "settings.stats_inline": "マッピング行にトリガー回数を表示", "stats.inline_help": "トリガー回数（全期間）",
+            "settings.layer_key": "レイヤーキー", "layer_key.caps_lock": "Caps Lock", "settings.layer_key_hint": "押している間レイヤーになるキーです。使っていない右側の修飾キーを選ぶと Caps Lock は通常どおり使えます。そのキー本来の機能はなくなります（左側の同じキーはそのまま使えます）。",
+            "settings.caps_layer": "Caps レイヤー", "caps_layer.hold": "長押し", "caps_layer.one_shot": "ワンショット", "caps_layer.both": "長押しまたはタップ", "settings.caps_layer_hint": "ワンショット：Caps をタップしてから次のキーを押すと、Caps を押しているのと同じようにレイヤーを通り、その後自動で解除されます（3 秒使わなかった場合も解除）。もう一度 Caps をタップすると取り消しになるため、タップで Caps Lock は切り替わりません。必要なら「Caps Lock 切り替え」をキーに割り当ててください。", "toast.caps_layer_mode_failed": "Caps レイヤーの設定を保存できませんでした",
+            "settings.caps_tap": "Caps のタップ", "caps_tap.toggle_caps": "Caps Lock を切り替え", "caps_tap.escape": "Esc", "caps_tap.nothing": "何もしない", "caps_tap.custom": "カスタム（マッピングを参照）", "settings.caps_tap_hint": "Caps を素早くタップしたときの動作です。長押しは引き続きレイヤーになります。Esc は定番の Vim 向け設定、「何もしない」にすると Caps はレイヤーキー専用になります。その他のシングルタップのマッピングはカスタムと表示されます。", "toast.caps_toggle_failed": "Caps Lock 切り替え設定の保存に失敗しました",
+            "settings.caps_pin_off": "Caps Lock を常にオフ", "settings.caps_pin_off_hint": "Caps Lock はオンになりません。切り替えはオフにするだけで、Caps を離すたびにオフに戻ります。", "toast.caps_pin_off_failed": "「Caps Lock を常にオフ」設定の保存に失敗しました",
+            "settings.shift_layer": "Caps + Shift レイヤー", "shift_layer.fallback": "Caps + キーにフォールバック", "shift_layer.strict": "独立したレイヤー", "settings.shift_layer_hint": "フォールバック：未割り当ての Caps+Shift+キーは、対応する Caps+キーの移動を Shift 付きで実行します（Caps+Shift+H で左へ選択）。独立：Caps+Shift+キーは割り当てられた動作だけを行います——Shift に別の意味を持たせるキーマップ向けです。", "toast.shift_layer_failed": "Caps + Shift レイヤーの設定を保存できませんでした",
+            "settings.raw_escape": "キーをそのまま入力", "raw_escape.off": "オフ", "raw_escape.fn": "Fn", "raw_escape.control": "Control", "raw_escape.option": "Option", "raw_escape.command": "Command", "settings.raw_escape_hint": "Caps と一緒にこのキーを押すと、割り当て済みのキーをそのまま入力します（例：Caps + Fn + H で h）。設定中は、同じ修飾キーを使う Caps の組み合わせは発動しません。", "toast.raw_escape_failed": "キーをそのまま入力の設定を保存できませんでした",
+            "settings.inject_delay": "注入の間隔", "settings.inject_delay_off": "なし", "settings.inject_delay_hint": "ジャンプや挿入テキストのキーの間に待ち時間を入れます。連続した合成キーを取りこぼすアプリ（Electron 製エディタ、リモートデスクトップ）向けです。長いシーケンスほど遅くなります：5 ms なら 10 行ジャンプに 50 ms かかります。", "toast.inject_delay_failed": "注入の間隔を保存できませんでした",
+            "settings.debounce": "キーのチャタリング防止", "settings.debounce_off": "オフ", "settings.debounce_hint": "同じキーがこの時間内にもう一度押されたら無視します。1 回の押下が 2 回として認識される古いキーボード向けです（コマンドが 2 回実行される、ジャンプが二重になる）。キーを押し続けたときのリピートには影響しません。", "toast.debounce_failed": "チャタリング防止の設定を保存できませんでした",
+            "settings.caps_mouse": "Caps + マウスクリック", "settings.caps_mouse_hint": "マウスボタンも監視し、Caps を押しながらのクリックを HUD とログに表示します。クリック自体はそのまま届きます。オンにするとすべてのクリックが本アプリを経由するため、必要なときだけ有効にしてください。", "toast.caps_mouse_failed": "マウスクリックの設定を保存できませんでした",
+            "settings.default_jump": "既定のジャンプ行数", "settings.default_jump_hint": "新規・リセットしたマッピングで Caps + U / Caps + D が移動する行数（最大 200）。", "settings.default_jump_reset": "Caps + U / D をリセット", "toast.default_jump_failed": "既定のジャンプ行数を保存できませんでした", "toast.jump_mappings_reset": "ジャンプのマッピングをリセットしました",
+            "settings.tap_location": "キーボードタップの位置", "settings.post_location": "キー注入の位置",
+            "settings.event_location_hint": "上級者向け。他の入力ツールと競合しない限り HID のままにしてください。Session または Annotated session にすると HyperCapslock はイベントチェーンの後段に移ります。",
+            "event_location.hid": "HID", "event_location.session": "Session", "event_location.annotated_session": "Annotated session",
+            "toast.event_location_failed": "イベント位置の保存に失敗しました",
+            "settings.word_motion": "単語移動", "word_motion.word": "単語全体（⌥←/→）", "word_motion.subword": "サブワード（⌃⌥←/→）", "settings.word_motion_hint": "「次の単語 / 前の単語」が送るショートカット。サブワードは対応エディタで camelCase や snake_case の区切りで止まります。特定のキーだけ別のショートカットにするには Key Combo に割り当ててください。", "toast.word_motion_failed": "単語移動の設定を保存できませんでした",
+            "settings.line_edge": "「次の行」の行末", "line_edge.visual": "表示上の行（⌘→）", "line_edge.logical": "段落全体（⌃E）", "settings.line_edge_hint": "「次の行 / 上に新しい行を作る」が Return を押す前に移動する位置。折り返し表示のテキストでは表示上の行は段落の途中で終わります。段落全体を選ぶと折り返しを越えて移動します。", "toast.line_edge_failed": "行末の設定を保存できませんでした",
             "common.cancel": "キャンセル",
         ],
         .de: [
@@ -468,12 +675,19 @@ final class LocalizationManager: ObservableObject {
             "status.label": "Status", "status.initializing": "Initialisierung...",
             "status.running": "Läuft", "status.paused": "Pausiert", "status.error": "Fehler",
             "status.pause": "Pause", "status.resume": "Fortsetzen",
+            "status.disabled": "Deaktiviert", "tray.disable": "Vollständig deaktivieren", "tray.enable": "Aktivieren",
+            "status.auto_paused_excluded": "Automatisch pausiert (ausgeschlossene App)",
+            "status.secure_input": "Pausiert: Sichere Eingabe aktiv", "status.secure_input_app": "Pausiert: Sichere Eingabe aktiv ({app})",
+            "status.resumes_in": "Fortsetzung in {minutes} Min.",
             "settings.label": "Optionen", "settings.autostart": "Beim Anmelden starten",
+            "settings.autostart_approval": "Wartet auf Freigabe unter Systemeinstellungen ▸ Anmeldeobjekte.", "settings.autostart_open": "Anmeldeobjekte öffnen…",
             "settings.hide_dock": "Dock-Symbol ausblenden", "settings.show_hud": "Bildschirm-HUD anzeigen",
+            "settings.tray_style": "Menüleiste", "tray_style.icon": "Symbol", "tray_style.title": "Text", "tray_style.both": "Beides",
             "settings.show_window_on_launch": "Fenster beim Start anzeigen",
             "settings.show_window_on_launch_hint": "Wenn aus, startet die App still in der Menüleiste. Das Fenster lässt sich über das Menüleistensymbol oder das Dock öffnen.",
             "settings.anydrag_caps_hold": "CapsLock halten, um Fenster zu ziehen (mit AnyDrag)",
             "settings.anydrag_caps_hold_hint": "Erfordert AnyDrag mit aktiviertem „CapsLock (über HyperCapslock)“.",
+            "settings.control_socket": "Skript-Socket", "settings.control_socket_hint": "Lokale Skripte können HyperCapslock über einen Unix-Socket unter {path} steuern. Jede Anfrage braucht das Token.", "settings.control_socket_copy_token": "Token kopieren",
             "remap.label": "Tastenneubelegung",
             "remap.hint": "Verwandle eine ungenutzte rechte Modifikatortaste in eine freie Funktionstaste (F13–F19), die du in jeder App – auch in HyperCapslock selbst – als globales Tastenkürzel belegen kannst. ⚠️ Die neu belegte Taste verliert ihre normale Funktion (ihr linkes Gegenstück funktioniert weiter).",
             "remap.add": "Belegung hinzufügen",
@@ -491,6 +705,8 @@ final class LocalizationManager: ObservableObject {
             "mappings.title": "Tastenbelegungen (Caps+Taste)", "mappings.add": "Hinzufügen",
             "mappings.add_title": "Belegung hinzufügen", "mappings.edit": "Bearbeiten", "mappings.edit_title": "Belegung bearbeiten",
             "mappings.delete": "Löschen", "mappings.save": "Speichern", "mappings.empty": "Noch keine Tastenbelegungen",
+            "mappings.reset_one": "Auf Standard zurücksetzen", "mappings.reset_one_hint": "Die mitgelieferte Belegung dieses Auslösers wiederherstellen", "mappings.reset_one_removes": "Dieser Auslöser hat keine Standardbelegung – Zurücksetzen entfernt ihn", "toast.mapping_reset": "Belegung auf Standard zurückgesetzt", "tray.reset_mappings": "Belegungen auf Standard zurücksetzen…", "mappings.reset_all_title": "Alle Belegungen zurücksetzen?", "mappings.reset_all_prompt": "Alle Belegungen werden durch die Standardwerte ersetzt. Eigene Aktionen bleiben erhalten. Auf der Belegungsseite lässt sich das rückgängig machen.", "mappings.reset_all_confirm": "Zurücksetzen", "toast.mappings_reset": "Belegungen auf Standard zurückgesetzt",
+            "tray.presets": "Presets", "tray.presets_none": "Keine gespeicherten Presets", "tray.presets_save": "Aktuelle Belegung als Preset sichern…", "presets.save_title": "Preset sichern", "presets.save_prompt": "Sichert die aktuellen Belegungen und eigenen Aktionen unter diesem Namen. Ein gleichnamiges Preset wird ersetzt.", "presets.save_confirm": "Sichern", "toast.preset_saved": "Preset „{name}“ gesichert", "toast.preset_loaded": "Preset „{name}“ geladen", "toast.preset_failed": "Preset fehlgeschlagen: {error}",
             "mappings.press_key": "Taste drücken", "mappings.caps": "Caps + …", "mappings.caps_shift": "Caps + Shift + …",
             "trigger.hyper_plus_key": "Caps + Taste", "trigger.single_tap_hyper": "Caps einmal tippen",
             "trigger.double_tap_hyper": "Caps doppelt tippen", "trigger.double_tap_prefix": "Doppeltippen",
@@ -498,15 +714,25 @@ final class LocalizationManager: ObservableObject {
             "trigger.double_tap_modifier": "Modifizierer doppelt tippen", "side.left": "Links", "side.right": "Rechts",
             "group.directional": "Richtung", "group.jump": "Sprung", "group.independent": "Unabhängig",
             "group.input_source": "Eingabequelle", "group.command": "Befehl", "group.key_combo": "Tastenkombination",
+            "group.script": "Skript", "script.language": "Sprache", "script.shell": "Shell-Skript", "script.applescript": "AppleScript", "script.source": "Skript",
+            "group.notify": "Mitteilung", "actions.notify_title": "Titel", "actions.notify_body": "Nachricht",
             "group.open_app": "App öffnen", "actions.app": "App", "actions.choose_app": "Auswählen…", "actions.no_app": "Keine App ausgewählt",
             "group.hold_modifier": "Modifier halten",
             "actions.hold_modifier_hint": "Halte den Trigger, um diesen Modifier gedrückt zu halten; loslassen zum Beenden (z. B. Push-to-Talk). Mit einem Caps+Taste-Trigger verwenden.",
+            "actions.command_capture": "Ausgabe erfassen", "actions.command_capture_hint": "Auf den Befehl warten und seine Ausgabe anzeigen (erste Zeile im HUD); ein Exit-Status ungleich 0 markiert die Belegung als fehlgeschlagen. Ausgabe über 16 KB wird verworfen.",
             "action.left": "Links", "action.right": "Rechts", "action.up": "Oben", "action.down": "Unten",
             "action.word_forward": "Wort vor", "action.word_back": "Wort zurück",
-            "action.home": "Zeilenanfang", "action.end": "Zeilenende", "action.backspace": "Rücktaste",
+            "action.home": "Zeilenanfang", "action.end": "Zeilenende", "action.doc_start": "Dokumentanfang", "action.doc_end": "Dokumentende", "action.backspace": "Rücktaste",
             "action.next_line": "Nächste Zeile", "action.insert_quotes": "Anführungszeichen",
             "action.toggle_caps_lock": "Caps Lock umschalten", "action.switch_input_source": "Eingabequelle wechseln",
             "action.noop": "Nichts tun",
+            "action.release_modifiers": "Alle Sondertasten lösen",
+            "action.repeat_last": "Letzte Aktion wiederholen",
+            "action.sticky_shift": "Feststehende Umschalttaste",
+            "action.uppercase": "Auswahl in Großbuchstaben", "action.lowercase": "Auswahl in Kleinbuchstaben", "action.trim": "Leerraum um Auswahl entfernen", "group.selection": "Auswahl",
+            "action.toggle_pause": "HyperCapslock pausieren / fortsetzen", "action.reload_config": "Konfiguration neu laden", "action.show_window": "Fenster anzeigen", "action.quit": "HyperCapslock beenden",
+            "action.show_palette": "Befehlspalette", "palette.search": "Belegungen durchsuchen …", "palette.empty": "Keine passenden Belegungen",
+            "action.open_line_above": "Neue Zeile darüber",
             "action.unknown": "Unbekannt",
             "theme.light": "Zum hellen Modus wechseln", "theme.dark": "Zum dunklen Modus wechseln",
             "toast.perm_refreshed": "Berechtigungen aktualisiert", "toast.perm_failed": "Aktualisierung fehlgeschlagen",
@@ -524,9 +750,11 @@ final class LocalizationManager: ObservableObject {
             "toast.anydrag_caps_hold_enabled": "AnyDrag-CapsLock-Übertragung aktiviert",
             "toast.anydrag_caps_hold_disabled": "AnyDrag-CapsLock-Übertragung deaktiviert",
             "toast.anydrag_caps_hold_failed": "AnyDrag-CapsLock-Einstellung konnte nicht geändert werden",
+            "toast.control_socket_enabled": "Skript-Socket aktiviert", "toast.control_socket_disabled": "Skript-Socket deaktiviert", "toast.control_socket_failed": "Skript-Socket konnte nicht gestartet werden", "toast.control_token_copied": "Token kopiert",
             "toast.show_hud_failed": "HUD-Einstellung fehlgeschlagen",
             "config.export": "Exportieren", "config.import": "Importieren", "config.import_title": "Konfiguration importieren",
-            "config.import_prompt": "Dies ersetzt Ihre aktuellen Belegungen. Fortfahren?", "config.import_confirm": "Ersetzen",
+            "mappings.undo": "Zuordnungsänderung widerrufen", "mappings.redo": "Zuordnungsänderung wiederholen",
+            "config.import_prompt": "Dies ersetzt Ihre aktuellen Belegungen. Fortfahren?", "config.import_diff": "{added} neu, {changed} geändert, {removed} entfernt.", "config.import_confirm": "Ersetzen",
             "config.overwrite_title": "Datei existiert bereits", "config.overwrite_prompt": "{path} existiert bereits. Überschreiben?",
             "config.overwrite_confirm": "Überschreiben",
             "toast.config_exported": "Konfiguration exportiert", "toast.config_export_failed": "Export fehlgeschlagen",
@@ -538,6 +766,13 @@ final class LocalizationManager: ObservableObject {
             "update.failed": "Update-Prüfung fehlgeschlagen: {error}", "update.error": "Fehler", "update.check": "Nach Updates suchen",
             "footer.by": "Von", "footer.github": "GitHub-Repository", "footer.more_apps_desc": "Weitere Apps von Xueshi:",
             "tray.open": "Fenster öffnen", "tray.quit": "HyperCapslock beenden", "tray.more_apps": "Weitere Apps des Autors…",
+            "tray.release_modifiers": "Hängende Sondertasten lösen",
+            "tray.input_source": "Eingabequelle: {name}", "is.current": "Aktuelle Eingabequelle", "mappings.input_source_active": "Diese Eingabequelle ist gerade aktiv",
+            "tray.grant_accessibility": "Bedienungshilfen-Zugriff erlauben…",
+            "excluded.label": "Ausgeschlossene Apps", "excluded.add": "App hinzufügen…", "excluded.hint": "Solange eine dieser Apps aktiv ist, lässt HyperCapslock alle Tasten durch – nützlich für Remote-Desktops, VMs und Spiele mit eigener CapsLock-Behandlung.", "toast.excluded_apps_failed": "Ausgeschlossene Apps konnten nicht gespeichert werden",
+            "passthrough.label": "Durchgereichte Tasten", "passthrough.add": "Taste hinzufügen", "passthrough.capture": "Taste drücken", "passthrough.everywhere": "Alle Apps", "passthrough.limit_app": "Auf App beschränken…", "passthrough.hint": "Caps + diese Tasten kommen unverändert bei der App an, auch wenn ein Mapping darauf liegt – das Mapping bleibt erhalten und ist nur hier abgeschaltet. Beschränke eine Taste auf bestimmte Apps, um das Loch nur dort zu öffnen.", "toast.passthrough_failed": "Durchgereichte Tasten konnten nicht gespeichert werden",
+            "excluded.recent": "Zuletzt verwendet: {app} ({id})", "excluded.add_recent": "Ausschließen",
+            "tray.pause_for": "Pausieren für", "tray.pause_minutes": "{minutes} Min.",
             "nav.settings": "Einstellungen", "nav.mappings": "Belegungen", "nav.actions": "Aktionen", "nav.about": "Über", "nav.toggle_sidebar": "Seitenleiste ein-/ausblenden",
             "nav.input_source": "Eingabequelle",
             "is.fix_title": "Umschalten der Eingabequelle reparieren",
@@ -552,17 +787,33 @@ final class LocalizationManager: ObservableObject {
             "is.attribution": "Eingabequellen-Reparaturmethoden inspiriert von Input Source Pro.",
             "toast.is_strategy_failed": "Ändern der Eingabequellen-Reparaturmethode fehlgeschlagen",
             "is.source_unavailable_suffix": "(nicht verfügbar)",
+            "is.source_not_enabled_warning": "Diese Eingabequelle ist installiert, aber nicht aktiviert – füge sie unter Systemeinstellungen ▸ Tastatur ▸ Eingabequellen hinzu, sonst schaltet die Zuordnung nicht um.",
+            "is.source_missing_warning": "Diese Eingabequelle ist auf diesem Mac nicht installiert – die Zuordnung schaltet nicht um.",
             "mappings.switch_input_source": "Eingabequelle wechseln",
             "mappings.inline_section": "Aktionstyp",
             "is.refresh": "Eingabequellen-Einstellungen neu prüfen",
             "appearance.label": "Erscheinungsbild", "settings.language": "Sprache", "settings.language_system": "Systemstandard", "settings.theme": "Design",
             "theme.light_opt": "Hell", "theme.dark_opt": "Dunkel", "theme.system_opt": "System",
             "perm.refresh_label": "Nach Erteilung erneut prüfen",
+            "selftest.label": "Selbsttest", "selftest.hint": "Prüft Berechtigungen und sichere Eingabe und tippt dann eine Taste in ein Testfenster, um zu bestätigen, dass eingefügte Tasten ankommen.", "selftest.run": "Selbsttest starten", "selftest.ok": "Selbsttest bestanden: eingefügte Tasten kommen an", "selftest.title": "Der Selbsttest hat Probleme gefunden", "selftest.done": "OK", "selftest.open_accessibility": "Bedienungshilfen-Einstellungen öffnen", "selftest.unknown_app": "eine andere App", "selftest.accessibility_missing": "Bedienungshilfen sind nicht erlaubt. Erlaube HyperCapslock unter Systemeinstellungen ▸ Datenschutz & Sicherheit ▸ Bedienungshilfen.", "selftest.tap_not_installed": "Der Tastatur-Tap ist nicht installiert. Prüfe, dass HyperCapslock nicht deaktiviert ist, oder schalte es in der Menüleiste aus und wieder ein.", "selftest.secure_input": "Sichere Eingabe ist aktiv, gehalten von {app}. Solange ein Passwortfeld fokussiert ist, verbirgt macOS Tasten vor HyperCapslock – verlasse das Feld oder beende {app}.", "selftest.injection_blocked": "Eine eingefügte Taste ist nicht angekommen. Ein anderes Tool filtert eventuell synthetische Tasten; probiere in den Einstellungen eine andere Injektionsposition.", "selftest.injection_untested": "Die Einfügeprüfung konnte nicht laufen. Lass das Testfenster im Fokus und versuche es erneut.",
+            "perm.caps_remap": "CapsLock-Umbelegung", "perm.caps_remap_hint": "CapsLock → F18 per hidutil, damit Caps als Ebenentaste funktioniert", "perm.caps_remap_active": "Aktiv", "perm.caps_remap_inactive": "Nicht aktiv", "perm.caps_remap_fallback": "Ersatzmodus", "perm.caps_remap_fallback_hint": "hidutil konnte CapsLock nicht umbelegen, daher schaltet Caps die Ebene jetzt um: einmal drücken schaltet sie ein, noch einmal aus.", "onboarding.label": "Erste Schritte", "onboarding.intro": "Zwei Dinge, bevor Caps etwas tut. Beides wird live geprüft – komm nach dem Erteilen einfach hierher zurück.", "onboarding.accessibility": "Bedienungshilfen-Zugriff erteilen", "onboarding.grant": "Erteilen…", "onboarding.caps_remap": "CapsLock ist umbelegt", "onboarding.done": "Fertig", "toast.onboarding_failed": "Einrichtungsstatus konnte nicht gespeichert werden",
+            "safe_mode.title": "Abgesicherter Modus", "safe_mode.body": "Mit --safe-mode gestartet: Standardeinstellungen, kein Tastatur-Hook und keine CapsLock-Umbelegung. Deine Konfigurationsdateien wurden nicht gelesen, und Änderungen hier werden nicht gespeichert. Korrigiere oder entferne die fehlerhafte Datei im Konfigurationsordner, beende die App und starte sie normal neu.", "safe_mode.reveal": "Konfigurationsordner zeigen", "toast.safe_mode_engine_off": "Im abgesicherten Modus bleibt die Engine aus – starte normal neu, um sie einzuschalten",
+            "logs.label": "Protokoll", "logs.copy": "Letzte Einträge kopieren", "logs.open": "Protokoll öffnen", "logs.hint": "Kopieren legt die letzten {lines} Zeilen von {path} in die Zwischenablage – häng sie an, wenn du einen Fehler meldest.", "toast.logs_copied": "Letzte Protokolleinträge kopiert", "toast.logs_empty": "Noch nichts protokolliert",
+            "logs.debug": "Debug-Mitschnitt", "logs.debug_start": "{seconds} s aufzeichnen", "logs.debug_stop": "Stopp", "logs.debug_hint": "Protokolliert kurz jedes Tastenereignis und schaltet sich dann selbst ab. Problem nachstellen, dann das Protokoll kopieren.", "tray.debug_logging": "Debug-Protokoll läuft — Stopp",
+            "files.label": "Dateien", "files.hint": "Sichere diese Dateien oder nimm sie in die Versionsverwaltung auf, um deine Einrichtung auf einen anderen Mac mitzunehmen. Noch nicht angelegte Dateien entstehen, sobald es etwas zu speichern gibt.", "files.reveal": "Im Finder zeigen", "files.missing": "{path} (noch nicht angelegt)", "files.mappings": "Belegungen und eigene Aktionen", "files.settings": "Einstellungen", "files.presets": "Presets", "files.backups": "Konfigurations-Backups", "files.usage_stats": "Nutzungsstatistik", "files.log": "Protokoll",
+            "logs.max_size": "Maximale Protokollgröße", "logs.max_size_hint": "Über der Grenze wird das Protokoll nach .1 und .2 rotiert, es bleibt also höchstens die dreifache Größe erhalten.",
             "mappings.action": "Aktion", "mappings.action_hint": "Aktion aus der Bibliothek wählen oder unten in der Liste eine neue erstellen.",
+            "mappings.repeat": "Beim Halten", "mappings.repeat.auto": "Automatisch", "mappings.repeat.never": "Einmal auslösen", "mappings.repeat.on_hold": "Wiederholen",
+            "mappings.label": "Bezeichnung", "mappings.label_placeholder": "z. B. Sichern", "mappings.note": "Notiz",
+            "mappings.enabled_help": "Ausschalten, damit die Taste normal durchgeht; die Belegung bleibt erhalten",
             "mappings.default_action": "Standardaktion", "mappings.default_action_hint": "Gilt überall, sofern keine App-Regel unten zur aktiven App passt.",
             "mappings.app_rules": "App-Regeln", "mappings.app_rules_hint": "Von oben nach unten geprüft; die erste Regel, deren Apps die aktive App enthalten, gewinnt – sonst läuft die Standardaktion.",
             "mappings.style": "Ansicht", "mappings.style.grouped": "Nach Trigger gruppiert", "mappings.style.keyboard": "Tastaturansicht",
             "mappings.group.caps_key": "Caps + Taste", "mappings.group.caps_shift_key": "Caps + Shift + Taste", "mappings.group.single_tap": "Caps einfach tippen", "mappings.group.double_tap": "Caps doppelt tippen", "mappings.group.double_tap_modifier": "Modifier doppelt tippen",
+            "mappings.group.hotkey": "Tastenkürzel (ohne Caps)", "trigger.hotkey": "Tastenkürzel (ohne Caps)", "mappings.hotkey_modifiers": "Modifier", "mappings.hotkey_hint": "Löst überall aus, ohne Caps zu halten. Benötigt ⌃, ⌥ oder ⌘ (nur Shift würde normales Tippen schlucken) – oder eine Funktionstaste. Nur die exakte Kombination wird abgefangen; solange Caps gehalten wird, hat die Caps-Ebene Vorrang.",
+            "mappings.resolves_passthrough": "Derzeit nicht belegt: {chord} wird unverändert an die App durchgereicht.", "mappings.resolves_fallback": "Derzeit nicht belegt: {chord} fällt auf {action} (mit Shift) zurück.",
+            "mappings.resolves_modified": "Derzeit nicht belegt: {chord} führt {action} mit den zusätzlichen Modifikatoren aus.", "mappings.caps_modifiers": "Zusätzlich halten", "mappings.caps_modifiers_hint": "Mit ⌃/⌥/⌘ löst dieses Mapping nur aus, wenn genau diese gehalten werden. Ein Mapping ohne sie löst auch mit ihnen aus und fügt sie seinem Tastendruck hinzu (Caps + ⌘ + L → ⌘→).",
+            "mappings.action_failed": "Diese Aktion ist beim letzten Ausführen fehlgeschlagen.",
             "mappings.kb.hint": "Klicke eine Taste, um ihre Belegung anzuzeigen oder zu bearbeiten.", "mappings.kb.other": "Weitere Trigger", "mappings.kb.empty_hint": "Klicke eine Taste, um „Caps + diese Taste“ zu belegen.",
             "mappings.add_app_rule": "App-Regel hinzufügen", "mappings.applies_in": "Gilt für", "mappings.add_app": "App hinzufügen", "mappings.rule_action": "Aktion", "mappings.advanced_rule": "Erweiterte Regel (im YAML bearbeiten)",
             "toast.rule_needs_app": "Jede App-Regel braucht mindestens eine App",
@@ -571,15 +822,24 @@ final class LocalizationManager: ObservableObject {
             "actions.add": "Aktion hinzufügen", "actions.custom": "Benutzerdefiniert", "actions.builtin": "Integriert",
             "actions.none_custom": "Noch keine eigenen Aktionen", "actions.builtin_hint": "Integrierte Aktionen sind nicht editier-/löschbar, aber jeder Auslöser kann sie nutzen.",
             "actions.used_by": "{count} Belegung(en)", "actions.delete_blocked": "Löschen nicht möglich — verwendet von: {triggers}",
+            "actions.try": "Im Sandbox-Fenster ausprobieren", "toast.sandbox_failed": "Ausführung in der Sandbox fehlgeschlagen: {error}",
+            "mappings.key_unsupported": "Diese Taste lässt sich unter macOS nicht belegen, daher löst das Mapping nie aus",
             "actions.edit_title": "Aktion bearbeiten", "actions.add_title": "Neue Aktion",
             "actions.name": "Name", "actions.name_placeholder": "z. B. Rechner öffnen", "actions.type": "Typ",
             "about.version": "Version", "about.links": "Links",
             "mappings.trigger": "Auslöser", "mappings.key": "Taste", "actions.count": "Anzahl",
+            "group.wrap_pair": "Paar einfügen", "actions.wrap_open": "Öffnend", "actions.wrap_close": "Schließend", "actions.wrap_spaces": "Innere Leerzeichen",
+            "group.go_to": "Gehe zu Zeile", "actions.go_to_line": "Zeile", "actions.go_to_column": "Spalte", "actions.go_to_shortcut": "Editor-Kurzbefehl",
+            "group.insert_date_time": "Datum/Uhrzeit einfügen", "actions.date_format": "Format", "actions.date_presets": "Vorlagen", "actions.date_preview": "Vorschau", "actions.date_hint": "strftime-Codes: %Y Jahr, %m Monat, %d Tag, %H:%M:%S Uhrzeit, %a / %b Wochentags- und Monatsname, %z Zeitzonenversatz; %% für ein Prozentzeichen.",
+            "actions.go_to_count": "Aus Zähler", "actions.go_to_hint": "Öffnet das Gehe-zu-Feld des Editors und tippt die Zeile. Bleibt Zeile leer, kommt sie aus einem Zähler (zuerst Caps+123). Xcode nutzt ⌘L, VS Code und Sublime Text ⌃G – per App-Regel einstellen.",
+            "group.app_control": "App-Steuerung", "toast.config_reloaded": "Konfiguration neu geladen ({count} Belegungen)",
+            "actions.jump_unit": "Schritt", "actions.jump_unit.line": "Zeile", "actions.jump_unit.page": "Seite", "actions.jump_unit.paragraph": "Absatz",
             "toast.action_saved": "Aktion gespeichert", "toast.action_removed": "Aktion entfernt", "toast.action_remove_failed": "Aktion konnte nicht entfernt werden",
             "toast.app_no_bundle_id": "App-Kennung konnte nicht gelesen werden.",
             "nav.statistics": "Statistik",
             "stats.range.today": "Heute", "stats.range.7d": "7 Tage", "stats.range.30d": "30 Tage", "stats.range.all": "Gesamt",
             "stats.total_label": "Auslösungen gesamt", "stats.ranking": "Meistgenutzt",
+            "stats.unused": "Nie benutzt", "stats.unused_hint": "Zuordnungen ohne Auslösung in diesem Zeitraum – Kandidaten zum Entfernen oder Neubelegen.",
             "stats.empty": "In diesem Zeitraum noch keine Auslösungen.",
             "stats.empty_all": "Noch keine Auslösungen — nutze deine Belegungen!",
             "stats.reset": "Statistik zurücksetzen", "stats.reset_title": "Alle Statistiken zurücksetzen?",
@@ -587,6 +847,22 @@ final class LocalizationManager: ObservableObject {
             "stats.reset_ok": "Zurücksetzen", "toast.stats_reset": "Statistik zurückgesetzt", "stats.removed": "entfernt",
             "stats.page_hint": "Wie oft jede Belegung ausgelöst wurde. Eine Taste gedrückt zu halten zählt als eine Auslösung.",
             "settings.stats_inline": "Auslösungszahlen in den Belegungszeilen anzeigen", "stats.inline_help": "Auslösungen (gesamt)",
+            "settings.layer_key": "Ebenentaste", "layer_key.caps_lock": "Caps Lock", "settings.layer_key_hint": "Die Taste, die du für die Ebene hältst. Mit einer freien rechten Sondertaste bleibt Caps Lock normal nutzbar; diese Taste verliert dann ihre eigene Funktion (ihr linkes Gegenstück nicht).",
+            "settings.caps_layer": "Caps-Ebene", "caps_layer.hold": "Halten", "caps_layer.one_shot": "Einmal tippen", "caps_layer.both": "Halten oder tippen", "settings.caps_layer_hint": "Einmal tippen: Caps antippen, dann die nächste Taste drücken – sie läuft durch die Ebene, als wäre Caps gehalten, danach schaltet sich die Ebene ab (auch nach 3 s ohne Taste). Erneutes Antippen bricht ab, daher schaltet ein Tippen die Feststelltaste nicht mehr um; bei Bedarf „Caps Lock umschalten“ einer Taste zuordnen.", "toast.caps_layer_mode_failed": "Einstellung für die Caps-Ebene konnte nicht gespeichert werden",
+            "settings.caps_tap": "Caps antippen", "caps_tap.toggle_caps": "Feststelltaste umschalten", "caps_tap.escape": "Escape", "caps_tap.nothing": "Nichts", "caps_tap.custom": "Eigene (siehe Belegungen)", "settings.caps_tap_hint": "Was kurzes Antippen von Caps bewirkt; Gedrückthalten bleibt die Ebene. Escape ist das klassische Vim-Setup, Nichts macht Caps zur reinen Ebenen-Taste. Jede andere Belegung für einfaches Caps-Tippen erscheint als Eigene.", "toast.caps_toggle_failed": "Einstellung zum Umschalten der Feststelltaste konnte nicht gespeichert werden",
+            "settings.caps_pin_off": "Feststelltaste immer aus", "settings.caps_pin_off_hint": "Die Feststelltaste lässt sich nicht einschalten: Umschalten schaltet sie nur aus, und beim Loslassen von Caps wird sie jedes Mal ausgeschaltet.", "toast.caps_pin_off_failed": "Einstellung „Feststelltaste immer aus“ konnte nicht gespeichert werden",
+            "settings.shift_layer": "Caps + Shift-Ebene", "shift_layer.fallback": "Fällt auf Caps + Taste zurück", "shift_layer.strict": "Eigene Ebene", "settings.shift_layer_hint": "Zurückfallen: Ein unbelegtes Caps+Shift+Taste führt die Bewegung von Caps+Taste mit gehaltener Shift-Taste aus, Caps+Shift+H markiert also nach links. Eigene Ebene: Caps+Shift+Taste tut nur, was ihm zugewiesen ist – für Belegungen, in denen Shift eine eigene Bedeutung hat.", "toast.shift_layer_failed": "Einstellung für die Caps + Shift-Ebene konnte nicht gespeichert werden",
+            "settings.raw_escape": "Taste unverändert senden", "raw_escape.off": "Aus", "raw_escape.fn": "Fn", "raw_escape.control": "Control", "raw_escape.option": "Option", "raw_escape.command": "Command", "settings.raw_escape_hint": "Halte diese Taste zusammen mit Caps, um eine belegte Taste unverändert zu tippen, z. B. tippt Caps + Fn + H ein h. Caps-Chords mit derselben Sondertaste lösen dann nicht aus.", "toast.raw_escape_failed": "Einstellung zum unveränderten Senden konnte nicht gespeichert werden",
+            "settings.inject_delay": "Verzögerung beim Senden", "settings.inject_delay_off": "Keine", "settings.inject_delay_hint": "Pause zwischen den Tasten eines Sprungs oder eingefügten Texts, für Apps (Electron-Editoren, Remote-Desktops), die direkt aufeinanderfolgende simulierte Tasten verlieren. Jede Millisekunde verlangsamt lange Folgen: Ein Sprung über 10 Zeilen dauert bei 5 ms 50 ms.", "toast.inject_delay_failed": "Verzögerung konnte nicht gespeichert werden",
+            "settings.debounce": "Tastenentprellung", "settings.debounce_off": "Aus", "settings.debounce_hint": "Einen zweiten Druck derselben Taste so kurz nach dem ersten ignorieren – für abgenutzte Tastaturen, die einen Druck doppelt erkennen (ein Befehl läuft zweimal, ein doppelter Sprung). Die Tastenwiederholung beim Gedrückthalten ist nicht betroffen.", "toast.debounce_failed": "Tastenentprellung konnte nicht gespeichert werden",
+            "settings.caps_mouse": "Caps + Mausklick", "settings.caps_mouse_hint": "Auch Maustasten beobachten, sodass ein Klick bei gehaltener Caps-Taste im HUD und im Protokoll erscheint. Der Klick selbst kommt trotzdem an. Lass es aus, wenn du es nicht brauchst: Eingeschaltet läuft jeder Klick durch die App.", "toast.caps_mouse_failed": "Mausklick-Einstellung konnte nicht gespeichert werden",
+            "settings.default_jump": "Standard-Sprung", "settings.default_jump_hint": "Zeilen, die Caps + U / Caps + D in neuen und zurückgesetzten Zuordnungen springen (höchstens 200).", "settings.default_jump_reset": "Caps + U / D zurücksetzen", "toast.default_jump_failed": "Standard-Sprung konnte nicht gespeichert werden", "toast.jump_mappings_reset": "Sprung-Zuordnungen zurückgesetzt",
+            "settings.tap_location": "Position des Tastatur-Taps", "settings.post_location": "Injektionsposition",
+            "settings.event_location_hint": "Für Fortgeschrittene. Belass es bei HID, solange kein anderes Eingabewerkzeug kollidiert; Session oder Annotated session verschiebt HyperCapslock weiter nach hinten in der Ereigniskette.",
+            "event_location.hid": "HID", "event_location.session": "Session", "event_location.annotated_session": "Annotated session",
+            "toast.event_location_failed": "Ereignisposition konnte nicht gespeichert werden",
+            "settings.word_motion": "Wortweise bewegen", "word_motion.word": "Ganzes Wort (⌥←/→)", "word_motion.subword": "Wortteil (⌃⌥←/→)", "settings.word_motion_hint": "Was „Wort vor / Wort zurück“ senden. Wortteil hält in unterstützten Editoren an camelCase- und snake_case-Grenzen. Für ein abweichendes Kürzel auf einer Taste stattdessen eine Key Combo zuordnen.", "toast.word_motion_failed": "Einstellung für wortweises Bewegen konnte nicht gespeichert werden",
+            "settings.line_edge": "Zeilenende für „Nächste Zeile“", "line_edge.visual": "Angezeigte Zeile (⌘→)", "line_edge.logical": "Ganzer Absatz (⌃E)", "settings.line_edge_hint": "Wohin „Nächste Zeile“ und „Neue Zeile darüber“ vor dem Return springen. Bei umbrochenem Text endet die angezeigte Zeile mitten im Absatz; „Ganzer Absatz“ springt über den Umbruch hinweg.", "toast.line_edge_failed": "Zeilenende-Einstellung konnte nicht gespeichert werden",
             "common.cancel": "Abbrechen",
         ],
     ]
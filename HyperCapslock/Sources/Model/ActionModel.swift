@@ -12,16 +12,41 @@ enum DirectionalActionKind: String, Codable, CaseIterable, Equatable {
     case left, right, up, down
     case wordForward = "word_forward"
     case wordBack = "word_back"
+    /// Line-wise start/end (Cmd+Left/Right). The raw values predate the
+    /// document-wise pair below and stay as-is for config compatibility.
     case home, end
+    /// Document start/end (Cmd+Up/Down).
+    case docStart = "doc_start"
+    case docEnd = "doc_end"
 }
 
 enum JumpDirection: String, Codable, CaseIterable, Equatable {
     case up, down
 }
 
+/// How a Caps+key mapping behaves under OS key auto-repeat while held.
+enum RepeatMode: String, Codable, CaseIterable, Equatable {
+    /// The long-standing behavior: every action re-fires on auto-repeat except
+    /// a held modifier, which is pressed once and held, and an input-source
+    /// switch, which has nothing to gain from switching again.
+    case auto
+    /// Fire exactly once per physical press.
+    case never
+    /// Re-fire on every auto-repeat, even for one-shot actions.
+    case onHold = "on_hold"
+}
+
+/// What one jump step moves by: a line (arrow key), a page (PageUp/PageDown) or
+/// a paragraph (Option+Up/Down). Absent in older configs, which decode as `.line`.
+enum JumpUnit: String, Codable, CaseIterable, Equatable {
+    case line, page, paragraph
+}
+
 enum IndependentActionKind: String, Codable, CaseIterable, Equatable {
     case backspace
     case nextLine = "next_line"
+    /// Vim's `O`: open a new line above the cursor's line.
+    case openLineAbove = "open_line_above"
     case insertQuotes = "insert_quotes"
     case toggleCapsLock = "toggle_caps_lock"
     /// RETIRED tombstone. The auto 中/英 "Smart Toggle" was removed because its
@@ -36,6 +61,16 @@ enum IndependentActionKind: String, Codable, CaseIterable, Equatable {
     /// trigger only acts via its per-app rules and is inert everywhere else,
     /// or as a rule action to disable a key in specific apps.
     case noop
+    /// Panic button: post a key-up for every modifier and drop any latched
+    /// chords, for when an injected combo left Shift/Ctrl/Option/Cmd stuck.
+    case releaseModifiers = "release_modifiers"
+    /// Vim's `.`: run the last action any trigger fired again. A count
+    /// prefix repeats it that many times.
+    case repeatLast = "repeat_last"
+    /// Latch Shift for the Caps-layer motions that follow, to extend a
+    /// selection without holding it. Pressing it again, any other chord, a
+    /// pause of `stickyShiftTimeoutMs` or releasing Caps ends it.
+    case stickyShift = "sticky_shift"
 }
 
 enum ModifierKey: String, Codable, CaseIterable, Equatable {
@@ -50,14 +85,54 @@ enum ModifierKey: String, Codable, CaseIterable, Equatable {
     case fn
 }
 
+/// Interpreter for a `.script` action. `shell` is the same `/bin/sh -c` as a
+/// Command action; `applescript` hands the source to `osascript`.
+enum ScriptLanguage: String, Codable, CaseIterable, Equatable {
+    case shell
+    case applescript
+}
+
+/// Something HyperCapslock does to itself, bound like any other action so the
+/// app can be driven without the tray. Executed on the main queue by `AppState`.
+enum AppCommand: String, Codable, CaseIterable, Equatable {
+    case togglePause = "toggle_pause"
+    case reloadConfig = "reload_config"
+    case showWindow = "show_window"
+    case quit
+    /// Open the searchable list of bindings (see `CommandPalette`).
+    case showPalette = "show_palette"
+}
+
+/// What a `.transformSelection` action does to the selected text.
+enum SelectionTransform: String, Codable, CaseIterable, Equatable {
+    case uppercase, lowercase
+    /// Strip leading/trailing whitespace and newlines.
+    case trim
+}
+
+/// The key combo that opens an editor's go-to-line field, for `.goTo`.
+/// `targetKey` is a JS keycode, like a Key Combo's. Cmd+L is Xcode's and
+/// TextMate's; VS Code and Sublime Text use Ctrl+G (bind that in a per-app rule).
+struct GoToShortcut: Equatable {
+    var targetKey: UInt16
+    var withCtrl = false
+    var withAlt = false
+    var withCmd = false
+    var withShift = false
+
+    static let `default` = GoToShortcut(targetKey: 76, withCmd: true)
+}
+
 // MARK: - ActionConfig (internally tagged by `kind`)
 
 enum ActionConfig: Equatable {
     case directional(DirectionalActionKind)
-    case jump(direction: JumpDirection, count: Int)
+    case jump(direction: JumpDirection, count: Int, unit: JumpUnit = .line)
     case independent(IndependentActionKind)
     case inputSource(inputSourceID: String)
-    case command(String)
+    /// A `/bin/sh -c` command. With `capture`, the app waits for it and
+    /// surfaces its output and exit status; otherwise it's fire-and-forget.
+    case command(String, capture: Bool = false)
     case keyCombo(targetKey: UInt16, withCtrl: Bool, withAlt: Bool, withCmd: Bool, withTargetShift: Bool)
     case openApp(bundleID: String, name: String)
     /// Hold a single modifier key down for as long as the (hold-type) trigger is
@@ -66,6 +141,29 @@ enum ActionConfig: Equatable {
     /// a time (a second hold-modifier chord is ignored while one is active). See
     /// `ActionExecutor.execute`.
     case modifierKey(ModifierKey)
+    /// Type `open`, `innerSpaces` spaces twice, then `close`, and leave the caret
+    /// in the middle — e.g. `()`, `{ | }`, `« »`. The built-in Insert Quotes is
+    /// the `"""` / `"""` instance of this (see `ActionExecutor.wrapPairPlan`).
+    case wrapPair(open: String, close: String, innerSpaces: Int)
+    /// Control HyperCapslock itself (pause, reload, show window, quit).
+    case appControl(AppCommand)
+    /// Run `source` with the language's interpreter — AppleScript without
+    /// spelling out `osascript -e '…'` inside a shell command.
+    case script(language: ScriptLanguage, source: String)
+    /// Show a system notification — visible feedback for a binding.
+    case notify(title: String, body: String)
+    /// Rewrite the focused field's selection in place through the Accessibility
+    /// API (see `SelectionEditor`) — no synthesized keys, no clipboard.
+    case transformSelection(SelectionTransform)
+    /// Open the editor's go-to-line field with `shortcut`, type `line` (and
+    /// `:column`), then Return. With no `line`, a count prefix supplies it
+    /// (Caps+123 then the chord goes to line 123); with neither, the field is
+    /// just opened for the user to type. An app without the shortcut simply
+    /// receives the keys.
+    case goTo(line: Int?, column: Int?, shortcut: GoToShortcut = .default)
+    /// Type the current date/time, `strftime`-formatted (see `DateTimeText`),
+    /// the way a wrap pair types its text.
+    case insertDateTime(format: String)
 
     var kindTag: String {
         switch self {
@@ -77,6 +175,13 @@ enum ActionConfig: Equatable {
         case .keyCombo: return "key_combo"
         case .openApp: return "open_app"
         case .modifierKey: return "hold_modifier"
+        case .wrapPair: return "wrap_pair"
+        case .appControl: return "app_control"
+        case .script: return "script"
+        case .notify: return "notify"
+        case .transformSelection: return "transform_selection"
+        case .goTo: return "go_to"
+        case .insertDateTime: return "insert_date_time"
         }
     }
 
@@ -91,9 +196,9 @@ enum ActionConfig: Equatable {
 
 extension ActionConfig: Codable {
     private enum CodingKeys: String, CodingKey {
-        case kind, action, direction, count
+        case kind, action, direction, count, unit
         case inputSourceID = "input_source_id"
-        case command
+        case command, capture
         case targetKey = "target_key"
         case withCtrl = "with_ctrl"
         case withAlt = "with_alt"
@@ -102,6 +207,13 @@ extension ActionConfig: Codable {
         case bundleID = "bundle_id"
         case appName = "app_name"
         case modifier
+        case open, close
+        case innerSpaces = "inner_spaces"
+        case lang, source
+        case title, body
+        case op
+        case line, column
+        case format
     }
 
     init(from decoder: Decoder) throws {
@@ -112,13 +224,15 @@ extension ActionConfig: Codable {
             self = .directional(try c.decode(DirectionalActionKind.self, forKey: .action))
         case "jump":
             self = .jump(direction: try c.decode(JumpDirection.self, forKey: .direction),
-                         count: try c.decode(Int.self, forKey: .count))
+                         count: try c.decode(Int.self, forKey: .count),
+                         unit: try c.decodeIfPresent(JumpUnit.self, forKey: .unit) ?? .line)
         case "independent":
             self = .independent(try c.decode(IndependentActionKind.self, forKey: .action))
         case "input_source":
             self = .inputSource(inputSourceID: try c.decode(String.self, forKey: .inputSourceID))
         case "command":
-            self = .command(try c.decode(String.self, forKey: .command))
+            self = .command(try c.decode(String.self, forKey: .command),
+                            capture: try c.decodeIfPresent(Bool.self, forKey: .capture) ?? false)
         case "key_combo":
             self = .keyCombo(
                 targetKey: try c.decode(UInt16.self, forKey: .targetKey),
@@ -131,6 +245,34 @@ extension ActionConfig: Codable {
                             name: try c.decodeIfPresent(String.self, forKey: .appName) ?? "")
         case "hold_modifier":
             self = .modifierKey(try c.decode(ModifierKey.self, forKey: .modifier))
+        case "wrap_pair":
+            self = .wrapPair(open: try c.decode(String.self, forKey: .open),
+                             close: try c.decode(String.self, forKey: .close),
+                             innerSpaces: try c.decodeIfPresent(Int.self, forKey: .innerSpaces) ?? 0)
+        case "app_control":
+            self = .appControl(try c.decode(AppCommand.self, forKey: .command))
+        case "script":
+            self = .script(language: try c.decode(ScriptLanguage.self, forKey: .lang),
+                           source: try c.decode(String.self, forKey: .source))
+        case "notify":
+            self = .notify(title: try c.decode(String.self, forKey: .title),
+                           body: try c.decodeIfPresent(String.self, forKey: .body) ?? "")
+        case "transform_selection":
+            self = .transformSelection(try c.decode(SelectionTransform.self, forKey: .op))
+        case "go_to":
+            // The shortcut's fields sit beside line/column, spelled like a Key Combo's.
+            let shortcut = try c.decodeIfPresent(UInt16.self, forKey: .targetKey).map {
+                GoToShortcut(targetKey: $0,
+                             withCtrl: try c.decodeIfPresent(Bool.self, forKey: .withCtrl) ?? false,
+                             withAlt: try c.decodeIfPresent(Bool.self, forKey: .withAlt) ?? false,
+                             withCmd: try c.decodeIfPresent(Bool.self, forKey: .withCmd) ?? false,
+                             withShift: try c.decodeIfPresent(Bool.self, forKey: .withTargetShift) ?? false)
+            }
+            self = .goTo(line: try c.decodeIfPresent(Int.self, forKey: .line),
+                         column: try c.decodeIfPresent(Int.self, forKey: .column),
+                         shortcut: shortcut ?? .default)
+        case "insert_date_time":
+            self = .insertDateTime(format: try c.decodeIfPresent(String.self, forKey: .format) ?? DateTimeText.defaultFormat)
         default:
             throw DecodingError.dataCorruptedError(forKey: .kind, in: c,
                 debugDescription: "unknown action kind: \(kind)")
@@ -143,15 +285,19 @@ extension ActionConfig: Codable {
         switch self {
         case .directional(let a):
             try c.encode(a, forKey: .action)
-        case .jump(let dir, let count):
+        case .jump(let dir, let count, let unit):
             try c.encode(dir, forKey: .direction)
             try c.encode(count, forKey: .count)
+            // Omitted for lines so existing configs save byte-identical.
+            if unit != .line { try c.encode(unit, forKey: .unit) }
         case .independent(let a):
             try c.encode(a, forKey: .action)
         case .inputSource(let id):
             try c.encode(id, forKey: .inputSourceID)
-        case .command(let cmd):
+        case .command(let cmd, let capture):
             try c.encode(cmd, forKey: .command)
+            // Omitted when off so existing configs save byte-identical.
+            if capture { try c.encode(true, forKey: .capture) }
         case .keyCombo(let key, let ctrl, let alt, let cmd, let shift):
             try c.encode(key, forKey: .targetKey)
             try c.encode(ctrl, forKey: .withCtrl)
@@ -163,6 +309,30 @@ extension ActionConfig: Codable {
             try c.encode(name, forKey: .appName)
         case .modifierKey(let m):
             try c.encode(m, forKey: .modifier)
+        case .wrapPair(let open, let close, let spaces):
+            try c.encode(open, forKey: .open)
+            try c.encode(close, forKey: .close)
+            if spaces != 0 { try c.encode(spaces, forKey: .innerSpaces) }
+        case .appControl(let cmd):
+            try c.encode(cmd, forKey: .command)
+        case .script(let lang, let source):
+            try c.encode(lang, forKey: .lang)
+            try c.encode(source, forKey: .source)
+        case .notify(let title, let body):
+            try c.encode(title, forKey: .title)
+            try c.encode(body, forKey: .body)
+        case .transformSelection(let op):
+            try c.encode(op, forKey: .op)
+        case .goTo(let line, let column, let shortcut):
+            try c.encodeIfPresent(line, forKey: .line)
+            try c.encodeIfPresent(column, forKey: .column)
+            try c.encode(shortcut.targetKey, forKey: .targetKey)
+            try c.encode(shortcut.withCtrl, forKey: .withCtrl)
+            try c.encode(shortcut.withAlt, forKey: .withAlt)
+            try c.encode(shortcut.withCmd, forKey: .withCmd)
+            try c.encode(shortcut.withShift, forKey: .withTargetShift)
+        case .insertDateTime(let format):
+            try c.encode(format, forKey: .format)
         }
     }
 }
@@ -170,10 +340,16 @@ extension ActionConfig: Codable {
 // MARK: - Trigger (internally tagged by `kind`)
 
 enum Trigger: Equatable, Hashable {
-    case hyperPlusKey(key: UInt16, withShift: Bool)
+    /// Caps+key, optionally also holding Shift and/or Ctrl/Option/Cmd. A chord
+    /// bound with Ctrl/Option/Cmd fires only with exactly those held; a plain
+    /// one also takes presses with extra ones and forwards them to its action.
+    case hyperPlusKey(key: UInt16, withShift: Bool, ctrl: Bool = false, alt: Bool = false, cmd: Bool = false)
     case singleTapHyper
     case doubleTapHyper
     case doubleTapModifier(ModifierKey)
+    /// A global hotkey that fires without Caps: `key` with exactly these
+    /// modifiers held. See `hotkeyAllowed` for which combos are accepted.
+    case hotkey(key: UInt16, ctrl: Bool, alt: Bool, cmd: Bool, shift: Bool)
 
     var kindTag: String {
         switch self {
@@ -181,11 +357,18 @@ enum Trigger: Equatable, Hashable {
         case .singleTapHyper: return "single_tap_hyper"
         case .doubleTapHyper: return "double_tap_hyper"
         case .doubleTapModifier: return "double_tap_modifier"
+        case .hotkey: return "hotkey"
         }
     }
 
+    /// A hotkey must hold Ctrl, Option or Cmd, or be a function key (F1–F24) —
+    /// otherwise it would swallow ordinary typing (Shift+letter included).
+    static func hotkeyAllowed(key: UInt16, ctrl: Bool, alt: Bool, cmd: Bool) -> Bool {
+        ctrl || alt || cmd || (112...135).contains(key)
+    }
+
     var hyperPlusKey: (key: UInt16, withShift: Bool)? {
-        if case let .hyperPlusKey(key, withShift) = self { return (key, withShift) }
+        if case let .hyperPlusKey(key, withShift, _, _, _) = self { return (key, withShift) }
         return nil
     }
 }
@@ -195,6 +378,9 @@ extension Trigger: Codable {
         case kind, key
         case withShift = "with_shift"
         case modifier
+        case withCtrl = "with_ctrl"
+        case withAlt = "with_alt"
+        case withCmd = "with_cmd"
     }
 
     init(from decoder: Decoder) throws {
@@ -203,13 +389,22 @@ extension Trigger: Codable {
         switch kind {
         case "hyper_plus_key":
             self = .hyperPlusKey(key: try c.decode(UInt16.self, forKey: .key),
-                                 withShift: try c.decodeIfPresent(Bool.self, forKey: .withShift) ?? false)
+                                 withShift: try c.decodeIfPresent(Bool.self, forKey: .withShift) ?? false,
+                                 ctrl: try c.decodeIfPresent(Bool.self, forKey: .withCtrl) ?? false,
+                                 alt: try c.decodeIfPresent(Bool.self, forKey: .withAlt) ?? false,
+                                 cmd: try c.decodeIfPresent(Bool.self, forKey: .withCmd) ?? false)
         case "single_tap_hyper":
             self = .singleTapHyper
         case "double_tap_hyper":
             self = .doubleTapHyper
         case "double_tap_modifier":
             self = .doubleTapModifier(try c.decode(ModifierKey.self, forKey: .modifier))
+        case "hotkey":
+            self = .hotkey(key: try c.decode(UInt16.self, forKey: .key),
+                           ctrl: try c.decodeIfPresent(Bool.self, forKey: .withCtrl) ?? false,
+                           alt: try c.decodeIfPresent(Bool.self, forKey: .withAlt) ?? false,
+                           cmd: try c.decodeIfPresent(Bool.self, forKey: .withCmd) ?? false,
+                           shift: try c.decodeIfPresent(Bool.self, forKey: .withShift) ?? false)
         default:
             throw DecodingError.dataCorruptedError(forKey: .kind, in: c,
                 debugDescription: "unknown trigger kind: \(kind)")
@@ -220,13 +415,23 @@ extension Trigger: Codable {
         var c = encoder.container(keyedBy: CodingKeys.self)
         try c.encode(kindTag, forKey: .kind)
         switch self {
-        case .hyperPlusKey(let key, let withShift):
+        case .hyperPlusKey(let key, let withShift, let ctrl, let alt, let cmd):
             try c.encode(key, forKey: .key)
             try c.encode(withShift, forKey: .withShift)
+            // Only when set, so existing plain chords stay byte-identical.
+            if ctrl { try c.encode(true, forKey: .withCtrl) }
+            if alt { try c.encode(true, forKey: .withAlt) }
+            if cmd { try c.encode(true, forKey: .withCmd) }
         case .singleTapHyper, .doubleTapHyper:
             break
         case .doubleTapModifier(let m):
             try c.encode(m, forKey: .modifier)
+        case .hotkey(let key, let ctrl, let alt, let cmd, let shift):
+            try c.encode(key, forKey: .key)
+            if ctrl { try c.encode(true, forKey: .withCtrl) }
+            if alt { try c.encode(true, forKey: .withAlt) }
+            if cmd { try c.encode(true, forKey: .withCmd) }
+            if shift { try c.encode(true, forKey: .withShift) }
         }
     }
 }
@@ -245,12 +450,31 @@ struct ActionMappingEntry: Equatable {
     /// under the `bindings` key only when non-empty, so existing configs stay
     /// byte-identical until a per-app rule is added.
     var bindings: [MappingBinding]
+    /// Auto-repeat behavior while the chord is held. Only meaningful for
+    /// `hyperPlusKey` and `hotkey` triggers. Serialized under `repeat` only when not `.auto`.
+    var repeatMode: RepeatMode
+    /// What the binding is for, in the user's words ("Save", "Push to talk"),
+    /// shown next to the trigger. Serialized under `label` only when set, and
+    /// — unlike a `#` comment — kept by every save and returned over the socket.
+    var label: String?
+    /// Longer free text about the binding, shown on hover. Serialized under
+    /// `note` only when set.
+    var note: String?
+    /// Off = the engine skips the entry (its key passes through natively) but
+    /// it stays in the file, action and all, to switch back on later.
+    /// Serialized under `enabled` only when false.
+    var enabled: Bool
 
-    init(trigger: Trigger, actionId: String? = nil, inlineAction: ActionConfig? = nil, bindings: [MappingBinding] = []) {
+    init(trigger: Trigger, actionId: String? = nil, inlineAction: ActionConfig? = nil, bindings: [MappingBinding] = [],
+         repeatMode: RepeatMode = .auto, label: String? = nil, note: String? = nil, enabled: Bool = true) {
         self.trigger = trigger
         self.actionId = actionId
         self.inlineAction = inlineAction
         self.bindings = bindings
+        self.repeatMode = repeatMode
+        self.label = label
+        self.note = note
+        self.enabled = enabled
     }
 }
 
@@ -261,6 +485,8 @@ extension ActionMappingEntry: Codable {
         case actionId = "action_id"
         case action
         case bindings
+        case repeatMode = "repeat"
+        case label, note, enabled
     }
 
     init(from decoder: Decoder) throws {
@@ -279,6 +505,11 @@ extension ActionMappingEntry: Codable {
         self.actionId = try c.decodeIfPresent(String.self, forKey: .actionId)
         self.inlineAction = try c.decodeIfPresent(ActionConfig.self, forKey: .action)
         self.bindings = try c.decodeIfPresent([MappingBinding].self, forKey: .bindings) ?? []
+        // Tolerant: an unknown future mode falls back to `.auto`.
+        self.repeatMode = (try? c.decodeIfPresent(RepeatMode.self, forKey: .repeatMode)) ?? .auto
+        self.label = try c.decodeIfPresent(String.self, forKey: .label)
+        self.note = try c.decodeIfPresent(String.self, forKey: .note)
+        self.enabled = try c.decodeIfPresent(Bool.self, forKey: .enabled) ?? true
     }
 
     func encode(to encoder: Encoder) throws {
@@ -287,5 +518,9 @@ extension ActionMappingEntry: Codable {
         try c.encodeIfPresent(actionId, forKey: .actionId)
         try c.encodeIfPresent(inlineAction, forKey: .action)
         if !bindings.isEmpty { try c.encode(bindings, forKey: .bindings) }
+        if repeatMode != .auto { try c.encode(repeatMode, forKey: .repeatMode) }
+        try c.encodeIfPresent(label, forKey: .label)
+        try c.encodeIfPresent(note, forKey: .note)
+        if !enabled { try c.encode(enabled, forKey: .enabled) }
     }
 }
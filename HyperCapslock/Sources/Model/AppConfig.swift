@@ -13,6 +13,18 @@ enum MappingsViewStyle: String, Codable, CaseIterable, Equatable {
     case keyboard  // a visual keyboard map
 }
 
+/// What the menu-bar item shows: the template icon, a short text title
+/// ("HC" running, "HC•" paused / off — see `TrayController.title`), or both.
+/// Some users keep a text-only menu bar.
+enum TrayStyle: String, Codable, CaseIterable, Equatable {
+    case icon
+    case title
+    case both
+
+    var showsIcon: Bool { self != .title }
+    var showsTitle: Bool { self != .icon }
+}
+
 /// Reliability workaround applied when a `Caps+key → input source` mapping
 /// targets a CJKV IME (Chinese / Japanese / Korean / Vietnamese), where a plain
 /// `TISSelectInputSource` often changes the menu-bar icon but leaves typing in
@@ -25,6 +37,103 @@ enum CJKVFixStrategy: String, Codable, CaseIterable, Equatable {
     case switchingFocus = "switching_focus"
 }
 
+/// What the Word Forward / Word Back actions send. `word` is Option+Arrow (the
+/// system whole-word boundary); `subword` is Ctrl+Option+Arrow, which editors
+/// like VS Code bind to camelCase / snake_case part motions. A single mapping
+/// can still pin its own shortcut with a Key Combo action.
+enum WordMotionStyle: String, Codable, CaseIterable, Equatable {
+    case word
+    case subword
+}
+
+/// How New Line and Open Line Above reach the edge of the line. `visual` is
+/// Cmd+Right / Cmd+Left, the end of the line as displayed, so in a soft-wrapped
+/// paragraph the Return lands mid-paragraph. `logical` is Ctrl+E / Ctrl+A, the
+/// text system's end / start of the paragraph, which skips past the wrapping.
+enum LineEdgeStyle: String, Codable, CaseIterable, Equatable {
+    case visual
+    case logical
+}
+
+/// How the Caps layer is entered. `hold` is the classic hold-Caps-and-press;
+/// `oneShot` turns a short tap into "the next key goes through the layer" (for
+/// anyone who can't comfortably hold two keys); `both` allows either. With
+/// one-shot on, a tap no longer toggles CapsLock — a second tap cancels instead.
+enum CapsLayerMode: String, Codable, CaseIterable, Equatable {
+    case hold
+    case oneShot = "one_shot"
+    case both
+
+    /// Whether Caps held down acts as the layer.
+    var allowsHold: Bool { self != .oneShot }
+    /// Whether a short Caps tap arms a one-shot layer.
+    var allowsOneShot: Bool { self != .hold }
+}
+
+/// How the Caps+Shift layer relates to the Caps layer. `fallback` lets an
+/// unbound Caps+Shift+key borrow its Caps+key motion (Shift then selects);
+/// `strict` keeps the two layers separate — an unbound Caps+Shift+key is just
+/// unbound, for keymaps that give Shift its own meaning.
+enum ShiftLayerMode: String, Codable, CaseIterable, Equatable {
+    case fallback
+    case strict
+}
+
+/// The modifier that, held together with Caps, sends the next key through raw:
+/// Caps+Fn+H types `h` even though Caps+H is mapped. `off` disables the escape.
+/// A modified Caps chord bound with the same modifier can't fire while it's set.
+enum RawEscapeModifier: String, Codable, CaseIterable, Equatable {
+    case off, fn, control, option, command
+}
+
+/// Where in the system's event chain the keyboard tap sits, and where our
+/// synthesized keys are posted. `hid` (default) sees keys before any other
+/// session-level tap; `session` and `annotatedSession` move further down the
+/// chain, for the rare assistive tool or remapper that needs to see keys first.
+enum EventTapLocation: String, Codable, CaseIterable, Equatable {
+    case hid
+    case session
+    case annotatedSession = "annotated_session"
+}
+
+/// The engine state the app comes back in after a relaunch: whatever the user
+/// last picked from the tray (or a pause/resume chord). A timed pause isn't
+/// recorded — it would have resumed by itself anyway.
+enum ServiceState: String, Codable, CaseIterable, Equatable {
+    case running
+    case paused
+    /// Tap and CapsLock remap never installed at launch.
+    case disabled
+}
+
+/// What a short Caps tap does, as offered by the Settings picker. Not stored
+/// itself: it's a view over the single-tap Caps mapping plus
+/// `capsToggleEnabled`, so the Mappings page and Settings never disagree.
+enum CapsTapChoice: String, CaseIterable, Equatable {
+    case toggleCaps = "toggle_caps"
+    case escape
+    case nothing
+    /// Some other single-tap mapping, set up on the Mappings page.
+    case custom
+
+    /// The action "Escape" binds to single-tap Caps.
+    static let escapeAction = ActionConfig.keyCombo(targetKey: 27, withCtrl: false, withAlt: false, withCmd: false,
+                                                    withTargetShift: false)
+
+    /// Classify the current state. `singleTap` is the single-tap Caps mapping,
+    /// if any; per-app rules make it `.custom` whatever its default action.
+    static func current(singleTap: ActionMappingEntry?, toggleEnabled: Bool) -> CapsTapChoice {
+        guard let entry = singleTap else { return toggleEnabled ? .toggleCaps : .nothing }
+        guard entry.bindings.isEmpty else { return .custom }
+        switch ActionsRegistry.shared.resolve(entry) {
+        case escapeAction?: return .escape
+        case .independent(.toggleCapsLock)?: return .toggleCaps
+        case .independent(.noop)?: return .nothing
+        default: return .custom
+        }
+    }
+}
+
 /// App-level preferences, persisted as `app_config.yml`. Unknown keys are
 /// ignored on decode (tolerant); known keys round-trip.
 struct AppConfig: Codable, Equatable {
@@ -46,9 +155,66 @@ struct AppConfig: Codable, Equatable {
     /// opened from the tray's "Open Window" item or by clicking the Dock icon.
     var showWindowOnLaunch: Bool = true
     /// User-configured low-level key remaps (a spare right modifier → a free
-    /// function key), applied via `hidutil` on top of the built-in CapsLock→F18
+    /// function key), applied via `hidutil` on top of the built-in layer-key→F18
     /// remap. Empty by default. See `KeyRemap`.
     var keyRemaps: [KeyRemap] = []
+    /// How long a leader-key sequence (`Caps+<leader>`, see `SequenceTable`)
+    /// waits for its follow-up key before it lapses.
+    var sequenceTimeoutMs: Int = 800
+    /// Lines the default Caps+U / Caps+D jumps move. `defaultJumpCountUp` /
+    /// `Down` override it per direction; a count other than the shipped 10
+    /// binds an inline jump instead of `builtin.jump_*_10` (see
+    /// `ConfigStore.defaultMappings`). Applies to seeded and reset mappings.
+    var defaultJumpCount: Int = 10
+    var defaultJumpCountUp: Int?
+    var defaultJumpCountDown: Int?
+    /// Bundle ids of apps where HyperCapslock steps aside entirely (remote
+    /// desktops, VMs, games with their own CapsLock handling) — the engine
+    /// passes every key through while one of them is frontmost.
+    var excludedApps: [String] = []
+    var wordMotionStyle: WordMotionStyle = .word
+    var lineEdgeStyle: LineEdgeStyle = .visual
+    var capsLayerMode: CapsLayerMode = .hold
+    /// Whether a short Caps tap with no single-tap mapping toggles Caps Lock.
+    /// Off turns Caps into a pure layer key: a bare tap does nothing.
+    var capsToggleEnabled: Bool = true
+    /// Keep Caps Lock off: a toggle can only clear it, and it's cleared when
+    /// this is applied and whenever the layer key is released.
+    var capsLockPinnedOff: Bool = false
+    var shiftLayerMode: ShiftLayerMode = .fallback
+    /// Held with Caps, passes the chord key through unmapped (see `RawEscapeModifier`).
+    var rawEscapeModifier: RawEscapeModifier = .fn
+    var serviceState: ServiceState = .running
+    /// Listen on the local scripting socket (`ControlServer`). Off by default:
+    /// nothing outside the app can drive it unless the user opts in.
+    var controlSocketEnabled: Bool = false
+    /// Advanced: where the keyboard tap is installed. Changing it reinstalls the tap.
+    var tapLocation: EventTapLocation = .hid
+    /// Advanced: where synthesized keystrokes are posted.
+    var postLocation: EventTapLocation = .hid
+    /// Advanced: pause between the keys of a multi-key sequence (a jump's taps,
+    /// a wrap pair's characters), for apps that drop back-to-back synthetic
+    /// keys. 0 = none.
+    var injectDelayMs: Int = 0
+    /// Advanced: also tap mouse buttons, so a click while Caps is held is
+    /// seen (see `CapsMouseButton`). Off by default — keyboard-only users
+    /// shouldn't have every click routed through us.
+    var capsMouseEvents: Bool = false
+    /// Drop a second key-down of the same key within this many ms, for
+    /// keyboards whose switches chatter. 0 = off.
+    var debounceMs: Int = 0
+    /// Caps+key chords that always pass through, mapping or not. See `PassthroughKey`.
+    var passthroughKeys: [PassthroughKey] = []
+    /// Set once the user finishes (or dismisses) the first-run checklist in
+    /// Settings. Existing installs that already have Accessibility are marked
+    /// done on launch, so only genuinely new users see it.
+    var onboardingCompleted: Bool = false
+    /// Size cap for the engine log before it is rotated (see `FileLog`).
+    var logMaxMb: Int = 5
+    /// The physical key remapped to F18 to act as the layer key. See `LayerKey`.
+    var layerKey: LayerKey = .capsLock
+    /// Icon, text title, or both in the menu bar. See `TrayStyle`.
+    var trayStyle: TrayStyle = .icon
 
     enum CodingKeys: String, CodingKey {
         case hideDockIcon = "hide_dock_icon"
@@ -61,6 +227,30 @@ struct AppConfig: Codable, Equatable {
         case statsShowInline = "stats_show_inline"
         case showWindowOnLaunch = "show_window_on_launch"
         case keyRemaps = "key_remaps"
+        case sequenceTimeoutMs = "sequence_timeout_ms"
+        case defaultJumpCount = "default_jump_count"
+        case defaultJumpCountUp = "default_jump_count_up"
+        case defaultJumpCountDown = "default_jump_count_down"
+        case excludedApps = "excluded_apps"
+        case wordMotionStyle = "word_motion_style"
+        case lineEdgeStyle = "line_edge_style"
+        case capsLayerMode = "caps_layer_mode"
+        case capsToggleEnabled = "caps_toggle_enabled"
+        case capsLockPinnedOff = "caps_lock_pinned_off"
+        case shiftLayerMode = "shift_layer_mode"
+        case rawEscapeModifier = "raw_escape_modifier"
+        case serviceState = "service_state"
+        case controlSocketEnabled = "control_socket_enabled"
+        case tapLocation = "tap_location"
+        case postLocation = "post_location"
+        case injectDelayMs = "inject_delay_ms"
+        case capsMouseEvents = "caps_mouse_events"
+        case debounceMs = "debounce_ms"
+        case passthroughKeys = "passthrough_keys"
+        case onboardingCompleted = "onboarding_completed"
+        case logMaxMb = "log_max_mb"
+        case layerKey = "layer_key"
+        case trayStyle = "tray_style"
     }
 
     init(hideDockIcon: Bool = false, showHud: Bool = false, hudDurationMs: Int = 1350,
@@ -69,7 +259,31 @@ struct AppConfig: Codable, Equatable {
          mappingsViewStyle: MappingsViewStyle = .grouped,
          statsShowInline: Bool = true,
          showWindowOnLaunch: Bool = true,
-         keyRemaps: [KeyRemap] = []) {
+         keyRemaps: [KeyRemap] = [],
+         sequenceTimeoutMs: Int = 800,
+         defaultJumpCount: Int = 10,
+         defaultJumpCountUp: Int? = nil,
+         defaultJumpCountDown: Int? = nil,
+         excludedApps: [String] = [],
+         wordMotionStyle: WordMotionStyle = .word,
+         lineEdgeStyle: LineEdgeStyle = .visual,
+         capsLayerMode: CapsLayerMode = .hold,
+         capsToggleEnabled: Bool = true,
+         capsLockPinnedOff: Bool = false,
+         shiftLayerMode: ShiftLayerMode = .fallback,
+         rawEscapeModifier: RawEscapeModifier = .fn,
+         serviceState: ServiceState = .running,
+         controlSocketEnabled: Bool = false,
+         tapLocation: EventTapLocation = .hid,
+         postLocation: EventTapLocation = .hid,
+         injectDelayMs: Int = 0,
+         capsMouseEvents: Bool = false,
+         debounceMs: Int = 0,
+         passthroughKeys: [PassthroughKey] = [],
+         onboardingCompleted: Bool = false,
+         logMaxMb: Int = 5,
+         layerKey: LayerKey = .capsLock,
+         trayStyle: TrayStyle = .icon) {
         self.hideDockIcon = hideDockIcon
         self.showHud = showHud
         self.hudDurationMs = hudDurationMs
@@ -80,6 +294,30 @@ struct AppConfig: Codable, Equatable {
         self.statsShowInline = statsShowInline
         self.showWindowOnLaunch = showWindowOnLaunch
         self.keyRemaps = keyRemaps
+        self.sequenceTimeoutMs = sequenceTimeoutMs
+        self.defaultJumpCount = defaultJumpCount
+        self.defaultJumpCountUp = defaultJumpCountUp
+        self.defaultJumpCountDown = defaultJumpCountDown
+        self.excludedApps = excludedApps
+        self.wordMotionStyle = wordMotionStyle
+        self.lineEdgeStyle = lineEdgeStyle
+        self.capsLayerMode = capsLayerMode
+        self.capsToggleEnabled = capsToggleEnabled
+        self.capsLockPinnedOff = capsLockPinnedOff
+        self.shiftLayerMode = shiftLayerMode
+        self.rawEscapeModifier = rawEscapeModifier
+        self.serviceState = serviceState
+        self.controlSocketEnabled = controlSocketEnabled
+        self.tapLocation = tapLocation
+        self.postLocation = postLocation
+        self.injectDelayMs = injectDelayMs
+        self.capsMouseEvents = capsMouseEvents
+        self.debounceMs = debounceMs
+        self.passthroughKeys = passthroughKeys
+        self.onboardingCompleted = onboardingCompleted
+        self.logMaxMb = logMaxMb
+        self.layerKey = layerKey
+        self.trayStyle = trayStyle
     }
 
     init(from decoder: Decoder) throws {
@@ -99,5 +337,38 @@ struct AppConfig: Codable, Equatable {
         // Tolerant: a missing list, or one with an unknown source/target token,
         // decodes back to empty rather than failing the whole config load.
         self.keyRemaps = (try? c.decodeIfPresent([KeyRemap].self, forKey: .keyRemaps)) ?? []
+        self.sequenceTimeoutMs = try c.decodeIfPresent(Int.self, forKey: .sequenceTimeoutMs) ?? 800
+        self.defaultJumpCount = try c.decodeIfPresent(Int.self, forKey: .defaultJumpCount) ?? 10
+        self.defaultJumpCountUp = try c.decodeIfPresent(Int.self, forKey: .defaultJumpCountUp)
+        self.defaultJumpCountDown = try c.decodeIfPresent(Int.self, forKey: .defaultJumpCountDown)
+        self.excludedApps = try c.decodeIfPresent([String].self, forKey: .excludedApps) ?? []
+        self.wordMotionStyle = (try? c.decodeIfPresent(WordMotionStyle.self, forKey: .wordMotionStyle)) ?? .word
+        self.lineEdgeStyle = (try? c.decodeIfPresent(LineEdgeStyle.self, forKey: .lineEdgeStyle)) ?? .visual
+        self.capsLayerMode = (try? c.decodeIfPresent(CapsLayerMode.self, forKey: .capsLayerMode)) ?? .hold
+        self.capsToggleEnabled = try c.decodeIfPresent(Bool.self, forKey: .capsToggleEnabled) ?? true
+        self.capsLockPinnedOff = try c.decodeIfPresent(Bool.self, forKey: .capsLockPinnedOff) ?? false
+        self.shiftLayerMode = (try? c.decodeIfPresent(ShiftLayerMode.self, forKey: .shiftLayerMode)) ?? .fallback
+        self.rawEscapeModifier = (try? c.decodeIfPresent(RawEscapeModifier.self, forKey: .rawEscapeModifier)) ?? .fn
+        self.serviceState = (try? c.decodeIfPresent(ServiceState.self, forKey: .serviceState)) ?? .running
+        self.controlSocketEnabled = try c.decodeIfPresent(Bool.self, forKey: .controlSocketEnabled) ?? false
+        self.tapLocation = (try? c.decodeIfPresent(EventTapLocation.self, forKey: .tapLocation)) ?? .hid
+        self.postLocation = (try? c.decodeIfPresent(EventTapLocation.self, forKey: .postLocation)) ?? .hid
+        self.injectDelayMs = try c.decodeIfPresent(Int.self, forKey: .injectDelayMs) ?? 0
+        self.capsMouseEvents = try c.decodeIfPresent(Bool.self, forKey: .capsMouseEvents) ?? false
+        self.debounceMs = try c.decodeIfPresent(Int.self, forKey: .debounceMs) ?? 0
+        self.passthroughKeys = (try? c.decodeIfPresent([PassthroughKey].self, forKey: .passthroughKeys)) ?? []
+        self.onboardingCompleted = try c.decodeIfPresent(Bool.self, forKey: .onboardingCompleted) ?? false
+        self.logMaxMb = try c.decodeIfPresent(Int.self, forKey: .logMaxMb) ?? 5
+        // Tolerant: an unknown key name falls back to Caps Lock.
+        self.layerKey = (try? c.decodeIfPresent(LayerKey.self, forKey: .layerKey)) ?? .capsLock
+        self.trayStyle = (try? c.decodeIfPresent(TrayStyle.self, forKey: .trayStyle)) ?? .icon
+    }
+
+    /// The default jump's count for `direction`: its override, else the shared count.
+    func defaultJumpCount(_ direction: JumpDirection) -> Int {
+        switch direction {
+        case .up: return defaultJumpCountUp ?? defaultJumpCount
+        case .down: return defaultJumpCountDown ?? defaultJumpCount
+        }
     }
 }
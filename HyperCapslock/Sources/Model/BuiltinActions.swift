@@ -19,10 +19,13 @@ enum BuiltinActions {
         a("builtin.word_back",        "action.word_back",     .directional(.wordBack)),
         a("builtin.line_start",       "action.home",          .directional(.home)),
         a("builtin.line_end",         "action.end",           .directional(.end)),
+        a("builtin.doc_start",        "action.doc_start",     .directional(.docStart)),
+        a("builtin.doc_end",          "action.doc_end",       .directional(.docEnd)),
         a("builtin.jump_up_10",       "action.up",            .jump(direction: .up, count: 10)),
         a("builtin.jump_down_10",     "action.down",          .jump(direction: .down, count: 10)),
         a("builtin.backspace",        "action.backspace",     .independent(.backspace)),
         a("builtin.new_line",         "action.next_line",     .independent(.nextLine)),
+        a("builtin.open_line_above",  "action.open_line_above", .independent(.openLineAbove)),
         a("builtin.insert_quotes",    "action.insert_quotes", .independent(.insertQuotes)),
         a("builtin.toggle_caps_lock", "action.toggle_caps_lock", .independent(.toggleCapsLock)),
         // NOTE: `builtin.switch_input_source` (the auto 中/英 Smart Toggle) was
@@ -30,6 +33,17 @@ enum BuiltinActions {
         // `.switchInputSource` enum case is kept as an inert tombstone (see
         // ActionModel.swift); not re-listing it here is what hides it from users.
         a("builtin.noop",             "action.noop",          .independent(.noop)),
+        a("builtin.release_modifiers", "action.release_modifiers", .independent(.releaseModifiers)),
+        a("builtin.repeat_last",      "action.repeat_last",   .independent(.repeatLast)),
+        a("builtin.sticky_shift",     "action.sticky_shift",  .independent(.stickyShift)),
+        a("builtin.uppercase_selection", "action.uppercase", .transformSelection(.uppercase)),
+        a("builtin.lowercase_selection", "action.lowercase", .transformSelection(.lowercase)),
+        a("builtin.trim_selection",   "action.trim",          .transformSelection(.trim)),
+        a("builtin.toggle_pause",     "action.toggle_pause",  .appControl(.togglePause)),
+        a("builtin.reload_config",    "action.reload_config", .appControl(.reloadConfig)),
+        a("builtin.show_window",      "action.show_window",   .appControl(.showWindow)),
+        a("builtin.quit",             "action.quit",          .appControl(.quit)),
+        a("builtin.show_palette",     "action.show_palette",  .appControl(.showPalette)),
     ]
 
     private static let ids: Set<String> = Set(all.map(\.id))
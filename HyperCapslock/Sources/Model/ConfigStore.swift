@@ -6,26 +6,72 @@ import CryptoKit
 enum ConfigError: LocalizedError {
     case fileExists           // export target exists and overwrite not requested
     case emptyImport
-    case invalidEntry(String)
+    case invalidEntry(String, code: Code = .invalidEntry)
     case actionInUse(String)  // delete blocked: action referenced by mappings
+    case parse(String)        // the document isn't valid YAML, or isn't a config
     case io(String)
 
+    /// Stable, machine-readable kind of failure — the socket's `error_code` —
+    /// so a client can branch (or localize) without matching message text.
+    enum Code: String, Encodable {
+        case fileExists = "file_exists"
+        case emptyImport = "empty_import"
+        case invalidEntry = "invalid_entry"
+        case emptyCommand = "empty_command"
+        case emptyInputSource = "empty_input_source"
+        case invalidJumpCount = "invalid_jump_count"
+        case unknownAction = "unknown_action"
+        case actionInUse = "action_in_use"
+        case layerKeyInUse = "layer_key_in_use"
+        case unknownMapping = "unknown_mapping"
+        case parseError = "parse_error"
+        case io
+    }
+
+    var code: Code {
+        switch self {
+        case .fileExists: return .fileExists
+        case .emptyImport: return .emptyImport
+        case .invalidEntry(_, let code): return code
+        case .actionInUse: return .actionInUse
+        case .parse: return .parseError
+        case .io: return .io
+        }
+    }
+
     var errorDescription: String? {
         switch self {
         case .fileExists: return "FILE_EXISTS"
         case .emptyImport: return "Imported file contains no mappings"
-        case .invalidEntry(let m): return m
+        case .invalidEntry(let m, _): return m
         case .actionInUse(let m): return m
+        case .parse(let m): return m
         case .io(let m): return m
         }
     }
 }
 
+/// One file or folder HyperCapslock keeps on disk, for "where are my
+/// settings?" (Settings ▸ Files, the socket's `get_config_paths`).
+struct ConfigPath: Encodable, Equatable {
+    /// Stable id: mappings, settings, presets, backups, usage_stats, log.
+    var name: String
+    var path: String
+    var isDirectory: Bool
+    var exists: Bool
+
+    enum CodingKeys: String, CodingKey {
+        case name, path, exists
+        case isDirectory = "is_directory"
+    }
+}
+
 /// Owns the action mappings, the custom-action library, and app config.
 ///
 /// Config file (`action_mappings.yml`) is a structured document
-/// `{ actions: [custom…], mappings: [...] }`. A legacy bare-list (2.0) is read
-/// as mappings-with-inline-actions. Unknown keys — both top-level and per-entry —
+/// `{ version, actions: [custom…], mappings: [...] }`. Older shapes — the
+/// legacy bare-list (2.0), an unversioned document — are upgraded step by step
+/// on read (see `migrateDocument`) and written back at the current version. Unknown keys — both top-level and per-entry —
 /// are **preserved** across save (lossless) and never stripped, so a newer
 /// version's config survives an older build (downgrade-test safety). A parse
 /// failure NEVER overwrites the existing file. Built-ins live in code.
@@ -36,6 +82,31 @@ final class ConfigStore: ObservableObject {
     @Published private(set) var mappings: [ActionMappingEntry] = []
     @Published private(set) var customActions: [Action] = []
     @Published private(set) var appConfig = AppConfig()
+    /// Leader-key sequences from the optional top-level `sequences:` key. Hand-
+    /// edited only (no UI yet), so the raw node stays in `preservedTopLevel` and
+    /// round-trips verbatim on save.
+    @Published private(set) var sequences = SequenceTable()
+
+    /// What caused a `mappingsDidChange` post — lets a view pick an animation
+    /// (slide a row in/out on upsert/remove, plain refresh on reload).
+    enum MappingsChangeKind: String {
+        case upsert, remove, reload, undo, redo, reset
+    }
+
+    /// Posted on the main thread after every mapping mutation has been
+    /// committed (registry updated, file written). `userInfo` carries
+    /// `mappingsChangeKindKey` (a `MappingsChangeKind`) and `mappingsKey` (the
+    /// full new `[ActionMappingEntry]`), so observers outside SwiftUI — the
+    /// tray menu, a second window — never need to re-read the store.
+    static let mappingsDidChange = Notification.Name("me.xueshi.hypercapslock.mappingsDidChange")
+    static let mappingsChangeKindKey = "kind"
+    static let mappingsKey = "mappings"
+
+    /// Undo/redo for mapping edits made in this session (upsert, remove,
+    /// import). Not persisted; a load from disk starts a fresh history.
+    private var history = MappingsHistory()
+    @Published private(set) var canUndoMappings = false
+    @Published private(set) var canRedoMappings = false
 
     /// Lossless preservation: unknown top-level keys + per-entry raw nodes
     /// (keyed by trigger / action id), re-emitted on save.
@@ -47,10 +118,30 @@ final class ConfigStore: ObservableObject {
     /// an older build never drops a newer build's data (downgrade-safety).
     private var unknownMappingNodes: [Node] = []
     private var unknownActionNodes: [Node] = []
+    /// Hand-written comments (file header + the lines above each entry) and
+    /// the file's top-level key order, both re-applied on save so a UI edit
+    /// doesn't rewrite a hand-annotated file into a different shape.
+    private var comments = YamlComments()
+    private var topLevelOrder: [String] = []
+
+    /// The `version` this build writes. Bump it together with a new step in
+    /// `migrations` whenever the document's shape changes.
+    static let documentVersion = 3
+    /// Upgrade steps keyed by the version they upgrade from; each returns the
+    /// document one version up. Never edit a shipped step, only add the next.
+    private static let migrations: [Int: (note: String, upgrade: (Node) throws -> Node)] = [
+        1: ("wrap the legacy bare mappings list in a structured document",
+            { .mapping(Node.Mapping([(Node("mappings"), $0)])) }),
+        2: ("stamp the document version", { $0 }),
+    ]
+    /// The version the loaded file declared, when newer than ours — kept on
+    /// save so a downgraded build doesn't relabel a newer build's file.
+    private var loadedVersion = documentVersion
 
     // "bindings" is known so the fresh encode owns it: when a user clears all
     // per-app rules, the merge step must NOT resurrect a stale preserved node.
-    private static let mappingKnownKeys: Set<String> = ["trigger", "key", "with_shift", "action_id", "action", "bindings"]
+    private static let mappingKnownKeys: Set<String> = ["trigger", "key", "with_shift", "action_id", "action", "bindings", "repeat",
+                                                        "label", "note", "enabled"]
     private static let actionKnownKeys: Set<String> = ["id", "name", "action"]
 
     // MARK: Default keycodes (JavaScript keyCode values)
@@ -59,6 +150,8 @@ final class ConfigStore: ObservableObject {
         static let p: UInt16 = 80, y: UInt16 = 89, a: UInt16 = 65, e: UInt16 = 69
         static let u: UInt16 = 85, d: UInt16 = 68, i: UInt16 = 73, n: UInt16 = 78
         static let o: UInt16 = 79
+        static let esc: UInt16 = 27
+        static let semicolon: UInt16 = 186
         static let abc: UInt16 = 188      // ',' → ABC layout
         static let wechat: UInt16 = 190   // '.' → WeChat pinyin
     }
@@ -70,22 +163,62 @@ final class ConfigStore: ObservableObject {
     // Single source of truth: `AppEnvironment.appSupportDirectory` owns the
     // -uitest temp-dir isolation + the bundle-id data dir (shared with UsageStats
     // so the path can't drift). An empty dir makes ConfigStore seed defaults.
-    private var appDataDir: URL { AppEnvironment.appSupportDirectory }
+    // Tests pass a scratch dir to round-trip a real save and reload.
+    private let appDataDir: URL
+
+    init(appDataDir: URL = AppEnvironment.appSupportDirectory) {
+        self.appDataDir = appDataDir
+    }
+
     private var mappingsURL: URL { appDataDir.appendingPathComponent("action_mappings.yml") }
     private var appConfigURL: URL { appDataDir.appendingPathComponent("app_config.yml") }
+    private var backupsDir: URL { appDataDir.appendingPathComponent("backups", isDirectory: true) }
+
+    /// Every file and folder we read or write, resolved for this launch
+    /// (-uitest points them at a temp dir), and whether each exists yet.
+    func configPaths() -> [ConfigPath] {
+        let entries: [(String, URL, Bool)] = [
+            ("mappings", mappingsURL, false),
+            ("settings", appConfigURL, false),
+            ("presets", presetsDir, true),
+            ("backups", backupsDir, true),
+            ("usage_stats", UsageStats.shared.fileURL, false),
+            ("log", URL(fileURLWithPath: FileLog.shared.path), false),
+        ]
+        return entries.map { name, url, isDirectory in
+            ConfigPath(name: name, path: url.path, isDirectory: isDirectory,
+                       exists: FileManager.default.fileExists(atPath: url.path))
+        }
+    }
 
     // MARK: - Load
 
     func load() {
-        loadDocument()
+        // Safe mode runs on built-in defaults and leaves the files alone (see
+        // `saveToDisk` / `persistAppConfig`), whatever state they're in.
+        guard !AppEnvironment.isSafeMode else {
+            FileLog.shared.warn("SAFE MODE: not reading \(appDataDir.path); running on default mappings and settings.")
+            mappings = Self.defaultMappings()
+            customActions = []
+            sequences = SequenceTable()
+            appConfig = AppConfig()
+            KeySequences.shared.setTable(sequences)
+            ActionsRegistry.shared.setCustom([])
+            MappingsRegistry.shared.set(mappings)
+            return
+        }
+        // App config first: seeding fresh mappings reads its jump counts.
         loadAppConfig()
+        loadDocument()
     }
 
     private func loadDocument() {
         let fileExists = FileManager.default.fileExists(atPath: mappingsURL.path)
         var loadedMappings: [ActionMappingEntry] = []
         var loadedActions: [Action] = []
+        var loadedSequences = SequenceTable()
         var parseOK = true
+        var migratedFrom = Self.documentVersion
 
         // Read the raw bytes first so we can back them up even if they aren't
         // valid UTF-8 / YAML (any parse-failure case must be backed up).
@@ -95,10 +228,11 @@ final class ConfigStore: ObservableObject {
                 let data = try Data(contentsOf: mappingsURL)
                 rawData = data
                 guard let content = String(data: data, encoding: .utf8) else {
-                    throw ConfigError.io("config file is not valid UTF-8")
+                    throw ConfigError.parse("config file is not valid UTF-8")
                 }
                 if let node = try Yams.compose(yaml: content) {
-                    try parseDocument(node, into: &loadedMappings, actions: &loadedActions)
+                    migratedFrom = try parseDocument(node, source: content, into: &loadedMappings, actions: &loadedActions,
+                                                     sequences: &loadedSequences)
                 } else {
                     // Empty/whitespace file → treat as empty, safe to seed.
                     resetPreserved()
@@ -123,24 +257,33 @@ final class ConfigStore: ObservableObject {
         // Seed defaults ONLY when it's safe: file absent, or present-but-empty
         // (parsed cleanly with nothing in it). Never when parsing failed.
         let shouldSeed = parseOK && loadedMappings.isEmpty && loadedActions.isEmpty
-            && unknownMappingNodes.isEmpty && unknownActionNodes.isEmpty
+            && loadedSequences.isEmpty && unknownMappingNodes.isEmpty && unknownActionNodes.isEmpty
         if shouldSeed {
-            loadedMappings = Self.defaultMappings()
+            loadedMappings = configuredDefaultMappings
         }
         Self.normalize(&loadedMappings)
 
         mappings = loadedMappings
         customActions = loadedActions
+        sequences = loadedSequences
+        KeySequences.shared.setTable(loadedSequences)
         // Register actions BEFORE mappings so the tap thread never resolves a
         // mapping/binding against a stale action registry (matters on import,
         // which runs while the tap is live).
         ActionsRegistry.shared.setCustom(loadedActions)
         MappingsRegistry.shared.set(loadedMappings)
+        history.clear()
+        syncHistoryFlags()
+        postMappingsChange(.reload)
 
         // Persist only when we seeded into a fresh/empty file — never overwrite
         // an existing file we couldn't parse.
         if shouldSeed && (!fileExists || isFilePresentButEmpty()) {
             saveToDisk()
+        } else if parseOK, migratedFrom < Self.documentVersion, let rawData {
+            // Upgraded in memory: keep the old file, then write the new shape.
+            backupConfigByHash(rawData, reason: "Config upgraded from version \(migratedFrom)")
+            saveToDisk()
         }
     }
 
@@ -153,10 +296,10 @@ final class ConfigStore: ObservableObject {
     /// content, when a parse problem made the in-memory view lossy. Idempotent:
     /// if a snapshot for this exact content already exists we skip it, so the
     /// same broken file doesn't spawn a new backup on every launch.
-    private func backupConfigByHash(_ data: Data) {
+    private func backupConfigByHash(_ data: Data, reason: String = "Config parse issue") {
         let hash = SHA256.hash(data: data).prefix(8)
             .map { String(format: "%02x", $0) }.joined()   // 16 hex chars — ample to dedupe by content
-        let dir = mappingsURL.deletingLastPathComponent().appendingPathComponent("backups", isDirectory: true)
+        let dir = backupsDir
         let backupURL = dir.appendingPathComponent("action_mappings-\(hash).yml")
         guard !FileManager.default.fileExists(atPath: backupURL.path) else {
             FileLog.shared.info("Config backup \(backupURL.lastPathComponent) already exists; skipping.")
@@ -165,7 +308,7 @@ final class ConfigStore: ObservableObject {
         do {
             try FileManager.default.createDirectory(at: dir, withIntermediateDirectories: true)
             try data.write(to: backupURL, options: .atomic)
-            FileLog.shared.warn("Config parse issue — backed up original to backups/\(backupURL.lastPathComponent)")
+            FileLog.shared.warn("\(reason) — backed up original to backups/\(backupURL.lastPathComponent)")
         } catch {
             FileLog.shared.error("Failed to write config backup: \(error.localizedDescription)")
         }
@@ -177,71 +320,149 @@ final class ConfigStore: ObservableObject {
         preservedActionNodes = [:]
         unknownMappingNodes = []
         unknownActionNodes = []
+        comments = YamlComments()
+        topLevelOrder = []
+        loadedVersion = Self.documentVersion
+    }
+
+    /// Take over `other`'s preserved nodes, comments and key order, once the
+    /// document it parsed has been accepted.
+    private func adoptPreserved(from other: ConfigStore) {
+        preservedTopLevel = other.preservedTopLevel
+        preservedMappingNodes = other.preservedMappingNodes
+        preservedActionNodes = other.preservedActionNodes
+        unknownMappingNodes = other.unknownMappingNodes
+        unknownActionNodes = other.unknownActionNodes
+        comments = other.comments
+        topLevelOrder = other.topLevelOrder
+        loadedVersion = other.loadedVersion
     }
 
-    /// Parse the new structured doc or the legacy bare-list. Captures unknown
-    /// top-level keys and per-entry nodes for lossless re-emit. Throws on a
-    /// malformed entry (so the caller leaves the file untouched).
-    private func parseDocument(_ node: Node, into mappings: inout [ActionMappingEntry], actions: inout [Action]) throws {
+    /// The schema version of a composed document: a bare list is 1, a mapping
+    /// without `version` is 2, otherwise what it declares.
+    static func documentVersion(of node: Node) throws -> Int {
+        switch node {
+        case .sequence: return 1
+        case .mapping(let map):
+            guard let value = map.first(where: { $0.key.string == "version" })?.value else { return 2 }
+            guard let v = value.int, v >= 1 else { throw ConfigError.parse("Invalid config version: \(value.string ?? "?")") }
+            return v
+        default:
+            throw ConfigError.parse("Unexpected top-level YAML node")
+        }
+    }
+
+    /// Upgrade `node` to `documentVersion`, one logged step at a time. Returns
+    /// the upgraded document and the version it started at. A document from a
+    /// newer build is returned as is.
+    static func migrateDocument(_ node: Node) throws -> (node: Node, from: Int) {
+        let from = try documentVersion(of: node)
+        var node = node
+        var version = from
+        while version < documentVersion {
+            guard let step = migrations[version] else { throw ConfigError.parse("No migration from config version \(version)") }
+            node = try step.upgrade(node)
+            FileLog.shared.info("Config migration \(version) → \(version + 1): \(step.note).")
+            version += 1
+        }
+        if from > documentVersion {
+            FileLog.shared.warn("Config version \(from) is newer than this build's \(documentVersion); loading what it can and keeping the rest.")
+        }
+        return (node, from)
+    }
+
+    /// Parse a config document, first migrated to the current version.
+    /// Captures unknown top-level keys and per-entry nodes for lossless
+    /// re-emit. Throws on a malformed entry (so the caller leaves the file
+    /// untouched). Returns the version the document was written at.
+    @discardableResult
+    private func parseDocument(_ node: Node, source: String, into mappings: inout [ActionMappingEntry],
+                               actions: inout [Action], sequences: inout SequenceTable) throws -> Int {
         resetPreserved()
+        let (node, fromVersion) = try Self.migrateDocument(node)
+        loadedVersion = max(fromVersion, Self.documentVersion)
+        let lines = source.components(separatedBy: "\n")
+        comments.header = YamlComments.header(of: source)
         switch node {
-        case .sequence(let seq):
-            mappings = try captureMappings(seq)
-            FileLog.shared.info("Loaded legacy bare-list config (\(mappings.count) mappings).")
         case .mapping(let map):
             for (key, value) in map {
                 guard let k = key.string else { continue }
+                topLevelOrder.append(k)
                 switch k {
+                case "version":
+                    continue  // re-stamped on save
                 case "mappings":
                     guard case .sequence(let seq) = value else { continue }
-                    mappings = try captureMappings(seq)
+                    let captured = try captureMappings(seq)
+                    mappings = captured.entries
+                    comments.capture(list: "mappings", seq: seq, lines: lines, key: { captured.keys[$0] })
                 case "actions":
                     guard case .sequence(let seq) = value else { continue }
-                    actions = try captureActions(seq)
+                    let captured = try captureActions(seq)
+                    actions = captured.entries
+                    comments.capture(list: "actions", seq: seq, lines: lines, key: { captured.keys[$0] })
+                case "sequences":
+                    // Kept verbatim for save either way; a malformed table just
+                    // leaves sequences off rather than failing the whole load.
+                    preservedTopLevel.append((key, value))
+                    do {
+                        sequences = try YAMLDecoder().decode(SequenceTable.self, from: try Yams.serialize(node: value))
+                    } catch {
+                        FileLog.shared.warn("Ignoring unparseable sequences table: \(error)")
+                    }
                 default:
                     preservedTopLevel.append((key, value))
                     FileLog.shared.info("Preserving unrecognized top-level config key: \(k)")
                 }
             }
-            FileLog.shared.info("Loaded structured config: \(mappings.count) mappings, \(actions.count) custom actions, \(preservedTopLevel.count) preserved key(s).")
+            FileLog.shared.info("Loaded structured config v\(fromVersion): \(mappings.count) mappings, \(actions.count) custom actions, \(sequences.leaders.count) sequence leader(s), \(preservedTopLevel.count) preserved key(s).")
         default:
-            throw ConfigError.io("Unexpected top-level YAML node")
+            throw ConfigError.parse("Unexpected top-level YAML node")
         }
+        return fromVersion
     }
 
-    private func captureMappings(_ seq: Node.Sequence) throws -> [ActionMappingEntry] {
+    /// Decoded entries plus, per element of `seq`, its preserve key (nil for an
+    /// entry kept only as a raw node).
+    private func captureMappings(_ seq: Node.Sequence) throws -> (entries: [ActionMappingEntry], keys: [String?]) {
         var result: [ActionMappingEntry] = []
+        var keys: [String?] = []
         for elem in seq {
             do {
                 let yaml = try Yams.serialize(node: elem)
                 let entry = try YAMLDecoder().decode(ActionMappingEntry.self, from: yaml)
                 preservedMappingNodes[triggerUniqueID(entry.trigger)] = elem
                 result.append(entry)
+                keys.append(triggerUniqueID(entry.trigger))
             } catch {
                 // An entry this build can't represent (e.g. a newer action kind).
                 // Skip it in memory but keep the raw node so it round-trips on
                 // save — one unknown entry must not drop the whole config.
                 unknownMappingNodes.append(elem)
+                keys.append(nil)
                 FileLog.shared.warn("Skipping unparseable mapping entry (preserved verbatim for save): \(error)")
             }
         }
-        return result
+        return (result, keys)
     }
 
-    private func captureActions(_ seq: Node.Sequence) throws -> [Action] {
+    private func captureActions(_ seq: Node.Sequence) throws -> (entries: [Action], keys: [String?]) {
         var result: [Action] = []
+        var keys: [String?] = []
         for elem in seq {
             do {
                 let yaml = try Yams.serialize(node: elem)
                 let action = try YAMLDecoder().decode(Action.self, from: yaml)
                 preservedActionNodes[action.id] = elem
                 result.append(action)
+                keys.append(action.id)
             } catch {
                 unknownActionNodes.append(elem)
+                keys.append(nil)
                 FileLog.shared.warn("Skipping unparseable action entry (preserved verbatim for save): \(error)")
             }
         }
-        return result
+        return (result, keys)
     }
 
     private func loadAppConfig() {
@@ -258,38 +479,125 @@ final class ConfigStore: ObservableObject {
     /// Upsert a mapping. Prefer binding by `actionId` (clears any inline action —
     /// the gradual inline→id migration). Pass `inlineAction` only for legacy/
     /// ad-hoc bindings without a library action.
-    func upsert(trigger: Trigger, actionId: String?, inlineAction: ActionConfig?, bindings: [MappingBinding] = []) throws {
+    func upsert(trigger: Trigger, actionId: String?, inlineAction: ActionConfig?, bindings: [MappingBinding] = [],
+                repeatMode: RepeatMode = .auto, label: String? = nil, note: String? = nil, enabled: Bool = true) throws {
         if actionId == nil, let inline = inlineAction {
             try Self.validate(inline)
         }
         if let id = actionId, ActionsRegistry.shared.action(byID: id) == nil {
-            throw ConfigError.invalidEntry("Unknown action id: \(id)")
+            throw ConfigError.invalidEntry("Unknown action id: \(id)", code: .unknownAction)
         }
         try bindings.forEach { try Self.validate($0) }
         var m = mappings
         let entry = ActionMappingEntry(trigger: trigger,
                                        actionId: actionId,
                                        inlineAction: actionId == nil ? inlineAction : nil,
-                                       bindings: bindings)
+                                       bindings: bindings,
+                                       repeatMode: repeatMode,
+                                       label: Self.trimmedText(label),
+                                       note: Self.trimmedText(note),
+                                       enabled: enabled)
         if let idx = m.firstIndex(where: { $0.trigger == trigger }) {
             m[idx] = entry
         } else {
             m.append(entry)
         }
         Self.normalize(&m)
-        commitMappings(m)
+        commitMappings(m, kind: .upsert)
+    }
+
+    /// Switch one mapping off (its key passes through) or back on, keeping its
+    /// action and rules. Saved and undoable like any edit.
+    func setEnabled(trigger: Trigger, enabled: Bool) throws {
+        guard let idx = mappings.firstIndex(where: { $0.trigger == trigger }) else {
+            throw ConfigError.invalidEntry("No mapping for \(Self.triggerLabel(trigger))", code: .unknownMapping)
+        }
+        guard mappings[idx].enabled != enabled else { return }
+        var m = mappings
+        m[idx].enabled = enabled
+        commitMappings(m, kind: .upsert)
+    }
+
+    /// `text` without surrounding whitespace; nil when that leaves nothing, so
+    /// a cleared label field drops the key from the file.
+    static func trimmedText(_ text: String?) -> String? {
+        guard let t = text?.trimmingCharacters(in: .whitespacesAndNewlines), !t.isEmpty else { return nil }
+        return t
     }
 
     func remove(trigger: Trigger) {
         var m = mappings
         m.removeAll { $0.trigger == trigger }
-        commitMappings(m)
+        commitMappings(m, kind: .remove)
+    }
+
+    /// Put one trigger back to its shipped default: re-bind it if the defaults
+    /// cover it, otherwise remove it. Undoable like any other edit.
+    func resetMapping(trigger: Trigger) {
+        guard let def = configuredDefault(for: trigger) else {
+            remove(trigger: trigger)
+            return
+        }
+        var m = mappings
+        if let idx = m.firstIndex(where: { $0.trigger == trigger }) { m[idx] = def } else { m.append(def) }
+        Self.normalize(&m)
+        commitMappings(m, kind: .upsert)
+    }
+
+    /// Replace every mapping with the shipped defaults. Custom actions are kept
+    /// (mappings that referenced them are simply gone). Undoable.
+    func resetAllMappings() {
+        commitMappings(configuredDefaultMappings, kind: .reset)
+    }
+
+    /// Re-bind the default jump chords (Caps+U / Caps+D) with the configured
+    /// counts, leaving every other mapping alone. Undoable.
+    func resetJumpMappings() {
+        var m = mappings
+        for def in configuredDefaultMappings where Self.isDefaultJump(def.trigger) {
+            if let idx = m.firstIndex(where: { $0.trigger == def.trigger }) { m[idx] = def } else { m.append(def) }
+        }
+        Self.normalize(&m)
+        commitMappings(m, kind: .upsert)
+    }
+
+    /// Restore the mapping list from before the last edit. Returns false if
+    /// there is nothing to undo.
+    @discardableResult
+    func undoMappings() -> Bool {
+        guard let restored = history.undo(from: mappings) else { return false }
+        applyMappings(restored, kind: .undo)
+        return true
     }
 
-    private func commitMappings(_ m: [ActionMappingEntry]) {
+    @discardableResult
+    func redoMappings() -> Bool {
+        guard let restored = history.redo(from: mappings) else { return false }
+        applyMappings(restored, kind: .redo)
+        return true
+    }
+
+    private func commitMappings(_ m: [ActionMappingEntry], kind: MappingsChangeKind) {
+        history.record(mappings)
+        applyMappings(m, kind: kind)
+    }
+
+    private func applyMappings(_ m: [ActionMappingEntry], kind: MappingsChangeKind) {
+        syncHistoryFlags()
         mappings = m
         MappingsRegistry.shared.set(m)
         saveToDisk()
+        postMappingsChange(kind)
+    }
+
+    private func syncHistoryFlags() {
+        canUndoMappings = history.canUndo
+        canRedoMappings = history.canRedo
+    }
+
+    private func postMappingsChange(_ kind: MappingsChangeKind) {
+        NotificationCenter.default.post(name: Self.mappingsDidChange, object: self,
+                                        userInfo: [Self.mappingsChangeKindKey: kind, Self.mappingsKey: mappings])
     }
 
     // MARK: - Custom action mutations
@@ -354,7 +662,43 @@ final class ConfigStore: ObservableObject {
     func setMappingsViewStyle(_ style: MappingsViewStyle) throws { try mutateConfig { $0.mappingsViewStyle = style } }
     func setStatsShowInline(_ on: Bool) throws { try mutateConfig { $0.statsShowInline = on } }
     func setShowWindowOnLaunch(_ on: Bool) throws { try mutateConfig { $0.showWindowOnLaunch = on } }
-    func setKeyRemaps(_ remaps: [KeyRemap]) throws { try mutateConfig { $0.keyRemaps = remaps } }
+    func setTrayStyle(_ style: TrayStyle) throws { try mutateConfig { $0.trayStyle = style } }
+    func setKeyRemaps(_ remaps: [KeyRemap]) throws {
+        try Self.validate(layerKey: appConfig.layerKey, keyRemaps: remaps)
+        try mutateConfig { $0.keyRemaps = remaps }
+    }
+    func setLayerKey(_ key: LayerKey) throws {
+        try Self.validate(layerKey: key, keyRemaps: appConfig.keyRemaps)
+        try mutateConfig { $0.layerKey = key }
+    }
+    func setExcludedApps(_ bundleIDs: [String]) throws { try mutateConfig { $0.excludedApps = bundleIDs } }
+    func setWordMotionStyle(_ style: WordMotionStyle) throws { try mutateConfig { $0.wordMotionStyle = style } }
+    func setLineEdgeStyle(_ style: LineEdgeStyle) throws { try mutateConfig { $0.lineEdgeStyle = style } }
+    func setCapsLayerMode(_ mode: CapsLayerMode) throws { try mutateConfig { $0.capsLayerMode = mode } }
+    func setCapsToggleEnabled(_ on: Bool) throws { try mutateConfig { $0.capsToggleEnabled = on } }
+    func setCapsLockPinnedOff(_ on: Bool) throws { try mutateConfig { $0.capsLockPinnedOff = on } }
+    func setShiftLayerMode(_ mode: ShiftLayerMode) throws { try mutateConfig { $0.shiftLayerMode = mode } }
+    func setRawEscapeModifier(_ modifier: RawEscapeModifier) throws { try mutateConfig { $0.rawEscapeModifier = modifier } }
+    func setTapLocation(_ location: EventTapLocation) throws { try mutateConfig { $0.tapLocation = location } }
+    func setPostLocation(_ location: EventTapLocation) throws { try mutateConfig { $0.postLocation = location } }
+    func setCapsMouseEvents(_ on: Bool) throws { try mutateConfig { $0.capsMouseEvents = on } }
+    func setDebounceMs(_ ms: Int) throws { try mutateConfig { $0.debounceMs = min(max(ms, 0), EngineConstants.maxDebounceMs) } }
+    func setInjectDelay(_ ms: Int) throws { try mutateConfig { $0.injectDelayMs = min(max(ms, 0), EngineConstants.maxInjectDelayMs) } }
+    func setPassthroughKeys(_ keys: [PassthroughKey]) throws { try mutateConfig { $0.passthroughKeys = keys } }
+    func setServiceState(_ state: ServiceState) throws { try mutateConfig { $0.serviceState = state } }
+    func setControlSocketEnabled(_ on: Bool) throws { try mutateConfig { $0.controlSocketEnabled = on } }
+    func setOnboardingCompleted(_ done: Bool) throws { try mutateConfig { $0.onboardingCompleted = done } }
+    func setSequenceTimeout(_ ms: Int) throws { try mutateConfig { $0.sequenceTimeoutMs = min(max(ms, 200), 3000) } }
+    func setDefaultJumpCount(_ count: Int, for direction: JumpDirection) throws {
+        let count = min(max(count, 1), Self.maxJumpCount)
+        try mutateConfig { c in
+            switch direction {
+            case .up: c.defaultJumpCountUp = count
+            case .down: c.defaultJumpCountDown = count
+            }
+        }
+    }
+    func setLogMaxMb(_ mb: Int) throws { try mutateConfig { $0.logMaxMb = min(max(mb, 1), 100) } }
 
     private func mutateConfig(_ change: (inout AppConfig) -> Void) throws {
         let prev = appConfig
@@ -385,13 +729,20 @@ final class ConfigStore: ObservableObject {
             throw ConfigError.io("Failed to read file")
         }
         guard let node = try? Yams.compose(yaml: content) else {
-            throw ConfigError.io("Invalid YAML")
+            throw ConfigError.parse("Invalid YAML")
         }
-        // Parse into temporaries; capture this file's preserved nodes too.
+        // Parse into temporaries and a scratch store, which captures this file's
+        // preserved nodes, comments and key order; a file that fails here or in
+        // validation leaves ours untouched.
         var importedMappings: [ActionMappingEntry] = []
         var importedActions: [Action] = []
-        do { try parseDocument(node, into: &importedMappings, actions: &importedActions) }
-        catch { throw ConfigError.io("Invalid config: \(error.localizedDescription)") }
+        var importedSequences = SequenceTable()
+        let scratch = ConfigStore(appDataDir: appDataDir)
+        do {
+            try scratch.parseDocument(node, source: content, into: &importedMappings, actions: &importedActions,
+                                      sequences: &importedSequences)
+        }
+        catch { throw ConfigError.parse("Invalid config: \(error.localizedDescription)") }
 
         if importedMappings.isEmpty { throw ConfigError.emptyImport }
         for entry in importedMappings {
@@ -407,19 +758,134 @@ final class ConfigStore: ObservableObject {
             else { merged.append(action) }
         }
 
+        history.record(mappings)
+        syncHistoryFlags()
+        adoptPreserved(from: scratch)
         mappings = importedMappings
         customActions = merged
+        sequences = importedSequences
+        KeySequences.shared.setTable(importedSequences)
         // Actions before mappings: the tap is live during import, so a binding
         // referencing a newly-imported custom action must find it registered.
         ActionsRegistry.shared.setCustom(merged)
         MappingsRegistry.shared.set(importedMappings)
         saveToDisk()
+        postMappingsChange(.reload)
         return importedMappings.count
     }
 
+    /// Parse, validate and normalize a config document (pasted or picked)
+    /// WITHOUT applying it — no disk write, no store or registry change — so the
+    /// UI can show what an import would do before the user confirms. Stricter
+    /// than load: an entry this build can't decode is an error naming its
+    /// position, not a silently preserved node.
+    static func previewMappings(yaml: String) throws -> [ActionMappingEntry] {
+        let node: Node?
+        do { node = try Yams.compose(yaml: yaml) }
+        catch { throw ConfigError.parse("Invalid YAML: \(error)") }
+        let seq: Node.Sequence
+        switch node {
+        case .sequence(let s)?:
+            seq = s
+        case .mapping(let map)?:
+            guard let value = map.first(where: { $0.key.string == "mappings" })?.value else { throw ConfigError.emptyImport }
+            guard case .sequence(let s) = value else { throw ConfigError.parse("\"mappings\" must be a list") }
+            seq = s
+        default:
+            throw ConfigError.parse("Unexpected top-level YAML node")
+        }
+        var entries: [ActionMappingEntry] = []
+        for (i, elem) in seq.enumerated() {
+            do {
+                entries.append(try YAMLDecoder().decode(ActionMappingEntry.self, from: try Yams.serialize(node: elem)))
+            } catch {
+                throw ConfigError.invalidEntry("Mapping #\(i + 1): \(describeDecodingError(error))")
+            }
+        }
+        if entries.isEmpty { throw ConfigError.emptyImport }
+        for entry in entries {
+            if entry.actionId == nil, let inline = entry.inlineAction { try validate(inline, importing: true) }
+            try entry.bindings.forEach { try validate($0, importing: true) }
+        }
+        normalize(&entries)
+        return entries
+    }
+
+    /// What replacing `old` with `new` does, by trigger: newly bound, rebound
+    /// (any field differs), and dropped.
+    static func mappingsDiff(from old: [ActionMappingEntry], to new: [ActionMappingEntry]) -> (added: Int, changed: Int, removed: Int) {
+        let before = Dictionary(old.map { (triggerUniqueID($0.trigger), $0) }, uniquingKeysWith: { _, last in last })
+        let after = Dictionary(new.map { (triggerUniqueID($0.trigger), $0) }, uniquingKeysWith: { _, last in last })
+        let added = after.keys.filter { before[$0] == nil }.count
+        let removed = before.keys.filter { after[$0] == nil }.count
+        let changed = after.filter { id, entry in before[id].map { $0 != entry } ?? false }.count
+        return (added, changed, removed)
+    }
+
+    /// The human part of a decoding error (e.g. "unknown action kind: foo").
+    private static func describeDecodingError(_ error: Error) -> String {
+        switch error as? DecodingError {
+        case .dataCorrupted(let ctx)?, .typeMismatch(_, let ctx)?, .valueNotFound(_, let ctx)?:
+            return ctx.debugDescription
+        case .keyNotFound(let key, _)?:
+            return "missing \"\(key.stringValue)\""
+        default:
+            return error.localizedDescription
+        }
+    }
+
+    // MARK: - Presets (named exports under presets/, loaded like an import)
+
+    private var presetsDir: URL { appDataDir.appendingPathComponent("presets", isDirectory: true) }
+
+    /// A preset name doubles as its file name, so it can't climb out of
+    /// `presets/` or hide as a dotfile.
+    static func isValidPresetName(_ name: String) -> Bool {
+        let trimmed = name.trimmingCharacters(in: .whitespacesAndNewlines)
+        return !trimmed.isEmpty && trimmed == name && name.count <= 64 && !name.hasPrefix(".")
+            && !name.contains(where: { $0 == "/" || $0 == ":" || $0.isNewline })
+    }
+
+    private func presetURL(_ name: String) throws -> URL {
+        guard Self.isValidPresetName(name) else { throw ConfigError.invalidEntry("Invalid preset name: \"\(name)\"") }
+        return presetsDir.appendingPathComponent(name + ".yml")
+    }
+
+    /// Saved preset names, sorted.
+    func listPresets() -> [String] {
+        let files = (try? FileManager.default.contentsOfDirectory(at: presetsDir, includingPropertiesForKeys: nil)) ?? []
+        return files.filter { $0.pathExtension == "yml" }
+            .map { $0.deletingPathExtension().lastPathComponent }
+            .filter(Self.isValidPresetName)
+            .sorted { $0.localizedStandardCompare($1) == .orderedAscending }
+    }
+
+    /// Snapshot the current mappings and custom actions as `name`, replacing
+    /// a preset of the same name.
+    func savePreset(_ name: String) throws {
+        try export(to: try presetURL(name).path, overwrite: true)
+    }
+
+    /// Make preset `name` the active mappings. Same semantics as an import —
+    /// mappings replaced, custom actions merged, undoable — and persisted.
+    @discardableResult
+    func loadPreset(_ name: String) throws -> Int {
+        let url = try presetURL(name)
+        guard FileManager.default.fileExists(atPath: url.path) else { throw ConfigError.io("No preset named \"\(name)\"") }
+        return try importDocument(from: url.path)
+    }
+
+    func deletePreset(_ name: String) throws {
+        let url = try presetURL(name)
+        guard FileManager.default.fileExists(atPath: url.path) else { throw ConfigError.io("No preset named \"\(name)\"") }
+        do { try FileManager.default.removeItem(at: url) }
+        catch { throw ConfigError.io("Failed to delete preset: \(error.localizedDescription)") }
+    }
+
     // MARK: - Persistence
 
     private func saveToDisk() {
+        guard !AppEnvironment.isSafeMode else { return }
         do {
             let content = try renderDocument()
             try FileManager.default.createDirectory(at: appDataDir, withIntermediateDirectories: true)
@@ -440,13 +906,23 @@ final class ConfigStore: ObservableObject {
                                               yaml: try YAMLEncoder().encode(mappings),
                                               preserved: preservedMappingNodes,
                                               known: Self.mappingKnownKeys)
-        var pairs: [(Node, Node)] = preservedTopLevel
+        var pairs: [(Node, Node)] = [(Node("version"), Node(String(loadedVersion)))] + preservedTopLevel
         // Re-emit entries this build couldn't decode, verbatim, after the known
         // ones — so a downgraded build round-trips a newer build's data instead
         // of silently dropping it.
         pairs.append((Node("actions"), Self.appendingNodes(actionsNode, unknownActionNodes)))
         pairs.append((Node("mappings"), Self.appendingNodes(mappingsNode, unknownMappingNodes)))
-        return try Yams.serialize(node: Node.mapping(Node.Mapping(pairs)))
+        // Keep the file's own top-level key order; keys it didn't have go last,
+        // except `version`, which a file that lacked it gets at the top.
+        let rank = { (key: Node) in
+            key.string.flatMap { self.topLevelOrder.firstIndex(of: $0) } ?? (key.string == "version" ? -1 : Int.max)
+        }
+        pairs = pairs.enumerated()
+            .sorted { (rank($0.element.0), $0.offset) < (rank($1.element.0), $1.offset) }
+            .map(\.element)
+        let yaml = try Yams.serialize(node: Node.mapping(Node.Mapping(pairs)))
+        return comments.apply(to: yaml, keys: ["actions": customActions.map(\.id),
+                                               "mappings": mappings.map { triggerUniqueID($0.trigger) }])
     }
 
     /// Compose freshly-encoded entries, then merge each entry's preserved
@@ -480,6 +956,7 @@ final class ConfigStore: ObservableObject {
     }
 
     private func persistAppConfig() throws {
+        guard !AppEnvironment.isSafeMode else { return }
         do {
             let content = try YAMLEncoder().encode(appConfig)
             try FileManager.default.createDirectory(at: appDataDir, withIntermediateDirectories: true)
@@ -491,14 +968,40 @@ final class ConfigStore: ObservableObject {
 
     // MARK: - Validation
 
+    /// Largest jump count accepted: a typo like 10000 would otherwise post
+    /// twenty thousand key events from the tap callback in one go.
+    static let maxJumpCount = 200
+
     static func validate(_ action: ActionConfig, importing: Bool = false) throws {
         switch action {
-        case .command(let c) where c.trimmingCharacters(in: .whitespaces).isEmpty:
-            throw ConfigError.invalidEntry(importing ? "Imported entry has empty command" : "command cannot be empty")
+        case .command(let c, _) where c.trimmingCharacters(in: .whitespaces).isEmpty:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has empty command" : "command cannot be empty",
+                                           code: .emptyCommand)
         case .inputSource(let id) where id.trimmingCharacters(in: .whitespaces).isEmpty:
-            throw ConfigError.invalidEntry(importing ? "Imported entry has empty input_source_id" : "input_source_id cannot be empty")
-        case .jump(_, let count) where count < 1:
-            throw ConfigError.invalidEntry(importing ? "Imported entry has invalid jump count (< 1)" : "jump count must be >= 1")
+            throw ConfigError.invalidEntry(importing ? "Imported entry has empty input_source_id" : "input_source_id cannot be empty",
+                                           code: .emptyInputSource)
+        case .jump(_, let count, _) where count < 1:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has invalid jump count (< 1)" : "jump count must be >= 1",
+                                           code: .invalidJumpCount)
+        case .jump(_, let count, _) where count > maxJumpCount:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has invalid jump count (> \(maxJumpCount))"
+                                                     : "jump count must be <= \(maxJumpCount)",
+                                           code: .invalidJumpCount)
+        case .wrapPair(let open, let close, _) where open.isEmpty && close.isEmpty:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has empty wrap_pair open/close" : "wrap pair needs an open or close string")
+        case .wrapPair(_, _, let spaces) where !(0...8).contains(spaces):
+            throw ConfigError.invalidEntry(importing ? "Imported entry has invalid wrap_pair inner_spaces" : "inner spaces must be 0–8")
+        case .script(_, let source) where source.trimmingCharacters(in: .whitespacesAndNewlines).isEmpty:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has empty script source" : "script cannot be empty")
+        case .notify(let title, let body) where title.trimmingCharacters(in: .whitespaces).isEmpty
+                                              && body.trimmingCharacters(in: .whitespaces).isEmpty:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has an empty notification" : "notification needs a title or body")
+        case .goTo(let line, let column, _) where (line ?? 1) < 1 || (column ?? 1) < 1:
+            throw ConfigError.invalidEntry(importing ? "Imported entry has invalid go_to line/column (< 1)" : "line and column must be >= 1")
+        case .insertDateTime(let format):
+            if let problem = DateTimeText.problem(format) {
+                throw ConfigError.invalidEntry(importing ? "Imported entry has an invalid date format: \(problem)" : "date format: \(problem)")
+            }
         case .openApp(let bid, _) where bid.trimmingCharacters(in: .whitespaces).isEmpty:
             throw ConfigError.invalidEntry(importing ? "Imported entry has empty bundle_id" : "bundle_id cannot be empty")
         default:
@@ -506,6 +1009,15 @@ final class ConfigStore: ObservableObject {
         }
     }
 
+    /// The layer key and a user remap can't share a source key: hidutil takes
+    /// one mapping per key, and the layer key's would silently win.
+    static func validate(layerKey: LayerKey, keyRemaps: [KeyRemap]) throws {
+        guard let clash = keyRemaps.first(where: { $0.source == layerKey.remapSource }) else { return }
+        throw ConfigError.invalidEntry("\(layerKey.rawValue) is the layer key and can't also be remapped to "
+                                       + "\(clash.destination.displayName); remove that remap first",
+                                       code: .layerKeyInUse)
+    }
+
     /// A per-app binding must target at least one condition and resolve to an
     /// action (known id, or a valid inline action).
     static func validate(_ binding: MappingBinding, importing: Bool = false) throws {
@@ -516,7 +1028,7 @@ final class ConfigStore: ObservableObject {
             // On import the referenced action may be a custom action from the
             // same file that isn't registered yet — only resolution-check live.
             if !importing && ActionsRegistry.shared.action(byID: id) == nil {
-                throw ConfigError.invalidEntry("Unknown action id in per-app rule: \(id)")
+                throw ConfigError.invalidEntry("Unknown action id in per-app rule: \(id)", code: .unknownAction)
             }
         } else if let inline = binding.inlineAction {
             try validate(inline, importing: importing)
@@ -541,30 +1053,74 @@ final class ConfigStore: ObservableObject {
 
     // MARK: - Defaults (bind to built-in action ids; ABC/WeChat stay inline)
 
-    static func defaultMappings() -> [ActionMappingEntry] {
-        func ref(_ key: UInt16, _ actionId: String) -> ActionMappingEntry {
-            ActionMappingEntry(trigger: .hyperPlusKey(key: key, withShift: false), actionId: actionId)
-        }
-        func inline(_ key: UInt16, _ config: ActionConfig) -> ActionMappingEntry {
-            ActionMappingEntry(trigger: .hyperPlusKey(key: key, withShift: false), inlineAction: config)
-        }
-        return [
-            ref(JS.h, "builtin.move_left"),
-            ref(JS.j, "builtin.move_down"),
-            ref(JS.k, "builtin.move_up"),
-            ref(JS.l, "builtin.move_right"),
-            ref(JS.p, "builtin.word_forward"),
-            ref(JS.y, "builtin.word_back"),
-            ref(JS.a, "builtin.line_start"),
-            ref(JS.e, "builtin.line_end"),
-            ref(JS.u, "builtin.jump_up_10"),
-            ref(JS.d, "builtin.jump_down_10"),
-            ref(JS.i, "builtin.backspace"),
-            ref(JS.n, "builtin.insert_quotes"),
-            ref(JS.o, "builtin.new_line"),
-            inline(JS.abc, .inputSource(inputSourceID: abcInputSourceID)),
-            inline(JS.wechat, .inputSource(inputSourceID: wechatInputSourceID)),
-        ]
+    /// The default Caps+key layer as data: (key, with Shift, built-in action id).
+    /// Edit this table, not `defaultMappings()`; the tests check every key here
+    /// has a display name and that no (key, Shift) slot is claimed twice.
+    static let defaultBuiltinTable: [(key: UInt16, withShift: Bool, actionId: String)] = [
+        (JS.h, false, "builtin.move_left"),
+        (JS.j, false, "builtin.move_down"),
+        (JS.k, false, "builtin.move_up"),
+        (JS.l, false, "builtin.move_right"),
+        (JS.p, false, "builtin.word_forward"),
+        (JS.y, false, "builtin.word_back"),
+        (JS.a, false, "builtin.line_start"),
+        (JS.e, false, "builtin.line_end"),
+        (JS.u, false, "builtin.jump_up_10"),
+        (JS.d, false, "builtin.jump_down_10"),
+        (JS.i, false, "builtin.backspace"),
+        (JS.n, false, "builtin.insert_quotes"),
+        (JS.o, false, "builtin.new_line"),
+        (JS.o, true, "builtin.open_line_above"),
+        (JS.esc, false, "builtin.release_modifiers"),
+        (JS.semicolon, false, "builtin.show_palette"),
+    ]
+
+    /// Machine-specific defaults, kept inline rather than as built-ins: the
+    /// macOS input-source IDs only make sense on a Mac that has them installed.
+    static let defaultInlineTable: [(key: UInt16, withShift: Bool, action: ActionConfig)] = [
+        (JS.abc, false, .inputSource(inputSourceID: abcInputSourceID)),
+        (JS.wechat, false, .inputSource(inputSourceID: wechatInputSourceID)),
+    ]
+
+    /// The default mappings. A built-in jump row whose direction has a count
+    /// in `jumpCounts` other than its own binds an inline jump with that count;
+    /// everything else binds the built-in id.
+    static func defaultMappings(jumpCounts: [JumpDirection: Int] = [:]) -> [ActionMappingEntry] {
+        defaultBuiltinTable.map { row in
+            let trigger = Trigger.hyperPlusKey(key: row.key, withShift: row.withShift)
+            if case .jump(let direction, let shipped, .line)? = BuiltinActions.byID(row.actionId)?.config,
+               let count = jumpCounts[direction], count != shipped {
+                return ActionMappingEntry(trigger: trigger, inlineAction: .jump(direction: direction, count: count))
+            }
+            return ActionMappingEntry(trigger: trigger, actionId: row.actionId)
+        } + defaultInlineTable.map {
+            ActionMappingEntry(trigger: .hyperPlusKey(key: $0.key, withShift: $0.withShift), inlineAction: $0.action)
+        }
+    }
+
+    static func defaultMapping(for trigger: Trigger) -> ActionMappingEntry? {
+        defaultMappings().first { $0.trigger == trigger }
+    }
+
+    /// Whether `trigger` is one of the default jump chords (Caps+U / Caps+D).
+    static func isDefaultJump(_ trigger: Trigger) -> Bool {
+        defaultBuiltinTable.contains { row in
+            guard case .jump? = BuiltinActions.byID(row.actionId)?.config else { return false }
+            return trigger == .hyperPlusKey(key: row.key, withShift: row.withShift)
+        }
+    }
+
+    /// The defaults with this user's jump counts — what seeding and the reset
+    /// commands write.
+    var configuredDefaultMappings: [ActionMappingEntry] {
+        let counts = Dictionary(uniqueKeysWithValues: JumpDirection.allCases.map {
+            ($0, min(max(appConfig.defaultJumpCount($0), 1), Self.maxJumpCount))
+        })
+        return Self.defaultMappings(jumpCounts: counts)
+    }
+
+    func configuredDefault(for trigger: Trigger) -> ActionMappingEntry? {
+        configuredDefaultMappings.first { $0.trigger == trigger }
     }
 
     // MARK: - Helpers
@@ -574,8 +1130,10 @@ final class ConfigStore: ObservableObject {
         case .singleTapHyper: return "Caps×1"
         case .doubleTapHyper: return "Caps×2"
         case .doubleTapModifier(let m): return "\(modifierGlyph(m))×2"
-        case .hyperPlusKey(let key, let withShift):
-            return withShift ? "Caps+Shift+\(keyCodeDisplay(key))" : "Caps+\(keyCodeDisplay(key))"
+        case .hyperPlusKey(let key, let withShift, let ctrl, let alt, let cmd):
+            return capsChordLabel(keyCodeDisplay(key), shift: withShift, ctrl: ctrl, alt: alt, cmd: cmd)
+        case .hotkey(let key, let ctrl, let alt, let cmd, let shift):
+            return hotkeyLabel(key, ctrl, alt, cmd, shift)
         }
     }
 }
@@ -26,9 +26,29 @@ enum KeyRemapSource: String, Codable, CaseIterable, Equatable, Identifiable {
   }
 }
 
+/// The physical key that works as the layer key. Whichever it is, `hidutil`
+/// remaps it to F18 and the tap only ever sees F18, so the rest of the engine
+/// doesn't care. Caps Lock by default; a spare right-side modifier lets Caps
+/// Lock keep its normal job. Only keys few people need are offered — never a
+/// letter, Space, Return or a left-hand modifier.
+enum LayerKey: String, Codable, CaseIterable, Equatable, Identifiable {
+  case capsLock = "caps_lock"
+  case rightCommand = "right_command"
+  case rightOption = "right_option"
+  case rightControl = "right_control"
+  case rightShift = "right_shift"
+
+  var id: String { rawValue }
+
+  /// The same key as a user remap source; nil for Caps Lock, which is never one.
+  var remapSource: KeyRemapSource? { KeyRemapSource(rawValue: rawValue) }
+
+  var hidUsage: UInt64 { remapSource?.hidUsage ?? HidUtil.capsLockUsage }
+}
+
 /// A free function key (F13–F19) that macOS assigns no default action to, so it's
 /// safe to repurpose as a global hotkey in other apps. **F18 is deliberately
-/// absent** — it's the app's own CapsLock remap target and is reserved.
+/// absent** — it's the app's own layer-key remap target and is reserved.
 enum KeyRemapTarget: String, Codable, CaseIterable, Equatable, Identifiable {
   case f13, f14, f15, f16, f17, f19
 
@@ -50,7 +70,7 @@ enum KeyRemapTarget: String, Codable, CaseIterable, Equatable, Identifiable {
 }
 
 /// One user-configured low-level remap: a spare right modifier → a free function
-/// key. Applied via `hidutil` on top of the built-in CapsLock→F18 remap, so the
+/// key. Applied via `hidutil` on top of the built-in layer-key→F18 remap, so the
 /// repurposed key can be bound as a global hotkey in any app (and used inside
 /// HyperCapslock too, once the recorder accepts F13–F19).
 struct KeyRemap: Codable, Equatable, Identifiable {
@@ -0,0 +1,72 @@
+import Foundation
+
+/// Leader-key sequences: `Caps+<leader>` arms a pending state and the next key
+/// pressed picks the action from that leader's follow-up map (e.g. Caps+G then
+/// G → top of document, Caps+G then E → end of document).
+///
+/// Persisted as the optional top-level `sequences:` key of `action_mappings.yml`,
+/// keyed by JS keyCode like every other trigger key:
+///
+///     sequences:
+///       71:
+///         71: { kind: key_combo, target_key: 38, with_cmd: true }
+///         69: { kind: key_combo, target_key: 40, with_cmd: true }
+///
+/// Follow-ups carry an inline `ActionConfig`, so a sequence needs no library
+/// action. Non-numeric keys are skipped on decode rather than failing the load.
+struct SequenceTable: Equatable {
+  var leaders: [UInt16: [UInt16: ActionConfig]] = [:]
+
+  init(leaders: [UInt16: [UInt16: ActionConfig]] = [:]) {
+    self.leaders = leaders
+  }
+
+  var isEmpty: Bool { leaders.isEmpty }
+
+  func isLeader(_ key: UInt16) -> Bool { leaders[key] != nil }
+
+  func action(leader: UInt16, followUp: UInt16) -> ActionConfig? { leaders[leader]?[followUp] }
+}
+
+extension SequenceTable: Codable {
+  /// A JS keyCode as a YAML map key.
+  private struct KeyCodeKey: CodingKey {
+    let value: UInt16
+    var stringValue: String { String(value) }
+    var intValue: Int? { Int(value) }
+    init(_ value: UInt16) { self.value = value }
+    init?(stringValue: String) {
+      guard let v = UInt16(stringValue) else { return nil }
+      self.value = v
+    }
+    init?(intValue: Int) {
+      guard let v = UInt16(exactly: intValue) else { return nil }
+      self.value = v
+    }
+  }
+
+  init(from decoder: Decoder) throws {
+    let c = try decoder.container(keyedBy: KeyCodeKey.self)
+    var leaders: [UInt16: [UInt16: ActionConfig]] = [:]
+    for leader in c.allKeys {
+      let inner = try c.nestedContainer(keyedBy: KeyCodeKey.self, forKey: leader)
+      var followUps: [UInt16: ActionConfig] = [:]
+      for key in inner.allKeys {
+        followUps[key.value] = try inner.decode(ActionConfig.self, forKey: key)
+      }
+      if !followUps.isEmpty { leaders[leader.value] = followUps }
+    }
+    self.leaders = leaders
+  }
+
+  /// Keys are emitted in ascending order so saving never reorders the file.
+  func encode(to encoder: Encoder) throws {
+    var c = encoder.container(keyedBy: KeyCodeKey.self)
+    for leader in leaders.keys.sorted() {
+      var inner = c.nestedContainer(keyedBy: KeyCodeKey.self, forKey: KeyCodeKey(leader))
+      for (key, action) in leaders[leader]!.sorted(by: { $0.key < $1.key }) {
+        try inner.encode(action, forKey: KeyCodeKey(key))
+      }
+    }
+  }
+}
@@ -0,0 +1,45 @@
+import Foundation
+
+/// Bounded undo/redo history of whole mapping lists. Pure value type — the
+/// store decides when to record and what to do with a restored list.
+struct MappingsHistory {
+  static let defaultLimit = 20
+
+  let limit: Int
+  private(set) var undoStack: [[ActionMappingEntry]] = []
+  private(set) var redoStack: [[ActionMappingEntry]] = []
+
+  init(limit: Int = MappingsHistory.defaultLimit) {
+    self.limit = max(limit, 1)
+  }
+
+  var canUndo: Bool { !undoStack.isEmpty }
+  var canRedo: Bool { !redoStack.isEmpty }
+
+  /// Remember `previous` (the list about to be replaced). A fresh edit
+  /// invalidates the redo branch; the oldest state falls off past `limit`.
+  mutating func record(_ previous: [ActionMappingEntry]) {
+    undoStack.append(previous)
+    if undoStack.count > limit { undoStack.removeFirst(undoStack.count - limit) }
+    redoStack.removeAll()
+  }
+
+  /// Step back from `current`. Returns the list to restore, or nil if there
+  /// is nothing to undo.
+  mutating func undo(from current: [ActionMappingEntry]) -> [ActionMappingEntry]? {
+    guard let previous = undoStack.popLast() else { return nil }
+    redoStack.append(current)
+    return previous
+  }
+
+  mutating func redo(from current: [ActionMappingEntry]) -> [ActionMappingEntry]? {
+    guard let next = redoStack.popLast() else { return nil }
+    undoStack.append(current)
+    return next
+  }
+
+  mutating func clear() {
+    undoStack.removeAll()
+    redoStack.removeAll()
+  }
+}
@@ -3,6 +3,9 @@ import Foundation
 /// Thread-safe holder of the live action mappings. The event-tap callback reads
 /// from here on its own thread; the UI writes through `ConfigStore`, which keeps
 /// this registry in sync. Mirrors the Rust `Mutex<Option<Vec<ActionMappingEntry>>>`.
+///
+/// Disabled entries (`enabled: false`) are kept: a lookup that lands on one
+/// must stop there, so the key passes through instead of falling back.
 final class MappingsRegistry {
     static let shared = MappingsRegistry()
 
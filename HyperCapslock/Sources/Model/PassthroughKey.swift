@@ -0,0 +1,30 @@
+import Foundation
+
+/// A key the Caps layer leaves alone: Caps+key (with or without Shift) is
+/// passed through to the app unchanged even if a mapping is bound to it. Lets
+/// the user punch a hole in the layer — everywhere, or only in some apps —
+/// without deleting the binding. Unlike an unmapped key it also skips the
+/// count prefix and sequence leaders: it is never ours.
+struct PassthroughKey: Codable, Equatable {
+  /// JS keycode, as in `Trigger.hyperPlusKey`.
+  var key: UInt16
+  /// Bundle ids where the hole applies (case-insensitive); empty = everywhere.
+  var apps: [String] = []
+
+  init(key: UInt16, apps: [String] = []) {
+    self.key = key
+    self.apps = apps
+  }
+
+  init(from decoder: Decoder) throws {
+    let c = try decoder.container(keyedBy: CodingKeys.self)
+    key = try c.decode(UInt16.self, forKey: .key)
+    apps = try c.decodeIfPresent([String].self, forKey: .apps) ?? []
+  }
+
+  func applies(_ ctx: RuntimeContext) -> Bool {
+    guard !apps.isEmpty else { return true }
+    guard let bundleID = ctx.frontmostBundleID else { return false }
+    return apps.contains { $0.caseInsensitiveCompare(bundleID) == .orderedSame }
+  }
+}
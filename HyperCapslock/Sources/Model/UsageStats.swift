@@ -72,7 +72,7 @@ final class UsageStats {
 
     // Path resolution (uitest temp-dir isolation + bundle-id dir) is the single
     // source of truth in `AppEnvironment.appSupportDirectory`, shared with ConfigStore.
-    private let fileURL = AppEnvironment.appSupportDirectory.appendingPathComponent("usage_stats.json")
+    let fileURL = AppEnvironment.appSupportDirectory.appendingPathComponent("usage_stats.json")
 
     private init() {}
 
@@ -0,0 +1,100 @@
+import Foundation
+import Yams
+
+/// Hand-written comments in `action_mappings.yml`, carried across a save.
+///
+/// libyaml drops comments on parse and `ConfigStore` re-renders the document
+/// from the model, so without this every UI edit wiped the user's notes. We
+/// keep the two kinds people actually write: a header block at the top of the
+/// file, and the comment lines directly above a mapping / custom-action entry
+/// (re-attached by that entry's key, so they follow it across edits). Comments
+/// anywhere else — trailing a value, inside an entry, under unknown keys — are
+/// still lost.
+struct YamlComments: Equatable {
+  /// Comment lines at the very top of the file, before any content.
+  var header: [String] = []
+  /// Comment lines directly above an entry, keyed per top-level list
+  /// (`"mappings"` / `"actions"`), then by the entry's key (trigger id / action id).
+  var entries: [String: [String: [String]]] = [:]
+
+  var isEmpty: Bool { header.isEmpty && entries.values.allSatisfy(\.isEmpty) }
+
+  /// The comment block at the top of `text`. Blank lines inside the block are
+  /// kept; trailing blank lines are not.
+  static func header(of text: String) -> [String] {
+    var block: [String] = []
+    for line in text.components(separatedBy: "\n") {
+      let trimmed = line.trimmingCharacters(in: .whitespaces)
+      if trimmed.hasPrefix("#") {
+        block.append(trimmed)
+      } else if trimmed.isEmpty {
+        if !block.isEmpty { block.append("") }
+      } else {
+        break
+      }
+    }
+    while block.last == "" { block.removeLast() }
+    return block
+  }
+
+  /// Comment lines directly above 1-based `line` (no blank line in between),
+  /// top to bottom, with indentation stripped. Lines before index `floor` are
+  /// never taken (they belong to the header).
+  static func comments(above line: Int, in lines: [String], floor: Int = 0) -> [String] {
+    var block: [String] = []
+    var i = line - 2
+    while i >= floor {
+      let trimmed = lines[i].trimmingCharacters(in: .whitespaces)
+      guard trimmed.hasPrefix("#") else { break }
+      block.insert(trimmed, at: 0)
+      i -= 1
+    }
+    return block
+  }
+
+  /// Record the comments above each element of `seq`. `key(i)` names element
+  /// `i`, or nil to skip it (e.g. an entry this build couldn't decode).
+  mutating func capture(list: String, seq: Node.Sequence, lines: [String], key: (Int) -> String?) {
+    // A bare-list file's first entry sits right under the header; don't
+    // claim the header twice.
+    let floor = lines.firstIndex { line in
+      let trimmed = line.trimmingCharacters(in: .whitespaces)
+      return !trimmed.isEmpty && !trimmed.hasPrefix("#")
+    } ?? 0
+    var found: [String: [String]] = [:]
+    for (i, elem) in seq.enumerated() {
+      guard let k = key(i), let line = elem.mark?.line else { continue }
+      let block = Self.comments(above: line, in: lines, floor: floor)
+      if !block.isEmpty { found[k] = block }
+    }
+    entries[list] = found
+  }
+
+  /// Re-insert the header and entry comments into freshly rendered YAML.
+  /// `keys[list][i]` names the i-th element of that top-level list in `yaml`.
+  func apply(to yaml: String, keys: [String: [String]]) -> String {
+    guard !isEmpty else { return yaml }
+    var lines = yaml.components(separatedBy: "\n")
+    // (0-based line index, comment block), applied bottom-up so earlier
+    // indices stay valid.
+    var inserts: [(Int, [String])] = []
+    if let node = try? Yams.compose(yaml: yaml), case .mapping(let map) = node {
+      for (k, v) in map {
+        guard let list = k.string, let byKey = entries[list], !byKey.isEmpty,
+              let names = keys[list], case .sequence(let seq) = v else { continue }
+        for (i, elem) in seq.enumerated() where i < names.count {
+          guard let block = byKey[names[i]], let line = elem.mark?.line, line - 1 < lines.count else { continue }
+          let indent = String(lines[line - 1].prefix { $0 == " " })
+          inserts.append((line - 1, block.map { indent + $0 }))
+        }
+      }
+    }
+    for (index, block) in inserts.sorted(by: { $0.0 > $1.0 }) {
+      lines.insert(contentsOf: block, at: index)
+    }
+    if !header.isEmpty {
+      lines.insert(contentsOf: header + [""], at: 0)
+    }
+    return lines.joined(separator: "\n")
+  }
+}
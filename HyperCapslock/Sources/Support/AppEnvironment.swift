@@ -11,6 +11,15 @@ enum AppEnvironment {
         CommandLine.arguments.contains("-uitest")
         || ProcessInfo.processInfo.environment["HC_UITEST"] == "1"
 
+    /// True when launched with `--safe-mode` (or `HC_SAFE_MODE=1`): the way out
+    /// when a bad config or the hook wedges startup. The config and stats files
+    /// aren't read (defaults load in memory) or written, the engine stays
+    /// disabled — no tap, no `hidutil` remap — and the window opens so the user
+    /// can see what to fix. E.g. `open -a HyperCapslock --args --safe-mode`.
+    static let isSafeMode: Bool =
+        CommandLine.arguments.contains("--safe-mode")
+        || ProcessInfo.processInfo.environment["HC_SAFE_MODE"] == "1"
+
     /// The app's per-process Application Support directory: an isolated temp dir
     /// under `-uitest` (so tests never touch the user's data), else
     /// `…/Application Support/<bundle id>`. Single source of truth for the data
@@ -0,0 +1,572 @@
+import Foundation
+import AppKit
+
+// MARK: - Wire format
+
+/// The scripting socket speaks newline-delimited JSON: one `ControlRequest` per
+/// line in, one `ControlResponse` per line out, in order. Mappings and actions
+/// use the same field names as `action_mappings.yml`.
+///
+///     {"id":1,"token":"…","method":"set_paused","params":{"paused":true}}
+///     {"id":1,"ok":true}
+enum ControlMethod: String, Codable, CaseIterable {
+  case getStatus = "get_status"
+  case getMappings = "get_mappings"
+  /// `params.mapping`: a full mapping entry, replacing any with the same trigger.
+  case upsertMapping = "upsert_mapping"
+  /// `params.paused`.
+  case setPaused = "set_paused"
+  /// `params.action_id` (a library action) or `params.action` (inline), run
+  /// once as a tap — exactly what its chord would do.
+  case simulateAction = "simulate_action"
+  /// Same params as `simulate_action`, but typed into the in-app sandbox field
+  /// (see `ActionSandbox`) instead of the focused app; returns the field after.
+  case testAction = "test_action"
+  /// `params.key` (JS keycode), `params.with_shift` and optional
+  /// `with_ctrl` / `with_alt` / `with_cmd`: what Caps+key would do right now,
+  /// resolved exactly as the keyboard hook does.
+  case resolve
+  /// Every key a mapping can name, with whether it works on this Mac (see
+  /// `KeyCodes.bindableKeys`).
+  case getBindableKeys = "get_bindable_keys"
+  /// `params.lines` (default 200, at most 2000): the end of the engine log.
+  case getLogTail = "get_log_tail"
+  /// Saved keymap presets (`<app support>/presets/*.yml`), by name.
+  case listPresets = "list_presets"
+  /// `params.name`: snapshot the current mappings, replacing that preset.
+  case savePreset = "save_preset"
+  /// `params.name`: make that preset the active mappings, as an import would;
+  /// returns them.
+  case loadPreset = "load_preset"
+  /// `params.name`.
+  case deletePreset = "delete_preset"
+  /// Where the config, settings, presets, backups, stats and log live, and
+  /// whether each exists yet.
+  case getConfigPaths = "get_config_paths"
+  /// Accessibility, tap, Secure Input, and a key typed into the sandbox
+  /// window to prove injection works (see `SelfTest`).
+  case runSelfTest = "run_self_test"
+  /// Optional `params.bundle_id` (default: the frontmost app): every mapping
+  /// as it behaves in that app — per-app rules applied, passthrough keys
+  /// marked — and whether the app is excluded or the service paused.
+  case getEffectiveMappings = "get_effective_mappings"
+  /// What the menu bar shows: `icon`, `title` or `both`.
+  case getTrayStyle = "get_tray_style"
+  /// `params.style`: `icon`, `title` or `both`; persisted like the Settings picker.
+  case setTrayStyle = "set_tray_style"
+  /// `params.seconds` (at most 600): log every tapped event for that long,
+  /// then stop on its own; 0 stops early. `get_status` reports whether one is
+  /// running, and `get_log_tail` fetches what it caught.
+  case debugLoggingBurst = "debug_logging_burst"
+  /// `params.key` (JS keycode), `params.with_shift` and optional
+  /// `with_ctrl` / `with_alt` / `with_cmd` name a Caps+key mapping, as for
+  /// `resolve`; `params.enabled` false lets that key pass through natively,
+  /// keeping the mapping to switch back on. Saved like any edit.
+  case setBindingEnabled = "set_binding_enabled"
+}
+
+struct ControlRequest: Decodable {
+  /// Echoed back so a client can pipeline requests.
+  var id: Int?
+  var token: String
+  var method: ControlMethod
+  var params: Params?
+
+  struct Params: Decodable {
+    var mapping: ActionMappingEntry?
+    var paused: Bool?
+    var actionId: String?
+    var action: ActionConfig?
+    var key: UInt16?
+    var withShift: Bool?
+    var withCtrl: Bool?
+    var withAlt: Bool?
+    var withCmd: Bool?
+    var lines: Int?
+    var name: String?
+    var bundleId: String?
+    var style: TrayStyle?
+    var seconds: Int?
+    var enabled: Bool?
+
+    enum CodingKeys: String, CodingKey {
+      case mapping, paused, action, key, lines, name, style, seconds, enabled
+      case actionId = "action_id"
+      case bundleId = "bundle_id"
+      case withShift = "with_shift"
+      case withCtrl = "with_ctrl"
+      case withAlt = "with_alt"
+      case withCmd = "with_cmd"
+    }
+  }
+}
+
+struct ControlStatus: Encodable, Equatable {
+  /// `AppState.ServiceStatus` raw value: running / paused / disabled / ….
+  var status: String
+  var accessibilityGranted: Bool
+  /// Another app holds Secure Input, so keys can't reach us right now.
+  var secureInput: Bool
+  var mappingCount: Int
+  var version: String
+  /// A `debug_logging_burst` is capturing right now.
+  var debugLogging = false
+
+  enum CodingKeys: String, CodingKey {
+    case status, version
+    case accessibilityGranted = "accessibility_granted"
+    case secureInput = "secure_input"
+    case mappingCount = "mapping_count"
+    case debugLogging = "debug_logging"
+  }
+}
+
+/// `resolve`'s result: the mapping that matched, the action it runs in the
+/// frontmost app (absent = swallowed), and whether Shift fallback chose it.
+struct ControlResolution: Encodable, Equatable {
+  var mapping: ActionMappingEntry
+  var action: ActionConfig?
+  var viaShiftFallback: Bool
+
+  enum CodingKeys: String, CodingKey {
+    case mapping, action
+    case viaShiftFallback = "via_shift_fallback"
+  }
+}
+
+/// `get_effective_mappings`' result. `excluded` or `paused` means every key
+/// passes through right now, whatever `mappings` says.
+struct ControlEffectiveMappings: Encodable, Equatable {
+  var app: String?
+  var bundleId: String?
+  var excluded: Bool
+  var paused: Bool
+  var mappings: [ControlEffectiveMapping]
+
+  enum CodingKeys: String, CodingKey {
+    case app, excluded, paused, mappings
+    case bundleId = "bundle_id"
+  }
+}
+
+/// One entry of `get_effective_mappings`: the mapping, the action it runs in
+/// that app (absent = swallowed), and why.
+struct ControlEffectiveMapping: Encodable, Equatable {
+  var mapping: ActionMappingEntry
+  var action: ActionConfig?
+  var viaAppRule: Bool
+  var passthrough: Bool
+
+  enum CodingKeys: String, CodingKey {
+    case mapping, action, passthrough
+    case viaAppRule = "via_app_rule"
+  }
+}
+
+/// `test_action`'s result: the sandbox field's text and selection afterwards.
+struct ControlSandboxResult: Encodable, Equatable {
+  var text: String
+  var selectionStart: Int
+  var selectionLength: Int
+
+  enum CodingKeys: String, CodingKey {
+    case text
+    case selectionStart = "selection_start"
+    case selectionLength = "selection_length"
+  }
+}
+
+/// Exactly one of `error` (when `ok` is false) or a method's result field is set.
+/// A failure from the config store also carries `error_code`, a stable
+/// `ConfigError.Code` to branch on; `error` stays the human-readable message.
+/// `resolve` for an unbound key (it passes through), `save_preset` and
+/// `delete_preset` succeed with none.
+struct ControlResponse: Encodable {
+  var id: Int?
+  var ok: Bool
+  var error: String?
+  var errorCode: ConfigError.Code?
+  var status: ControlStatus?
+  var mappings: [ActionMappingEntry]?
+  var resolution: ControlResolution?
+  var sandbox: ControlSandboxResult?
+  var keys: [BindableKey]?
+  var log: String?
+  var presets: [String]?
+  var paths: [ConfigPath]?
+  var selfTest: SelfTest.Report?
+  var effective: ControlEffectiveMappings?
+  var trayStyle: TrayStyle?
+
+  enum CodingKeys: String, CodingKey {
+    case id, ok, error, status, mappings, resolution, sandbox, keys, log, presets, paths, effective
+    case errorCode = "error_code"
+    case trayStyle = "tray_style"
+    case selfTest = "self_test"
+  }
+
+  static func failure(_ id: Int?, _ message: String) -> ControlResponse {
+    ControlResponse(id: id, ok: false, error: message)
+  }
+
+  /// A thrown error: the message, plus its code when it's a `ConfigError`.
+  static func failure(_ id: Int?, _ error: Error) -> ControlResponse {
+    let configError = error as? ConfigError
+    return ControlResponse(id: id, ok: false, error: configError?.errorDescription ?? error.localizedDescription,
+                           errorCode: configError?.code)
+  }
+}
+
+// MARK: - Server
+
+/// Optional local control channel for scripts and window-manager glue: a Unix
+/// socket at `<app support>/control.sock`, off unless Settings ▸ *Scripting
+/// socket* is on. Every request carries the token stored next to it in
+/// `control_token` (mode 0600, created on first start); the socket itself is
+/// 0600 and only answers peers running as the same user.
+///
+/// Requests are handled by the same calls the UI makes (`AppState`,
+/// `ConfigStore`), hopped onto the main queue; only `simulate_action`'s
+/// keystrokes are posted from the connection's queue, as the tap thread would.
+/// `test_action` waits there for the sandbox run to finish.
+final class ControlServer {
+  static let shared = ControlServer()
+
+  static var socketURL: URL { AppEnvironment.appSupportDirectory.appendingPathComponent("control.sock") }
+  static var tokenURL: URL { AppEnvironment.appSupportDirectory.appendingPathComponent("control_token") }
+
+  /// `get_log_tail`'s default and largest line counts.
+  static let defaultLogLines = 200
+  static let maxLogLines = 2000
+
+  /// Longest request line we buffer before dropping the connection.
+  private static let maxLineBytes = 1 << 20
+
+  private let queue = DispatchQueue(label: "me.xueshi.hypercapslock.control", attributes: .concurrent)
+  private let lock = NSLock()
+  private var listener: DispatchSourceRead?
+
+  private init() {}
+
+  var isRunning: Bool {
+    lock.lock(); defer { lock.unlock() }
+    return listener != nil
+  }
+
+  /// Bind the socket and start accepting. Returns false (and logs) if it
+  /// can't; a second call while running is a no-op.
+  @discardableResult
+  func start() -> Bool {
+    guard !isRunning else { return true }
+    guard let token = Self.loadOrCreateToken() else { return false }
+    let path = Self.socketURL.path
+    unlink(path)   // a stale socket from a crash would make bind fail
+
+    let fd = socket(AF_UNIX, SOCK_STREAM, 0)
+    guard fd >= 0 else {
+      FileLog.shared.error("Control socket: socket() failed (errno \(errno)).")
+      return false
+    }
+    var addr = sockaddr_un()
+    addr.sun_family = sa_family_t(AF_UNIX)
+    let capacity = MemoryLayout.size(ofValue: addr.sun_path)
+    guard path.utf8.count < capacity else {
+      FileLog.shared.error("Control socket: path too long for a Unix socket: \(path)")
+      close(fd)
+      return false
+    }
+    withUnsafeMutableBytes(of: &addr.sun_path) { raw in
+      raw.copyBytes(from: path.utf8)
+      raw[path.utf8.count] = 0
+    }
+    let bound = withUnsafePointer(to: &addr) {
+      $0.withMemoryRebound(to: sockaddr.self, capacity: 1) {
+        bind(fd, $0, socklen_t(MemoryLayout<sockaddr_un>.size))
+      }
+    }
+    guard bound == 0, chmod(path, 0o600) == 0, listen(fd, 8) == 0 else {
+      FileLog.shared.error("Control socket: bind/listen on \(path) failed (errno \(errno)).")
+      close(fd)
+      unlink(path)
+      return false
+    }
+    _ = fcntl(fd, F_SETFL, fcntl(fd, F_GETFL) | O_NONBLOCK)
+
+    let source = DispatchSource.makeReadSource(fileDescriptor: fd, queue: queue)
+    source.setEventHandler { [weak self] in
+      let client = accept(fd, nil, nil)
+      guard client >= 0 else { return }
+      self?.queue.async { self?.serve(client, token: token) }
+    }
+    source.setCancelHandler {
+      close(fd)
+      unlink(path)
+    }
+    lock.lock()
+    listener = source
+    lock.unlock()
+    source.resume()
+    FileLog.shared.info("Control socket listening at \(path).")
+    return true
+  }
+
+  /// Stop accepting and remove the socket. Connections already open finish
+  /// their current request and then see EOF on their next read.
+  func stop() {
+    lock.lock()
+    let source = listener
+    listener = nil
+    lock.unlock()
+    guard let source else { return }
+    source.cancel()
+    FileLog.shared.info("Control socket stopped.")
+  }
+
+  /// The shared secret clients send with every request. Created with 0600
+  /// permissions the first time the socket starts, then kept, so scripts
+  /// don't need updating on each launch.
+  static func loadOrCreateToken() -> String? {
+    let url = tokenURL
+    if let existing = try? String(contentsOf: url, encoding: .utf8)
+      .trimmingCharacters(in: .whitespacesAndNewlines), !existing.isEmpty {
+      return existing
+    }
+    let token = UUID().uuidString.replacingOccurrences(of: "-", with: "").lowercased()
+      + UUID().uuidString.replacingOccurrences(of: "-", with: "").lowercased()
+    do {
+      try FileManager.default.createDirectory(at: url.deletingLastPathComponent(), withIntermediateDirectories: true)
+      guard FileManager.default.createFile(atPath: url.path, contents: Data((token + "\n").utf8),
+                                           attributes: [.posixPermissions: 0o600]) else {
+        throw CocoaError(.fileWriteUnknown)
+      }
+      return token
+    } catch {
+      FileLog.shared.error("Control socket: could not write token file \(url.path): \(error)")
+      return nil
+    }
+  }
+
+  // MARK: Connection
+
+  private func serve(_ fd: Int32, token: String) {
+    defer { close(fd) }
+    // Accepted sockets inherit the listener's O_NONBLOCK on Darwin; this side
+    // wants plain blocking reads. And a client hanging up mid-reply must not
+    // SIGPIPE the whole app.
+    _ = fcntl(fd, F_SETFL, fcntl(fd, F_GETFL) & ~O_NONBLOCK)
+    var on: Int32 = 1
+    setsockopt(fd, SOL_SOCKET, SO_NOSIGPIPE, &on, socklen_t(MemoryLayout<Int32>.size))
+    var uid: uid_t = 0
+    var gid: gid_t = 0
+    guard getpeereid(fd, &uid, &gid) == 0, uid == getuid() else {
+      FileLog.shared.warn("Control socket: rejected a connection from another user.")
+      return
+    }
+    var pending = Data()
+    var chunk = [UInt8](repeating: 0, count: 4096)
+    while true {
+      let n = read(fd, &chunk, chunk.count)
+      guard n > 0 else { return }
+      pending.append(contentsOf: chunk[0..<n])
+      while let newline = pending.firstIndex(of: UInt8(ascii: "\n")) {
+        let line = pending[pending.startIndex..<newline]
+        pending.removeSubrange(pending.startIndex...newline)
+        guard !line.allSatisfy({ $0 == UInt8(ascii: " ") || $0 == UInt8(ascii: "\r") }) else { continue }
+        guard send(fd, response(to: Data(line), token: token)) else { return }
+      }
+      guard pending.count <= Self.maxLineBytes else {
+        _ = send(fd, .failure(nil, "Request line too long"))
+        return
+      }
+    }
+  }
+
+  private func send(_ fd: Int32, _ response: ControlResponse) -> Bool {
+    var data = (try? JSONEncoder().encode(response)) ?? Data(#"{"ok":false,"error":"Encoding failed"}"#.utf8)
+    data.append(UInt8(ascii: "\n"))
+    return data.withUnsafeBytes { buf -> Bool in
+      var offset = 0
+      while offset < buf.count {
+        let n = write(fd, buf.baseAddress! + offset, buf.count - offset)
+        guard n > 0 else { return false }
+        offset += n
+      }
+      return true
+    }
+  }
+
+  private func response(to line: Data, token: String) -> ControlResponse {
+    let request: ControlRequest
+    do {
+      request = try JSONDecoder().decode(ControlRequest.self, from: line)
+    } catch {
+      return .failure(nil, "Malformed request: \(error)")
+    }
+    guard request.token == token else {
+      FileLog.shared.warn("Control socket: request with a bad token refused.")
+      return .failure(request.id, "Bad token")
+    }
+    FileLog.shared.info("Control socket: \(request.method.rawValue)")
+    let (out, simulated) = DispatchQueue.main.sync {
+      MainActor.assumeIsolated { Self.handleOnMain(request) }
+    }
+    if request.method == .runSelfTest { return runSelfTest(out) }
+    if let action = simulated {
+      if request.method == .testAction { return runInSandbox(action, out) }
+      ActionExecutor.execute(action, keyDown: true, activeModifiers: [])
+      ActionExecutor.execute(action, keyDown: false, activeModifiers: [])
+    }
+    return out
+  }
+
+  /// Longest `test_action` waits for the sandbox to focus, type and settle.
+  private static let sandboxTimeout: DispatchTimeInterval = .seconds(5)
+
+  private func runInSandbox(_ action: ActionConfig, _ out: ControlResponse) -> ControlResponse {
+    let done = DispatchSemaphore(value: 0)
+    var result: Result<ActionSandbox.Outcome, ActionSandbox.SandboxError>?
+    DispatchQueue.main.async {
+      MainActor.assumeIsolated {
+        ActionSandbox.shared.run(action) { result = $0; done.signal() }
+      }
+    }
+    guard done.wait(timeout: .now() + Self.sandboxTimeout) == .success, let result else {
+      return .failure(out.id, "test_action timed out")
+    }
+    switch result {
+    case .success(let outcome):
+      var out = out
+      out.sandbox = ControlSandboxResult(text: outcome.text, selectionStart: outcome.selection.location,
+                                         selectionLength: outcome.selection.length)
+      return out
+    case .failure(let error):
+      return .failure(out.id, error.localizedDescription)
+    }
+  }
+
+  private func runSelfTest(_ out: ControlResponse) -> ControlResponse {
+    let done = DispatchSemaphore(value: 0)
+    var report: SelfTest.Report?
+    DispatchQueue.main.async {
+      MainActor.assumeIsolated {
+        SelfTest.run { report = $0; done.signal() }
+      }
+    }
+    guard done.wait(timeout: .now() + Self.sandboxTimeout) == .success, let report else {
+      return .failure(out.id, "run_self_test timed out")
+    }
+    var out = out
+    out.selfTest = report
+    return out
+  }
+
+  /// The response, plus the action to post for `simulate_action` or run in
+  /// the sandbox for `test_action` — either done by the caller once off the
+  /// main queue.
+  @MainActor
+  private static func handleOnMain(_ request: ControlRequest) -> (ControlResponse, ActionConfig?) {
+    let app = AppState.shared
+    var out = ControlResponse(id: request.id, ok: true)
+    switch request.method {
+    case .getStatus:
+      out.status = ControlStatus(status: app.status.rawValue,
+                                 accessibilityGranted: app.accessibilityGranted,
+                                 secureInput: app.secureInputActive,
+                                 mappingCount: app.config.mappings.count,
+                                 version: app.appVersion,
+                                 debugLogging: app.debugLoggingUntil != nil)
+    case .getMappings:
+      out.mappings = app.config.mappings
+    case .upsertMapping:
+      guard let m = request.params?.mapping else { return (.failure(request.id, "upsert_mapping needs params.mapping"), nil) }
+      do {
+        try app.upsertMapping(trigger: m.trigger, actionId: m.actionId, inlineAction: m.inlineAction,
+                              bindings: m.bindings, repeatMode: m.repeatMode, label: m.label, note: m.note,
+                              enabled: m.enabled)
+      } catch {
+        return (.failure(request.id, error), nil)
+      }
+    case .setBindingEnabled:
+      guard let params = request.params, let key = params.key, let enabled = params.enabled else {
+        return (.failure(request.id, "set_binding_enabled needs params.key and params.enabled"), nil)
+      }
+      let trigger = Trigger.hyperPlusKey(key: key, withShift: params.withShift ?? false, ctrl: params.withCtrl ?? false,
+                                         alt: params.withAlt ?? false, cmd: params.withCmd ?? false)
+      do { try app.setMappingEnabled(trigger, enabled) } catch { return (.failure(request.id, error), nil) }
+    case .setPaused:
+      guard let paused = request.params?.paused else { return (.failure(request.id, "set_paused needs params.paused"), nil) }
+      guard !app.isDisabled else { return (.failure(request.id, "HyperCapslock is disabled"), nil) }
+      app.setPaused(paused)
+    case .simulateAction, .testAction:
+      let params = request.params
+      guard let action = ActionsRegistry.shared.resolve(actionId: params?.actionId, inline: params?.action) else {
+        return (.failure(request.id, params?.actionId.map { "Unknown action id: \($0)" }
+                         ?? "\(request.method.rawValue) needs params.action_id or params.action"), nil)
+      }
+      if params?.actionId == nil {
+        do { try ConfigStore.validate(action) } catch { return (.failure(request.id, error), nil) }
+      }
+      return (out, action)
+    case .resolve:
+      guard let params = request.params, let key = params.key else {
+        return (.failure(request.id, "resolve needs params.key"), nil)
+      }
+      let modifiers = ActionExecutor.hotkeyFlags(ctrl: params.withCtrl ?? false, alt: params.withAlt ?? false,
+                                                 cmd: params.withCmd ?? false, shift: false)
+      if let r = ActionExecutor.resolveChord(jsKeycode: key, shiftHeld: params.withShift ?? false, modifiers: modifiers,
+                                             ctx: ActionExecutor.currentContext()) {
+        out.resolution = ControlResolution(mapping: r.entry, action: r.action, viaShiftFallback: r.viaShiftFallback)
+      }
+    case .getEffectiveMappings:
+      let bundleID = request.params?.bundleId ?? FrontmostAppTracker.shared.currentBundleID()
+      let ctx = RuntimeContext(frontmostBundleID: bundleID)
+      let name = bundleID.flatMap { NSRunningApplication.runningApplications(withBundleIdentifier: $0).first?.localizedName }
+      out.effective = ControlEffectiveMappings(
+        app: name, bundleId: bundleID, excluded: EngineState.shared.isExcluded(bundleID), paused: !app.isRunning,
+        mappings: ActionExecutor.effectiveMappings(ctx).map {
+          ControlEffectiveMapping(mapping: $0.entry, action: $0.action, viaAppRule: $0.viaAppRule, passthrough: $0.passthrough)
+        })
+    case .getBindableKeys:
+      out.keys = KeyCodes.bindableKeys
+    case .getTrayStyle:
+      out.trayStyle = app.trayStyle
+    case .setTrayStyle:
+      guard let style = request.params?.style else {
+        return (.failure(request.id, "set_tray_style needs params.style (icon, title or both)"), nil)
+      }
+      do { try app.setTrayStyle(style) } catch { return (.failure(request.id, error), nil) }
+      out.trayStyle = style
+    case .getLogTail:
+      let lines = request.params?.lines ?? defaultLogLines
+      guard lines > 0 else { return (.failure(request.id, "get_log_tail needs params.lines > 0"), nil) }
+      out.log = FileLog.shared.tail(lines: min(lines, maxLogLines))
+    case .debugLoggingBurst:
+      guard let seconds = request.params?.seconds, (0...AppState.maxDebugLoggingSeconds).contains(seconds) else {
+        return (.failure(request.id, "debug_logging_burst needs params.seconds from 0 to \(AppState.maxDebugLoggingSeconds)"), nil)
+      }
+      if seconds == 0 { app.stopDebugLogging() } else { app.startDebugLogging(for: seconds) }
+    case .listPresets:
+      out.presets = app.config.listPresets()
+    case .getConfigPaths:
+      out.paths = app.config.configPaths()
+    case .runSelfTest:
+      break  // run by the caller, off the main queue
+    case .savePreset, .loadPreset, .deletePreset:
+      guard let name = request.params?.name else {
+        return (.failure(request.id, "\(request.method.rawValue) needs params.name"), nil)
+      }
+      do {
+        switch request.method {
+        case .savePreset: try app.config.savePreset(name)
+        case .loadPreset:
+          try app.config.loadPreset(name)
+          out.mappings = app.config.mappings
+        default: try app.config.deletePreset(name)
+        }
+      } catch {
+        return (.failure(request.id, error), nil)
+      }
+    }
+    return (out, nil)
+  }
+}
@@ -4,13 +4,49 @@ import os
 /// Append-only file logger. Keeps the original engine log path
 /// (`/tmp/hypercapslock-macos.log`) and `[HYPERCAPS][macOS][ts][LEVEL] msg`
 /// line format so existing troubleshooting docs and `tail -f` habits still work.
+///
+/// The file is capped (Settings ▸ Logs, 5 MB by default): past the cap it is
+/// rotated to `.1`, the old `.1` to `.2`, and the old `.2` dropped. The size is
+/// checked on the first write and then every `sizeCheckInterval` lines, not
+/// on each one, so logging stays a plain append.
+///
+/// `debug` lines are dropped unless a debug burst is on (see
+/// `AppState.startDebugLogging(for:)`): they cover every tapped event, far
+/// too many to keep by default.
 final class FileLog: @unchecked Sendable {
     static let shared = FileLog()
 
-    private let path = "/tmp/hypercapslock-macos.log"
+    let path = "/tmp/hypercapslock-macos.log"
+    /// Most of the file `tail` reads: plenty for a few hundred lines, and a
+    /// bounded read however large the log has grown.
+    private let tailChunkBytes: UInt64 = 256 * 1024
     private let lock = NSLock()
     private let osLog = Logger(subsystem: "me.xueshi.hypercapslock", category: "engine")
 
+    static let defaultMaxBytes: UInt64 = 5 * 1024 * 1024
+    /// Lines written between size checks. Even long lines keep the overshoot
+    /// past the cap to a few hundred KB.
+    static let sizeCheckInterval = 500
+    /// Rotated files kept next to the live one: `.1` (newer) and `.2`.
+    static let rotatedCopies = 2
+
+    private var maxBytes = FileLog.defaultMaxBytes
+    /// Counts down to the next size check; 0 checks on the next write.
+    private var writesUntilSizeCheck = 0
+    /// Separate from `lock`, which is held across file writes: the tap thread
+    /// checks this on every event.
+    private let debugOn = OSAllocatedUnfairLock(initialState: false)
+
+    var isDebugEnabled: Bool { debugOn.withLock { $0 } }
+
+    func setDebugEnabled(_ on: Bool) { debugOn.withLock { $0 = on } }
+
+    /// Logged only while debug is on; the message isn't even built otherwise.
+    func debug(_ message: @autoclosure () -> String) {
+        guard isDebugEnabled else { return }
+        log("DEBUG", message())
+    }
+
     func info(_ message: String) { log("INFO", message) }
     func warn(_ message: String) { log("WARN", message) }
     func error(_ message: String) { log("ERROR", message) }
@@ -21,11 +57,17 @@ final class FileLog: @unchecked Sendable {
         switch level {
         case "ERROR": osLog.error("\(message, privacy: .public)")
         case "WARN": osLog.warning("\(message, privacy: .public)")
+        case "DEBUG": osLog.debug("\(message, privacy: .public)")
         default: osLog.info("\(message, privacy: .public)")
         }
 
         lock.lock(); defer { lock.unlock() }
         guard let data = (line + "\n").data(using: .utf8) else { return }
+        if writesUntilSizeCheck <= 0 {
+            Self.rotateIfNeeded(path, maxBytes: maxBytes)
+            writesUntilSizeCheck = Self.sizeCheckInterval
+        }
+        writesUntilSizeCheck -= 1
         if let handle = FileHandle(forWritingAtPath: path) {
             handle.seekToEndOfFile()
             handle.write(data)
@@ -34,4 +76,53 @@ final class FileLog: @unchecked Sendable {
             try? data.write(to: URL(fileURLWithPath: path))
         }
     }
+
+    /// Change the size cap; the next write checks the file against it.
+    func setMaxBytes(_ bytes: UInt64) {
+        lock.lock(); defer { lock.unlock() }
+        maxBytes = bytes
+        writesUntilSizeCheck = 0
+    }
+
+    /// If the file at `path` is over `maxBytes`, shift it and its rotated
+    /// copies down one (`path` → `.1` → `.2`, the oldest removed). Returns
+    /// whether it rotated. Static and path-taking, for tests.
+    @discardableResult
+    static func rotateIfNeeded(_ path: String, maxBytes: UInt64) -> Bool {
+        let fm = FileManager.default
+        guard let size = (try? fm.attributesOfItem(atPath: path))?[.size] as? UInt64, size > maxBytes else { return false }
+        try? fm.removeItem(atPath: "\(path).\(rotatedCopies)")
+        for n in stride(from: rotatedCopies - 1, through: 1, by: -1) {
+            try? fm.moveItem(atPath: "\(path).\(n)", toPath: "\(path).\(n + 1)")
+        }
+        try? fm.moveItem(atPath: path, toPath: "\(path).1")
+        return true
+    }
+
+    /// The last `lines` lines of the log (fewer if the file is shorter or a
+    /// line is longer than the chunk read), for "Copy Logs" and `get_log_tail`.
+    /// Empty when nothing has been logged yet.
+    func tail(lines: Int) -> String {
+        lock.lock(); defer { lock.unlock() }
+        guard let handle = FileHandle(forReadingAtPath: path) else { return "" }
+        defer { try? handle.close() }
+        let size = handle.seekToEndOfFile()
+        let start = size > tailChunkBytes ? size - tailChunkBytes : 0
+        handle.seek(toFileOffset: start)
+        var text = String(decoding: handle.readDataToEndOfFile(), as: UTF8.self)
+        // Starting mid-file, the first line is cut off; drop it.
+        if start > 0, let newline = text.firstIndex(of: "\n") {
+            text = String(text[text.index(after: newline)...])
+        }
+        return Self.lastLines(of: text, lines)
+    }
+
+    /// The last `n` complete lines of `text`, newline-joined. A trailing
+    /// newline doesn't count as an empty line. Pure, for tests.
+    static func lastLines(of text: String, _ n: Int) -> String {
+        guard n > 0 else { return "" }
+        var lines = text.split(separator: "\n", omittingEmptySubsequences: false)
+        if lines.last?.isEmpty == true { lines.removeLast() }
+        return lines.suffix(n).joined(separator: "\n")
+    }
 }
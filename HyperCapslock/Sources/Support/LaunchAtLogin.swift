@@ -8,6 +8,16 @@ enum LaunchAtLogin {
         SMAppService.mainApp.status == .enabled
     }
 
+    /// Registered, but the user still has to allow it under System Settings ▸
+    /// General ▸ Login Items (or switched it off there).
+    static var needsApproval: Bool {
+        SMAppService.mainApp.status == .requiresApproval
+    }
+
+    static func openLoginItemsSettings() {
+        SMAppService.openSystemSettingsLoginItems()
+    }
+
     static func setEnabled(_ enabled: Bool) throws {
         if enabled {
             if SMAppService.mainApp.status != .enabled {
@@ -0,0 +1,36 @@
+import Foundation
+import UserNotifications
+
+/// Posts `.notify` actions as system notifications. The first one asks for
+/// permission (macOS remembers the answer); if the user declines, the action
+/// reports a failure next to its binding instead of vanishing silently. Also
+/// the notification center's delegate, so a banner still shows while our own
+/// window is frontmost.
+final class Notifier: NSObject, UNUserNotificationCenterDelegate {
+  static let shared = Notifier()
+
+  /// Deliver one notification. Called on the main queue (see `ActionExecutor`).
+  func post(title: String, body: String, trigger: Trigger?, action: ActionConfig) {
+    let center = UNUserNotificationCenter.current()
+    if center.delegate == nil { center.delegate = self }
+    center.requestAuthorization(options: [.alert, .sound]) { granted, error in
+      guard granted else {
+        let reason = error?.localizedDescription ?? "Notifications are turned off for HyperCapslock"
+        ActionResults.report(trigger, action, error: reason)
+        return
+      }
+      let content = UNMutableNotificationContent()
+      content.title = title
+      content.body = body
+      let request = UNNotificationRequest(identifier: UUID().uuidString, content: content, trigger: nil)
+      center.add(request) { error in
+        ActionResults.report(trigger, action, error: error?.localizedDescription)
+      }
+    }
+  }
+
+  func userNotificationCenter(_ center: UNUserNotificationCenter, willPresent notification: UNNotification,
+                              withCompletionHandler completionHandler: @escaping (UNNotificationPresentationOptions) -> Void) {
+    completionHandler([.banner, .sound])
+  }
+}
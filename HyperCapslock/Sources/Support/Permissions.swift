@@ -3,7 +3,7 @@ import ApplicationServices
 import CoreGraphics
 import AppKit
 
-/// Accessibility TCC check and System Settings deep link.
+/// Accessibility TCC check, change watcher, and System Settings deep link.
 ///
 /// The app's CGEventTap is an active `.defaultTap`, which macOS gates on
 /// Accessibility only — Input Monitoring is for `.listenOnly` taps, which we
@@ -28,4 +28,49 @@ enum Permissions {
         }
         if let url = URL(string: urlString) { NSWorkspace.shared.open(url) }
     }
+
+    /// Posted on the main queue when a permission's status flips (never on a
+    /// steady state). userInfo[`statusesKey`] is `[Pane: Status]` with the new
+    /// status of every permission we track.
+    static let didChangeNotification = Notification.Name("me.xueshi.hypercapslock.permissionsDidChange")
+    static let statusesKey = "statuses"
+}
+
+/// Watches the TCC statuses from a background timer and posts
+/// `Permissions.didChangeNotification` only on a transition, so the UI reacts
+/// to a grant made in System Settings without polling (or the app regaining
+/// focus). `AXIsProcessTrusted` is a cheap IPC read, fine every couple of seconds.
+final class PermissionWatcher {
+    static let shared = PermissionWatcher()
+
+    private let queue = DispatchQueue(label: "me.xueshi.hypercapslock.permissions", qos: .utility)
+    private var timer: DispatchSourceTimer?
+    /// Last observed status; touched only on `queue`.
+    private var lastAccessibility: Permissions.Status?
+
+    private init() {}
+
+    /// Start watching (idempotent). The first reading only seeds the baseline.
+    func start(interval: TimeInterval = 2.0) {
+        queue.async { [self] in
+            guard timer == nil else { return }
+            let t = DispatchSource.makeTimerSource(queue: queue)
+            t.schedule(deadline: .now(), repeating: interval, leeway: .milliseconds(250))
+            t.setEventHandler { [weak self] in self?.check() }
+            timer = t
+            t.resume()
+        }
+    }
+
+    private func check() {
+        let now: Permissions.Status = Permissions.isAccessibilityGranted ? .granted : .notGranted
+        defer { lastAccessibility = now }
+        guard let previous = lastAccessibility, previous != now else { return }
+        FileLog.shared.info("Permissions changed: accessibility \(previous.rawValue) → \(now.rawValue)")
+        let statuses: [Permissions.Pane: Permissions.Status] = [.accessibility: now]
+        DispatchQueue.main.async {
+            NotificationCenter.default.post(name: Permissions.didChangeNotification, object: nil,
+                                            userInfo: [Permissions.statusesKey: statuses])
+        }
+    }
 }
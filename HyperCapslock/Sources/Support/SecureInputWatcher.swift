@@ -0,0 +1,52 @@
+import AppKit
+import Carbon.HIToolbox
+
+/// Polls `IsSecureEventInputEnabled()` from a background timer and posts
+/// `didChangeNotification` when Secure Input turns on or off. While it's on,
+/// macOS hides keys from our tap and drops what we inject, so Caps + anything
+/// looks dead; the tray and Settings say so instead. The call is a cheap read
+/// of the session state, fine every couple of seconds.
+final class SecureInputWatcher {
+  static let shared = SecureInputWatcher()
+
+  /// Posted on the main queue on a transition (never on a steady state).
+  /// userInfo[`activeKey`] is the new state; userInfo[`appKey`] names the app
+  /// holding it, when the window server says.
+  static let didChangeNotification = Notification.Name("me.xueshi.hypercapslock.secureInputDidChange")
+  static let activeKey = "active"
+  static let appKey = "app"
+
+  private let queue = DispatchQueue(label: "me.xueshi.hypercapslock.secure-input", qos: .utility)
+  private var timer: DispatchSourceTimer?
+  /// Last observed state; touched only on `queue`. Starts off, so a session
+  /// that launches with Secure Input already on reports it straight away.
+  private var lastActive = false
+
+  private init() {}
+
+  /// Start polling (idempotent).
+  func start(interval: TimeInterval = 2.0) {
+    queue.async { [self] in
+      guard timer == nil else { return }
+      let t = DispatchSource.makeTimerSource(queue: queue)
+      t.schedule(deadline: .now(), repeating: interval, leeway: .milliseconds(250))
+      t.setEventHandler { [weak self] in self?.check() }
+      timer = t
+      t.resume()
+    }
+  }
+
+  private func check() {
+    let active = IsSecureEventInputEnabled()
+    guard active != lastActive else { return }
+    lastActive = active
+    let owner = active ? SelfTest.secureInputOwner() : nil
+    FileLog.shared.info(active ? "[STATE] Secure Input on\(owner.map { " (held by \($0))" } ?? "") — keys can't reach HyperCapslock."
+                               : "[STATE] Secure Input off — active again.")
+    var info: [String: Any] = [Self.activeKey: active]
+    if let owner { info[Self.appKey] = owner }
+    DispatchQueue.main.async {
+      NotificationCenter.default.post(name: Self.didChangeNotification, object: nil, userInfo: info)
+    }
+  }
+}
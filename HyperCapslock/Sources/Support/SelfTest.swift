@@ -0,0 +1,105 @@
+import AppKit
+import Carbon.HIToolbox
+
+/// Diagnoses the "permissions look fine but nothing happens" state: checks
+/// Accessibility and the tap, asks macOS whether Secure Input is on (a focused
+/// password field anywhere makes the system drop synthetic keys, and hides real
+/// ones from our tap), then types one key into the `ActionSandbox` field to see
+/// whether injection actually lands. Settings ▸ Permissions ▸ *Run Self-Test*
+/// and the socket's `run_self_test` both end up here.
+enum SelfTest {
+  enum Finding: String, Encodable, Equatable {
+    case accessibilityMissing = "accessibility_missing"
+    case tapNotInstalled = "tap_not_installed"
+    case secureInput = "secure_input"
+    case injectionBlocked = "injection_blocked"
+    /// The probe couldn't run (sandbox busy, or another window took focus).
+    case injectionUntested = "injection_untested"
+  }
+
+  struct Report: Encodable, Equatable {
+    var accessibilityGranted: Bool
+    var tapInstalled: Bool
+    var secureInput: Bool
+    /// The app that turned Secure Input on, when the window server says.
+    var secureInputApp: String?
+    /// Nil when the probe couldn't run.
+    var injectionWorks: Bool?
+    var findings: [Finding]
+    /// One line per finding, saying what to do about it.
+    var messages: [String]
+
+    var passed: Bool { findings.isEmpty }
+
+    enum CodingKeys: String, CodingKey {
+      case findings, messages
+      case accessibilityGranted = "accessibility_granted"
+      case tapInstalled = "tap_installed"
+      case secureInput = "secure_input"
+      case secureInputApp = "secure_input_app"
+      case injectionWorks = "injection_works"
+    }
+
+    init(accessibilityGranted: Bool, tapInstalled: Bool, secureInput: Bool, secureInputApp: String?,
+         injectionWorks: Bool?) {
+      self.accessibilityGranted = accessibilityGranted
+      self.tapInstalled = tapInstalled
+      self.secureInput = secureInput
+      self.secureInputApp = secureInputApp
+      self.injectionWorks = injectionWorks
+      var findings: [Finding] = []
+      if !accessibilityGranted { findings.append(.accessibilityMissing) }
+      if !tapInstalled { findings.append(.tapNotInstalled) }
+      if secureInput { findings.append(.secureInput) }
+      switch injectionWorks {
+      case false?: findings.append(.injectionBlocked)
+      case nil: findings.append(.injectionUntested)
+      case true?: break
+      }
+      self.findings = findings
+      self.messages = findings.map { SelfTest.message($0, secureInputApp: secureInputApp) }
+    }
+  }
+
+  /// Run every check; `completion` is called on the main thread. Brings the
+  /// sandbox window forward for a moment to type into it.
+  @MainActor
+  static func run(completion: @escaping (Report) -> Void) {
+    let granted = Permissions.isAccessibilityGranted
+    let installed = KeyboardHook.shared.isInstalled
+    let secure = IsSecureEventInputEnabled()
+    let owner = secure ? secureInputOwner() : nil
+    ActionSandbox.shared.probeInjection { result in
+      let report = Report(accessibilityGranted: granted, tapInstalled: installed, secureInput: secure,
+                          secureInputApp: owner, injectionWorks: try? result.get())
+      FileLog.shared.info("Self-test: \(report.passed ? "passed" : report.findings.map(\.rawValue).joined(separator: ", "))"
+                          + (owner.map { " (Secure Input held by \($0))" } ?? ""))
+      completion(report)
+    }
+  }
+
+  /// The app holding Secure Input: the window server keeps its pid in the
+  /// login session's dictionary.
+  static func secureInputOwner() -> String? {
+    guard let session = CGSessionCopyCurrentDictionary() as? [String: Any],
+          let pid = session["kCGSSessionSecureInputPID"] as? Int, pid > 0 else { return nil }
+    return NSRunningApplication(processIdentifier: pid_t(pid))?.localizedName ?? "pid \(pid)"
+  }
+
+  /// English, for the socket and the log; Settings shows the localized text.
+  static func message(_ finding: Finding, secureInputApp: String?) -> String {
+    switch finding {
+    case .accessibilityMissing:
+      return "Accessibility isn't granted: allow HyperCapslock in System Settings ▸ Privacy & Security ▸ Accessibility."
+    case .tapNotInstalled:
+      return "The keyboard tap isn't installed: check that HyperCapslock isn't disabled, or toggle it off and on in the menu bar."
+    case .secureInput:
+      return "Secure Input is on\(secureInputApp.map { " (held by \($0))" } ?? ""), so macOS hides keys from HyperCapslock: "
+        + "leave the password field or quit that app."
+    case .injectionBlocked:
+      return "A synthetic key didn't arrive: another tool may be filtering injected events; try Settings ▸ Injection location."
+    case .injectionUntested:
+      return "The injection probe couldn't run: keep the test window focused and try again."
+    }
+  }
+}
@@ -0,0 +1,30 @@
+import AppKit
+
+/// Keeps a single copy of HyperCapslock running. Two copies would each install
+/// an event tap and act on every chord ("every key fires twice"), and the second
+/// one's quit would reset the `hidutil` remap out from under the first. A
+/// second launch instead asks the running copy to show its window and quits
+/// before touching the keyboard or the remap.
+enum SingleInstance {
+  /// Posted on `DistributedNotificationCenter` by a launch that's handing off.
+  static let showWindowNotification = Notification.Name("me.xueshi.hypercapslock.showWindow")
+
+  /// Another running copy of this app (same bundle identifier), if any.
+  static func otherInstance() -> NSRunningApplication? {
+    guard let bundleID = Bundle.main.bundleIdentifier else { return nil }
+    let me = ProcessInfo.processInfo.processIdentifier
+    return NSRunningApplication.runningApplications(withBundleIdentifier: bundleID)
+      .first { $0.processIdentifier != me && !$0.isTerminated }
+  }
+
+  /// If another copy is running, bring its window forward and return true —
+  /// the caller then quits without starting anything.
+  static func handOffIfRunning() -> Bool {
+    guard let other = otherInstance() else { return false }
+    FileLog.shared.warn("HyperCapslock is already running (pid \(other.processIdentifier)); showing it and quitting this launch.")
+    DistributedNotificationCenter.default().postNotificationName(showWindowNotification, object: nil,
+                                                                 userInfo: nil, deliverImmediately: true)
+    other.activate()
+    return true
+  }
+}
@@ -0,0 +1,158 @@
+import AppKit
+
+/// Debug aid: a throwaway text window to try an action in before binding it.
+/// `simulate_action` (and a real chord) types into whatever is focused; here
+/// the keystrokes land in our own field, reset to the same sample text before
+/// every run, so the result is safe and repeatable. Only actions that work
+/// purely through keystrokes in the focused field are allowed — a shell
+/// command or an app launch would escape the sandbox.
+///
+/// Off by default, like the frontmost-app overlay: Settings ▸ Debug (or
+/// `defaults write me.xueshi.hypercapslock hc-debug-action-sandbox -bool true`)
+/// turns it on, which adds a Try button to the Actions page and enables the
+/// socket's `test_action`.
+@MainActor
+final class ActionSandbox {
+  static let shared = ActionSandbox()
+
+  static let defaultsKey = "hc-debug-action-sandbox"
+  static var isEnabled: Bool { UserDefaults.standard.bool(forKey: defaultsKey) }
+
+  /// What the field holds at the start of each run; the caret starts before "over".
+  static let sampleText = "The quick brown fox\njumps over the lazy dog.\nPack my box with five dozen liquor jugs."
+  static let sampleCaret = (sampleText as NSString).range(of: "over").location
+
+  /// Time for the window to become key before posting, and for the posted
+  /// events to reach the field before reading it back.
+  private static let focusDelay = 0.15
+  private static let settleDelay = 0.3
+
+  /// The field after a run: its text and the selection (a caret when empty).
+  struct Outcome: Equatable {
+    var text: String
+    var selection: NSRange
+  }
+
+  enum SandboxError: LocalizedError {
+    case disabled
+    case notSandboxable
+    case busy
+    case lostFocus
+
+    var errorDescription: String? {
+      switch self {
+      case .disabled: return "The action sandbox is off (Settings ▸ Debug)"
+      case .notSandboxable: return "This action acts outside the text field, so it can't run in the sandbox"
+      case .busy: return "Another sandbox run is still in progress"
+      case .lostFocus: return "The sandbox window isn't focused; nothing was typed"
+      }
+    }
+  }
+
+  private var window: NSWindow?
+  private let textView = NSTextView()
+  private var running = false
+
+  private init() {}
+
+  /// Whether `action` only types into / moves within the focused field. A
+  /// combo with ⌘ is refused: it would hit our own menu (⌘W, ⌘Q) instead.
+  static func isSandboxable(_ action: ActionConfig) -> Bool {
+    switch action {
+    case .directional, .jump, .wrapPair, .transformSelection, .insertDateTime:
+      return true
+    case .independent(let kind):
+      switch kind {
+      case .backspace, .nextLine, .openLineAbove, .insertQuotes, .noop: return true
+      case .toggleCapsLock, .switchInputSource, .releaseModifiers, .repeatLast, .stickyShift: return false
+      }
+    case .keyCombo(_, _, _, let withCmd, _):
+      return !withCmd
+    case .inputSource, .command, .openApp, .modifierKey, .appControl, .script, .notify, .goTo:
+      return false
+    }
+  }
+
+  /// Reset the field, focus it, tap `action` once, and report what the field
+  /// ended up as. Keys are posted only if the sandbox is still the key window.
+  func run(_ action: ActionConfig, completion: @escaping (Result<Outcome, SandboxError>) -> Void) {
+    guard Self.isEnabled else { return completion(.failure(.disabled)) }
+    guard Self.isSandboxable(action) else { return completion(.failure(.notSandboxable)) }
+    perform(action, completion: completion)
+  }
+
+  /// The self-test's probe (see `SelfTest`): one injected Right arrow, which
+  /// should move the caret by one. Runs whatever the debug setting, and puts
+  /// the window away afterwards. Reports whether the key arrived.
+  func probeInjection(completion: @escaping (Result<Bool, SandboxError>) -> Void) {
+    perform(.directional(.right)) { [self] result in
+      window?.orderOut(nil)
+      completion(result.map { $0.selection == NSRange(location: Self.sampleCaret + 1, length: 0) })
+    }
+  }
+
+  private func perform(_ action: ActionConfig, completion: @escaping (Result<Outcome, SandboxError>) -> Void) {
+    guard !running else { return completion(.failure(.busy)) }
+    running = true
+    let window = show()
+    reset()
+    DispatchQueue.main.asyncAfter(deadline: .now() + Self.focusDelay) { [self] in
+      guard NSApp.isActive, window.isKeyWindow, window.firstResponder === textView else {
+        running = false
+        return completion(.failure(.lostFocus))
+      }
+      FileLog.shared.info("Action sandbox: running \(describeAction(action))")
+      DispatchQueue.global(qos: .userInitiated).async {
+        ActionExecutor.execute(action, keyDown: true, activeModifiers: [])
+        ActionExecutor.execute(action, keyDown: false, activeModifiers: [])
+        DispatchQueue.main.asyncAfter(deadline: .now() + Self.settleDelay) {
+          MainActor.assumeIsolated {
+            self.running = false
+            completion(.success(Outcome(text: self.textView.string, selection: self.textView.selectedRange())))
+          }
+        }
+      }
+    }
+  }
+
+  /// Bring the sandbox window forward with the field focused, creating it on first use.
+  @discardableResult
+  func show() -> NSWindow {
+    let window = self.window ?? makeWindow()
+    self.window = window
+    NSApp.activate(ignoringOtherApps: true)
+    window.makeKeyAndOrderFront(nil)
+    window.makeFirstResponder(textView)
+    return window
+  }
+
+  private func reset() {
+    textView.string = Self.sampleText
+    textView.setSelectedRange(NSRange(location: Self.sampleCaret, length: 0))
+  }
+
+  private func makeWindow() -> NSWindow {
+    let window = NSWindow(contentRect: NSRect(x: 0, y: 0, width: 440, height: 140),
+                          styleMask: [.titled, .closable, .resizable], backing: .buffered, defer: false)
+    window.title = "Action Sandbox"
+    window.isReleasedWhenClosed = false
+    let scroll = NSScrollView(frame: window.contentLayoutRect)
+    scroll.autoresizingMask = [.width, .height]
+    scroll.hasVerticalScroller = true
+    textView.frame = scroll.contentView.bounds
+    textView.autoresizingMask = [.width]
+    textView.isRichText = false
+    textView.font = .monospacedSystemFont(ofSize: 13, weight: .regular)
+    textView.textContainerInset = NSSize(width: 6, height: 6)
+    // Keep what the action typed exactly as typed.
+    textView.isAutomaticQuoteSubstitutionEnabled = false
+    textView.isAutomaticDashSubstitutionEnabled = false
+    textView.isAutomaticTextReplacementEnabled = false
+    textView.isAutomaticSpellingCorrectionEnabled = false
+    scroll.documentView = textView
+    window.contentView = scroll
+    window.center()
+    reset()
+    return window
+  }
+}
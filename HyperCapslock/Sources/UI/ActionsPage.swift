@@ -11,6 +11,7 @@ struct ActionsPage: View {
     @EnvironmentObject var config: ConfigStore
     @EnvironmentObject var loc: LocalizationManager
     @State private var sheet: ActionSheetMode?
+    @AppStorage(ActionSandbox.defaultsKey) private var sandboxEnabled = false
 
     var body: some View {
         Form {
@@ -62,6 +63,12 @@ struct ActionsPage: View {
                         .padding(.horizontal, 6).padding(.vertical, 2)
                         .background(Capsule().fill(Color.secondary.opacity(0.15)))
                 }
+                if sandboxEnabled && ActionSandbox.isSandboxable(action.config) {
+                    Button { tryInSandbox(action) } label: { Image(systemName: "flask") }
+                        .buttonStyle(.borderless)
+                        .help(loc.t("actions.try"))
+                        .accessibilityIdentifier("actions.try.\(action.id)")
+                }
                 if editable {
                     Button { sheet = .edit(action) } label: { Image(systemName: "pencil") }.buttonStyle(.borderless)
                     Button { delete(action) } label: { Image(systemName: "trash") }.buttonStyle(.borderless)
@@ -84,6 +91,14 @@ struct ActionsPage: View {
         }
     }
 
+    private func tryInSandbox(_ action: Action) {
+        ActionSandbox.shared.run(action.config) { result in
+            if case .failure(let error) = result {
+                app.showToast(loc.t("toast.sandbox_failed", ["error": error.localizedDescription]), isError: true)
+            }
+        }
+    }
+
     private func delete(_ action: Action) {
         do {
             try app.removeCustomAction(id: action.id)
@@ -33,9 +33,14 @@ struct AddEditActionView: View {
                             Text(loc.t("group.input_source")).tag("input_source")
                         }
                         Text(loc.t("group.command")).tag("command")
+                        Text(loc.t("group.script")).tag("script")
+                        Text(loc.t("group.notify")).tag("notify")
                         Text(loc.t("group.key_combo")).tag("key_combo")
                         Text(loc.t("group.open_app")).tag("open_app")
                         Text(loc.t("group.hold_modifier")).tag("hold_modifier")
+                        Text(loc.t("group.wrap_pair")).tag("wrap_pair")
+                        Text(loc.t("group.go_to")).tag("go_to")
+                        Text(loc.t("group.insert_date_time")).tag("insert_date_time")
                     }
                     ActionConfigDetail(draft: $draft)
                 }
@@ -55,7 +60,7 @@ struct AddEditActionView: View {
             }
             .padding(12)
         }
-        .frame(width: 480, height: 300)
+        .frame(width: 480, height: 340)
         .auroraBackground()
         .navigationTitle(editing ? loc.t("actions.edit_title") : loc.t("actions.add_title"))
         .onAppear(perform: prefill)
@@ -91,35 +96,61 @@ struct ActionConfigDraft {
     var directional: DirectionalActionKind = .left
     var jumpDir: JumpDirection = .up
     var jumpCount = 10
+    var jumpUnit: JumpUnit = .line
     var independent: IndependentActionKind = .backspace
     var inputSourceID = ""
     var command = ""
+    var commandCapture = false
+    var scriptLanguage: ScriptLanguage = .applescript
+    var scriptSource = ""
+    var notifyTitle = ""
+    var notifyBody = ""
     var targetKey: UInt16?
     var tCtrl = false, tAlt = false, tCmd = false, tShift = false
     var appBundleID = ""
     var appName = ""
     var modifier: ModifierKey = .leftOption
+    var wrapOpen = "("
+    var wrapClose = ")"
+    var wrapSpaces = 0
+    var appCommand: AppCommand = .togglePause
+    var selectionTransform: SelectionTransform = .uppercase
+    var goToLine: Int?
+    var goToColumn: Int?
+    var goToKey: UInt16? = GoToShortcut.default.targetKey
+    var gCtrl = false, gAlt = false, gCmd = true, gShift = false
+    var dateFormat = DateTimeText.defaultFormat
 
     mutating func load(_ config: ActionConfig) {
         switch config {
         case .directional(let a): kind = "directional"; directional = a
-        case .jump(let d, let c): kind = "jump"; jumpDir = d; jumpCount = c
+        case .jump(let d, let c, let u): kind = "jump"; jumpDir = d; jumpCount = c; jumpUnit = u
         case .independent(let a): kind = "independent"; independent = a
         case .inputSource(let id): kind = "input_source"; inputSourceID = id
-        case .command(let c): kind = "command"; command = c
+        case .command(let c, let capture): kind = "command"; command = c; commandCapture = capture
         case .keyCombo(let k, let ctrl, let alt, let cmd, let shift):
             kind = "key_combo"; targetKey = k; tCtrl = ctrl; tAlt = alt; tCmd = cmd; tShift = shift
         case .openApp(let bid, let name):
             kind = "open_app"; appBundleID = bid; appName = name
         case .modifierKey(let m):
             kind = "hold_modifier"; modifier = m
+        case .wrapPair(let open, let close, let spaces):
+            kind = "wrap_pair"; wrapOpen = open; wrapClose = close; wrapSpaces = spaces
+        case .appControl(let c): kind = "app_control"; appCommand = c
+        case .script(let lang, let source): kind = "script"; scriptLanguage = lang; scriptSource = source
+        case .notify(let title, let body): kind = "notify"; notifyTitle = title; notifyBody = body
+        case .transformSelection(let op): kind = "transform_selection"; selectionTransform = op
+        case .goTo(let line, let column, let s):
+            kind = "go_to"; goToLine = line; goToColumn = column
+            goToKey = s.targetKey; gCtrl = s.withCtrl; gAlt = s.withAlt; gCmd = s.withCmd; gShift = s.withShift
+        case .insertDateTime(let format): kind = "insert_date_time"; dateFormat = format
         }
     }
 
     func build() -> ActionConfig? {
         switch kind {
         case "directional": return .directional(directional)
-        case "jump": return .jump(direction: jumpDir, count: min(99, max(1, jumpCount)))
+        case "jump": return .jump(direction: jumpDir, count: min(99, max(1, jumpCount)), unit: jumpUnit)
         case "independent": return .independent(independent)
         case "input_source":
             let id = inputSourceID.trimmingCharacters(in: .whitespaces)
@@ -128,7 +159,14 @@ struct ActionConfigDraft {
             // Trim newlines too: a command that is only blank lines is invalid, and
             // a `/bin/sh -c` script never needs leading/trailing blank lines.
             let c = command.trimmingCharacters(in: .whitespacesAndNewlines)
-            return c.isEmpty ? nil : .command(c)
+            return c.isEmpty ? nil : .command(c, capture: commandCapture)
+        case "script":
+            let s = scriptSource.trimmingCharacters(in: .whitespacesAndNewlines)
+            return s.isEmpty ? nil : .script(language: scriptLanguage, source: s)
+        case "notify":
+            let title = notifyTitle.trimmingCharacters(in: .whitespaces)
+            let body = notifyBody.trimmingCharacters(in: .whitespaces)
+            return title.isEmpty && body.isEmpty ? nil : .notify(title: title, body: body)
         case "key_combo":
             guard let k = targetKey else { return nil }
             return .keyCombo(targetKey: k, withCtrl: tCtrl, withAlt: tAlt, withCmd: tCmd, withTargetShift: tShift)
@@ -137,6 +175,17 @@ struct ActionConfigDraft {
             return bid.isEmpty ? nil : .openApp(bundleID: bid, name: appName.isEmpty ? bid : appName)
         case "hold_modifier":
             return .modifierKey(modifier)
+        case "wrap_pair":
+            guard !wrapOpen.isEmpty || !wrapClose.isEmpty else { return nil }
+            return .wrapPair(open: wrapOpen, close: wrapClose, innerSpaces: min(8, max(0, wrapSpaces)))
+        case "app_control": return .appControl(appCommand)
+        case "transform_selection": return .transformSelection(selectionTransform)
+        case "go_to":
+            guard let k = goToKey, (goToLine ?? 1) >= 1, (goToColumn ?? 1) >= 1 else { return nil }
+            return .goTo(line: goToLine, column: goToColumn,
+                         shortcut: GoToShortcut(targetKey: k, withCtrl: gCtrl, withAlt: gAlt, withCmd: gCmd, withShift: gShift))
+        case "insert_date_time":
+            return DateTimeText.problem(dateFormat) == nil ? .insertDateTime(format: dateFormat) : nil
         default: return nil
         }
     }
@@ -167,6 +216,12 @@ struct ActionConfigDetail: View {
             Picker(loc.t("group.directional"), selection: $draft.jumpDir) {
                 Text(loc.t("action.up")).tag(JumpDirection.up); Text(loc.t("action.down")).tag(JumpDirection.down)
             }
+            Picker(loc.t("actions.jump_unit"), selection: $draft.jumpUnit) {
+                ForEach(JumpUnit.allCases, id: \.self) { unit in
+                    Text(loc.t("actions.jump_unit.\(unit.rawValue)")).tag(unit)
+                }
+            }
+            .accessibilityIdentifier("actions.jump_unit")
             LabeledContent(loc.t("actions.count")) {
                 TextField("", value: $draft.jumpCount, format: .number).frame(width: 70).multilineTextAlignment(.trailing)
             }
@@ -179,15 +234,23 @@ struct ActionConfigDetail: View {
             // leading-aligned + full-width, and renders typed chars immediately. A
             // command runs as `/bin/sh -c`, so newlines are just shell statements.
             // Bounded height (scrolls past ~7 lines) to fit the fixed editor window.
-            VStack(alignment: .leading, spacing: 4) {
-                Text(loc.t("group.command"))
-                TextEditor(text: $draft.command)
-                    .font(.system(.body, design: .monospaced))
-                    .scrollContentBackground(.hidden)
-                    .padding(6)
-                    .frame(minHeight: 60, maxHeight: 150)
-                    .overlay(RoundedRectangle(cornerRadius: 6).stroke(Color.secondary.opacity(0.3)))
+            sourceEditor(loc.t("group.command"), $draft.command)
+            Toggle(loc.t("actions.command_capture"), isOn: $draft.commandCapture)
+                .accessibilityIdentifier("actions.command_capture")
+            Text(loc.t("actions.command_capture_hint")).font(.caption).foregroundStyle(.secondary)
+        case "script":
+            Picker(loc.t("script.language"), selection: $draft.scriptLanguage) {
+                ForEach(ScriptLanguage.allCases, id: \.self) { lang in
+                    Text(loc.t("script.\(lang.rawValue)")).tag(lang)
+                }
             }
+            .accessibilityIdentifier("actions.script_language")
+            sourceEditor(loc.t("script.source"), $draft.scriptSource)
+        case "notify":
+            TextField(loc.t("actions.notify_title"), text: $draft.notifyTitle)
+                .accessibilityIdentifier("actions.notify_title")
+            TextField(loc.t("actions.notify_body"), text: $draft.notifyBody)
+                .accessibilityIdentifier("actions.notify_body")
         case "open_app":
             LabeledContent(loc.t("actions.app")) {
                 HStack(spacing: 8) {
@@ -218,10 +281,76 @@ struct ActionConfigDetail: View {
                 }
             }
             Text(loc.t("actions.hold_modifier_hint")).font(.caption).foregroundStyle(.secondary)
+        case "wrap_pair":
+            LabeledContent(loc.t("actions.wrap_open")) {
+                TextField("", text: $draft.wrapOpen).frame(width: 70).multilineTextAlignment(.trailing)
+                    .accessibilityIdentifier("actions.wrap_open")
+            }
+            LabeledContent(loc.t("actions.wrap_close")) {
+                TextField("", text: $draft.wrapClose).frame(width: 70).multilineTextAlignment(.trailing)
+                    .accessibilityIdentifier("actions.wrap_close")
+            }
+            LabeledContent(loc.t("actions.wrap_spaces")) {
+                Stepper("\(draft.wrapSpaces)", value: $draft.wrapSpaces, in: 0...8)
+                    .accessibilityIdentifier("actions.wrap_spaces")
+            }
+        case "go_to":
+            LabeledContent(loc.t("actions.go_to_line")) {
+                TextField("", value: $draft.goToLine, format: .number, prompt: Text(loc.t("actions.go_to_count")))
+                    .frame(width: 120).multilineTextAlignment(.trailing)
+                    .accessibilityIdentifier("actions.go_to_line")
+            }
+            LabeledContent(loc.t("actions.go_to_column")) {
+                TextField("", value: $draft.goToColumn, format: .number).frame(width: 70).multilineTextAlignment(.trailing)
+                    .accessibilityIdentifier("actions.go_to_column")
+            }
+            HStack {
+                Text(loc.t("actions.go_to_shortcut"))
+                Spacer()
+                KeyCaptureField(jsKeyCode: $draft.goToKey, enabled: true, placeholder: loc.t("mappings.press_key"))
+                    .frame(width: 140, height: 28)
+            }
+            LabeledContent("") {
+                HStack(spacing: 6) {
+                    modToggle("⌘", $draft.gCmd); modToggle("⌃", $draft.gCtrl); modToggle("⌥", $draft.gAlt); modToggle("⇧", $draft.gShift)
+                }
+            }
+            Text(loc.t("actions.go_to_hint")).font(.caption).foregroundStyle(.secondary)
+        case "insert_date_time":
+            LabeledContent(loc.t("actions.date_format")) {
+                HStack(spacing: 6) {
+                    TextField("", text: $draft.dateFormat).frame(width: 180).font(.body.monospaced())
+                        .accessibilityIdentifier("actions.date_format")
+                    Menu(loc.t("actions.date_presets")) {
+                        ForEach(DateTimeText.presets, id: \.self) { preset in
+                            Button("\(preset)  →  \(DateTimeText.render(preset, at: Date()) ?? "")") { draft.dateFormat = preset }
+                        }
+                    }
+                    .fixedSize()
+                }
+            }
+            if let problem = DateTimeText.problem(draft.dateFormat) {
+                Text(problem).font(.caption).foregroundStyle(.red)
+            } else {
+                LabeledContent(loc.t("actions.date_preview"), value: DateTimeText.render(draft.dateFormat, at: Date()) ?? "")
+            }
+            Text(loc.t("actions.date_hint")).font(.caption).foregroundStyle(.secondary)
         default: EmptyView()
         }
     }
 
+    private func sourceEditor(_ title: String, _ text: Binding<String>) -> some View {
+        VStack(alignment: .leading, spacing: 4) {
+            Text(title)
+            TextEditor(text: text)
+                .font(.system(.body, design: .monospaced))
+                .scrollContentBackground(.hidden)
+                .padding(6)
+                .frame(minHeight: 60, maxHeight: 150)
+                .overlay(RoundedRectangle(cornerRadius: 6).stroke(Color.secondary.opacity(0.3)))
+        }
+    }
+
     private func modToggle(_ symbol: String, _ binding: Binding<Bool>) -> some View {
         Button { binding.wrappedValue.toggle() } label: { Text(symbol).frame(width: 26) }
             .buttonStyle(.bordered).tint(binding.wrappedValue ? .blue : .secondary)
@@ -20,9 +20,14 @@ private let inlineKindSentinels: [(sentinel: String, kind: String, labelKey: Str
     (switchInputSourceSentinel, "input_source", "mappings.switch_input_source", "globe"),
     ("__inline_jump__", "jump", "group.jump", "chevron.up.2"),
     ("__inline_command__", "command", "group.command", "terminal"),
+    ("__inline_script__", "script", "group.script", "applescript"),
+    ("__inline_notify__", "notify", "group.notify", "bell"),
     ("__inline_key_combo__", "key_combo", "group.key_combo", "keyboard"),
     ("__inline_open_app__", "open_app", "group.open_app", "arrow.up.forward.app"),
     ("__inline_hold_modifier__", "hold_modifier", "group.hold_modifier", "hand.tap"),
+    ("__inline_wrap_pair__", "wrap_pair", "group.wrap_pair", "parentheses"),
+    ("__inline_go_to__", "go_to", "group.go_to", "number"),
+    ("__inline_insert_date_time__", "insert_date_time", "group.insert_date_time", "calendar.badge.clock"),
 ]
 
 /// The `ActionConfigDraft.kind` an inline sentinel selects, or nil if `sentinel`
@@ -127,6 +132,15 @@ struct AddEditMappingView: View {
     @State private var triggerSel = "plain"
     @State private var key: UInt16?
     @State private var dtModifier: ModifierKey = .rightCommand   // chosen in the Key row when triggerSel == "dtm"
+    // Modifiers of a global hotkey (triggerSel == "hotkey").
+    @State private var hkCtrl = false
+    @State private var hkAlt = true
+    @State private var hkCmd = false
+    @State private var hkShift = false
+    // Extra modifiers of a Caps(+Shift)+key chord (triggerSel == "plain" / "with_shift").
+    @State private var capsCtrl = false
+    @State private var capsAlt = false
+    @State private var capsCmd = false
     @State private var selectedActionId = "builtin.move_left"
     @State private var inlineDraft = ActionConfigDraft()   // when selectedActionId is an inline-kind sentinel
     @State private var keptInlineConfig: ActionConfig?
@@ -134,9 +148,14 @@ struct AddEditMappingView: View {
     @State private var showCreateAction = false
     @State private var createActionSentinel = "__create_action__-" + UUID().uuidString
     @State private var rules: [BindingDraft] = []
+    @State private var repeatMode: RepeatMode = .auto
+    @State private var label = ""
+    @State private var note = ""
+    /// Kept as loaded so editing a switched-off mapping doesn't switch it on.
+    @State private var enabled = true
 
     private var editing: Bool { if case .edit = mode { return true }; return false }
-    private var triggerNeedsKey: Bool { triggerSel == "plain" || triggerSel == "with_shift" }
+    private var triggerNeedsKey: Bool { triggerSel == "plain" || triggerSel == "with_shift" || triggerSel == "hotkey" }
     private var triggerNeedsModifier: Bool { triggerSel == "dtm" }
 
     var body: some View {
@@ -149,6 +168,7 @@ struct AddEditMappingView: View {
                         Text(loc.t("trigger.single_tap_hyper")).tag("single_tap")
                         Text(loc.t("trigger.double_tap_hyper")).tag("double_tap")
                         Text(doubleTapModifierLabel).tag("dtm")
+                        Text(loc.t("trigger.hotkey")).tag("hotkey")
                     }
                     .disabled(editing)
                     .accessibilityIdentifier("mapping.trigger")
@@ -161,12 +181,53 @@ struct AddEditMappingView: View {
                                             accessibilityID: "mapping.key_field")
                                 .frame(width: 140, height: 28)
                         }
+                        if triggerSel == "hotkey" {
+                            HStack {
+                                Text(loc.t("mappings.hotkey_modifiers"))
+                                Spacer()
+                                Toggle("⌃", isOn: $hkCtrl)
+                                Toggle("⌥", isOn: $hkAlt)
+                                Toggle("⇧", isOn: $hkShift)
+                                Toggle("⌘", isOn: $hkCmd)
+                            }
+                            .toggleStyle(.button)
+                            .disabled(editing)
+                            .accessibilityIdentifier("mapping.hotkey_modifiers")
+                            Text(loc.t("mappings.hotkey_hint")).font(.caption).foregroundStyle(.secondary)
+                        } else {
+                            HStack {
+                                Text(loc.t("mappings.caps_modifiers"))
+                                Spacer()
+                                Toggle("⌃", isOn: $capsCtrl)
+                                Toggle("⌥", isOn: $capsAlt)
+                                Toggle("⌘", isOn: $capsCmd)
+                            }
+                            .toggleStyle(.button)
+                            .disabled(editing)
+                            .accessibilityIdentifier("mapping.caps_modifiers")
+                            Text(loc.t("mappings.caps_modifiers_hint")).font(.caption).foregroundStyle(.secondary)
+                        }
                     } else if triggerNeedsModifier {
                         Picker(loc.t("mappings.key"), selection: $dtModifier) {
                             ForEach(modifierOrder, id: \.self) { m in Text(modifierPickerLabel(m)).tag(m) }
                         }
                         .disabled(editing)
                     }
+                    if let hint = currentBehaviorHint {
+                        Text(hint).font(.caption).foregroundStyle(.secondary)
+                            .accessibilityIdentifier("mapping.current_behavior")
+                    }
+                    if triggerNeedsKey {
+                        Picker(loc.t("mappings.repeat"), selection: $repeatMode) {
+                            ForEach(RepeatMode.allCases, id: \.self) { m in Text(loc.t("mappings.repeat.\(m.rawValue)")).tag(m) }
+                        }
+                        .accessibilityIdentifier("mapping.repeat")
+                    }
+                    TextField(loc.t("mappings.label"), text: $label, prompt: Text(loc.t("mappings.label_placeholder")))
+                        .accessibilityIdentifier("mapping.label")
+                    TextField(loc.t("mappings.note"), text: $note, axis: .vertical)
+                        .lineLimit(1...3)
+                        .accessibilityIdentifier("mapping.note")
                 }
 
                 Section {
@@ -227,6 +288,17 @@ struct AddEditMappingView: View {
 
             Divider()
             HStack {
+                if case .edit(let entry) = mode {
+                    Button(loc.t("mappings.reset_one")) {
+                        app.resetMapping(entry.trigger)
+                        app.showToast(loc.t("toast.mapping_reset"))
+                        dismiss()
+                    }
+                    .help(config.configuredDefault(for: entry.trigger) == nil
+                          ? loc.t("mappings.reset_one_removes") : loc.t("mappings.reset_one_hint"))
+                    .disabled(config.configuredDefault(for: entry.trigger) == entry)
+                    .accessibilityIdentifier("mapping.reset")
+                }
                 Spacer()
                 Button(loc.t("update.cancel")) { dismiss() }
                 Button(loc.t("mappings.save")) { save() }
@@ -277,14 +349,38 @@ struct AddEditMappingView: View {
     /// like a "⌘+R" combo. fn has no side and is flagged experimental.
     private func modifierPickerLabel(_ m: ModifierKey) -> String { modifierFullLabel(m, loc) }
 
+    /// For a new Caps(+Shift)+key trigger that nothing is bound to yet, what
+    /// the key does today — passes through, or borrows the Caps+key motion via
+    /// the Shift fallback or with the extra modifiers forwarded. Resolved exactly as the hook would (default action,
+    /// no per-app rule).
+    private var currentBehaviorHint: String? {
+        guard !editing, triggerSel == "plain" || triggerSel == "with_shift", let key else { return nil }
+        let shift = triggerSel == "with_shift"
+        let chord = capsChordLabel(KeyCodes.name(key), shift: shift, ctrl: capsCtrl, alt: capsAlt, cmd: capsCmd)
+            .replacingOccurrences(of: "+", with: " + ")
+        let modifiers = ActionExecutor.hotkeyFlags(ctrl: capsCtrl, alt: capsAlt, cmd: capsCmd, shift: false)
+        guard let resolved = ActionExecutor.resolveChord(jsKeycode: key, shiftHeld: shift, modifiers: modifiers,
+                                                         ctx: RuntimeContext(frontmostBundleID: nil)) else {
+            return loc.t("mappings.resolves_passthrough", ["chord": chord])
+        }
+        // A plain chord answering for a modified press forwards the modifiers.
+        let borrowsPlain = !modifiers.isEmpty && ActionExecutor.capsChordModifiers(resolved.entry.trigger).isEmpty
+        guard resolved.viaShiftFallback || borrowsPlain, let action = resolved.action else { return nil }
+        return loc.t(borrowsPlain ? "mappings.resolves_modified" : "mappings.resolves_fallback",
+                     ["chord": chord, "action": actionPresentation(action, loc).value])
+    }
+
     private var draftTrigger: Trigger? {
         switch triggerSel {
         case "single_tap": return .singleTapHyper
         case "double_tap": return .doubleTapHyper
         case "dtm": return .doubleTapModifier(dtModifier)
+        case "hotkey":
+            guard let key, Trigger.hotkeyAllowed(key: key, ctrl: hkCtrl, alt: hkAlt, cmd: hkCmd) else { return nil }
+            return .hotkey(key: key, ctrl: hkCtrl, alt: hkAlt, cmd: hkCmd, shift: hkShift)
         default:
             guard let key else { return nil }
-            return .hyperPlusKey(key: key, withShift: triggerSel == "with_shift")
+            return .hyperPlusKey(key: key, withShift: triggerSel == "with_shift", ctrl: capsCtrl, alt: capsAlt, cmd: capsCmd)
         }
     }
 
@@ -296,16 +392,20 @@ struct AddEditMappingView: View {
             return
         }
         let bindings = rules.map { $0.toBinding() }
+        let repeatMode = triggerNeedsKey ? self.repeatMode : .auto
         do {
             if let kind = inlineKind(for: selectedActionId) {
                 var d = inlineDraft
                 d.kind = kind
                 guard let cfg = d.build() else { return }   // guarded by the disabled Save button
-                try app.upsertMapping(trigger: trigger, actionId: nil, inlineAction: cfg, bindings: bindings)
+                try app.upsertMapping(trigger: trigger, actionId: nil, inlineAction: cfg, bindings: bindings, repeatMode: repeatMode,
+                                      label: label, note: note, enabled: enabled)
             } else if selectedActionId == keepInlineSentinel, let inline = keptInlineConfig {
-                try app.upsertMapping(trigger: trigger, actionId: nil, inlineAction: inline, bindings: bindings)
+                try app.upsertMapping(trigger: trigger, actionId: nil, inlineAction: inline, bindings: bindings, repeatMode: repeatMode,
+                                      label: label, note: note, enabled: enabled)
             } else {
-                try app.upsertMapping(trigger: trigger, actionId: selectedActionId, bindings: bindings)
+                try app.upsertMapping(trigger: trigger, actionId: selectedActionId, bindings: bindings, repeatMode: repeatMode,
+                                      label: label, note: note, enabled: enabled)
             }
             app.showToast(loc.t("toast.mapping_saved"))
             dismiss()
@@ -320,7 +420,12 @@ struct AddEditMappingView: View {
         case .singleTapHyper: triggerSel = "single_tap"
         case .doubleTapHyper: triggerSel = "double_tap"
         case .doubleTapModifier(let m): triggerSel = "dtm"; dtModifier = m
-        case .hyperPlusKey(let k, let withShift): triggerSel = withShift ? "with_shift" : "plain"; key = k
+        case .hyperPlusKey(let k, let withShift, let ctrl, let alt, let cmd):
+            triggerSel = withShift ? "with_shift" : "plain"; key = k
+            capsCtrl = ctrl; capsAlt = alt; capsCmd = cmd
+        case .hotkey(let k, let ctrl, let alt, let cmd, let shift):
+            triggerSel = "hotkey"; key = k
+            hkCtrl = ctrl; hkAlt = alt; hkCmd = cmd; hkShift = shift
         }
     }
 
@@ -354,6 +459,10 @@ struct AddEditMappingView: View {
         }
         lastRealActionId = selectedActionId
         rules = entry.bindings.map(BindingDraft.init(from:))
+        repeatMode = entry.repeatMode
+        label = entry.label ?? ""
+        note = entry.note ?? ""
+        enabled = entry.enabled
     }
 }
 
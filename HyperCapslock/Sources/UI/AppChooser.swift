@@ -24,6 +24,13 @@ enum AppChooser {
     return AppRef(bundleID: bid, name: name)
   }
 
+  /// Best-effort display name for a bundle id (falls back to the id itself).
+  @MainActor static func displayName(_ bundleID: String) -> String {
+    guard let url = NSWorkspace.shared.urlForApplication(withBundleIdentifier: bundleID) else { return bundleID }
+    let n = FileManager.default.displayName(atPath: url.path)
+    return n.hasSuffix(".app") ? String(n.dropLast(4)) : n
+  }
+
   @MainActor static func icon(_ bundleID: String) -> NSImage? {
     guard let url = NSWorkspace.shared.urlForApplication(withBundleIdentifier: bundleID) else { return nil }
     return NSWorkspace.shared.icon(forFile: url.path)
@@ -2,8 +2,22 @@ import AppKit
 
 final class AppDelegate: NSObject, NSApplicationDelegate {
     private var tray: TrayController?
+    /// Set when this launch found another copy running and is quitting; nothing
+    /// was started, so there's nothing to tear down (see `SingleInstance`).
+    private var handedOff = false
 
     func applicationDidFinishLaunching(_ notification: Notification) {
+        // Hand off to a copy that's already running before loading config or
+        // touching the keyboard. XCUITest launches its own copy alongside.
+        if !AppEnvironment.isUITest && SingleInstance.handOffIfRunning() {
+            handedOff = true
+            NSApp.terminate(nil)
+            return
+        }
+        if AppEnvironment.isSafeMode {
+            FileLog.shared.warn("==================== SAFE MODE ====================")
+            FileLog.shared.warn("Launched with --safe-mode: config files ignored and left untouched, keyboard hook and hidutil remap NOT installed.")
+        }
         // A pure-AppKit app (main.swift, no nib) has no main menu, so text fields
         // wouldn't get the standard Cmd-A/C/V/X/Z editing shortcuts. Install one.
         setupMainMenu()
@@ -11,8 +25,9 @@ final class AppDelegate: NSObject, NSApplicationDelegate {
         AppState.shared.bootstrap()
         // Under -uitest (XCUITest), do NOT install the global keyboard hook /
         // hidutil remap — tests must never grab the host keyboard. Config is
-        // already isolated to a temp dir (see ConfigStore.appDataDir).
-        if !AppEnvironment.isUITest {
+        // already isolated to a temp dir (see ConfigStore.appDataDir). A launch
+        // restored as disabled leaves the keyboard alone until re-enabled.
+        if !AppEnvironment.isUITest && !AppState.shared.isDisabled {
             KeyboardHook.shared.start()
         }
         HudController.shared.install()
@@ -27,8 +42,9 @@ final class AppDelegate: NSObject, NSApplicationDelegate {
         MainWindowController.shared = MainWindowController()
         // Show the window on launch unless the user turned it off (then the app
         // starts silently in the menu bar — reopen via the tray or Dock icon).
-        // Always show under -uitest so XCUITest can drive the UI.
-        if AppEnvironment.isUITest || AppState.shared.showWindowOnLaunch {
+        // Always show under -uitest so XCUITest can drive the UI, and in safe
+        // mode, which exists so the user can see what to fix.
+        if AppEnvironment.isUITest || AppEnvironment.isSafeMode || AppState.shared.showWindowOnLaunch {
             MainWindowController.shared?.show()
         }
         if !AppEnvironment.isUITest {
@@ -41,6 +57,13 @@ final class AppDelegate: NSObject, NSApplicationDelegate {
             forName: NSApplication.didBecomeActiveNotification, object: nil, queue: .main
         ) { _ in
             AppState.shared.refreshPermissions()
+            AppState.shared.refreshAutostart()
+        }
+        // A second launch asks us to come forward instead of running alongside.
+        DistributedNotificationCenter.default().addObserver(
+            forName: SingleInstance.showWindowNotification, object: nil, queue: .main
+        ) { _ in
+            MainWindowController.shared?.show()
         }
     }
 
@@ -51,9 +74,13 @@ final class AppDelegate: NSObject, NSApplicationDelegate {
     }
 
     func applicationWillTerminate(_ notification: Notification) {
+        // A handed-off launch never loaded stats or installed the remap; flushing
+        // or cleaning up would clobber the running copy's.
+        guard !handedOff else { return }
         // Persist any presses recorded since the last debounced flush before we
         // exit. Safe in -uitest too (no-op: the hook never recorded anything).
         UsageStats.shared.flushNow()
+        ControlServer.shared.stop()
         // -uitest never installed the hook / remap, so there's nothing to tear
         // down — and we must not touch global hidutil state on test exit.
         guard !AppEnvironment.isUITest else { return }
@@ -9,14 +9,45 @@ import Combine
 final class AppState: ObservableObject {
     static let shared = AppState()
 
-    enum ServiceStatus: String { case initializing, running, paused, error }
+    /// `paused` keeps the tap installed and passes everything through;
+    /// `disabled` tears the tap and the hidutil remap down entirely.
+    enum ServiceStatus: String { case initializing, running, paused, disabled, error }
 
     @Published var status: ServiceStatus = .initializing
     @Published var accessibilityGranted = false
     @Published var permissionsResolved = false   // false until first refresh completes
     @Published var autostart = false
+    /// Launch at login was requested but is waiting for the user's approval in
+    /// System Settings. Re-read with `autostart` on every activation.
+    @Published private(set) var autostartNeedsApproval = false
     @Published var permissionsExpandedManually: Bool? = nil
     @Published var toast: ToastMessage?
+    /// When a timed pause (`pause(for:)`) auto-resumes; nil for an indefinite
+    /// pause or while running.
+    @Published private(set) var pausedUntil: Date?
+    /// When a debug logging burst (`startDebugLogging(for:)`) ends; nil while
+    /// none is running. Drives the recording indicator.
+    @Published private(set) var debugLoggingUntil: Date?
+    /// True while the frontmost app is on the exclusion list, so the engine is
+    /// passing everything through even though the service is running.
+    @Published private(set) var excludedAppFrontmost = false
+    /// triggerID → error of the last run of that mapping's action, for mappings
+    /// whose most recent run failed. Cleared per mapping by its next success.
+    @Published private(set) var actionFailures: [String: String] = [:]
+    /// The selected keyboard input source, kept current from the system's
+    /// change notification.
+    @Published private(set) var currentInputSource: InputSourceController.InputSourceInfo?
+    /// Whether hidutil currently reports the CapsLock→F18 remap; nil until the
+    /// first probe (and always under -uitest, which never reads hidutil).
+    @Published private(set) var capsRemapActive: Bool?
+    /// The remap couldn't be applied, so Caps is read from the raw Caps Lock
+    /// toggle (see `EngineState.rawCapsFallback`).
+    @Published private(set) var rawCapsFallback = false
+    /// Another app has Secure Input on (a focused password field), so macOS
+    /// hides keys from the tap; nothing works until it clears.
+    @Published private(set) var secureInputActive = false
+    /// The app holding Secure Input, when the window server says.
+    @Published private(set) var secureInputApp: String?
 
     struct ToastMessage: Identifiable, Equatable {
         let id = UUID()
@@ -25,6 +56,14 @@ final class AppState: ObservableObject {
     }
 
     private var toastClearWork: DispatchWorkItem?
+    private var autoResumeWork: DispatchWorkItem?
+    private var debugLoggingEndWork: DispatchWorkItem?
+    private var frontmostObserver: NSObjectProtocol?
+    private var actionResultObserver: NSObjectProtocol?
+    private var permissionsObserver: NSObjectProtocol?
+    private var appCommandObserver: NSObjectProtocol?
+    private var inputSourceObserver: NSObjectProtocol?
+    private var secureInputObserver: NSObjectProtocol?
 
     func showToast(_ text: String, isError: Bool = false) {
         toast = ToastMessage(text: text, isError: isError)
@@ -46,6 +85,7 @@ final class AppState: ObservableObject {
 
     var isRunning: Bool { status == .running }
     var isPaused: Bool { status == .paused }
+    var isDisabled: Bool { status == .disabled }
 
     private init() {}
 
@@ -55,18 +95,61 @@ final class AppState: ObservableObject {
         config.load()
         // Load before the keyboard hook installs, so the first recorded press
         // accumulates onto the persisted history instead of a blank slate.
-        UsageStats.shared.load()
+        // Safe mode reads none of our files; with no hook, nothing is recorded.
+        if !AppEnvironment.isSafeMode { UsageStats.shared.load() }
         FileLog.shared.info("bootstrap: \(config.mappings.count) mappings, \(config.customActions.count) custom actions; appConfig=\(config.appConfig)")
         applyHudSettings()
         applyInputSourceSettings()
+        applySequenceSettings()
+        applyLogSettings()
+        applyWordMotionStyle()
+        applyLineEdgeStyle()
+        applyShiftLayerMode()
+        applyRawEscapeModifier()
+        applyEventLocations()
+        applyKeyDebounce()
+        applyCapsLayerMode()
+        applyCapsToggle()
+        applyExclusions()
+        applyPassthroughKeys()
+        restoreServiceState()
+        frontmostObserver = NotificationCenter.default.addObserver(forName: FrontmostAppTracker.didChangeNotification, object: nil, queue: .main) { [weak self] _ in
+            MainActor.assumeIsolated { self?.refreshExcludedAppFrontmost() }
+        }
+        actionResultObserver = NotificationCenter.default.addObserver(forName: ActionResults.didReportNotification, object: nil, queue: .main) { [weak self] note in
+            guard let result = note.userInfo?[ActionResults.resultKey] as? ActionResult else { return }
+            MainActor.assumeIsolated { self?.recordActionResult(result) }
+        }
+        appCommandObserver = NotificationCenter.default.addObserver(forName: AppCommands.didRequestNotification, object: nil, queue: .main) { [weak self] note in
+            guard let command = note.userInfo?[AppCommands.commandKey] as? AppCommand else { return }
+            MainActor.assumeIsolated { self?.perform(command) }
+        }
+        currentInputSource = InputSourceController.current()
+        inputSourceObserver = DistributedNotificationCenter.default().addObserver(forName: InputSourceController.selectionDidChangeNotification, object: nil, queue: .main) { [weak self] _ in
+            MainActor.assumeIsolated { self?.currentInputSource = InputSourceController.current() }
+        }
         applyKeyRemaps()
         applyActivationPolicy(hide: config.appConfig.hideDockIcon)
         applyAppearance(config.appConfig.themeMode)
-        autostart = LaunchAtLogin.isEnabled
-        status = .running
-        EngineState.shared.isPaused = false
+        refreshAutostart()
         applyAnyDragIntegration(config.appConfig.broadcastCapsHoldForAnyDrag)
+        applyControlSocket()
         refreshPermissions()
+        markOnboardingDoneForExistingInstall()
+        permissionsObserver = NotificationCenter.default.addObserver(forName: Permissions.didChangeNotification, object: nil, queue: .main) { [weak self] note in
+            guard let statuses = note.userInfo?[Permissions.statusesKey] as? [Permissions.Pane: Permissions.Status] else { return }
+            MainActor.assumeIsolated { self?.permissionsChanged(statuses) }
+        }
+        PermissionWatcher.shared.start()
+        secureInputObserver = NotificationCenter.default.addObserver(forName: SecureInputWatcher.didChangeNotification, object: nil, queue: .main) { [weak self] note in
+            let active = note.userInfo?[SecureInputWatcher.activeKey] as? Bool ?? false
+            let owner = note.userInfo?[SecureInputWatcher.appKey] as? String
+            MainActor.assumeIsolated {
+                self?.secureInputActive = active
+                self?.secureInputApp = owner
+            }
+        }
+        SecureInputWatcher.shared.start()
     }
 
     private func applyHudSettings() {
@@ -108,8 +191,48 @@ final class AppState: ObservableObject {
 
     // MARK: - Service pause/resume
 
-    func setPaused(_ paused: Bool) {
+    /// Come back in the state the user left: running, paused, or fully
+    /// disabled. Runs before `applyKeyRemaps()` and before `AppDelegate`
+    /// installs the tap, so a disabled launch never touches the keyboard.
+    private func restoreServiceState() {
+        // Safe mode always starts disabled: no tap, no remap (see `setEnabled`).
+        if AppEnvironment.isSafeMode {
+            status = .disabled
+            EngineState.shared.isPaused = true
+            return
+        }
+        let restored = config.appConfig.serviceState
+        switch restored {
+        case .running: status = .running
+        case .paused: status = .paused
+        case .disabled: status = .disabled
+        }
+        EngineState.shared.isPaused = restored != .running
+        if restored != .running { FileLog.shared.info("[STATE] Restored service state: \(restored.rawValue)") }
+    }
+
+    /// Remember the state for the next launch. A failed write only costs the
+    /// restore, so it's logged rather than surfaced.
+    private func persistServiceState(_ state: ServiceState) {
+        guard config.appConfig.serviceState != state else { return }
+        do {
+            try config.setServiceState(state)
+        } catch {
+            FileLog.shared.error("Failed to persist service state \(state.rawValue): \(error)")
+        }
+    }
+
+    /// `persist: false` leaves the remembered state alone — used by the timed
+    /// pause, which shouldn't outlive a relaunch.
+    func setPaused(_ paused: Bool, persist: Bool = true) {
+        // Pause is meaningless with no tap installed; re-enable first.
+        guard !isDisabled else { return }
+        // Any explicit pause/resume supersedes a pending timed resume.
+        autoResumeWork?.cancel()
+        autoResumeWork = nil
+        pausedUntil = nil
         EngineState.shared.isPaused = paused
+        EngineState.shared.pausedCapsDown = false
         if paused {
             // Paused → the tap returns early, so a chord mid-hold would never see
             // its key-up. Release everything (esp. a held push-to-talk modifier)
@@ -121,11 +244,102 @@ final class AppState: ObservableObject {
             endCapsHold()
         }
         status = paused ? .paused : .running
+        if persist { persistServiceState(paused ? .paused : .running) }
         FileLog.shared.info("[STATE] Service \(paused ? "paused" : "resumed")")
     }
 
     func togglePause() { setPaused(!isPaused) }
 
+    /// An `.appControl` action fired from the keyboard (see `AppCommands`).
+    private func perform(_ command: AppCommand) {
+        switch command {
+        case .togglePause: togglePause()
+        case .reloadConfig: reloadConfig()
+        case .showWindow: MainWindowController.shared?.show()
+        case .quit: NSApp.terminate(nil)
+        case .showPalette: CommandPalette.shared.show()
+        }
+    }
+
+    /// Re-read both config files from disk and re-apply everything derived
+    /// from them, as at launch — for edits made outside the app.
+    func reloadConfig() {
+        config.load()
+        applyHudSettings()
+        applyInputSourceSettings()
+        applySequenceSettings()
+        applyLogSettings()
+        applyWordMotionStyle()
+        applyLineEdgeStyle()
+        applyShiftLayerMode()
+        applyRawEscapeModifier()
+        applyEventLocations()
+        applyKeyDebounce()
+        applyCapsLayerMode()
+        applyCapsToggle()
+        applyExclusions()
+        applyPassthroughKeys()
+        applyKeyRemaps()
+        applyAppearance(config.appConfig.themeMode)
+        applyControlSocket()
+        objectWillChange.send()
+        FileLog.shared.info("[STATE] Config reloaded from disk: \(config.mappings.count) mappings")
+        showToast(loc.t("toast.config_reloaded", ["count": String(config.mappings.count)]))
+    }
+
+    /// Pause now and resume automatically after `seconds`. A later
+    /// `setPaused(_:)` or another `pause(for:)` replaces the pending resume.
+    func pause(for seconds: TimeInterval) {
+        guard !isDisabled else { return }
+        setPaused(true, persist: false)
+        let until = Date().addingTimeInterval(seconds)
+        pausedUntil = until
+        let work = DispatchWorkItem { [weak self] in
+            FileLog.shared.info("[STATE] Timed pause elapsed; resuming.")
+            self?.setPaused(false)
+        }
+        autoResumeWork = work
+        DispatchQueue.main.asyncAfter(deadline: .now() + seconds, execute: work)
+        FileLog.shared.info("[STATE] Service paused for \(Int(seconds))s (until \(until))")
+    }
+
+    // MARK: - Full disable (tap + remap removed)
+
+    /// Fully disable or re-enable the engine. Unlike pause, disabling stops the
+    /// event tap and restores the original CapsLock mapping, so the keyboard
+    /// behaves 100% natively; enabling re-applies the remap and reinstalls the
+    /// tap. Remembered across launches, like pause.
+    func setEnabled(_ enabled: Bool) {
+        guard enabled == isDisabled else { return }
+        if enabled && AppEnvironment.isSafeMode {
+            showToast(loc.t("toast.safe_mode_engine_off"), isError: true)
+            return
+        }
+        if enabled {
+            status = .running
+            applyKeyRemaps()
+            EngineState.shared.isPaused = false
+            if !AppEnvironment.isUITest { KeyboardHook.shared.start() }
+        } else {
+            autoResumeWork?.cancel()
+            autoResumeWork = nil
+            pausedUntil = nil
+            // Same order as quitting: stop claiming chords, release what's held
+            // (drained on the tap thread), then take the tap and remap away.
+            EngineState.shared.isPaused = true
+            endCapsHold()
+            if !AppEnvironment.isUITest {
+                KeyboardHook.shared.releaseHeldChordsSerialized(wait: true)
+                KeyboardHook.shared.stop()
+                KeyboardHook.shared.cleanup()
+            }
+            status = .disabled
+            refreshCapsRemapState()
+        }
+        persistServiceState(enabled ? .running : .disabled)
+        FileLog.shared.info("[STATE] Service \(enabled ? "enabled" : "disabled (tap + remap removed)")")
+    }
+
     // MARK: - Settings toggles
 
     func setHideDockIcon(_ hide: Bool) throws {
@@ -177,6 +391,86 @@ final class AppState: ObservableObject {
         }
     }
 
+    /// Turning the socket on can still fail to bind (e.g. an unwritable
+    /// data dir); the setting is kept and the error surfaced, so a relaunch
+    /// retries.
+    func setControlSocketEnabled(_ on: Bool) throws {
+        try config.setControlSocketEnabled(on)
+        guard applyControlSocket() else { throw ConfigError.io("Control socket failed to start") }
+    }
+
+    @discardableResult
+    private func applyControlSocket() -> Bool {
+        guard !AppEnvironment.isUITest else { return true }
+        if config.appConfig.controlSocketEnabled { return ControlServer.shared.start() }
+        ControlServer.shared.stop()
+        return true
+    }
+
+    /// Copy the scripting socket's token, creating it if the socket has never run.
+    func copyControlToken() -> Bool {
+        guard let token = ControlServer.loadOrCreateToken() else { return false }
+        NSPasteboard.general.clearContents()
+        return NSPasteboard.general.setString(token, forType: .string)
+    }
+
+    /// Choices offered for the log size cap, in MB.
+    static let logSizeOptions = [1, 5, 20, 50]
+
+    var logMaxMb: Int { config.appConfig.logMaxMb }
+
+    func setLogMaxMb(_ mb: Int) throws {
+        try config.setLogMaxMb(mb)
+        applyLogSettings()
+    }
+
+    private func applyLogSettings() {
+        // Hand-edited configs may hold any value; clamp as the setter does.
+        let mb = min(max(config.appConfig.logMaxMb, 1), 100)
+        FileLog.shared.setMaxBytes(UInt64(mb) * 1024 * 1024)
+    }
+
+    /// Lines "Copy Logs" puts on the clipboard — enough to cover a session's
+    /// startup plus whatever went wrong, small enough to paste into an issue.
+    static let copiedLogLines = 500
+
+    /// Copy the end of the engine log for a bug report. False when there is
+    /// nothing logged yet or the pasteboard refused it.
+    func copyRecentLogs() -> Bool {
+        let log = FileLog.shared.tail(lines: Self.copiedLogLines)
+        guard !log.isEmpty else { return false }
+        NSPasteboard.general.clearContents()
+        return NSPasteboard.general.setString(log, forType: .string)
+    }
+
+    /// Longest debug logging burst, and the one the Settings button starts.
+    static let maxDebugLoggingSeconds = 600
+    static let defaultDebugLoggingSeconds = 60
+
+    /// Log every tapped event for `seconds`, then go back to normal logging on
+    /// its own — a focused capture for a bug report (grab it with Copy Logs or
+    /// `get_log_tail`). Never persisted: a relaunch always starts quiet. A second
+    /// call restarts the window.
+    func startDebugLogging(for seconds: Int) {
+        let seconds = min(max(seconds, 1), Self.maxDebugLoggingSeconds)
+        debugLoggingEndWork?.cancel()
+        FileLog.shared.setDebugEnabled(true)
+        debugLoggingUntil = Date().addingTimeInterval(TimeInterval(seconds))
+        let work = DispatchWorkItem { [weak self] in self?.stopDebugLogging() }
+        debugLoggingEndWork = work
+        DispatchQueue.main.asyncAfter(deadline: .now() + .seconds(seconds), execute: work)
+        FileLog.shared.info("[STATE] Debug logging on for \(seconds)s")
+    }
+
+    func stopDebugLogging() {
+        debugLoggingEndWork?.cancel()
+        debugLoggingEndWork = nil
+        guard debugLoggingUntil != nil else { return }
+        FileLog.shared.setDebugEnabled(false)
+        debugLoggingUntil = nil
+        FileLog.shared.info("[STATE] Debug logging off")
+    }
+
     var mappingsViewStyle: MappingsViewStyle { config.appConfig.mappingsViewStyle }
 
     /// Pure presentation toggle — just persist it; the Mappings page re-renders
@@ -185,6 +479,14 @@ final class AppState: ObservableObject {
         try config.setMappingsViewStyle(style)
     }
 
+    var trayStyle: TrayStyle { config.appConfig.trayStyle }
+
+    /// The tray watches `appConfig` and redraws itself, so persisting is all
+    /// there is to it.
+    func setTrayStyle(_ style: TrayStyle) throws {
+        try config.setTrayStyle(style)
+    }
+
     var statsShowInline: Bool { config.appConfig.statsShowInline }
 
     /// Pure presentation toggle for the Mappings-page inline press counts.
@@ -192,6 +494,142 @@ final class AppState: ObservableObject {
         try config.setStatsShowInline(on)
     }
 
+    func setWordMotionStyle(_ style: WordMotionStyle) throws {
+        try config.setWordMotionStyle(style)
+        applyWordMotionStyle()
+    }
+
+    private func applyWordMotionStyle() {
+        EngineState.shared.wordMotionStyle = config.appConfig.wordMotionStyle
+        FileLog.shared.info("Word motion style applied: \(config.appConfig.wordMotionStyle.rawValue)")
+    }
+
+    func setLineEdgeStyle(_ style: LineEdgeStyle) throws {
+        try config.setLineEdgeStyle(style)
+        applyLineEdgeStyle()
+    }
+
+    private func applyLineEdgeStyle() {
+        EngineState.shared.lineEdgeStyle = config.appConfig.lineEdgeStyle
+        FileLog.shared.info("Line edge style applied: \(config.appConfig.lineEdgeStyle.rawValue)")
+    }
+
+    func setShiftLayerMode(_ mode: ShiftLayerMode) throws {
+        try config.setShiftLayerMode(mode)
+        applyShiftLayerMode()
+    }
+
+    func setRawEscapeModifier(_ modifier: RawEscapeModifier) throws {
+        try config.setRawEscapeModifier(modifier)
+        applyRawEscapeModifier()
+    }
+
+    func setTapLocation(_ location: EventTapLocation) throws {
+        try config.setTapLocation(location)
+        applyEventLocations()
+    }
+
+    func setPostLocation(_ location: EventTapLocation) throws {
+        try config.setPostLocation(location)
+        applyEventLocations()
+    }
+
+    func setInjectDelay(_ ms: Int) throws {
+        try config.setInjectDelay(ms)
+        applyEventLocations()
+    }
+
+    func setCapsMouseEvents(_ on: Bool) throws {
+        try config.setCapsMouseEvents(on)
+        applyEventLocations()
+    }
+
+    func setDebounceMs(_ ms: Int) throws {
+        try config.setDebounceMs(ms)
+        applyKeyDebounce()
+    }
+
+    private func applyKeyDebounce() {
+        let ms = min(max(config.appConfig.debounceMs, 0), EngineConstants.maxDebounceMs)
+        guard EngineState.shared.debounceMs != ms else { return }
+        EngineState.shared.debounceMs = ms
+        KeyDebounce.shared.reset()
+        FileLog.shared.info("Key debounce applied: \(ms)ms")
+    }
+
+    /// The tap is created at its location with its event mask, so moving it
+    /// or toggling mouse buttons reinstalls a running tap; the post location
+    /// applies from the next synthesized key.
+    private func applyEventLocations() {
+        let c = config.appConfig
+        let moved = EngineState.shared.tapLocation != c.tapLocation
+            || EngineState.shared.capsMouseEvents != c.capsMouseEvents
+        EngineState.shared.tapLocation = c.tapLocation
+        EngineState.shared.capsMouseEvents = c.capsMouseEvents
+        EngineState.shared.postLocation = c.postLocation
+        EngineState.shared.injectDelayMs = min(max(c.injectDelayMs, 0), EngineConstants.maxInjectDelayMs)
+        FileLog.shared.info("Event locations applied: tap=\(c.tapLocation.rawValue) post=\(c.postLocation.rawValue) delay=\(c.injectDelayMs)ms mouse=\(c.capsMouseEvents)")
+        if moved && KeyboardHook.shared.isRunning { KeyboardHook.shared.restart() }
+    }
+
+    private func applyShiftLayerMode() {
+        EngineState.shared.shiftLayerMode = config.appConfig.shiftLayerMode
+        FileLog.shared.info("Shift layer mode applied: \(config.appConfig.shiftLayerMode.rawValue)")
+    }
+
+    private func applyRawEscapeModifier() {
+        EngineState.shared.rawEscapeModifier = config.appConfig.rawEscapeModifier
+        FileLog.shared.info("Raw escape modifier applied: \(config.appConfig.rawEscapeModifier.rawValue)")
+    }
+
+    func setCapsLayerMode(_ mode: CapsLayerMode) throws {
+        try config.setCapsLayerMode(mode)
+        applyCapsLayerMode()
+    }
+
+    private func applyCapsLayerMode() {
+        EngineState.shared.capsLayerMode = config.appConfig.capsLayerMode
+        _ = EngineState.shared.swapOneShotArmedAtMs(0)   // don't carry an armed tap across modes
+        FileLog.shared.info("Caps layer mode applied: \(config.appConfig.capsLayerMode.rawValue)")
+    }
+
+    func setCapsToggleEnabled(_ on: Bool) throws {
+        try config.setCapsToggleEnabled(on)
+        applyCapsToggle()
+    }
+
+    func setCapsLockPinnedOff(_ on: Bool) throws {
+        try config.setCapsLockPinnedOff(on)
+        applyCapsToggle()
+    }
+
+    var capsTapChoice: CapsTapChoice {
+        CapsTapChoice.current(singleTap: config.mappings.first { $0.trigger == .singleTapHyper },
+                              toggleEnabled: config.appConfig.capsToggleEnabled)
+    }
+
+    /// Settings' "Caps tap" picker. Toggle / Nothing clear the single-tap
+    /// mapping and flip `capsToggleEnabled`; Escape binds single-tap Caps to
+    /// an Esc key combo. `.custom` is display-only.
+    func setCapsTapChoice(_ choice: CapsTapChoice) throws {
+        switch choice {
+        case .toggleCaps, .nothing:
+            removeMapping(.singleTapHyper)
+            try setCapsToggleEnabled(choice == .toggleCaps)
+        case .escape:
+            try upsertMapping(trigger: .singleTapHyper, actionId: nil, inlineAction: CapsTapChoice.escapeAction)
+        case .custom:
+            break
+        }
+    }
+
+    private func applyCapsToggle() {
+        EngineState.shared.capsToggleEnabled = config.appConfig.capsToggleEnabled
+        EngineState.shared.capsLockPinnedOff = config.appConfig.capsLockPinnedOff
+        FileLog.shared.info("Caps tap toggles Caps Lock: \(config.appConfig.capsToggleEnabled), pinned off: \(config.appConfig.capsLockPinnedOff)")
+        if config.appConfig.capsLockPinnedOff { CapsLockState.forceOff() }
+    }
+
     var showWindowOnLaunch: Bool { config.appConfig.showWindowOnLaunch }
 
     /// Whether the main window auto-shows at launch. Just persist it — there's no
@@ -205,6 +643,114 @@ final class AppState: ObservableObject {
         FileLog.shared.info("Input-source fix strategy applied: \(config.appConfig.cjkvFixStrategy.rawValue)")
     }
 
+    // MARK: - Excluded apps (auto-pause)
+
+    var excludedApps: [String] { config.appConfig.excludedApps }
+
+    func addExcludedApp(_ bundleID: String) throws {
+        let apps = Self.excludedApps(excludedApps, adding: bundleID)
+        guard apps != excludedApps else { return }
+        try config.setExcludedApps(apps)
+        applyExclusions()
+    }
+
+    func removeExcludedApp(_ bundleID: String) throws {
+        try config.setExcludedApps(Self.excludedApps(excludedApps, removing: bundleID))
+        applyExclusions()
+    }
+
+    /// `apps` with `bundleID` appended, unless it's already there. Bundle IDs
+    /// compare case-insensitively, as the engine matches them.
+    static func excludedApps(_ apps: [String], adding bundleID: String) -> [String] {
+        apps.contains(where: { $0.caseInsensitiveCompare(bundleID) == .orderedSame }) ? apps : apps + [bundleID]
+    }
+
+    /// `apps` without `bundleID`, in any case.
+    static func excludedApps(_ apps: [String], removing bundleID: String) -> [String] {
+        apps.filter { $0.caseInsensitiveCompare(bundleID) != .orderedSame }
+    }
+
+    private func applyExclusions() {
+        EngineState.shared.setExcludedBundleIDs(config.appConfig.excludedApps)
+        FileLog.shared.info("Excluded apps applied: \(config.appConfig.excludedApps)")
+        refreshExcludedAppFrontmost()
+    }
+
+    // MARK: - Passthrough keys
+
+    var passthroughKeys: [PassthroughKey] { config.appConfig.passthroughKeys }
+
+    /// Add Caps+`key` to the passthrough list (everywhere); a key already on
+    /// the list is left as is.
+    func addPassthroughKey(_ key: UInt16) throws {
+        guard !passthroughKeys.contains(where: { $0.key == key }) else { return }
+        try config.setPassthroughKeys(passthroughKeys + [PassthroughKey(key: key)])
+        applyPassthroughKeys()
+    }
+
+    /// Limit a passthrough key to `apps` (empty = everywhere).
+    func setPassthroughApps(_ key: UInt16, _ apps: [String]) throws {
+        try config.setPassthroughKeys(passthroughKeys.map { $0.key == key ? PassthroughKey(key: key, apps: apps) : $0 })
+        applyPassthroughKeys()
+    }
+
+    func removePassthroughKey(_ key: UInt16) throws {
+        try config.setPassthroughKeys(passthroughKeys.filter { $0.key != key })
+        applyPassthroughKeys()
+    }
+
+    private func applyPassthroughKeys() {
+        EngineState.shared.passthroughKeys = config.appConfig.passthroughKeys
+        FileLog.shared.info("Passthrough keys applied: \(config.appConfig.passthroughKeys.map { KeyCodes.name($0.key) })")
+    }
+
+    /// "Paused: Secure Input active", naming the holder when known; shared by
+    /// the tray status line and the Settings status row.
+    var secureInputStatusText: String {
+        guard let owner = secureInputApp else { return loc.t("status.secure_input") }
+        return loc.t("status.secure_input_app", ["app": owner])
+    }
+
+    /// Recompute `excludedAppFrontmost`. On entering an excluded app the tap
+    /// stops seeing key-ups, so release held chords and the Caps hold exactly
+    /// as pausing does.
+    private func refreshExcludedAppFrontmost() {
+        let excluded = EngineState.shared.isExcluded(FrontmostAppTracker.shared.currentBundleID())
+        guard excluded != excludedAppFrontmost else { return }
+        excludedAppFrontmost = excluded
+        if excluded {
+            KeyboardHook.shared.releaseHeldChordsSerialized()
+            endCapsHold()
+        }
+        FileLog.shared.info("[STATE] Excluded app \(excluded ? "frontmost — auto-paused" : "left — active again")")
+    }
+
+    private func recordActionResult(_ result: ActionResult) {
+        if let output = result.output, !output.isEmpty {
+            showToast(String(output.prefix(300)), isError: !result.ok)
+        }
+        guard let id = result.triggerID else { return }
+        if result.ok {
+            if actionFailures[id] != nil { actionFailures[id] = nil }
+        } else {
+            actionFailures[id] = result.error ?? loc.t("mappings.action_failed")
+        }
+    }
+
+    // MARK: - Leader-key sequences
+
+    var sequenceTimeoutMs: Int { config.appConfig.sequenceTimeoutMs }
+
+    func setSequenceTimeout(_ ms: Int) throws {
+        try config.setSequenceTimeout(ms)
+        applySequenceSettings()
+    }
+
+    private func applySequenceSettings() {
+        KeySequences.shared.setTimeout(ms: config.appConfig.sequenceTimeoutMs)
+        FileLog.shared.info("Sequence timeout applied: \(config.appConfig.sequenceTimeoutMs)ms (\(config.sequences.leaders.count) leader(s))")
+    }
+
     // MARK: - Key remapping (hidutil)
 
     var keyRemaps: [KeyRemap] { config.appConfig.keyRemaps }
@@ -230,25 +776,90 @@ final class AppState: ObservableObject {
         }
     }
 
-    /// (Re)apply the base CapsLock→F18 remap plus the user remaps via hidutil.
+    /// Make a different physical key the layer key (see `LayerKey`), and
+    /// re-apply the remap so it takes effect at once. Rolled back, like a key
+    /// remap, if hidutil rejects it. Throws when a user remap already uses the key.
+    func setLayerKey(_ key: LayerKey) throws {
+        let prev = config.appConfig.layerKey
+        try config.setLayerKey(key)
+        if !applyKeyRemaps() {
+            try? config.setLayerKey(prev)
+            applyKeyRemaps()
+            showToast(loc.t("toast.remap_failed"), isError: true)
+        }
+    }
+
+    /// (Re)apply the base layer-key→F18 remap plus the user remaps via hidutil.
     /// This is the single owner of the live `UserKeyMapping` (KeyboardHook.start
     /// no longer touches it), so a settings edit and launch never clobber one
     /// another. Skipped under -uitest, which must never touch global hidutil state.
     /// Returns whether the apply succeeded (always true under -uitest).
     @discardableResult
     private func applyKeyRemaps() -> Bool {
-        guard !AppEnvironment.isUITest else { return true }
-        let ok = HidUtil.setupRemap(extra: config.appConfig.keyRemaps)
+        let layerKey = config.appConfig.layerKey
+        if layerKey != EngineState.shared.layerKey {
+            EngineState.shared.layerKey = layerKey
+            FileLog.shared.info("Layer key applied: \(layerKey.rawValue)")
+        }
+        // Disabled means CapsLock is native: persist remap edits, apply on enable.
+        guard !AppEnvironment.isUITest, !isDisabled else { return true }
+        let ok = HidUtil.setupRemap(layerKey: layerKey, extra: config.appConfig.keyRemaps)
         if !ok {
             FileLog.shared.warn("hidutil remap apply failed — CapsLock / key remaps may be unreliable.")
         }
+        refreshCapsRemapState()
+        let fallback = HidUtil.needsRawCapsFallback(applied: ok, remapActive: capsRemapActive, layerKey: layerKey)
+        rawCapsFallback = fallback
+        if fallback != EngineState.shared.rawCapsFallback {
+            EngineState.shared.rawCapsFallback = fallback
+            FileLog.shared.warn(fallback ? "CapsLock → F18 remap unavailable; reading raw Caps Lock events instead (Caps latches the layer)."
+                                         : "CapsLock → F18 remap active again; raw Caps Lock fallback off.")
+        }
         return ok
     }
 
+    /// Settings ▸ Permissions ▸ Run Self-Test: run `SelfTest`, then say what
+    /// it found — a toast when all is well, an alert listing fixes otherwise.
+    func runSelfTest() {
+        SelfTest.run { [self] report in
+            guard !report.passed else { return showToast(loc.t("selftest.ok")) }
+            let alert = NSAlert()
+            alert.messageText = loc.t("selftest.title")
+            alert.informativeText = report.findings.map { finding in
+                "• " + loc.t("selftest.\(finding.rawValue)", ["app": report.secureInputApp ?? loc.t("selftest.unknown_app")])
+            }.joined(separator: "\n\n")
+            if report.findings.contains(.accessibilityMissing) {
+                alert.addButton(withTitle: loc.t("selftest.open_accessibility"))
+            }
+            alert.addButton(withTitle: loc.t("selftest.done"))
+            NSApp.activate(ignoringOtherApps: true)
+            if alert.runModal() == .alertFirstButtonReturn && report.findings.contains(.accessibilityMissing) {
+                openAccessibilitySettings()
+            }
+        }
+    }
+
+    /// Read the live remap back from hidutil for the Settings badge and the
+    /// first-run checklist.
+    func refreshCapsRemapState() {
+        guard !AppEnvironment.isUITest else { return }
+        capsRemapActive = HidUtil.isCapsRemapActive(layerKey: config.appConfig.layerKey)
+    }
+
     func toggleAutostart() throws {
-        let next = !autostart
-        try LaunchAtLogin.setEnabled(next)
-        autostart = next
+        try setAutostart(!autostart)
+    }
+
+    func setAutostart(_ enabled: Bool) throws {
+        defer { refreshAutostart() }
+        try LaunchAtLogin.setEnabled(enabled)
+    }
+
+    /// Re-read the login-item state; the user can change it in System Settings
+    /// behind our back.
+    func refreshAutostart() {
+        autostart = LaunchAtLogin.isEnabled
+        autostartNeedsApproval = LaunchAtLogin.needsApproval
     }
 
     private func applyActivationPolicy(hide: Bool) {
@@ -260,18 +871,135 @@ final class AppState: ObservableObject {
     func refreshPermissions() {
         accessibilityGranted = Permissions.isAccessibilityGranted
         permissionsResolved = true
+        refreshCapsRemapState()
+    }
+
+    // MARK: - Onboarding
+
+    func setOnboardingCompleted(_ done: Bool) throws {
+        try config.setOnboardingCompleted(done)
+    }
+
+    /// Installs from before the flag existed already went through setup; if
+    /// Accessibility is granted there's nothing left for the checklist to teach.
+    private func markOnboardingDoneForExistingInstall() {
+        guard !config.appConfig.onboardingCompleted, accessibilityGranted, !AppEnvironment.isUITest else { return }
+        do {
+            try config.setOnboardingCompleted(true)
+        } catch {
+            FileLog.shared.error("Failed to persist onboarding state: \(error)")
+        }
+    }
+
+    private func permissionsChanged(_ statuses: [Permissions.Pane: Permissions.Status]) {
+        if let ax = statuses[.accessibility] {
+            accessibilityGranted = ax == .granted
+            // Granted after launch: get the tap in without the grant-then-relaunch dance.
+            if ax == .granted && !isDisabled && !AppEnvironment.isUITest {
+                KeyboardHook.shared.accessibilityGranted()
+            }
+        }
+        permissionsResolved = true
+    }
+
+    /// "Grant" in Settings and the tray: register with TCC (so the app is listed)
+    /// and jump straight to Privacy & Security ▸ Accessibility.
+    func openAccessibilitySettings() {
+        Permissions.promptAccessibility()
+        Permissions.openPrivacyPane(.accessibility)
     }
 
     // MARK: - Mapping operations (wrap ConfigStore, surface errors as messages)
 
-    func upsertMapping(trigger: Trigger, actionId: String?, inlineAction: ActionConfig? = nil, bindings: [MappingBinding] = []) throws {
-        try config.upsert(trigger: trigger, actionId: actionId, inlineAction: inlineAction, bindings: bindings)
+    func upsertMapping(trigger: Trigger, actionId: String?, inlineAction: ActionConfig? = nil, bindings: [MappingBinding] = [],
+                       repeatMode: RepeatMode = .auto, label: String? = nil, note: String? = nil,
+                       enabled: Bool = true) throws {
+        try config.upsert(trigger: trigger, actionId: actionId, inlineAction: inlineAction, bindings: bindings,
+                          repeatMode: repeatMode, label: label, note: note, enabled: enabled)
+    }
+
+    func setMappingEnabled(_ trigger: Trigger, _ enabled: Bool) throws {
+        try config.setEnabled(trigger: trigger, enabled: enabled)
     }
 
     func removeMapping(_ trigger: Trigger) {
         config.remove(trigger: trigger)
     }
 
+    /// Tray "Release Stuck Modifiers": the same panic release as Caps+Esc.
+    func releaseAllModifiers() {
+        // Chords through the tap thread, like every off-thread release, so this
+        // can't race a chord key-down in flight; the modifier key-ups are safe here.
+        KeyboardHook.shared.releaseHeldChordsSerialized()
+        ActionExecutor.releaseModifierKeys()
+    }
+
+    func resetMapping(_ trigger: Trigger) {
+        config.resetMapping(trigger: trigger)
+    }
+
+    /// Settings ▸ Default jump. Only the stored default changes; existing
+    /// bindings keep their counts until `resetJumpMappings`.
+    func setDefaultJumpCount(_ count: Int, for direction: JumpDirection) throws {
+        try config.setDefaultJumpCount(count, for: direction)
+    }
+
+    func resetJumpMappings() {
+        config.resetJumpMappings()
+    }
+
+    /// Tray "Reset Mappings to Defaults…". Confirms first; the reset itself is
+    /// undoable from the Mappings page.
+    func confirmResetAllMappings() {
+        let alert = NSAlert()
+        alert.messageText = loc.t("mappings.reset_all_title")
+        alert.informativeText = loc.t("mappings.reset_all_prompt")
+        alert.addButton(withTitle: loc.t("mappings.reset_all_confirm"))
+        alert.addButton(withTitle: loc.t("update.cancel"))
+        NSApp.activate(ignoringOtherApps: true)
+        guard alert.runModal() == .alertFirstButtonReturn else { return }
+        config.resetAllMappings()
+        showToast(loc.t("toast.mappings_reset"))
+    }
+
+    /// Ask for a name and save the current mappings as a preset (tray ▸ Presets).
+    func promptSavePreset() {
+        let alert = NSAlert()
+        alert.messageText = loc.t("presets.save_title")
+        alert.informativeText = loc.t("presets.save_prompt")
+        let field = NSTextField(frame: NSRect(x: 0, y: 0, width: 240, height: 24))
+        alert.accessoryView = field
+        alert.addButton(withTitle: loc.t("presets.save_confirm"))
+        alert.addButton(withTitle: loc.t("update.cancel"))
+        alert.window.initialFirstResponder = field
+        NSApp.activate(ignoringOtherApps: true)
+        guard alert.runModal() == .alertFirstButtonReturn else { return }
+        let name = field.stringValue.trimmingCharacters(in: .whitespacesAndNewlines)
+        do {
+            try config.savePreset(name)
+            showToast(loc.t("toast.preset_saved", ["name": name]))
+        } catch {
+            let msg = (error as? ConfigError)?.errorDescription ?? error.localizedDescription
+            showToast(loc.t("toast.preset_failed", ["error": msg]), isError: true)
+        }
+    }
+
+    func loadPreset(_ name: String) {
+        do {
+            try config.loadPreset(name)
+            showToast(loc.t("toast.preset_loaded", ["name": name]))
+        } catch {
+            let msg = (error as? ConfigError)?.errorDescription ?? error.localizedDescription
+            showToast(loc.t("toast.preset_failed", ["error": msg]), isError: true)
+        }
+    }
+
+    @discardableResult
+    func undoMappings() -> Bool { config.undoMappings() }
+
+    @discardableResult
+    func redoMappings() -> Bool { config.redoMappings() }
+
     // MARK: - Custom action operations
 
     @discardableResult
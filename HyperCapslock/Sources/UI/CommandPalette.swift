@@ -0,0 +1,245 @@
+import AppKit
+import SwiftUI
+
+/// Caps + ; (`builtin.show_palette`): a searchable overlay listing every
+/// binding as it behaves in the frontmost app, for finding a chord in a dense
+/// keymap or running an action without remembering it. Type to filter, ↑/↓
+/// to pick, Return to run, Escape (or clicking elsewhere) to close.
+///
+/// The panel is non-activating, so the user's app stays active behind it and
+/// gets the chosen action's keys once the panel is gone.
+@MainActor
+final class CommandPalette: NSObject, NSWindowDelegate {
+  static let shared = CommandPalette()
+
+  /// One row: a mapping and the action it runs in the app the palette was
+  /// opened over.
+  struct Item: Identifiable, Equatable {
+    let id: String
+    let trigger: Trigger
+    let triggerLabel: String
+    let title: String
+    let category: String
+    let symbol: String
+    let action: ActionConfig
+  }
+
+  private static let panelSize = NSSize(width: 560, height: 380)
+  /// Time for the key window to fall back to the user's app before the
+  /// action's keys are posted.
+  private static let runDelay = 0.08
+
+  private var panel: NSPanel?
+  private let model = CommandPaletteModel()
+
+  /// The rows for `mappings`: only those that do something when tapped —
+  /// not swallowed or passed through here, not a held modifier, and not the
+  /// palette itself. A mapping's own label names it, unless a per-app rule
+  /// swapped in a different action here.
+  static func items(_ mappings: [ActionExecutor.EffectiveMapping], ctx: RuntimeContext,
+                    loc: LocalizationManager) -> [Item] {
+    mappings.compactMap { m in
+      guard let action = m.action, !m.passthrough else { return nil }
+      switch action {
+      case .appControl(.showPalette), .modifierKey, .independent(.noop): return nil
+      default: break
+      }
+      let presentation = actionPresentation(action, loc)
+      return Item(id: triggerUniqueID(m.entry.trigger), trigger: m.entry.trigger,
+                  triggerLabel: ConfigStore.triggerLabel(m.entry.trigger),
+                  title: (m.viaAppRule ? nil : m.entry.label) ?? actionName(m, ctx: ctx, loc: loc) ?? presentation.value,
+                  category: presentation.category, symbol: presentation.symbol, action: action)
+    }
+  }
+
+  /// Every space-separated word of `query` must appear (case-insensitively)
+  /// in the chord, the action's name or its group.
+  static func filter(_ items: [Item], query: String) -> [Item] {
+    let words = query.lowercased().split(separator: " ")
+    guard !words.isEmpty else { return items }
+    return items.filter { item in
+      let haystack = "\(item.triggerLabel) \(item.title) \(item.category)".lowercased()
+      return words.allSatisfy { haystack.contains($0) }
+    }
+  }
+
+  /// The library name of the action `m` runs (the matching per-app rule's,
+  /// or the mapping's own), or nil for an inline action.
+  private static func actionName(_ m: ActionExecutor.EffectiveMapping, ctx: RuntimeContext,
+                                 loc: LocalizationManager) -> String? {
+    let id = m.viaAppRule
+      ? m.entry.bindings.first { $0.matches(ctx) && ActionsRegistry.shared.resolve($0) != nil }?.actionId
+      : m.entry.actionId
+    guard let id, let action = ActionsRegistry.shared.action(byID: id) else { return nil }
+    return action.nameKey.map { loc.t($0) } ?? action.name
+  }
+
+  func show() {
+    let ctx = ActionExecutor.currentContext()
+    model.items = Self.items(ActionExecutor.effectiveMappings(ctx), ctx: ctx, loc: LocalizationManager.shared)
+    model.query = ""
+    model.selection = 0
+    let panel = self.panel ?? makePanel()
+    self.panel = panel
+    position(panel)
+    panel.makeKeyAndOrderFront(nil)
+    FileLog.shared.info("Command palette shown: \(model.items.count) bindings for \(ctx.frontmostBundleID ?? "no app")")
+  }
+
+  func dismiss() {
+    panel?.orderOut(nil)
+  }
+
+  private func run(_ item: Item) {
+    dismiss()
+    FileLog.shared.info("Command palette: running \(item.triggerLabel) → \(describeAction(item.action))")
+    DispatchQueue.global(qos: .userInitiated).asyncAfter(deadline: .now() + Self.runDelay) {
+      ActionExecutor.execute(item.action, keyDown: true, activeModifiers: [], trigger: item.trigger)
+      ActionExecutor.execute(item.action, keyDown: false, activeModifiers: [], trigger: item.trigger)
+    }
+  }
+
+  func windowDidResignKey(_ notification: Notification) {
+    dismiss()
+  }
+
+  /// Centered horizontally, in the upper part of the screen under the mouse.
+  private func position(_ panel: NSPanel) {
+    let mouse = NSEvent.mouseLocation
+    guard let screen = NSScreen.screens.first(where: { $0.frame.contains(mouse) }) ?? NSScreen.main else { return }
+    let visible = screen.visibleFrame
+    let origin = NSPoint(x: visible.midX - Self.panelSize.width / 2,
+                         y: visible.minY + visible.height * 0.66 - Self.panelSize.height / 2)
+    panel.setFrame(NSRect(origin: origin, size: Self.panelSize), display: false)
+  }
+
+  private func makePanel() -> NSPanel {
+    let panel = PalettePanel(contentRect: NSRect(origin: .zero, size: Self.panelSize),
+                             styleMask: [.borderless, .nonactivatingPanel, .fullSizeContentView],
+                             backing: .buffered, defer: false)
+    panel.isOpaque = false
+    panel.backgroundColor = .clear
+    panel.hasShadow = true
+    panel.level = .modalPanel
+    panel.isFloatingPanel = true
+    panel.hidesOnDeactivate = false
+    panel.isReleasedWhenClosed = false
+    panel.collectionBehavior = [.canJoinAllSpaces, .fullScreenAuxiliary, .ignoresCycle]
+    panel.delegate = self
+    panel.onCancel = { [weak self] in self?.dismiss() }
+    panel.contentView = NSHostingView(rootView: CommandPaletteView(
+      model: model, onRun: { [weak self] in self?.run($0) }, onCancel: { [weak self] in self?.dismiss() }))
+    return panel
+  }
+}
+
+/// A borderless panel that can still take the keyboard, so the search field
+/// gets typing; Escape anywhere in it closes the palette.
+private final class PalettePanel: NSPanel {
+  var onCancel: (() -> Void)?
+
+  override var canBecomeKey: Bool { true }
+
+  override func cancelOperation(_ sender: Any?) {
+    onCancel?()
+  }
+}
+
+@MainActor
+final class CommandPaletteModel: ObservableObject {
+  @Published var items: [CommandPalette.Item] = []
+  @Published var query = "" { didSet { selection = 0 } }
+  /// Index into `visible`.
+  @Published var selection = 0
+
+  var visible: [CommandPalette.Item] { CommandPalette.filter(items, query: query) }
+
+  var selectedItem: CommandPalette.Item? {
+    let rows = visible
+    return rows.indices.contains(selection) ? rows[selection] : nil
+  }
+
+  /// Move the highlight by `delta`, wrapping at either end.
+  func move(_ delta: Int) {
+    let count = visible.count
+    guard count > 0 else { return }
+    selection = (selection + delta + count) % count
+  }
+}
+
+struct CommandPaletteView: View {
+  @ObservedObject var model: CommandPaletteModel
+  @ObservedObject private var loc = LocalizationManager.shared
+  let onRun: (CommandPalette.Item) -> Void
+  let onCancel: () -> Void
+  @FocusState private var searchFocused: Bool
+
+  var body: some View {
+    let rows = model.visible
+    VStack(spacing: 0) {
+      HStack(spacing: 10) {
+        Image(systemName: "magnifyingglass").foregroundStyle(.secondary)
+        TextField(loc.t("palette.search"), text: $model.query)
+          .textFieldStyle(.plain)
+          .font(.system(size: 17))
+          .focused($searchFocused)
+          .onKeyPress(.downArrow) { model.move(1); return .handled }
+          .onKeyPress(.upArrow) { model.move(-1); return .handled }
+          .onKeyPress(.return) {
+            if let item = model.selectedItem { onRun(item) }
+            return .handled
+          }
+          .onKeyPress(.escape) { onCancel(); return .handled }
+          .accessibilityIdentifier("palette.search")
+      }
+      .padding(14)
+      Divider()
+      if rows.isEmpty {
+        Text(loc.t("palette.empty"))
+          .foregroundStyle(.secondary)
+          .frame(maxWidth: .infinity, maxHeight: .infinity)
+      } else {
+        ScrollViewReader { proxy in
+          ScrollView {
+            LazyVStack(spacing: 2) {
+              ForEach(Array(rows.enumerated()), id: \.element.id) { index, item in
+                row(item, selected: index == model.selection)
+                  .id(item.id)
+                  .contentShape(Rectangle())
+                  .onTapGesture { onRun(item) }
+              }
+            }
+            .padding(6)
+          }
+          .onChange(of: model.selection) { _, index in
+            if rows.indices.contains(index) { proxy.scrollTo(rows[index].id) }
+          }
+        }
+      }
+    }
+    .frame(maxWidth: .infinity, maxHeight: .infinity)
+    .background(.regularMaterial, in: RoundedRectangle(cornerRadius: 12))
+    .overlay(RoundedRectangle(cornerRadius: 12).stroke(Color.primary.opacity(0.12), lineWidth: 1))
+    .onAppear { searchFocused = true }
+  }
+
+  private func row(_ item: CommandPalette.Item, selected: Bool) -> some View {
+    HStack(spacing: 10) {
+      Image(systemName: item.symbol)
+        .frame(width: 20)
+        .foregroundStyle(selected ? Color.white : Color.secondary)
+      VStack(alignment: .leading, spacing: 1) {
+        Text(item.title).lineLimit(1)
+        Text(item.category).font(.caption).foregroundStyle(selected ? Color.white.opacity(0.8) : Color.secondary)
+      }
+      Spacer()
+      Text(item.triggerLabel)
+        .font(.system(.callout, design: .monospaced))
+        .foregroundStyle(selected ? Color.white : Color.secondary)
+    }
+    .padding(.horizontal, 10)
+    .padding(.vertical, 6)
+    .foregroundStyle(selected ? Color.white : Color.primary)
+    .background(RoundedRectangle(cornerRadius: 7).fill(selected ? Color.accentColor : Color.clear))
+  }
+}
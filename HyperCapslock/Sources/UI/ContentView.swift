@@ -26,7 +26,12 @@ func triggerUniqueID(_ t: Trigger) -> String {
     case .singleTapHyper: return "single_tap_hyper"
     case .doubleTapHyper: return "double_tap_hyper"
     case .doubleTapModifier(let m): return "dtm:\(m.rawValue)"
-    case .hyperPlusKey(let key, let withShift): return "hyper:\(key):\(withShift ? "s" : "n")"
+    case .hyperPlusKey(let key, let withShift, let ctrl, let alt, let cmd):
+        // Plain chords keep the original two-part id, so their usage history carries over.
+        let base = "hyper:\(key):\(withShift ? "s" : "n")"
+        return ctrl || alt || cmd ? base + ":\(ctrl ? "c" : "")\(alt ? "a" : "")\(cmd ? "m" : "")" : base
+    case .hotkey(let key, let ctrl, let alt, let cmd, let shift):
+        return "hotkey:\(key):\(ctrl ? "c" : "")\(alt ? "a" : "")\(cmd ? "m" : "")\(shift ? "s" : "")"
     }
 }
 
@@ -141,7 +146,7 @@ struct ContentView: View {
     private var statusFooter: some View {
         HStack(spacing: 7) {
             StatusDot(running: app.isRunning)
-            Text(app.isRunning ? loc.t("status.running") : loc.t("status.paused"))
+            Text(app.isRunning ? loc.t("status.running") : loc.t(app.isDisabled ? "status.disabled" : "status.paused"))
                 .font(.system(size: 11)).foregroundColor(.secondary)
             Spacer()
         }
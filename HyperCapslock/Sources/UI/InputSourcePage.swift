@@ -24,6 +24,12 @@ struct InputSourcePage: View {
 
     var body: some View {
         Form {
+            Section {
+                LabeledContent(loc.t("is.current")) {
+                    Text(app.currentInputSource?.name ?? "—")
+                        .accessibilityIdentifier("is.current")
+                }
+            }
             Section {
                 Text(loc.t("is.fix_desc"))
                     .font(.callout).foregroundStyle(.secondary)
@@ -6,7 +6,7 @@ import SwiftUI
 ///
 /// A stored id that's no longer installed is preserved as a selectable
 /// "⚠️ <id> (unavailable)" row, so editing a mapping for a removed source never
-/// silently drops it.
+/// silently drops it; a caption under the picker says why it won't switch.
 struct InputSourcePicker: View {
     @EnvironmentObject var loc: LocalizationManager
     let title: String
@@ -16,8 +16,29 @@ struct InputSourcePicker: View {
     var defaultsToFirst: Bool = true
 
     @State private var sources: [InputSourceFix.AvailableSource] = []
+    @State private var availability: InputSourceFix.SourceAvailability = .available
 
     var body: some View {
+        Group {
+            picker
+            switch availability {
+            case .available:
+                EmptyView()
+            case .installedNotEnabled:
+                Text(loc.t("is.source_not_enabled_warning")).font(.caption).foregroundStyle(.orange)
+            case .missing:
+                Text(loc.t("is.source_missing_warning")).font(.caption).foregroundStyle(.orange)
+            }
+        }
+        .onAppear {
+            sources = InputSourceFix.availableSources()
+            if defaultsToFirst, sourceID.isEmpty { sourceID = sources.first?.id ?? "" }
+            checkAvailability()
+        }
+        .onChange(of: sourceID) { _, _ in checkAvailability() }
+    }
+
+    private var picker: some View {
         Picker(title, selection: $sourceID) {
             if !sourceID.isEmpty, !sources.contains(where: { $0.id == sourceID }) {
                 Label("\(sourceID) \(loc.t("is.source_unavailable_suffix"))",
@@ -34,9 +55,9 @@ struct InputSourcePicker: View {
                 .tag(src.id)
             }
         }
-        .onAppear {
-            sources = InputSourceFix.availableSources()
-            if defaultsToFirst, sourceID.isEmpty { sourceID = sources.first?.id ?? "" }
-        }
+    }
+
+    private func checkAvailability() {
+        availability = sourceID.isEmpty ? .available : InputSourceFix.availability(ofSourceID: sourceID)
     }
 }
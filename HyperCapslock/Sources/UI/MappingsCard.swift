@@ -23,7 +23,9 @@ private func triggerSortKey(_ t: Trigger) -> String {
     case .singleTapHyper: return "0:single"
     case .doubleTapHyper: return "0:double"
     case .doubleTapModifier(let m): return "0:modifier:\(m.rawValue)"
-    case .hyperPlusKey(let key, let withShift): return "1:\(String(format: "%04d", key)):\(withShift ? "1" : "0")"
+    case .hyperPlusKey(let key, let withShift, _, _, _):
+        return "1:\(String(format: "%04d", key)):\(withShift ? "1" : "0"):" + triggerUniqueID(t)
+    case .hotkey: return "2:" + triggerUniqueID(t)
     }
 }
 
@@ -59,6 +61,15 @@ func mappingActionDisplay(_ entry: ActionMappingEntry, _ loc: LocalizationManage
     return actionRefDisplay(ref.actionId, ref.inline, loc, availableInputSources: availableInputSources)
 }
 
+/// The input source an entry's representative action switches to, if it's an
+/// input-source mapping.
+@MainActor
+func mappingInputSourceID(_ entry: ActionMappingEntry) -> String? {
+    let ref = representativeActionRef(entry)
+    guard case .inputSource(let id)? = ActionsRegistry.shared.resolve(actionId: ref.actionId, inline: ref.inline) else { return nil }
+    return id
+}
+
 @MainActor
 private func actionRefDisplay(_ actionId: String?, _ inlineAction: ActionConfig?, _ loc: LocalizationManager,
                               availableInputSources: [String: InputSourceFix.AvailableSource]) -> ActionDisplay {
@@ -103,20 +114,44 @@ func actionSymbol(_ config: ActionConfig) -> String {
         case .up: return "arrow.up"; case .down: return "arrow.down"
         case .wordForward: return "arrow.right.to.line"; case .wordBack: return "arrow.left.to.line"
         case .home: return "arrow.up.left"; case .end: return "arrow.down.right"
+        case .docStart: return "arrow.up.to.line"; case .docEnd: return "arrow.down.to.line"
         }
-    case .jump(let dir, _): return dir == .up ? "chevron.up.2" : "chevron.down.2"
+    case .jump(let dir, _, let unit):
+        if unit == .page { return dir == .up ? "arrow.up.doc" : "arrow.down.doc" }
+        if unit == .paragraph { return "paragraphsign" }
+        return dir == .up ? "chevron.up.2" : "chevron.down.2"
     case .independent(let a):
         switch a {
         case .backspace: return "delete.left"; case .nextLine: return "return"
+        case .openLineAbove: return "arrow.turn.left.up"
         case .insertQuotes: return "quote.opening"; case .toggleCapsLock: return "capslock"
         case .switchInputSource: return "globe"
         case .noop: return "nosign"
+        case .releaseModifiers: return "escape"
+        case .repeatLast: return "arrow.counterclockwise"
+        case .stickyShift: return "shift"
         }
     case .inputSource: return "globe"
     case .command: return "terminal"
+    case .script(let lang, _): return lang == .shell ? "terminal" : "applescript"
+    case .notify: return "bell"
+    case .transformSelection(let op):
+        switch op {
+        case .uppercase: return "textformat.size.larger"; case .lowercase: return "textformat.size.smaller"
+        case .trim: return "scissors"
+        }
     case .keyCombo: return "keyboard"
     case .openApp: return "arrow.up.forward.app"
     case .modifierKey: return "hand.tap"
+    case .wrapPair: return "parentheses"
+    case .goTo: return "number"
+    case .insertDateTime: return "calendar.badge.clock"
+    case .appControl(let cmd):
+        switch cmd {
+        case .togglePause: return "playpause"; case .reloadConfig: return "arrow.clockwise"
+        case .showWindow: return "macwindow"; case .quit: return "power"
+        case .showPalette: return "list.bullet.rectangle"
+        }
     }
 }
 
@@ -127,14 +162,21 @@ func actionPresentation(_ action: ActionConfig, _ loc: LocalizationManager) -> A
     switch action {
     case .directional(let a):
         return ActionPresentation(category: loc.t("group.directional"), value: loc.t("action.\(a.rawValue)"), symbol: actionSymbol(action))
-    case .jump(let direction, let count):
-        return ActionPresentation(category: loc.t("group.jump"), value: "\(loc.t("action.\(direction.rawValue)")) ×\(count)", symbol: actionSymbol(action))
+    case .jump(let direction, let count, let unit):
+        let step = unit == .line ? "" : " \(loc.t("actions.jump_unit.\(unit.rawValue)"))"
+        return ActionPresentation(category: loc.t("group.jump"), value: "\(loc.t("action.\(direction.rawValue)"))\(step) ×\(count)", symbol: actionSymbol(action))
     case .independent(let a):
         return ActionPresentation(category: loc.t("group.independent"), value: loc.t("action.\(a.rawValue)"), symbol: actionSymbol(action))
     case .inputSource(let id):
         return ActionPresentation(category: loc.t("group.input_source"), value: id, symbol: actionSymbol(action))
-    case .command(let cmd):
+    case .command(let cmd, _):
         return ActionPresentation(category: loc.t("group.command"), value: cmd, symbol: actionSymbol(action))
+    case .script(let lang, let source):
+        return ActionPresentation(category: loc.t("script.\(lang.rawValue)"), value: source, symbol: actionSymbol(action))
+    case .notify(let title, let body):
+        return ActionPresentation(category: loc.t("group.notify"), value: title.isEmpty ? body : title, symbol: actionSymbol(action))
+    case .transformSelection(let op):
+        return ActionPresentation(category: loc.t("group.selection"), value: loc.t("action.\(op.rawValue)"), symbol: actionSymbol(action))
     case .keyCombo(let key, let ctrl, let alt, let cmd, let shift):
         var parts: [String] = []
         if ctrl { parts.append("Ctrl") }; if alt { parts.append("Option") }
@@ -146,6 +188,17 @@ func actionPresentation(_ action: ActionConfig, _ loc: LocalizationManager) -> A
     case .modifierKey(let m):
         return ActionPresentation(category: loc.t("group.hold_modifier"),
                                   value: modifierHudLabel(m), symbol: actionSymbol(action))
+    case .wrapPair(let open, let close, let spaces):
+        let pad = String(repeating: " ", count: spaces)
+        return ActionPresentation(category: loc.t("group.wrap_pair"), value: "\(open)\(pad)…\(pad)\(close)", symbol: actionSymbol(action))
+    case .appControl(let cmd):
+        return ActionPresentation(category: loc.t("group.app_control"), value: loc.t("action.\(cmd.rawValue)"), symbol: actionSymbol(action))
+    case .goTo(let line, let column, _):
+        return ActionPresentation(category: loc.t("group.go_to"),
+                                  value: ActionExecutor.goToText(line: line, column: column) ?? loc.t("actions.go_to_count"),
+                                  symbol: actionSymbol(action))
+    case .insertDateTime(let format):
+        return ActionPresentation(category: loc.t("group.insert_date_time"), value: format, symbol: actionSymbol(action))
     }
 }
 
@@ -191,6 +244,16 @@ struct MappingsPage: View {
                     Button { importReleaseConfig() } label: { Image(systemName: "arrow.down.doc.fill") }
                         .help("Import release config (debug)")
                     #endif
+                    Button { app.undoMappings() } label: { Image(systemName: "arrow.uturn.backward") }
+                        .help(loc.t("mappings.undo"))
+                        .keyboardShortcut("z", modifiers: .command)
+                        .disabled(!config.canUndoMappings)
+                        .accessibilityIdentifier("mappings.undo")
+                    Button { app.redoMappings() } label: { Image(systemName: "arrow.uturn.forward") }
+                        .help(loc.t("mappings.redo"))
+                        .keyboardShortcut("z", modifiers: [.command, .shift])
+                        .disabled(!config.canRedoMappings)
+                        .accessibilityIdentifier("mappings.redo")
                     Button { exportConfig() } label: { Image(systemName: "square.and.arrow.up") }.help(loc.t("config.export"))
                     Button { sheet = .add } label: { Image(systemName: "plus") }
                         .help(loc.t("mappings.add"))
@@ -208,14 +271,15 @@ struct MappingsPage: View {
         switch config.appConfig.mappingsViewStyle {
         case .grouped:
             MappingsGroupedStyleView(entries: sorted, availableInputSources: availableInputSources,
-                                     usageTotals: usageTotals,
+                                     usageTotals: usageTotals, failures: app.actionFailures,
+                                     activeInputSourceID: app.currentInputSource?.id,
                                      onEdit: { sheet = .edit($0) }, onDelete: deleteEntry)
         case .keyboard:
             MappingsKeyboardStyleView(entries: sorted, availableInputSources: availableInputSources,
                                       onEdit: { sheet = .edit($0) },
                                       onAddTrigger: { sheet = .addForTrigger($0) },
                                       onDelete: deleteEntry,
-                                      usageTotals: usageTotals)
+                                      usageTotals: usageTotals, failures: app.actionFailures)
         }
     }
 
@@ -283,9 +347,22 @@ struct MappingsPage: View {
         }
         panel.begin { resp in
             guard resp == .OK, let url = panel.url else { return }
+            // Validate before asking, and show what the import would change.
+            let preview: [ActionMappingEntry]
+            do {
+                let content = try String(contentsOf: url, encoding: .utf8)
+                preview = try ConfigStore.previewMappings(yaml: content)
+            } catch {
+                let msg = (error as? ConfigError)?.errorDescription ?? error.localizedDescription
+                app.showToast(loc.t("toast.config_import_failed", ["error": msg]), isError: true)
+                return
+            }
+            let diff = ConfigStore.mappingsDiff(from: config.mappings, to: preview)
             let alert = NSAlert()
             alert.messageText = loc.t("config.import_title")
-            alert.informativeText = loc.t("config.import_prompt")
+            alert.informativeText = loc.t("config.import_diff", ["added": String(diff.added), "changed": String(diff.changed),
+                                                                 "removed": String(diff.removed)])
+                + "\n\n" + loc.t("config.import_prompt")
             alert.addButton(withTitle: loc.t("config.import_confirm"))
             alert.addButton(withTitle: loc.t("update.cancel"))
             guard alert.runModal() == .alertFirstButtonReturn else { return }
@@ -15,20 +15,22 @@ import AppKit
 /// the palette from the design mockup (docs/design/mappings-mockups.html).
 func actionCategoryColor(_ config: ActionConfig) -> Color {
     switch config {
-    case .directional, .jump:
+    case .directional, .jump, .goTo:
         return Color(red: 0.23, green: 0.61, blue: 1.00)      // navigation — blue
     case .independent(let a):
         switch a {
-        case .toggleCapsLock, .noop, .switchInputSource:
+        case .toggleCapsLock, .noop, .switchInputSource, .releaseModifiers, .stickyShift:
             return Color(red: 0.54, green: 0.58, blue: 0.65)  // system — muted
         default:
             return Color(red: 0.96, green: 0.65, blue: 0.14)  // editing — amber
         }
     case .inputSource: return Color(red: 0.69, green: 0.49, blue: 1.00)  // purple
     case .keyCombo:     return Color(red: 0.96, green: 0.45, blue: 0.71)  // pink
-    case .command:      return Color(red: 0.20, green: 0.83, blue: 0.60)  // green
+    case .command, .script: return Color(red: 0.20, green: 0.83, blue: 0.60)  // green
     case .openApp:      return Color(red: 0.13, green: 0.83, blue: 0.93)  // cyan
     case .modifierKey:  return Color(red: 0.98, green: 0.44, blue: 0.52)  // rose
+    case .wrapPair, .transformSelection, .insertDateTime: return Color(red: 0.96, green: 0.65, blue: 0.14)  // editing — amber
+    case .appControl, .notify: return Color(red: 0.54, green: 0.58, blue: 0.65)  // system — muted
     }
 }
 
@@ -42,6 +44,8 @@ struct MappingsKeyboardStyleView: View {
     let onDelete: (ActionMappingEntry) -> Void
     /// triggerID → all-time press count; empty when inline counts are disabled.
     var usageTotals: [String: Int] = [:]
+    /// triggerID → last failure (see `AppState.actionFailures`).
+    var failures: [String: String] = [:]
     @EnvironmentObject var loc: LocalizationManager
 
     @State private var layerShift = false
@@ -50,16 +54,17 @@ struct MappingsKeyboardStyleView: View {
     private var mappedByKeycode: [UInt16: ActionMappingEntry] {
         var m: [UInt16: ActionMappingEntry] = [:]
         for e in entries {
-            if case .hyperPlusKey(let key, let withShift) = e.trigger, withShift == layerShift {
+            if case .hyperPlusKey(let key, let withShift, false, false, false) = e.trigger, withShift == layerShift {
                 m[key] = e
             }
         }
         return m
     }
 
-    /// Triggers with no physical key — listed below the keyboard.
+    /// Triggers with no key on the map (taps, hotkeys, and Caps chords that
+    /// also need Ctrl/Option/Cmd) — listed below the keyboard.
     private var specialEntries: [ActionMappingEntry] {
-        entries.filter { if case .hyperPlusKey = $0.trigger { return false }; return true }
+        entries.filter { if case .hyperPlusKey(_, _, false, false, false) = $0.trigger { return false }; return true }
     }
 
     var body: some View {
@@ -82,6 +87,7 @@ struct MappingsKeyboardStyleView: View {
                 MagicKeyboardView(layerShift: layerShift,
                                   mapped: mappedByKeycode,
                                   availableInputSources: availableInputSources,
+                                  failures: failures,
                                   onEdit: onEdit, onAddTrigger: onAddTrigger)
 
                 if !specialEntries.isEmpty { otherTriggers }
@@ -104,6 +110,7 @@ struct MappingsKeyboardStyleView: View {
                     MappingRow(entry: e, availableInputSources: availableInputSources,
                                keycapStyle: .raised,
                                usageCount: usageTotals[triggerUniqueID(e.trigger)],
+                               failure: failures[triggerUniqueID(e.trigger)],
                                onEdit: { onEdit(e) }, onDelete: { onDelete(e) })
                         .padding(.horizontal, 14).padding(.vertical, 8)
                 }
@@ -148,6 +155,7 @@ private struct MagicKeyboardView: View {
     let layerShift: Bool
     let mapped: [UInt16: ActionMappingEntry]
     let availableInputSources: [String: InputSourceFix.AvailableSource]
+    let failures: [String: String]
     let onEdit: (ActionMappingEntry) -> Void
     let onAddTrigger: (Trigger) -> Void
     @EnvironmentObject var loc: LocalizationManager
@@ -286,6 +294,7 @@ private struct MagicKeyboardView: View {
         let info = mapInfo(key)
         return cap(for: key, info: info)
             .frame(maxWidth: .infinity, maxHeight: .infinity)
+            .overlay(alignment: .topTrailing) { failureDot(info) }
             .padding(gap / 2)
             .frame(width: key.units * colW, height: rowH)
             .contentShape(Rectangle())
@@ -295,6 +304,15 @@ private struct MagicKeyboardView: View {
 
     private struct MapInfo { var entry: ActionMappingEntry?; var cfg: ActionConfig? }
 
+    /// Red dot on a key whose action failed on its last run (the error itself
+    /// is in the grouped view's row tooltip).
+    @ViewBuilder private func failureDot(_ info: MapInfo) -> some View {
+        if let entry = info.entry, failures[triggerUniqueID(entry.trigger)] != nil {
+            Circle().fill(.red).frame(width: 6, height: 6).padding(4)
+                .accessibilityIdentifier("mapping.kb.failure.\(triggerUniqueID(entry.trigger))")
+        }
+    }
+
     private func mapInfo(_ key: KKey) -> MapInfo {
         guard key.role == .normal, let js = key.js, let entry = mapped[js] else { return MapInfo() }
         // Tint by the action the key is *represented* by — for a noop-default key
@@ -337,6 +355,7 @@ private struct MagicKeyboardView: View {
         let info = mapInfo(key)
         return cap(for: key, info: info)
             .frame(maxWidth: .infinity, maxHeight: .infinity)
+            .overlay(alignment: .topTrailing) { failureDot(info) }
             .padding(gap / 2)
             .contentShape(Rectangle())
             .onTapGesture { tap(key, info) }
@@ -382,7 +401,10 @@ private struct MagicKeyboardView: View {
     private func cap(for key: KKey, info: MapInfo) -> some View {
         let hyper = key.role == .hyper
         let tint: Color? = hyper ? Color(red: 0.05, green: 0.52, blue: 1.0)
-                                 : info.entry.map { _ in info.cfg.map(actionCategoryColor) ?? .orange }
+                                 : info.entry.map { e in
+                                     // Switched off: grey, so it reads as neither mapped nor broken.
+                                     e.enabled ? info.cfg.map(actionCategoryColor) ?? .orange : .gray
+                                 }
         let labelText = hyper ? "caps" : key.label
         let radius = max(5, colW * 0.16)
 
@@ -125,9 +125,18 @@ struct TriggerChips: View {
                 cap("Caps", modifier: true); times; cap("2")
             case .doubleTapModifier(let m):
                 cap(modifierGlyph(m), modifier: true); times; cap("2")
-            case .hyperPlusKey(let key, let withShift):
+            case .hyperPlusKey(let key, let withShift, let ctrl, let alt, let cmd):
                 cap("Caps", modifier: true); plus
+                if ctrl { cap("⌃", modifier: true); plus }
+                if alt { cap("⌥", modifier: true); plus }
                 if withShift { cap("Shift", modifier: true); plus }
+                if cmd { cap("⌘", modifier: true); plus }
+                cap(keyCodeDisplay(key))
+            case .hotkey(let key, let ctrl, let alt, let cmd, let shift):
+                if ctrl { cap("⌃", modifier: true); plus }
+                if alt { cap("⌥", modifier: true); plus }
+                if shift { cap("⇧", modifier: true); plus }
+                if cmd { cap("⌘", modifier: true); plus }
                 cap(keyCodeDisplay(key))
             }
         }
@@ -145,7 +154,7 @@ struct TriggerChips: View {
 /// Buckets a trigger into one of the grouped-style sections. Declaration order
 /// is section order.
 enum TriggerCategory: CaseIterable {
-    case capsKey, capsShiftKey, singleTap, doubleTap, doubleTapModifier
+    case capsKey, capsShiftKey, singleTap, doubleTap, doubleTapModifier, hotkey
 
     var nameKey: String {
         switch self {
@@ -154,16 +163,18 @@ enum TriggerCategory: CaseIterable {
         case .singleTap:         return "mappings.group.single_tap"
         case .doubleTap:         return "mappings.group.double_tap"
         case .doubleTapModifier: return "mappings.group.double_tap_modifier"
+        case .hotkey:            return "mappings.group.hotkey"
         }
     }
 }
 
 func triggerCategory(_ t: Trigger) -> TriggerCategory {
     switch t {
-    case .hyperPlusKey(_, let withShift): return withShift ? .capsShiftKey : .capsKey
+    case .hyperPlusKey(_, let withShift, _, _, _): return withShift ? .capsShiftKey : .capsKey
     case .singleTapHyper:                 return .singleTap
     case .doubleTapHyper:                 return .doubleTap
     case .doubleTapModifier:              return .doubleTapModifier
+    case .hotkey:                         return .hotkey
     }
 }
 
@@ -233,9 +244,10 @@ struct PerAppRulesBadge: View {
 
 // MARK: - Shared mapping row
 
-/// One mapping row: trigger chips on the left, the resolved action on the right,
-/// the per-app-rules badge, and hover edit/delete. Designed to live inside a
-/// `Form` (used by both the list and grouped styles).
+/// One mapping row: trigger chips and the user's label (note on hover) on the
+/// left, the resolved action on the right, the per-app-rules badge, and hover
+/// edit/delete. Designed to live inside a `Form` (used by both the list and
+/// grouped styles).
 struct MappingRow: View {
     let entry: ActionMappingEntry
     let availableInputSources: [String: InputSourceFix.AvailableSource]
@@ -243,9 +255,23 @@ struct MappingRow: View {
     /// All-time press count, shown as a subtle inline badge when non-nil and > 0
     /// (gated by the `stats_show_inline` setting at the call site).
     var usageCount: Int? = nil
+    /// Error from the last run of this mapping's action, if it failed.
+    var failure: String? = nil
+    /// True for an input-source mapping whose source is the one selected now.
+    var isActiveInputSource = false
     let onEdit: () -> Void
     let onDelete: () -> Void
     @EnvironmentObject var loc: LocalizationManager
+    @EnvironmentObject var app: AppState
+
+    /// A (hand-edited) trigger on a key macOS never delivers — see
+    /// `KeyCodes.bindableKeys`. It saves fine but can't fire.
+    private var unsupportedKey: Bool {
+        switch entry.trigger {
+        case .hyperPlusKey(let key, _, _, _, _), .hotkey(let key, _, _, _, _): return KeyCodes.jsToMac(key) == nil
+        case .singleTapHyper, .doubleTapHyper, .doubleTapModifier: return false
+        }
+    }
 
     var body: some View {
         let d = mappingActionDisplay(entry, loc, availableInputSources: availableInputSources)
@@ -255,8 +281,37 @@ struct MappingRow: View {
         // it would center.
         return HStack(spacing: 8) {
             TriggerChips(trigger: entry.trigger, style: keycapStyle)
+            if let label = entry.label {
+                Text(label)
+                    .foregroundStyle(.secondary)
+                    .lineLimit(1).truncationMode(.tail)
+                    .help(entry.note ?? "")
+                    .accessibilityIdentifier("mapping.label.\(triggerUniqueID(entry.trigger))")
+            } else if let note = entry.note {
+                Image(systemName: "note.text")
+                    .foregroundStyle(.secondary)
+                    .help(note)
+            }
             Spacer(minLength: 12)
             ActionPill(display: d, accent: actionAccent(entry, invalid: d.invalid))
+            if isActiveInputSource {
+                Image(systemName: "checkmark.circle.fill")
+                    .foregroundStyle(.green)
+                    .help(loc.t("mappings.input_source_active"))
+                    .accessibilityIdentifier("mapping.input_source_active.\(triggerUniqueID(entry.trigger))")
+            }
+            if unsupportedKey {
+                Image(systemName: "exclamationmark.triangle.fill")
+                    .foregroundStyle(.orange)
+                    .help(loc.t("mappings.key_unsupported"))
+                    .accessibilityIdentifier("mapping.key_unsupported.\(triggerUniqueID(entry.trigger))")
+            }
+            if let failure {
+                Image(systemName: "exclamationmark.triangle.fill")
+                    .foregroundStyle(.red)
+                    .help(loc.t("mappings.action_failed") + "\n" + failure)
+                    .accessibilityIdentifier("mapping.failure.\(triggerUniqueID(entry.trigger))")
+            }
             if let n = usageCount, n > 0 {
                 UsageCountBadge(count: n)
                     .help(loc.t("stats.inline_help"))
@@ -265,11 +320,18 @@ struct MappingRow: View {
             if !entry.bindings.isEmpty {
                 PerAppRulesBadge(bindings: entry.bindings)
             }
+            Toggle("", isOn: Binding(get: { entry.enabled },
+                                     set: { try? app.setMappingEnabled(entry.trigger, $0) }))
+                .toggleStyle(.switch).controlSize(.mini).labelsHidden()
+                .help(loc.t("mappings.enabled_help"))
+                .accessibilityIdentifier("mapping.enabled.\(triggerUniqueID(entry.trigger))")
             Button(action: onEdit) { Image(systemName: "pencil") }.buttonStyle(.borderless)
                 .accessibilityIdentifier("mapping.edit.\(triggerUniqueID(entry.trigger))")
             Button(action: onDelete) { Image(systemName: "trash") }.buttonStyle(.borderless)
                 .accessibilityIdentifier("mapping.delete.\(triggerUniqueID(entry.trigger))")
         }
+        // A switched-off mapping stays listed (and editable) but reads as inactive.
+        .opacity(entry.enabled ? 1 : 0.5)
         // Fill the row width so the Spacer actually spreads trigger-left /
         // action-right — needed outside a Form (Form rows already get full width).
         .frame(maxWidth: .infinity)
@@ -286,6 +348,10 @@ struct MappingsGroupedStyleView: View {
     var keycapStyle: KeycapStyle = .glass
     /// triggerID → all-time press count; empty when inline counts are disabled.
     var usageTotals: [String: Int] = [:]
+    /// triggerID → last failure (see `AppState.actionFailures`).
+    var failures: [String: String] = [:]
+    /// ID of the selected input source (see `AppState.currentInputSource`).
+    var activeInputSourceID: String? = nil
     let onEdit: (ActionMappingEntry) -> Void
     let onDelete: (ActionMappingEntry) -> Void
     @EnvironmentObject var loc: LocalizationManager
@@ -305,6 +371,9 @@ struct MappingsGroupedStyleView: View {
                                 MappingRow(entry: entry, availableInputSources: availableInputSources,
                                            keycapStyle: keycapStyle,
                                            usageCount: usageTotals[triggerUniqueID(entry.trigger)],
+                                           failure: failures[triggerUniqueID(entry.trigger)],
+                                           isActiveInputSource: activeInputSourceID != nil
+                                               && mappingInputSourceID(entry) == activeInputSourceID,
                                            onEdit: { onEdit(entry) }, onDelete: { onDelete(entry) })
                             }
                         } header: {
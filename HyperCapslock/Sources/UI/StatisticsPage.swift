@@ -41,8 +41,17 @@ func triggerFromUniqueID(_ id: String) -> Trigger? {
         }
         if id.hasPrefix("hyper:") {
             let parts = id.split(separator: ":")
+            guard parts.count == 3 || parts.count == 4, let key = UInt16(parts[1]) else { return nil }
+            let mods = parts.count == 4 ? parts[3] : ""
+            return .hyperPlusKey(key: key, withShift: parts[2] == "s", ctrl: mods.contains("c"),
+                                 alt: mods.contains("a"), cmd: mods.contains("m"))
+        }
+        if id.hasPrefix("hotkey:") {
+            let parts = id.split(separator: ":", omittingEmptySubsequences: false)
             guard parts.count == 3, let key = UInt16(parts[1]) else { return nil }
-            return .hyperPlusKey(key: key, withShift: parts[2] == "s")
+            let mods = parts[2]
+            return .hotkey(key: key, ctrl: mods.contains("c"), alt: mods.contains("a"),
+                           cmd: mods.contains("m"), shift: mods.contains("s"))
         }
         return nil
     }
@@ -134,6 +143,14 @@ struct StatisticsPage: View {
     }
     private var grandTotal: Int { totals.values.reduce(0, +) }
     private var maxCount: Int { ranked.first?.count ?? 0 }
+    /// Configured mappings with no press in the range, in Mappings-page order —
+    /// keys the user might repurpose. Empty until anything has been recorded,
+    /// so a fresh install doesn't list every default as "unused".
+    private var unused: [(id: String, entry: ActionMappingEntry)] {
+        guard hasAny else { return [] }
+        return config.mappings.map { (id: triggerUniqueID($0.trigger), entry: $0) }
+                              .filter { totals[$0.id] == nil }
+    }
     private var entriesByID: [String: ActionMappingEntry] {
         Dictionary(config.mappings.map { (triggerUniqueID($0.trigger), $0) }, uniquingKeysWith: { a, _ in a })
     }
@@ -190,6 +207,20 @@ struct StatisticsPage: View {
                 Text(loc.t("stats.ranking"))
             }
 
+            if !unused.isEmpty {
+                Section {
+                    ForEach(unused, id: \.id) { item in
+                        StatRow(triggerID: item.id, entry: item.entry, count: 0, fraction: 0,
+                                availableInputSources: availableInputSources)
+                            .accessibilityIdentifier("stats.unused.\(item.id)")
+                    }
+                } header: {
+                    Text(loc.t("stats.unused"))
+                } footer: {
+                    Text(loc.t("stats.unused_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+            }
+
             Section {
                 Button(role: .destructive) { showResetConfirm = true } label: {
                     HStack(spacing: 10) {
@@ -5,13 +5,22 @@ struct SettingsPage: View {
     @EnvironmentObject var app: AppState
     @EnvironmentObject var config: ConfigStore
     @EnvironmentObject var loc: LocalizationManager
+    /// The app the user most recently came from, offered as a one-click exclusion.
+    @State private var recentApp: FrontmostApp?
+    /// Bound to the passthrough section's capture field; cleared once added.
+    @State private var newPassthroughKey: UInt16?
 
     #if DEBUG
     @AppStorage(FrontmostAppHud.defaultsKey) private var debugFrontmostHud = false
+    @AppStorage(ActionSandbox.defaultsKey) private var debugActionSandbox = false
     #endif
 
     var body: some View {
         Form {
+            if AppEnvironment.isSafeMode { safeModeSection }
+
+            if !config.appConfig.onboardingCompleted { onboardingSection }
+
             Section { statusRow }
 
             Section(loc.t("perm.label")) {
@@ -20,8 +29,7 @@ struct SettingsPage: View {
                         Text(loc.t("perm.granted")).modifier(BadgeStyle(color: .green))
                     } else {
                         Button {
-                            Permissions.promptAccessibility()
-                            Permissions.openPrivacyPane(.accessibility)
+                            app.openAccessibilitySettings()
                         } label: {
                             HStack(spacing: 4) { Text(loc.t("perm.not_granted")); Image(systemName: "arrow.right") }
                         }
@@ -36,6 +44,25 @@ struct SettingsPage: View {
                         }
                     }
                 }
+                LabeledContent {
+                    switch app.capsRemapActive {
+                    case true?: Text(loc.t("perm.caps_remap_active")).modifier(BadgeStyle(color: .green))
+                    case false? where app.rawCapsFallback:
+                        Text(loc.t("perm.caps_remap_fallback")).modifier(BadgeStyle(color: .orange))
+                            .help(loc.t("perm.caps_remap_fallback_hint"))
+                    case false?: Text(loc.t("perm.caps_remap_inactive")).modifier(BadgeStyle(color: .red))
+                    case nil: Text("—").foregroundStyle(.secondary)
+                    }
+                } label: {
+                    HStack(spacing: 10) {
+                        IconTile(symbol: "capslock", color: .orange)
+                        VStack(alignment: .leading, spacing: 2) {
+                            Text(loc.t("perm.caps_remap"))
+                            Text(loc.t("perm.caps_remap_hint")).font(.caption).foregroundStyle(.secondary)
+                        }
+                    }
+                }
+                .accessibilityIdentifier("settings.caps_remap_status")
                 LabeledContent {
                     Button(loc.t("perm.refresh")) {
                         app.refreshPermissions()
@@ -44,6 +71,15 @@ struct SettingsPage: View {
                 } label: {
                     iconLabel("arrow.clockwise", .gray, loc.t("perm.refresh_label"))
                 }
+                LabeledContent {
+                    Button(loc.t("selftest.run")) { app.runSelfTest() }
+                        .accessibilityIdentifier("settings.self_test")
+                } label: {
+                    VStack(alignment: .leading, spacing: 2) {
+                        iconLabel("stethoscope", .teal, loc.t("selftest.label"))
+                        Text(loc.t("selftest.hint")).font(.caption).foregroundStyle(.secondary)
+                    }
+                }
             }
 
             Section(loc.t("settings.label")) {
@@ -55,12 +91,31 @@ struct SettingsPage: View {
                             app.showToast(app.autostart ? loc.t("toast.autostart_enabled") : loc.t("toast.autostart_disabled"))
                         } catch { app.showToast(loc.t("toast.autostart_failed"), isError: true) }
                     })) { iconLabel("power", .green, loc.t("settings.autostart")) }
+                if app.autostartNeedsApproval {
+                    HStack(spacing: 10) {
+                        Text(loc.t("settings.autostart_approval")).font(.caption).foregroundStyle(.orange)
+                        Spacer()
+                        Button(loc.t("settings.autostart_open")) { LaunchAtLogin.openLoginItemsSettings() }
+                            .buttonStyle(.borderless)
+                            .accessibilityIdentifier("settings.autostart.open_login_items")
+                    }
+                }
                 Toggle(isOn: Binding(
                     get: { config.appConfig.hideDockIcon },
                     set: { v in
                         do { try app.setHideDockIcon(v); app.showToast(v ? loc.t("toast.hide_dock_enabled") : loc.t("toast.hide_dock_disabled")) }
                         catch { app.showToast(loc.t("toast.hide_dock_failed"), isError: true) }
                     })) { iconLabel("dock.rectangle", .indigo, loc.t("settings.hide_dock")) }
+                Picker(selection: Binding(
+                    get: { config.appConfig.trayStyle },
+                    set: { v in try? app.setTrayStyle(v) })) {
+                    Text(loc.t("tray_style.icon")).tag(TrayStyle.icon)
+                    Text(loc.t("tray_style.title")).tag(TrayStyle.title)
+                    Text(loc.t("tray_style.both")).tag(TrayStyle.both)
+                } label: {
+                    iconLabel("menubar.rectangle", .indigo, loc.t("settings.tray_style"))
+                }
+                .accessibilityIdentifier("settings.tray_style")
                 VStack(alignment: .leading, spacing: 2) {
                     Toggle(isOn: Binding(
                         get: { config.appConfig.showWindowOnLaunch },
@@ -96,6 +151,235 @@ struct SettingsPage: View {
                     iconLabel("chart.bar.fill", .purple, loc.t("settings.stats_inline"))
                 }
                 .accessibilityIdentifier("settings.stats_inline")
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.layerKey },
+                        set: { v in
+                            do { try app.setLayerKey(v) }
+                            catch { app.showToast((error as? ConfigError)?.errorDescription ?? loc.t("toast.remap_failed"), isError: true) }
+                        })) {
+                        ForEach(availableLayerKeys) { k in
+                            Text(k == .capsLock ? loc.t("layer_key.caps_lock") : loc.t("remap.src.\(k.rawValue)")).tag(k)
+                        }
+                    } label: {
+                        iconLabel("keyboard", .indigo, loc.t("settings.layer_key"))
+                    }
+                    .accessibilityIdentifier("settings.layer_key")
+                    Text(loc.t("settings.layer_key_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.capsLayerMode },
+                        set: { v in
+                            do { try app.setCapsLayerMode(v) }
+                            catch { app.showToast(loc.t("toast.caps_layer_mode_failed"), isError: true) }
+                        })) {
+                        Text(loc.t("caps_layer.hold")).tag(CapsLayerMode.hold)
+                        Text(loc.t("caps_layer.one_shot")).tag(CapsLayerMode.oneShot)
+                        Text(loc.t("caps_layer.both")).tag(CapsLayerMode.both)
+                    } label: {
+                        iconLabel("capslock.fill", .indigo, loc.t("settings.caps_layer"))
+                    }
+                    .accessibilityIdentifier("settings.caps_layer")
+                    Text(loc.t("settings.caps_layer_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { app.capsTapChoice },
+                        set: { v in
+                            do { try app.setCapsTapChoice(v) }
+                            catch { app.showToast(loc.t("toast.caps_toggle_failed"), isError: true) }
+                        })) {
+                        Text(loc.t("caps_tap.toggle_caps")).tag(CapsTapChoice.toggleCaps)
+                        Text(loc.t("caps_tap.escape")).tag(CapsTapChoice.escape)
+                        Text(loc.t("caps_tap.nothing")).tag(CapsTapChoice.nothing)
+                        if app.capsTapChoice == .custom {
+                            Text(loc.t("caps_tap.custom")).tag(CapsTapChoice.custom)
+                        }
+                    } label: {
+                        iconLabel("capslock", .indigo, loc.t("settings.caps_tap"))
+                    }
+                    .accessibilityIdentifier("settings.caps_tap")
+                    Text(loc.t("settings.caps_tap_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Toggle(isOn: Binding(
+                        get: { config.appConfig.capsLockPinnedOff },
+                        set: { v in
+                            do { try app.setCapsLockPinnedOff(v) }
+                            catch { app.showToast(loc.t("toast.caps_pin_off_failed"), isError: true) }
+                        })) {
+                        iconLabel("lock.slash", .indigo, loc.t("settings.caps_pin_off"))
+                    }
+                    .accessibilityIdentifier("settings.caps_pin_off")
+                    Text(loc.t("settings.caps_pin_off_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.shiftLayerMode },
+                        set: { v in
+                            do { try app.setShiftLayerMode(v) }
+                            catch { app.showToast(loc.t("toast.shift_layer_failed"), isError: true) }
+                        })) {
+                        Text(loc.t("shift_layer.fallback")).tag(ShiftLayerMode.fallback)
+                        Text(loc.t("shift_layer.strict")).tag(ShiftLayerMode.strict)
+                    } label: {
+                        iconLabel("shift.fill", .indigo, loc.t("settings.shift_layer"))
+                    }
+                    .accessibilityIdentifier("settings.shift_layer")
+                    Text(loc.t("settings.shift_layer_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    LabeledContent {
+                        HStack(spacing: 12) {
+                            ForEach(JumpDirection.allCases, id: \.self) { dir in
+                                Stepper(value: Binding(
+                                    get: { config.appConfig.defaultJumpCount(dir) },
+                                    set: { v in
+                                        do { try app.setDefaultJumpCount(v, for: dir) }
+                                        catch { app.showToast(loc.t("toast.default_jump_failed"), isError: true) }
+                                    }), in: 1...ConfigStore.maxJumpCount) {
+                                    Text("\(dir == .up ? "↑" : "↓") \(config.appConfig.defaultJumpCount(dir))")
+                                        .monospacedDigit()
+                                }
+                                .accessibilityIdentifier("settings.default_jump.\(dir.rawValue)")
+                            }
+                        }
+                    } label: {
+                        iconLabel("chevron.up.chevron.down", .indigo, loc.t("settings.default_jump"))
+                    }
+                    HStack {
+                        Text(loc.t("settings.default_jump_hint")).font(.caption).foregroundStyle(.secondary)
+                        Spacer()
+                        Button(loc.t("settings.default_jump_reset")) {
+                            app.resetJumpMappings()
+                            app.showToast(loc.t("toast.jump_mappings_reset"))
+                        }
+                        .buttonStyle(.link)
+                        .accessibilityIdentifier("settings.default_jump.reset")
+                    }
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.rawEscapeModifier },
+                        set: { v in
+                            do { try app.setRawEscapeModifier(v) }
+                            catch { app.showToast(loc.t("toast.raw_escape_failed"), isError: true) }
+                        })) {
+                        ForEach(RawEscapeModifier.allCases, id: \.self) { m in
+                            Text(loc.t("raw_escape.\(m.rawValue)")).tag(m)
+                        }
+                    } label: {
+                        iconLabel("arrow.uturn.right", .indigo, loc.t("settings.raw_escape"))
+                    }
+                    .accessibilityIdentifier("settings.raw_escape")
+                    Text(loc.t("settings.raw_escape_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.tapLocation },
+                        set: { v in
+                            do { try app.setTapLocation(v) }
+                            catch { app.showToast(loc.t("toast.event_location_failed"), isError: true) }
+                        })) {
+                        ForEach(EventTapLocation.allCases, id: \.self) { l in
+                            Text(loc.t("event_location.\(l.rawValue)")).tag(l)
+                        }
+                    } label: {
+                        iconLabel("arrow.down.to.line", .gray, loc.t("settings.tap_location"))
+                    }
+                    .accessibilityIdentifier("settings.tap_location")
+                    Picker(selection: Binding(
+                        get: { config.appConfig.postLocation },
+                        set: { v in
+                            do { try app.setPostLocation(v) }
+                            catch { app.showToast(loc.t("toast.event_location_failed"), isError: true) }
+                        })) {
+                        ForEach(EventTapLocation.allCases, id: \.self) { l in
+                            Text(loc.t("event_location.\(l.rawValue)")).tag(l)
+                        }
+                    } label: {
+                        iconLabel("arrow.up.to.line", .gray, loc.t("settings.post_location"))
+                    }
+                    .accessibilityIdentifier("settings.post_location")
+                    Text(loc.t("settings.event_location_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    LabeledContent {
+                        Stepper(value: Binding(
+                            get: { config.appConfig.injectDelayMs },
+                            set: { v in
+                                do { try app.setInjectDelay(v) }
+                                catch { app.showToast(loc.t("toast.inject_delay_failed"), isError: true) }
+                            }), in: 0...EngineConstants.maxInjectDelayMs) {
+                            Text(config.appConfig.injectDelayMs == 0 ? loc.t("settings.inject_delay_off")
+                                 : "\(config.appConfig.injectDelayMs) ms").monospacedDigit()
+                        }
+                        .accessibilityIdentifier("settings.inject_delay")
+                    } label: {
+                        iconLabel("timer", .gray, loc.t("settings.inject_delay"))
+                    }
+                    Text(loc.t("settings.inject_delay_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    LabeledContent {
+                        Stepper(value: Binding(
+                            get: { config.appConfig.debounceMs },
+                            set: { v in
+                                do { try app.setDebounceMs(v) }
+                                catch { app.showToast(loc.t("toast.debounce_failed"), isError: true) }
+                            }), in: 0...EngineConstants.maxDebounceMs, step: 5) {
+                            Text(config.appConfig.debounceMs == 0 ? loc.t("settings.debounce_off")
+                                 : "\(config.appConfig.debounceMs) ms").monospacedDigit()
+                        }
+                        .accessibilityIdentifier("settings.debounce")
+                    } label: {
+                        iconLabel("waveform.path", .gray, loc.t("settings.debounce"))
+                    }
+                    Text(loc.t("settings.debounce_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Toggle(isOn: Binding(
+                        get: { config.appConfig.capsMouseEvents },
+                        set: { v in
+                            do { try app.setCapsMouseEvents(v) }
+                            catch { app.showToast(loc.t("toast.caps_mouse_failed"), isError: true) }
+                        })) {
+                        iconLabel("computermouse.fill", .gray, loc.t("settings.caps_mouse"))
+                    }
+                    .accessibilityIdentifier("settings.caps_mouse")
+                    Text(loc.t("settings.caps_mouse_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.wordMotionStyle },
+                        set: { v in
+                            do { try app.setWordMotionStyle(v) }
+                            catch { app.showToast(loc.t("toast.word_motion_failed"), isError: true) }
+                        })) {
+                        Text(loc.t("word_motion.word")).tag(WordMotionStyle.word)
+                        Text(loc.t("word_motion.subword")).tag(WordMotionStyle.subword)
+                    } label: {
+                        iconLabel("textformat.abc.dottedunderline", .blue, loc.t("settings.word_motion"))
+                    }
+                    .accessibilityIdentifier("settings.word_motion")
+                    Text(loc.t("settings.word_motion_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    Picker(selection: Binding(
+                        get: { config.appConfig.lineEdgeStyle },
+                        set: { v in
+                            do { try app.setLineEdgeStyle(v) }
+                            catch { app.showToast(loc.t("toast.line_edge_failed"), isError: true) }
+                        })) {
+                        Text(loc.t("line_edge.visual")).tag(LineEdgeStyle.visual)
+                        Text(loc.t("line_edge.logical")).tag(LineEdgeStyle.logical)
+                    } label: {
+                        iconLabel("return", .blue, loc.t("settings.line_edge"))
+                    }
+                    .accessibilityIdentifier("settings.line_edge")
+                    Text(loc.t("settings.line_edge_hint")).font(.caption).foregroundStyle(.secondary)
+                }
                 VStack(alignment: .leading, spacing: 2) {
                     Toggle(isOn: Binding(
                         get: { config.appConfig.broadcastCapsHoldForAnyDrag },
@@ -110,6 +394,26 @@ struct SettingsPage: View {
                     }
                     Text(loc.t("settings.anydrag_caps_hold_hint")).font(.caption).foregroundStyle(.secondary)
                 }
+                VStack(alignment: .leading, spacing: 2) {
+                    Toggle(isOn: Binding(
+                        get: { config.appConfig.controlSocketEnabled },
+                        set: { v in
+                            do { try app.setControlSocketEnabled(v); app.showToast(v ? loc.t("toast.control_socket_enabled") : loc.t("toast.control_socket_disabled")) }
+                            catch { app.showToast(loc.t("toast.control_socket_failed"), isError: true) }
+                        })) {
+                        iconLabel("terminal.fill", .gray, loc.t("settings.control_socket"))
+                    }
+                    .accessibilityIdentifier("settings.control_socket")
+                    Text(loc.t("settings.control_socket_hint", ["path": ControlServer.socketURL.path]))
+                        .font(.caption).foregroundStyle(.secondary).textSelection(.enabled)
+                    if config.appConfig.controlSocketEnabled {
+                        Button(loc.t("settings.control_socket_copy_token")) {
+                            if app.copyControlToken() { app.showToast(loc.t("toast.control_token_copied")) }
+                        }
+                        .buttonStyle(.link)
+                        .accessibilityIdentifier("settings.control_socket.copy_token")
+                    }
+                }
             }
 
             Section {
@@ -120,7 +424,7 @@ struct SettingsPage: View {
                     iconLabel("plus.circle.fill", .pink, loc.t("remap.add"))
                 }
                 .buttonStyle(.borderless)
-                .disabled(config.appConfig.keyRemaps.count >= KeyRemapSource.allCases.count)
+                .disabled(availableSources(forRow: config.appConfig.keyRemaps.count).isEmpty)
                 .accessibilityIdentifier("settings.key_remap.add")
             } header: {
                 Text(loc.t("remap.label"))
@@ -128,6 +432,62 @@ struct SettingsPage: View {
                 Text(loc.t("remap.hint")).font(.caption).foregroundStyle(.secondary)
             }
 
+            Section {
+                ForEach(config.appConfig.excludedApps, id: \.self) { bundleID in
+                    excludedAppRow(bundleID)
+                }
+                if let recent = recentApp, let bundleID = recent.bundleID,
+                   !config.appConfig.excludedApps.contains(where: { $0.caseInsensitiveCompare(bundleID) == .orderedSame }) {
+                    HStack(spacing: 10) {
+                        Text(loc.t("excluded.recent", ["app": recent.name, "id": bundleID]))
+                            .font(.callout).foregroundStyle(.secondary)
+                        Spacer()
+                        Button(loc.t("excluded.add_recent")) {
+                            do { try app.addExcludedApp(bundleID) }
+                            catch { app.showToast(loc.t("toast.excluded_apps_failed"), isError: true) }
+                        }
+                        .buttonStyle(.borderless)
+                        .accessibilityIdentifier("settings.excluded_apps.add_recent")
+                    }
+                }
+                Button {
+                    guard let picked = AppChooser.choose() else { return }
+                    do { try app.addExcludedApp(picked.bundleID) }
+                    catch { app.showToast(loc.t("toast.excluded_apps_failed"), isError: true) }
+                } label: {
+                    iconLabel("plus.circle.fill", .orange, loc.t("excluded.add"))
+                }
+                .buttonStyle(.borderless)
+                .accessibilityIdentifier("settings.excluded_apps.add")
+            } header: {
+                Text(loc.t("excluded.label"))
+            } footer: {
+                Text(loc.t("excluded.hint")).font(.caption).foregroundStyle(.secondary)
+            }
+
+            Section {
+                ForEach(config.appConfig.passthroughKeys, id: \.key) { entry in
+                    passthroughRow(entry)
+                }
+                HStack(spacing: 10) {
+                    iconLabel("arrow.right.circle", .teal, loc.t("passthrough.add"))
+                    Spacer()
+                    KeyCaptureField(jsKeyCode: $newPassthroughKey, placeholder: loc.t("passthrough.capture"),
+                                    accessibilityID: "settings.passthrough.capture")
+                        .frame(width: 140, height: 22)
+                }
+                .onChange(of: newPassthroughKey) { _, key in
+                    guard let key else { return }
+                    newPassthroughKey = nil
+                    do { try app.addPassthroughKey(key) }
+                    catch { app.showToast(loc.t("toast.passthrough_failed"), isError: true) }
+                }
+            } header: {
+                Text(loc.t("passthrough.label"))
+            } footer: {
+                Text(loc.t("passthrough.hint")).font(.caption).foregroundStyle(.secondary)
+            }
+
             Section(loc.t("appearance.label")) {
                 Picker(selection: Binding(
                     get: { loc.followsSystem ? LanguageChoice.system : LanguageChoice.fixed(loc.locale) },
@@ -155,12 +515,86 @@ struct SettingsPage: View {
                 .pickerStyle(.segmented)
             }
 
+            Section {
+                HStack(spacing: 10) {
+                    iconLabel("doc.text.fill", .gray, loc.t("logs.label"))
+                    Spacer()
+                    Button(loc.t("logs.copy")) {
+                        if app.copyRecentLogs() { app.showToast(loc.t("toast.logs_copied")) }
+                        else { app.showToast(loc.t("toast.logs_empty"), isError: true) }
+                    }
+                    .accessibilityIdentifier("settings.logs.copy")
+                    Button(loc.t("logs.open")) {
+                        NSWorkspace.shared.open(URL(fileURLWithPath: FileLog.shared.path))
+                    }
+                    .disabled(!FileManager.default.fileExists(atPath: FileLog.shared.path))
+                    .accessibilityIdentifier("settings.logs.open")
+                }
+                VStack(alignment: .leading, spacing: 2) {
+                    HStack(spacing: 10) {
+                        iconLabel("record.circle", .red, loc.t("logs.debug"))
+                        Spacer()
+                        if let until = app.debugLoggingUntil {
+                            Text(until, style: .timer)
+                                .foregroundStyle(.red).font(.callout).monospacedDigit()
+                            Button(loc.t("logs.debug_stop")) { app.stopDebugLogging() }
+                                .accessibilityIdentifier("settings.logs.debug_stop")
+                        } else {
+                            Button(loc.t("logs.debug_start", ["seconds": "\(AppState.defaultDebugLoggingSeconds)"])) {
+                                app.startDebugLogging(for: AppState.defaultDebugLoggingSeconds)
+                            }
+                            .accessibilityIdentifier("settings.logs.debug_start")
+                        }
+                    }
+                    Text(loc.t("logs.debug_hint")).font(.caption).foregroundStyle(.secondary)
+                }
+                Picker(selection: Binding(get: { app.logMaxMb }, set: { try? app.setLogMaxMb($0) })) {
+                    ForEach(AppState.logSizeOptions, id: \.self) { mb in
+                        Text("\(mb) MB").tag(mb)
+                    }
+                    if !AppState.logSizeOptions.contains(app.logMaxMb) {
+                        Text("\(app.logMaxMb) MB").tag(app.logMaxMb)
+                    }
+                } label: {
+                    iconLabel("internaldrive", .gray, loc.t("logs.max_size"))
+                }
+                .accessibilityIdentifier("settings.logs.max_size")
+            } footer: {
+                Text(loc.t("logs.hint", ["path": FileLog.shared.path, "lines": "\(AppState.copiedLogLines)"]) + " " + loc.t("logs.max_size_hint"))
+                    .font(.caption).foregroundStyle(.secondary).textSelection(.enabled)
+            }
+
+            Section {
+                ForEach(config.configPaths(), id: \.name) { file in
+                    LabeledContent {
+                        Button(loc.t("files.reveal")) {
+                            NSWorkspace.shared.activateFileViewerSelecting([URL(fileURLWithPath: file.path)])
+                        }
+                        .disabled(!file.exists)
+                    } label: {
+                        VStack(alignment: .leading, spacing: 2) {
+                            Text(loc.t("files.\(file.name)"))
+                            Text(file.exists ? file.path : loc.t("files.missing", ["path": file.path]))
+                                .font(.caption).foregroundStyle(.secondary).textSelection(.enabled)
+                        }
+                    }
+                    .accessibilityIdentifier("settings.files.\(file.name)")
+                }
+            } header: {
+                Text(loc.t("files.label"))
+            } footer: {
+                Text(loc.t("files.hint")).font(.caption).foregroundStyle(.secondary)
+            }
+
             #if DEBUG
             // Debug-only diagnostics. Compiled out of release builds entirely.
             Section("Debug") {
                 Toggle("Show frontmost-app overlay", isOn: $debugFrontmostHud)
                 Text("Shows the active app's name + bundle id, bottom-right, for 3s on each switch.")
                     .font(.caption).foregroundStyle(.secondary)
+                Toggle("Action sandbox", isOn: $debugActionSandbox)
+                Text("Adds a Try button to keystroke actions on the Actions page, typing into a scratch window instead of the focused app. Also enables the socket's test_action.")
+                    .font(.caption).foregroundStyle(.secondary)
             }
             .onChange(of: debugFrontmostHud) { _, on in
                 if !on { FrontmostAppHud.shared.hideNow() }
@@ -169,6 +603,10 @@ struct SettingsPage: View {
         }
         .formStyle(.grouped)
         .navigationTitle(loc.t("nav.settings"))
+        .onAppear { recentApp = FrontmostAppTracker.shared.lastOtherApp() }
+        .onReceive(NotificationCenter.default.publisher(for: FrontmostAppTracker.didChangeNotification)) { _ in
+            recentApp = FrontmostAppTracker.shared.lastOtherApp()
+        }
         .toolbar {
             ToolbarItem {
                 Button {
@@ -181,6 +619,7 @@ struct SettingsPage: View {
                         // Animate the pause.fill ↔ play.fill swap.
                         .contentTransition(.symbolEffect(.replace))
                 }
+                .disabled(app.isDisabled)
             }
         }
     }
@@ -190,6 +629,53 @@ struct SettingsPage: View {
         HStack(spacing: 10) { IconTile(symbol: symbol, color: color); Text(text) }
     }
 
+    // MARK: - Excluded app rows
+
+    private func excludedAppRow(_ bundleID: String) -> some View {
+        HStack(spacing: 10) {
+            if let icon = AppChooser.icon(bundleID) {
+                Image(nsImage: icon).resizable().frame(width: 20, height: 20)
+            }
+            Text(AppChooser.displayName(bundleID))
+            Text(bundleID).font(.caption).foregroundStyle(.secondary)
+            Spacer()
+            Button {
+                do { try app.removeExcludedApp(bundleID) }
+                catch { app.showToast(loc.t("toast.excluded_apps_failed"), isError: true) }
+            } label: {
+                Image(systemName: "minus.circle.fill").foregroundStyle(.red)
+            }
+            .buttonStyle(.borderless)
+            .accessibilityIdentifier("settings.excluded_apps.remove.\(bundleID)")
+        }
+    }
+
+    /// One passthrough key: Caps+key, where it applies, restrict-to-app, remove.
+    private func passthroughRow(_ entry: PassthroughKey) -> some View {
+        HStack(spacing: 10) {
+            Text("Caps + \(KeyCodes.name(entry.key))").font(.body.monospaced())
+            Text(entry.apps.isEmpty ? loc.t("passthrough.everywhere")
+                                    : entry.apps.map(AppChooser.displayName).joined(separator: ", "))
+                .font(.caption).foregroundStyle(.secondary)
+            Spacer()
+            Button(loc.t("passthrough.limit_app")) {
+                guard let picked = AppChooser.choose() else { return }
+                do { try app.setPassthroughApps(entry.key, entry.apps + [picked.bundleID]) }
+                catch { app.showToast(loc.t("toast.passthrough_failed"), isError: true) }
+            }
+            .buttonStyle(.borderless)
+            .accessibilityIdentifier("settings.passthrough.limit.\(entry.key)")
+            Button {
+                do { try app.removePassthroughKey(entry.key) }
+                catch { app.showToast(loc.t("toast.passthrough_failed"), isError: true) }
+            } label: {
+                Image(systemName: "minus.circle.fill").foregroundStyle(.red)
+            }
+            .buttonStyle(.borderless)
+            .accessibilityIdentifier("settings.passthrough.remove.\(entry.key)")
+        }
+    }
+
     // MARK: - Key remapping rows
 
     /// One editable remap row: source modifier → target function key + remove.
@@ -244,11 +730,21 @@ struct SettingsPage: View {
     }
 
     /// Source keys still available to row `i` (its own current source, plus any
-    /// not used by another row) — so each modifier can be remapped at most once.
+    /// not used by another row or as the layer key) — so each modifier can be
+    /// remapped at most once.
     private func availableSources(forRow i: Int) -> [KeyRemapSource] {
-        let usedByOthers = Set(config.appConfig.keyRemaps.enumerated()
+        var used = Set(config.appConfig.keyRemaps.enumerated()
             .filter { $0.offset != i }.map { $0.element.source })
-        return KeyRemapSource.allCases.filter { !usedByOthers.contains($0) }
+        if let layer = config.appConfig.layerKey.remapSource { used.insert(layer) }
+        return KeyRemapSource.allCases.filter { !used.contains($0) }
+    }
+
+    /// Layer keys not already taken by a key remap, plus the current one.
+    private var availableLayerKeys: [LayerKey] {
+        let remapped = Set(config.appConfig.keyRemaps.map(\.source))
+        return LayerKey.allCases.filter { k in
+            k == config.appConfig.layerKey || k.remapSource.map { !remapped.contains($0) } ?? true
+        }
     }
 
     /// Target keys still available to row `i` — each function key targeted once.
@@ -261,13 +757,61 @@ struct SettingsPage: View {
     /// Append a new remap with the first unused source + target.
     private func addKeyRemap() {
         let remaps = config.appConfig.keyRemaps
-        let usedSrc = Set(remaps.map { $0.source })
         let usedDst = Set(remaps.map { $0.destination })
-        guard let src = KeyRemapSource.allCases.first(where: { !usedSrc.contains($0) }),
+        guard let src = availableSources(forRow: remaps.count).first,
               let dst = KeyRemapTarget.allCases.first(where: { !usedDst.contains($0) }) else { return }
         app.setKeyRemaps(remaps + [KeyRemap(source: src, destination: dst)])
     }
 
+    /// Launched with `--safe-mode`: say why nothing works and where the files are.
+    private var safeModeSection: some View {
+        Section {
+            VStack(alignment: .leading, spacing: 6) {
+                Label(loc.t("safe_mode.title"), systemImage: "exclamationmark.shield.fill")
+                    .font(.headline).foregroundStyle(.orange)
+                Text(loc.t("safe_mode.body")).font(.caption).foregroundStyle(.secondary)
+                Button(loc.t("safe_mode.reveal")) {
+                    NSWorkspace.shared.activateFileViewerSelecting([AppEnvironment.appSupportDirectory])
+                }
+                .accessibilityIdentifier("settings.safe_mode.reveal")
+            }
+            .padding(.vertical, 4)
+        }
+    }
+
+    /// First-run checklist: the two things HyperCapslock needs before Caps does
+    /// anything. Shown until the user dismisses it (see `onboardingCompleted`).
+    private var onboardingSection: some View {
+        Section(loc.t("onboarding.label")) {
+            Text(loc.t("onboarding.intro")).font(.caption).foregroundStyle(.secondary)
+            onboardingStep(done: app.accessibilityGranted, loc.t("onboarding.accessibility")) {
+                Button(loc.t("onboarding.grant")) { app.openAccessibilitySettings() }
+            }
+            onboardingStep(done: app.capsRemapActive == true, loc.t("onboarding.caps_remap")) {
+                Button(loc.t("perm.refresh")) { app.refreshPermissions() }
+            }
+            HStack {
+                Spacer()
+                Button(loc.t("onboarding.done")) {
+                    do { try app.setOnboardingCompleted(true) }
+                    catch { app.showToast(loc.t("toast.onboarding_failed"), isError: true) }
+                }
+                .keyboardShortcut(.defaultAction)
+                .accessibilityIdentifier("settings.onboarding.done")
+            }
+        }
+    }
+
+    private func onboardingStep<Action: View>(done: Bool, _ text: String, @ViewBuilder action: () -> Action) -> some View {
+        HStack(spacing: 10) {
+            Image(systemName: done ? "checkmark.circle.fill" : "circle")
+                .foregroundStyle(done ? .green : .secondary)
+            Text(text)
+            Spacer()
+            if !done { action().buttonStyle(.borderless) }
+        }
+    }
+
     private var statusRow: some View {
         HStack(spacing: 13) {
             Image(nsImage: NSApp.applicationIconImage)
@@ -277,7 +821,10 @@ struct SettingsPage: View {
                 Text("HyperCapslock").font(.headline)
                 HStack(spacing: 6) {
                     StatusDot(running: app.isRunning)
-                    Text(loc.t("status.\(app.status.rawValue)")).font(.callout).foregroundStyle(.secondary)
+                    Text(app.isRunning && app.secureInputActive ? app.secureInputStatusText
+                         : app.isRunning && app.excludedAppFrontmost ? loc.t("status.auto_paused_excluded")
+                         : loc.t("status.\(app.status.rawValue)"))
+                        .font(.callout).foregroundStyle(.secondary)
                 }
             }
             Spacer()
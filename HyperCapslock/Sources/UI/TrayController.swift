@@ -1,17 +1,31 @@
 import AppKit
 import Combine
 
-/// Menu-bar status item + menu: a disabled status line, start/stop toggle,
-/// check-for-updates, more-apps, open-window, quit. Template
-/// icon reflects running/paused; text is fully localized and refreshes on
-/// status or locale changes.
+/// Menu-bar status item + menu: a disabled status line and active input source, a grant-Accessibility
+/// shortcut (only while it's missing), a stop item while a debug capture runs, start/stop toggle,
+/// timed pause, full disable/enable, launch at login, release stuck modifiers, keymap presets, reset mappings, check-for-updates, more-apps, open-window, quit. Template
+/// icon reflects running/paused (and Secure Input, which stalls everything), as does the optional text title
+/// (`TrayStyle`); text is fully localized and refreshes on status or locale changes (and on menu open, for a
+/// timed pause's countdown).
 @MainActor
-final class TrayController: NSObject {
+final class TrayController: NSObject, NSMenuDelegate {
+    /// Timed-pause presets offered under "Pause For", in minutes.
+    private static let pauseForMinutes = [5, 15, 30, 60]
+
     private let statusItem = NSStatusBar.system.statusItem(withLength: NSStatusItem.variableLength)
     private var cancellables = Set<AnyCancellable>()
 
     private let statusLine = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+    private let inputSourceLine = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+    private let grantAccessibilityItem = NSMenuItem(title: "", action: #selector(grantAccessibility), keyEquivalent: "")
+    private let debugLoggingItem = NSMenuItem(title: "", action: #selector(stopDebugLogging), keyEquivalent: "")
     private let toggleItem = NSMenuItem(title: "", action: #selector(toggleService), keyEquivalent: "")
+    private let pauseForItem = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+    private let enableItem = NSMenuItem(title: "", action: #selector(toggleEnabled), keyEquivalent: "")
+    private let launchAtLoginItem = NSMenuItem(title: "", action: #selector(toggleLaunchAtLogin), keyEquivalent: "")
+    private let releaseModifiersItem = NSMenuItem(title: "", action: #selector(releaseModifiers), keyEquivalent: "")
+    private let presetsItem = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+    private let resetMappingsItem = NSMenuItem(title: "", action: #selector(resetMappings), keyEquivalent: "")
     private let checkUpdateItem = NSMenuItem(title: "", action: #selector(checkForUpdates), keyEquivalent: "")
     private let moreAppsItem = NSMenuItem(title: "", action: #selector(openMoreApps), keyEquivalent: "")
     private let openItem = NSMenuItem(title: "", action: #selector(openWindow), keyEquivalent: "")
@@ -27,18 +41,86 @@ final class TrayController: NSObject {
             .sink { [weak self] _ in self?.refresh() }
             .store(in: &cancellables)
 
+        AppState.shared.$excludedAppFrontmost
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$secureInputActive
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$accessibilityGranted
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$pausedUntil
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$debugLoggingUntil
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$autostart
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
+        AppState.shared.$currentInputSource
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
+
         LocalizationManager.shared.$locale
             .receive(on: RunLoop.main)
             .sink { [weak self] _ in self?.refresh() }
             .store(in: &cancellables)
+
+        AppState.shared.config.$appConfig
+            .map(\.trayStyle)
+            .removeDuplicates()
+            .receive(on: RunLoop.main)
+            .sink { [weak self] _ in self?.refresh() }
+            .store(in: &cancellables)
     }
 
+    /// The menu-bar text for the title styles: "HC" while keys are being
+    /// handled, "HC•" while they pass through (paused, off, excluded app,
+    /// Secure Input) — the same split as the two icons.
+    static func title(active: Bool) -> String { active ? "HC" : "HC•" }
+
     private func buildMenu() {
         let menu = NSMenu()
         statusLine.isEnabled = false
-        for item in [statusLine, toggleItem, checkUpdateItem, moreAppsItem] { item.target = self }
+        inputSourceLine.isEnabled = false
+        for item in [statusLine, grantAccessibilityItem, debugLoggingItem, toggleItem, enableItem, launchAtLoginItem, releaseModifiersItem, resetMappingsItem, checkUpdateItem, moreAppsItem] {
+            item.target = self
+        }
         menu.addItem(statusLine)
+        menu.addItem(inputSourceLine)
+        menu.addItem(grantAccessibilityItem)
+        menu.addItem(debugLoggingItem)
         menu.addItem(toggleItem)
+        let pauseForMenu = NSMenu()
+        for minutes in Self.pauseForMinutes {
+            let item = NSMenuItem(title: "", action: #selector(pauseFor(_:)), keyEquivalent: "")
+            item.target = self
+            item.tag = minutes
+            pauseForMenu.addItem(item)
+        }
+        pauseForItem.submenu = pauseForMenu
+        menu.addItem(pauseForItem)
+        menu.addItem(enableItem)
+        menu.addItem(launchAtLoginItem)
+        menu.addItem(releaseModifiersItem)
+        presetsItem.submenu = NSMenu()
+        menu.addItem(presetsItem)
+        menu.addItem(resetMappingsItem)
         menu.addItem(checkUpdateItem)
         menu.addItem(moreAppsItem)
         menu.addItem(.separator())
@@ -46,30 +128,117 @@ final class TrayController: NSObject {
         quitItem.target = self
         menu.addItem(openItem)
         menu.addItem(quitItem)
+        menu.delegate = self
         statusItem.menu = menu
     }
 
     private func refresh() {
         let paused = AppState.shared.isPaused
+        let disabled = AppState.shared.isDisabled
         let t = LocalizationManager.shared.t
 
-        statusLine.title = paused ? t("status.label", [:]) + ": " + t("status.paused", [:])
-                                  : t("status.label", [:]) + ": " + t("status.running", [:])
+        let excluded = !paused && !disabled && AppState.shared.excludedAppFrontmost
+        let secureInput = !paused && !disabled && AppState.shared.secureInputActive
+        var state = disabled ? t("status.disabled", [:])
+                  : paused ? t("status.paused", [:])
+                  : secureInput ? AppState.shared.secureInputStatusText
+                  : excluded ? t("status.auto_paused_excluded", [:]) : t("status.running", [:])
+        if paused, let until = AppState.shared.pausedUntil {
+            // Round up so the last partial minute still reads "1 min", never "0".
+            let minutes = max(1, Int((until.timeIntervalSinceNow / 60).rounded(.up)))
+            state += " (" + t("status.resumes_in", ["minutes": String(minutes)]) + ")"
+        }
+        statusLine.title = t("status.label", [:]) + ": " + state
+        let source = AppState.shared.currentInputSource
+        inputSourceLine.title = t("tray.input_source", ["name": source?.name ?? ""])
+        inputSourceLine.isHidden = source == nil
+        grantAccessibilityItem.title = t("tray.grant_accessibility", [:])
+        // Read the live value: it's the first thing a new user needs, and the
+        // published flag only refreshes on app activation.
+        grantAccessibilityItem.isHidden = Permissions.isAccessibilityGranted
+        debugLoggingItem.title = t("tray.debug_logging", [:])
+        debugLoggingItem.isHidden = AppState.shared.debugLoggingUntil == nil
         toggleItem.title = paused ? t("status.resume", [:]) : t("status.pause", [:])
+        // Pause controls only apply while the tap is installed.
+        toggleItem.isHidden = disabled
+        pauseForItem.isHidden = disabled
+        pauseForItem.title = t("tray.pause_for", [:])
+        enableItem.title = disabled ? t("tray.enable", [:]) : t("tray.disable", [:])
+        for item in pauseForItem.submenu?.items ?? [] {
+            item.title = t("tray.pause_minutes", ["minutes": String(item.tag)])
+        }
+        launchAtLoginItem.title = t("settings.autostart", [:])
+        launchAtLoginItem.state = AppState.shared.autostart ? .on : .off
+        releaseModifiersItem.title = t("tray.release_modifiers", [:])
+        presetsItem.title = t("tray.presets", [:])
+        resetMappingsItem.title = t("tray.reset_mappings", [:])
         checkUpdateItem.title = t("update.check", [:])
         moreAppsItem.title = t("tray.more_apps", [:])
         openItem.title = t("tray.open", [:])
         quitItem.title = t("tray.quit", [:])
 
-        let imageName = paused ? "TrayPaused" : "TrayRunning"
-        if let image = NSImage(named: imageName) {
+        let active = !(paused || disabled || excluded || secureInput)
+        let style = AppState.shared.trayStyle
+        if style.showsIcon, let image = NSImage(named: active ? "TrayRunning" : "TrayPaused") {
             image.isTemplate = true
             image.size = NSSize(width: 18, height: 18)
             statusItem.button?.image = image
+        } else {
+            statusItem.button?.image = nil
+        }
+        statusItem.button?.title = style.showsTitle ? Self.title(active: active) : ""
+        switch style {
+        case .icon: statusItem.button?.imagePosition = .imageOnly
+        case .title: statusItem.button?.imagePosition = .noImage
+        case .both: statusItem.button?.imagePosition = .imageLeading
+        }
+    }
+
+    /// Presets live on disk, so the submenu is rebuilt each time the menu opens.
+    private func rebuildPresetsMenu() {
+        guard let submenu = presetsItem.submenu else { return }
+        let t = LocalizationManager.shared.t
+        submenu.removeAllItems()
+        let names = AppState.shared.config.listPresets()
+        for name in names {
+            let item = NSMenuItem(title: name, action: #selector(loadPreset(_:)), keyEquivalent: "")
+            item.target = self
+            item.representedObject = name
+            submenu.addItem(item)
         }
+        if names.isEmpty {
+            let none = NSMenuItem(title: t("tray.presets_none", [:]), action: nil, keyEquivalent: "")
+            none.isEnabled = false
+            submenu.addItem(none)
+        }
+        submenu.addItem(.separator())
+        let save = NSMenuItem(title: t("tray.presets_save", [:]), action: #selector(savePreset), keyEquivalent: "")
+        save.target = self
+        submenu.addItem(save)
+    }
+
+    func menuWillOpen(_ menu: NSMenu) {
+        AppState.shared.refreshAutostart()
+        refresh()
+        rebuildPresetsMenu()
     }
 
+    @objc private func grantAccessibility() { AppState.shared.openAccessibilitySettings() }
+    @objc private func stopDebugLogging() { AppState.shared.stopDebugLogging() }
     @objc private func toggleService() { AppState.shared.togglePause() }
+    @objc private func toggleEnabled() { AppState.shared.setEnabled(AppState.shared.isDisabled) }
+    @objc private func pauseFor(_ sender: NSMenuItem) { AppState.shared.pause(for: TimeInterval(sender.tag * 60)) }
+    @objc private func toggleLaunchAtLogin() {
+        do { try AppState.shared.toggleAutostart() }
+        catch { AppState.shared.showToast(LocalizationManager.shared.t("toast.autostart_failed", [:]), isError: true) }
+    }
+    @objc private func releaseModifiers() { AppState.shared.releaseAllModifiers() }
+    @objc private func loadPreset(_ sender: NSMenuItem) {
+        guard let name = sender.representedObject as? String else { return }
+        AppState.shared.loadPreset(name)
+    }
+    @objc private func savePreset() { AppState.shared.promptSavePreset() }
+    @objc private func resetMappings() { AppState.shared.confirmResetAllMappings() }
     @objc private func checkForUpdates() { UpdaterManager.shared.checkForUpdates() }
     @objc private func openMoreApps() {
         if let url = URL(string: "https://xueshi.dev") { NSWorkspace.shared.open(url) }
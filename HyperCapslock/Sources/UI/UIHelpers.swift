@@ -81,12 +81,11 @@ func keyCodeDisplay(_ keyCode: UInt16) -> String {
     let special: [UInt16: String] = [
         8: "Backspace", 9: "Tab", 13: "Enter", 27: "Esc", 32: "Space", 46: "⌦",
         33: "PgUp", 34: "PgDn", 35: "End", 36: "Home", 37: "←", 38: "↑", 39: "→", 40: "↓",
-        112: "F1", 113: "F2", 114: "F3", 115: "F4", 116: "F5", 117: "F6",
-        118: "F7", 119: "F8", 120: "F9", 121: "F10", 122: "F11", 123: "F12",
         186: ";", 187: "=", 188: ",", 189: "-", 190: ".", 191: "/",
         219: "[", 220: "\\", 221: "]", 222: "'", 192: "`",
     ]
     if let s = special[keyCode] { return s }
+    if (112...135).contains(keyCode) { return "F\(keyCode - 111)" }   // F1–F24, as `KeyCodes.name`
     if (keyCode >= 65 && keyCode <= 90) || (keyCode >= 48 && keyCode <= 57) {
         return String(UnicodeScalar(UInt8(keyCode)))
     }
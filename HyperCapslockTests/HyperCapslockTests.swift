@@ -1,4 +1,6 @@
 import XCTest
+import CoreGraphics
+import os
 import Yams
 @testable import HyperCapslock
 
@@ -15,6 +17,50 @@ final class HyperCapslockTests: XCTestCase {
         }
     }
 
+    /// Every bindable physical key must have a real display name (not the
+    /// `KeyNNN` fallback), and the full printable set — symbols and brackets
+    /// included — must be bindable.
+    func testEveryBindableKeyIsNamed() throws {
+        for mac in UInt16(0)...0x7F {
+            guard let js = KeyCodes.macToJs(mac) else { continue }
+            XCTAssertFalse(KeyCodes.name(js).hasPrefix("Key"), "mac 0x\(String(mac, radix: 16)) → js \(js) has no name")
+        }
+        let printable: [(UInt16, String)] = [
+            (0x29, ";"), (0x18, "="), (0x2B, ","), (0x1B, "-"), (0x2F, "."), (0x2C, "/"),
+            (0x32, "`"), (0x21, "["), (0x2A, "\\"), (0x1E, "]"), (0x27, "'"),
+        ]
+        for (mac, label) in printable {
+            XCTAssertEqual(KeyCodes.macToJs(mac).map(KeyCodes.name), label, "mac 0x\(String(mac, radix: 16))")
+        }
+        // The two tables are exact inverses, in both directions.
+        var translated = 0
+        for js in UInt16(0)...255 {
+            guard let mac = KeyCodes.jsToMac(js) else { continue }
+            XCTAssertEqual(KeyCodes.macToJs(mac), js, "js \(js) → mac 0x\(String(mac, radix: 16)) doesn't come back")
+            translated += 1
+        }
+        for mac in UInt16(0)...0x7F {
+            guard let js = KeyCodes.macToJs(mac) else { continue }
+            XCTAssertEqual(KeyCodes.jsToMac(js), mac, "mac 0x\(String(mac, radix: 16)) → js \(js) doesn't come back")
+            translated -= 1
+        }
+        XCTAssertEqual(translated, 0, "one table has entries the other lacks")
+    }
+
+    /// Whitespace/control and arrow keys are bindable as Caps+<key>. (A
+    /// NextLine action's own injected Enter is skipped by the tap via
+    /// `KeyPoster.injectedMagic`, so Caps+Enter can't re-trigger itself.)
+    func testControlAndArrowKeysBindable() throws {
+        let cases: [(UInt16, UInt16, String)] = [
+            (0x31, 32, "Space"), (0x30, 9, "Tab"), (0x24, 13, "Enter"), (0x35, 27, "Esc"),
+            (0x7B, 37, "Left"), (0x7E, 38, "Up"), (0x7C, 39, "Right"), (0x7D, 40, "Down"),
+        ]
+        for (mac, js, label) in cases {
+            XCTAssertEqual(KeyCodes.macToJs(mac), js, label)
+            XCTAssertEqual(KeyCodes.name(js), label)
+        }
+    }
+
     /// F13–F17 and F19 must be recordable (the key-capture field calls
     /// `macToJs`, then `name` for display). F18 stays reserved (CapsLock remap),
     /// so it must NOT translate. This is the contract the recorder relies on.
@@ -54,6 +100,33 @@ final class HyperCapslockTests: XCTestCase {
         XCTAssertEqual(decoded, [entry])
     }
 
+    /// `repeat` is omitted for `.auto` (existing configs save unchanged) and
+    /// round-trips otherwise.
+    func testMappingRepeatModeRoundTrip() throws {
+        let plain = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left")
+        XCTAssertFalse(try YAMLEncoder().encode([plain]).contains("repeat"))
+
+        let once = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left",
+                                      repeatMode: .never)
+        let decoded = try YAMLDecoder().decode([ActionMappingEntry].self, from: try YAMLEncoder().encode([once]))
+        XCTAssertEqual(decoded.first?.repeatMode, .never)
+    }
+
+    func testRepeatModeRefires() {
+        let arrow = ActionConfig.directional(.down)
+        let shell = ActionConfig.command("open -a Calculator")
+        XCTAssertTrue(ActionExecutor.refiresOnRepeat(arrow, .auto))
+        // `.auto` keeps the old behavior: one-shot actions re-fire too, only a
+        // held modifier (or an input-source switch) doesn't; opting out takes an
+        // explicit `repeat: never`.
+        XCTAssertTrue(ActionExecutor.refiresOnRepeat(shell, .auto))
+        XCTAssertTrue(ActionExecutor.refiresOnRepeat(.jump(direction: .down, count: 5), .auto))
+        XCTAssertFalse(ActionExecutor.refiresOnRepeat(.modifierKey(.leftShift), .auto))
+        XCTAssertFalse(ActionExecutor.refiresOnRepeat(shell, .never))
+        XCTAssertFalse(ActionExecutor.refiresOnRepeat(arrow, .never))
+        XCTAssertTrue(ActionExecutor.refiresOnRepeat(shell, .onHold))
+    }
+
     /// Legacy 2.0 bare-list (inline action, no action_id) must still decode.
     func testLegacyBareListDecodes() throws {
         let legacy = "- trigger:\n    kind: hyper_plus_key\n    key: 72\n    with_shift: false\n  action:\n    kind: directional\n    action: left\n"
@@ -64,6 +137,1129 @@ final class HyperCapslockTests: XCTestCase {
         XCTAssertEqual(decoded[0].inlineAction, .directional(.left))
     }
 
+    /// A line jump keeps the pre-`unit` wire shape; a page jump round-trips.
+    func testJumpUnitWireFormat() throws {
+        let lineYaml = try YAMLEncoder().encode(ActionConfig.jump(direction: .down, count: 10))
+        XCTAssertFalse(lineYaml.contains("unit"))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: jump\ndirection: up\ncount: 10\n"),
+                       .jump(direction: .up, count: 10, unit: .line))
+
+        let page = ActionConfig.jump(direction: .up, count: 2, unit: .page)
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: try YAMLEncoder().encode(page)), page)
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: jump\ndirection: down\ncount: 1\nunit: paragraph\n"),
+                       .jump(direction: .down, count: 1, unit: .paragraph))
+    }
+
+    func testDirectionalLineAndDocumentKinds() throws {
+        // `home`/`end` stay the line-wise wire names; the document pair is new.
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: directional\naction: home\n"),
+                       .directional(.home))
+        let doc = ActionConfig.directional(.docStart)
+        XCTAssertTrue(try YAMLEncoder().encode(doc).contains("doc_start"))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: try YAMLEncoder().encode(doc)), doc)
+    }
+
+    func testWordMotionStyle() throws {
+        XCTAssertEqual(ActionExecutor.wordMotionFlags(.word), .maskAlternate)
+        XCTAssertEqual(ActionExecutor.wordMotionFlags(.subword), [.maskControl, .maskAlternate])
+        // Tolerant: missing or unknown values fall back to whole-word motion.
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").wordMotionStyle, .word)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "word_motion_style: camel\n").wordMotionStyle, .word)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "word_motion_style: subword\n").wordMotionStyle, .subword)
+    }
+
+    func testNextLineFollowsLineEdgeStyle() throws {
+        let saved = EngineState.shared.lineEdgeStyle
+        defer { EngineState.shared.lineEdgeStyle = saved }
+        let rec = RecordingEmitter()
+        func tap(_ kc: UInt16, _ flags: CGEventFlags) -> [RecordingEmitter.Event] {
+            [.key(kc, down: true, flags: flags), .key(kc, down: false, flags: flags)]
+        }
+        for (style, end, start) in [(LineEdgeStyle.visual, tap(KeyCodes.right, .maskCommand), tap(KeyCodes.left, .maskCommand)),
+                                    (.logical, tap(KeyCodes.e, .maskControl), tap(KeyCodes.a, .maskControl))] {
+            EngineState.shared.lineEdgeStyle = style
+            rec.reset()
+            ActionExecutor.execute(.independent(.nextLine), keyDown: true, activeModifiers: [], emitter: rec)
+            ActionExecutor.execute(.independent(.nextLine), keyDown: false, activeModifiers: [], emitter: rec)
+            PostQueue.drain()
+            XCTAssertEqual(rec.events, end + tap(KeyCodes.return, []), style.rawValue)
+            rec.reset()
+            ActionExecutor.execute(.independent(.openLineAbove), keyDown: true, activeModifiers: [], emitter: rec)
+            PostQueue.drain()
+            XCTAssertEqual(rec.events, start + tap(KeyCodes.return, []) + tap(KeyCodes.up, []), style.rawValue)
+        }
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").lineEdgeStyle, .visual)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "line_edge_style: logical\n").lineEdgeStyle, .logical)
+    }
+
+    func testCapsLayerModeAndOneShotExpiry() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").capsLayerMode, .hold)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "caps_layer_mode: sticky\n").capsLayerMode, .hold)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "caps_layer_mode: one_shot\n").capsLayerMode, .oneShot)
+        XCTAssertFalse(CapsLayerMode.oneShot.allowsHold)
+        XCTAssertTrue(CapsLayerMode.both.allowsHold && CapsLayerMode.both.allowsOneShot)
+
+        let state = EngineState.shared
+        defer { _ = state.swapOneShotArmedAtMs(0) }
+        _ = state.swapOneShotArmedAtMs(1_000)
+        XCTAssertTrue(state.consumeOneShot(now: 1_000 + EngineConstants.oneShotTimeoutMs))
+        XCTAssertFalse(state.consumeOneShot(now: 1_001), "consumed once, then disarmed")
+        _ = state.swapOneShotArmedAtMs(1_000)
+        XCTAssertFalse(state.consumeOneShot(now: 1_001 + EngineConstants.oneShotTimeoutMs), "lapsed")
+    }
+
+    func testHotkeyTriggerWireFormatAndGuard() throws {
+        let t = Trigger.hotkey(key: 77, ctrl: true, alt: true, cmd: false, shift: false)
+        let yaml = try YAMLEncoder().encode(t)
+        XCTAssertTrue(yaml.contains("kind: hotkey"))
+        XCTAssertFalse(yaml.contains("with_cmd"), "false modifiers are omitted")
+        XCTAssertEqual(try YAMLDecoder().decode(Trigger.self, from: yaml), t)
+        XCTAssertEqual(triggerFromUniqueID(triggerUniqueID(t)), t)
+        let bare = Trigger.hotkey(key: 77, ctrl: false, alt: false, cmd: false, shift: false)
+        XCTAssertEqual(triggerFromUniqueID(triggerUniqueID(bare)), bare)
+
+        XCTAssertFalse(Trigger.hotkeyAllowed(key: 77, ctrl: false, alt: false, cmd: false), "bare letter")
+        XCTAssertTrue(Trigger.hotkeyAllowed(key: 124, ctrl: false, alt: false, cmd: false), "F13 alone")
+        XCTAssertEqual(ActionExecutor.hotkeyFlags(ctrl: true, alt: false, cmd: true, shift: true),
+                       [.maskControl, .maskCommand, .maskShift])
+        XCTAssertEqual(hotkeyLabel(77, true, true, false, false), "⌃⌥M")
+        XCTAssertEqual(hotkeyLabel(129, false, false, false, false), "F18")
+        XCTAssertEqual(ConfigStore.triggerLabel(.hotkey(key: 135, ctrl: false, alt: false, cmd: true, shift: true)), "⇧⌘F24")
+    }
+
+    func testCountPrefixAccumulatesAndExpires() {
+        XCTAssertEqual(CountPrefix.digit(51), 3)
+        XCTAssertNil(CountPrefix.digit(74))
+        XCTAssertEqual(CountPrefix.accumulate(0, digit: 3), 3)
+        XCTAssertEqual(CountPrefix.accumulate(1, digit: 2), 12)
+        XCTAssertNil(CountPrefix.accumulate(0, digit: 0), "a leading 0 is not a count")
+        XCTAssertEqual(CountPrefix.accumulate(99, digit: 9), CountPrefix.maxCount)
+        XCTAssertEqual(CountPrefix.accumulate(999, digit: 9), CountPrefix.maxCount)
+
+        XCTAssertTrue(CountPrefix.isCountable(.directional(.down)))
+        XCTAssertTrue(CountPrefix.isCountable(.jump(direction: .up, count: 10, unit: .line)))
+        XCTAssertFalse(CountPrefix.isCountable(.directional(.home)))
+        XCTAssertFalse(CountPrefix.isCountable(.independent(.backspace)))
+
+        let state = EngineState.shared
+        let t0: UInt64 = 1_000_000
+        _ = state.takeCountPrefix(now: t0)
+        XCTAssertEqual(state.pushCountDigit(1, now: t0), 1)
+        XCTAssertEqual(state.pushCountDigit(5, now: t0 + 100), 15)
+        XCTAssertEqual(state.takeCountPrefix(now: t0 + 200), 15)
+        XCTAssertEqual(state.takeCountPrefix(now: t0 + 300), 1, "taking clears the prefix")
+        XCTAssertEqual(state.pushCountDigit(4, now: t0), 4)
+        XCTAssertEqual(state.takeCountPrefix(now: t0 + EngineConstants.countPrefixTimeoutMs + 1), 1, "a lapsed prefix counts as 1")
+        XCTAssertEqual(state.pushCountDigit(2, now: t0), 2)
+        XCTAssertEqual(state.pushCountDigit(7, now: t0 + EngineConstants.countPrefixTimeoutMs + 1), 7, "a lapsed prefix starts over")
+        _ = state.takeCountPrefix(now: t0)
+    }
+
+    /// A counted motion is one queued burst of taps, never more than `maxTaps`.
+    func testCountedMotionsFoldIntoOneCappedSequence() {
+        func taps(_ action: ActionConfig, _ count: Int, last: ActionConfig? = nil) -> Int? {
+            CountPrefix.countedTaps(action, count: count, last: last, activeModifiers: [], wordMotion: .word)?.taps
+        }
+        XCTAssertEqual(taps(.directional(.down), 3), 3)
+        XCTAssertEqual(taps(.directional(.down), CountPrefix.maxCount), CountPrefix.maxTaps)
+        XCTAssertEqual(taps(.jump(direction: .down, count: 5), 4), 20)
+        XCTAssertEqual(taps(.jump(direction: .down, count: 200), CountPrefix.maxCount), CountPrefix.maxTaps)
+        XCTAssertEqual(taps(.independent(.repeatLast), 3, last: .jump(direction: .up, count: 10)), 30)
+        XCTAssertNil(taps(.directional(.down), 1), "no count: the plain press path")
+        XCTAssertNil(taps(.directional(.home), 5))
+        // Counted Repeat Last of a side effect folds to nothing: it runs once.
+        XCTAssertNil(taps(.independent(.repeatLast), 999, last: .command("say hi")))
+        XCTAssertNil(taps(.independent(.repeatLast), 999, last: .goTo(line: 12, column: nil)))
+        XCTAssertNil(taps(.independent(.repeatLast), 999, last: nil))
+
+        let page = CountPrefix.countedTaps(.jump(direction: .up, count: 2, unit: .paragraph), count: 3, last: nil,
+                                           activeModifiers: .maskShift, wordMotion: .word)
+        XCTAssertEqual(page?.keycode, KeyCodes.up)
+        XCTAssertEqual(page?.flags, [.maskShift, .maskAlternate])
+        XCTAssertEqual(page?.taps, 6)
+    }
+
+    func testPreviewMappingsValidatesWithoutApplying() throws {
+        let yaml = """
+        mappings:
+          - trigger: { kind: hyper_plus_key, key: 72, with_shift: false }
+            action_id: builtin.move_left
+          - trigger: { kind: hyper_plus_key, key: 74, with_shift: false }
+            action_id: builtin.move_down
+          - trigger: { kind: hyper_plus_key, key: 72, with_shift: false }
+            action_id: builtin.move_right
+        """
+        let entries = try ConfigStore.previewMappings(yaml: yaml)
+        // Duplicate trigger: last value wins, first position kept.
+        XCTAssertEqual(entries.map(\.actionId), ["builtin.move_right", "builtin.move_down"])
+
+        XCTAssertThrowsError(try ConfigStore.previewMappings(yaml: "mappings: [ {")) { error in
+            XCTAssertTrue((error as? ConfigError)?.errorDescription?.hasPrefix("Invalid YAML") ?? false)
+        }
+        let unknownKind = """
+        - trigger: { kind: hyper_plus_key, key: 72, with_shift: false }
+          action: { kind: teleport }
+        """
+        XCTAssertThrowsError(try ConfigStore.previewMappings(yaml: unknownKind)) { error in
+            let msg = (error as? ConfigError)?.errorDescription ?? ""
+            XCTAssertTrue(msg.contains("Mapping #1") && msg.contains("teleport"), msg)
+        }
+        XCTAssertThrowsError(try ConfigStore.previewMappings(yaml: "actions: []\n"))
+
+        let diff = ConfigStore.mappingsDiff(from: ConfigStore.defaultMappings(), to: entries)
+        XCTAssertEqual(diff.added, 0)
+        XCTAssertEqual(diff.changed, 1)   // Caps+H rebound; Caps+J unchanged
+        XCTAssertEqual(diff.removed, ConfigStore.defaultMappings().count - 2)
+    }
+
+    /// Older documents are upgraded one step at a time to the current version.
+    func testConfigDocumentMigratesStepByStep() throws {
+        let bare = try XCTUnwrap(Yams.compose(yaml: "- { trigger: { kind: hyper_plus_key, key: 72, with_shift: false }, action_id: builtin.move_left }\n"))
+        let (upgraded, from) = try ConfigStore.migrateDocument(bare)
+        XCTAssertEqual(from, 1)
+        guard case .mapping(let map) = upgraded, case .sequence(let seq)? = map[Node("mappings")] else {
+            return XCTFail("bare list not wrapped: \(upgraded)")
+        }
+        XCTAssertEqual(seq.count, 1)
+
+        XCTAssertEqual(try ConfigStore.migrateDocument(XCTUnwrap(Yams.compose(yaml: "mappings: []\n"))).from, 2)
+        let current = try XCTUnwrap(Yams.compose(yaml: "version: \(ConfigStore.documentVersion)\nmappings: []\n"))
+        XCTAssertEqual(try ConfigStore.migrateDocument(current).node, current)
+        // A newer build's file passes through untouched.
+        let newer = try XCTUnwrap(Yams.compose(yaml: "version: 99\nmappings: []\n"))
+        XCTAssertEqual(try ConfigStore.migrateDocument(newer).from, 99)
+        XCTAssertThrowsError(try ConfigStore.documentVersion(of: XCTUnwrap(Yams.compose(yaml: "version: nope\n"))))
+    }
+
+    func testShiftVariantResolution() {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        func resolve(_ key: UInt16, shift: Bool) -> ActionConfig? {
+            ActionExecutor.resolveEntry(jsKeycode: key, shiftHeld: shift, ctx: ctx)
+                .flatMap { ActionExecutor.effectiveAction($0, ctx) }
+        }
+        MappingsRegistry.shared.set([
+            // (a) both variants bound: each press gets its own.
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 49, withShift: false), actionId: "builtin.move_left"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 49, withShift: true), actionId: "builtin.doc_start"),
+            // (b) only the plain variant, fallback-allowed.
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left"),
+            // (c) only the plain variant, carrying its own modifier intent.
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 188, withShift: false), inlineAction: .inputSource(inputSourceID: "com.apple.keylayout.ABC")),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 190, withShift: false), inlineAction: .command("true")),
+        ])
+        XCTAssertEqual(resolve(49, shift: false), .directional(.left))
+        XCTAssertEqual(resolve(49, shift: true), .directional(.docStart))
+
+        XCTAssertEqual(resolve(72, shift: true), .directional(.left))
+        let stroke = ActionExecutor.directionalKeystroke(.left, activeModifiers: .maskShift, wordMotion: .word)
+        XCTAssertEqual(stroke.keycode, KeyCodes.left)
+        XCTAssertTrue(stroke.flags.contains(.maskShift), "the fallback must post Shift+Left (select), not a bare Left")
+        XCTAssertEqual(ActionExecutor.directionalKeystroke(.wordForward, activeModifiers: .maskShift, wordMotion: .word).flags,
+                       [.maskShift, .maskAlternate])
+
+        XCTAssertNil(resolve(188, shift: true))
+        XCTAssertNil(resolve(190, shift: true))
+        XCTAssertNotNil(resolve(190, shift: false))
+    }
+
+    /// A passthrough key beats any mapping on it (both Shift layers), but only
+    /// in the apps it's limited to; other keys resolve as usual.
+    func testPassthroughKeysOverrideMatchingMappings() throws {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        MappingsRegistry.shared.set([
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 78, withShift: false), actionId: "builtin.move_left"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 78, withShift: true), actionId: "builtin.doc_end"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left"),
+        ])
+        let anywhere = RuntimeContext(frontmostBundleID: "com.apple.Safari")
+        let terminal = RuntimeContext(frontmostBundleID: "com.apple.Terminal")
+        let everywhere = [PassthroughKey(key: 78)]
+        XCTAssertNil(ActionExecutor.resolveEntry(jsKeycode: 78, shiftHeld: false, ctx: anywhere, passthrough: everywhere))
+        XCTAssertNil(ActionExecutor.resolveEntry(jsKeycode: 78, shiftHeld: true, ctx: anywhere, passthrough: everywhere))
+        XCTAssertNotNil(ActionExecutor.resolveEntry(jsKeycode: 72, shiftHeld: false, ctx: anywhere, passthrough: everywhere))
+
+        let inTerminal = [PassthroughKey(key: 78, apps: ["com.apple.terminal"])]
+        XCTAssertNil(ActionExecutor.resolveEntry(jsKeycode: 78, shiftHeld: false, ctx: terminal, passthrough: inTerminal))
+        XCTAssertNotNil(ActionExecutor.resolveEntry(jsKeycode: 78, shiftHeld: false, ctx: anywhere, passthrough: inTerminal))
+        XCTAssertFalse(ActionExecutor.isPassthrough(78, RuntimeContext(frontmostBundleID: nil), inTerminal))
+
+        let config = try YAMLDecoder().decode(AppConfig.self, from: "passthrough_keys:\n- key: 78\n- key: 72\n  apps: [com.apple.Terminal]\n")
+        XCTAssertEqual(config.passthroughKeys, [PassthroughKey(key: 78), PassthroughKey(key: 72, apps: ["com.apple.Terminal"])])
+    }
+
+    /// The effective-mappings query applies per-app rules and passthrough
+    /// exactly as a key press would in that app.
+    func testEffectiveMappingsFollowTheFrontmostApp() throws {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let inTerminal = MappingBinding(when: [.frontmostApp(include: ["com.apple.Terminal"], exclude: [])],
+                                        actionId: "builtin.doc_end")
+        MappingsRegistry.shared.set([
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left",
+                               bindings: [inTerminal]),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 78, withShift: false), actionId: "builtin.move_down"),
+        ])
+        let passthrough = [PassthroughKey(key: 78, apps: ["com.apple.Terminal"])]
+        let terminal = ActionExecutor.effectiveMappings(RuntimeContext(frontmostBundleID: "com.apple.Terminal"), passthrough: passthrough)
+        XCTAssertEqual(terminal.map(\.action), [.directional(.docEnd), .directional(.down)])
+        XCTAssertEqual(terminal.map(\.viaAppRule), [true, false])
+        XCTAssertEqual(terminal.map(\.passthrough), [false, true])
+        let safari = ActionExecutor.effectiveMappings(RuntimeContext(frontmostBundleID: "com.apple.Safari"), passthrough: passthrough)
+        XCTAssertEqual(safari.map(\.action), [.directional(.left), .directional(.down)])
+        XCTAssertEqual(safari.map(\.passthrough), [false, false])
+
+        let request = try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"get_effective_mappings","params":{"bundle_id":"com.apple.Terminal"}}"#.utf8))
+        XCTAssertEqual(request.method, .getEffectiveMappings)
+        XCTAssertEqual(request.params?.bundleId, "com.apple.Terminal")
+    }
+
+    /// The tray style round-trips through the config (an unknown value falls
+    /// back to the icon) and the socket; the title marks pass-through with a dot.
+    func testTrayStyleDecodesTolerantlyAndOverTheSocket() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "tray_style: both\n").trayStyle, .both)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "tray_style: sparkles\n").trayStyle, .icon)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "hide_dock_icon: true\n").trayStyle, .icon)
+        XCTAssertFalse(TrayStyle.title.showsIcon)
+        XCTAssertFalse(TrayStyle.icon.showsTitle)
+        XCTAssertEqual(TrayController.title(active: true), "HC")
+        XCTAssertEqual(TrayController.title(active: false), "HC•")
+
+        let request = try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"set_tray_style","params":{"style":"title"}}"#.utf8))
+        XCTAssertEqual(request.method, .setTrayStyle)
+        XCTAssertEqual(request.params?.style, .title)
+    }
+
+    /// The resolver query reports what the hook does: exact matches, the Shift
+    /// fallback (flagged), pass-through for unbound and passthrough keys.
+    func testResolveChordMatchesHookResolution() throws {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        let left = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left")
+        let docEnd = ActionMappingEntry(trigger: .hyperPlusKey(key: 74, withShift: true), actionId: "builtin.doc_end")
+        MappingsRegistry.shared.set([left, docEnd])
+        for (key, shift) in [(UInt16(72), false), (72, true), (74, true), (74, false), (75, false)] {
+            let resolved = ActionExecutor.resolveChord(jsKeycode: key, shiftHeld: shift, ctx: ctx, shiftLayers: .fallback, passthrough: [])
+            XCTAssertEqual(resolved?.entry, ActionExecutor.resolveEntry(jsKeycode: key, shiftHeld: shift, ctx: ctx,
+                                                                        shiftLayers: .fallback, passthrough: []))
+        }
+        XCTAssertEqual(ActionExecutor.resolveChord(jsKeycode: 72, shiftHeld: true, ctx: ctx, shiftLayers: .fallback, passthrough: []),
+                       .init(entry: left, action: .directional(.left), viaShiftFallback: true))
+        XCTAssertEqual(ActionExecutor.resolveChord(jsKeycode: 74, shiftHeld: true, ctx: ctx, shiftLayers: .fallback, passthrough: [])?
+                        .viaShiftFallback, false)
+        XCTAssertNil(ActionExecutor.resolveChord(jsKeycode: 72, shiftHeld: true, ctx: ctx, shiftLayers: .strict, passthrough: []))
+        XCTAssertNil(ActionExecutor.resolveChord(jsKeycode: 72, shiftHeld: false, ctx: ctx, shiftLayers: .fallback,
+                                                 passthrough: [PassthroughKey(key: 72)]))
+
+        let request = try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"resolve","params":{"key":72,"with_shift":true}}"#.utf8))
+        XCTAssertEqual(request.method, .resolve)
+        XCTAssertEqual(request.params?.key, 72)
+        XCTAssertEqual(request.params?.withShift, true)
+    }
+
+    func testModifierSpecificCapsChordsResolveSeparately() throws {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        let left = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left")
+        let ctrlH = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false, ctrl: true), actionId: "builtin.backspace")
+        MappingsRegistry.shared.set([left, ctrlH])
+        func resolve(_ key: UInt16, shift: Bool = false, _ modifiers: CGEventFlags) -> ActionMappingEntry? {
+            ActionExecutor.resolveEntry(jsKeycode: key, shiftHeld: shift, modifiers: modifiers, ctx: ctx,
+                                        shiftLayers: .fallback, passthrough: [])
+        }
+        XCTAssertEqual(resolve(72, []), left)
+        XCTAssertEqual(resolve(72, .maskControl), ctrlH)
+        // Caps lock / Fn bits don't count as chord modifiers.
+        XCTAssertEqual(resolve(72, [.maskControl, .maskSecondaryFn, .maskAlphaShift]), ctrlH)
+        // No Caps+Cmd+H or Caps+Ctrl+Opt+H: the plain chord takes it (and forwards the extras).
+        XCTAssertEqual(resolve(72, .maskCommand), left)
+        XCTAssertEqual(resolve(72, [.maskControl, .maskAlternate]), left)
+        // Shift fallback applies within the modified layer too: Caps+Ctrl+Shift+H
+        // falls back to Caps+Ctrl+H, not to the plain chord.
+        XCTAssertEqual(resolve(72, shift: true, .maskControl), ctrlH)
+        XCTAssertEqual(ActionExecutor.capsChordModifiers(ctrlH.trigger), .maskControl)
+        XCTAssertEqual(ActionExecutor.capsChordModifiers(left.trigger), [])
+
+        // with_ctrl/with_alt/with_cmd only appear when set; plain chords keep their ids.
+        let yaml = try YAMLEncoder().encode([left, ctrlH])
+        XCTAssertEqual(yaml.components(separatedBy: "with_ctrl: true").count, 2)
+        XCTAssertFalse(yaml.contains("with_alt"))
+        XCTAssertEqual(try YAMLDecoder().decode([ActionMappingEntry].self, from: yaml), [left, ctrlH])
+        XCTAssertEqual(triggerUniqueID(left.trigger), "hyper:72:n")
+        XCTAssertEqual(triggerUniqueID(ctrlH.trigger), "hyper:72:n:c")
+        XCTAssertEqual(triggerFromUniqueID("hyper:72:n:c"), ctrlH.trigger)
+        XCTAssertEqual(ConfigStore.triggerLabel(ctrlH.trigger), "Caps+Ctrl+H")
+    }
+
+    func testStrictShiftLayersDisableFallback() {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        MappingsRegistry.shared.set([
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 74, withShift: true), actionId: "builtin.doc_end"),
+        ])
+        XCTAssertNotNil(ActionExecutor.resolveEntry(jsKeycode: 72, shiftHeld: true, ctx: ctx, shiftLayers: .fallback))
+        XCTAssertNil(ActionExecutor.resolveEntry(jsKeycode: 72, shiftHeld: true, ctx: ctx, shiftLayers: .strict))
+        XCTAssertNotNil(ActionExecutor.resolveEntry(jsKeycode: 72, shiftHeld: false, ctx: ctx, shiftLayers: .strict))
+        XCTAssertNotNil(ActionExecutor.resolveEntry(jsKeycode: 74, shiftHeld: true, ctx: ctx, shiftLayers: .strict))
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "shift_layer_mode: strict\n").shiftLayerMode, .strict)
+        XCTAssertEqual(AppConfig().shiftLayerMode, .fallback)
+    }
+
+    func testAppControlWireFormat() throws {
+        let action = ActionConfig.appControl(.togglePause)
+        let yaml = try YAMLEncoder().encode(action)
+        XCTAssertTrue(yaml.contains("kind: app_control") && yaml.contains("command: toggle_pause"), yaml)
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: yaml), action)
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: app_control\ncommand: quit\n"), .appControl(.quit))
+        XCTAssertEqual(BuiltinActions.byID("builtin.toggle_pause")?.config, action)
+        // A shifted chord must not borrow a plain one that quits or pauses the app.
+        XCTAssertFalse(ActionExecutor.allowShiftFallback(action))
+    }
+
+    func testCapsToggleEnabledDefaultsOn() throws {
+        XCTAssertTrue(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").capsToggleEnabled)
+        XCTAssertFalse(try YAMLDecoder().decode(AppConfig.self, from: "caps_toggle_enabled: false\n").capsToggleEnabled)
+    }
+
+    func testCapsLockPinnedOffOnlyLetsAToggleClearIt() throws {
+        XCTAssertFalse(AppConfig().capsLockPinnedOff)
+        XCTAssertTrue(try YAMLDecoder().decode(AppConfig.self, from: "caps_lock_pinned_off: true\n").capsLockPinnedOff)
+        XCTAssertTrue(CapsLockState.toggleTarget(from: false, pinnedOff: false))
+        XCTAssertFalse(CapsLockState.toggleTarget(from: true, pinnedOff: false))
+        XCTAssertFalse(CapsLockState.toggleTarget(from: false, pinnedOff: true), "pinned off: a toggle never turns it on")
+        XCTAssertFalse(CapsLockState.toggleTarget(from: true, pinnedOff: true))
+    }
+
+    func testServiceStateRoundTripsAndDefaultsToRunning() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").serviceState, .running)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "service_state: bogus\n").serviceState, .running)
+        let cfg = AppConfig(serviceState: .paused)
+        let yaml = try YAMLEncoder().encode(cfg)
+        XCTAssertTrue(yaml.contains("service_state: paused"))
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: yaml), cfg)
+    }
+
+    func testControlRequestDecodesSocketWireFormat() throws {
+        let line = #"{"id":7,"token":"t","method":"simulate_action","params":{"action_id":"builtin.noop"}}"#
+        let req = try JSONDecoder().decode(ControlRequest.self, from: Data(line.utf8))
+        XCTAssertEqual(req.id, 7)
+        XCTAssertEqual(req.method, .simulateAction)
+        XCTAssertEqual(req.params?.actionId, "builtin.noop")
+
+        let upsert = #"{"token":"t","method":"upsert_mapping","params":{"mapping":{"trigger":{"kind":"hyper_plus_key","key":74,"with_shift":false},"action_id":"builtin.move_down"}}}"#
+        let m = try XCTUnwrap(JSONDecoder().decode(ControlRequest.self, from: Data(upsert.utf8)).params?.mapping)
+        XCTAssertEqual(m.trigger, .hyperPlusKey(key: 74, withShift: false))
+
+        XCTAssertThrowsError(try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"rm_rf"}"#.utf8)))
+        let reply = String(decoding: try JSONEncoder().encode(ControlResponse.failure(3, "Bad token")), as: UTF8.self)
+        XCTAssertTrue(reply.contains(#""ok":false"#) && reply.contains(#""id":3"#))
+        XCTAssertFalse(reply.contains("mappings"))
+    }
+
+    func testConfigErrorsCarryStableCodes() throws {
+        XCTAssertThrowsError(try ConfigStore.validate(.command("  "))) {
+            XCTAssertEqual(($0 as? ConfigError)?.code, .emptyCommand)
+        }
+        XCTAssertThrowsError(try ConfigStore.validate(.jump(direction: .down, count: ConfigStore.maxJumpCount + 1))) {
+            XCTAssertEqual(($0 as? ConfigError)?.code, .invalidJumpCount)
+        }
+        XCTAssertThrowsError(try ConfigStore.previewMappings(yaml: "mappings: 3")) {
+            XCTAssertEqual(($0 as? ConfigError)?.code, .parseError)
+        }
+        let reply = ControlResponse.failure(4, ConfigError.invalidEntry("jump count must be >= 1", code: .invalidJumpCount))
+        let json = String(decoding: try JSONEncoder().encode(reply), as: UTF8.self)
+        XCTAssertTrue(json.contains(#""error_code":"invalid_jump_count""#) && json.contains(#""error":"jump count must be >= 1""#))
+    }
+
+    func testPresetNamesStayInsidePresetsFolder() throws {
+        for ok in ["work", "Vim 2", "日本語"] { XCTAssertTrue(ConfigStore.isValidPresetName(ok), ok) }
+        for bad in ["", " ", " padded", ".hidden", "../escape", "a/b", "a:b", "two\nlines", String(repeating: "x", count: 65)] {
+            XCTAssertFalse(ConfigStore.isValidPresetName(bad), bad)
+        }
+        let line = #"{"token":"t","method":"load_preset","params":{"name":"work"}}"#
+        let req = try JSONDecoder().decode(ControlRequest.self, from: Data(line.utf8))
+        XCTAssertEqual(req.method, .loadPreset)
+        XCTAssertEqual(req.params?.name, "work")
+    }
+
+    func testConfigPathsListEveryFileWeKeep() throws {
+        let paths = ConfigStore.shared.configPaths()
+        XCTAssertEqual(paths.map(\.name), ["mappings", "settings", "presets", "backups", "usage_stats", "log"])
+        let dir = AppEnvironment.appSupportDirectory.path
+        for p in paths where p.name != "log" { XCTAssertTrue(p.path.hasPrefix(dir), p.path) }
+        XCTAssertEqual(paths.last?.path, FileLog.shared.path)
+        let json = String(decoding: try JSONEncoder().encode(paths[2]), as: UTF8.self)
+        XCTAssertTrue(json.contains(#""is_directory":true"#))
+    }
+
+    func testSelfTestReportNamesEachProblem() throws {
+        let healthy = SelfTest.Report(accessibilityGranted: true, tapInstalled: true, secureInput: false,
+                                      secureInputApp: nil, injectionWorks: true)
+        XCTAssertTrue(healthy.passed)
+        XCTAssertEqual(healthy.messages, [])
+
+        let blocked = SelfTest.Report(accessibilityGranted: true, tapInstalled: true, secureInput: true,
+                                      secureInputApp: "Terminal", injectionWorks: false)
+        XCTAssertEqual(blocked.findings, [.secureInput, .injectionBlocked])
+        XCTAssertTrue(blocked.messages[0].contains("Terminal"))
+        let json = String(decoding: try JSONEncoder().encode(blocked), as: UTF8.self)
+        XCTAssertTrue(json.contains(#""secure_input_app":"Terminal""#) && json.contains(#""injection_blocked""#))
+
+        let untested = SelfTest.Report(accessibilityGranted: false, tapInstalled: false, secureInput: false,
+                                       secureInputApp: nil, injectionWorks: nil)
+        XCTAssertEqual(untested.findings, [.accessibilityMissing, .tapNotInstalled, .injectionUntested])
+    }
+
+    func testStatusReportsSecureInput() throws {
+        let status = ControlStatus(status: "running", accessibilityGranted: true, secureInput: true,
+                                   mappingCount: 3, version: "1.0")
+        let json = String(decoding: try JSONEncoder().encode(status), as: UTF8.self)
+        XCTAssertTrue(json.contains(#""secure_input":true"#))
+    }
+
+    func testCapsTapChoiceReflectsMappingAndToggle() {
+        XCTAssertEqual(CapsTapChoice.current(singleTap: nil, toggleEnabled: true), .toggleCaps)
+        XCTAssertEqual(CapsTapChoice.current(singleTap: nil, toggleEnabled: false), .nothing)
+        let esc = ActionMappingEntry(trigger: .singleTapHyper, inlineAction: CapsTapChoice.escapeAction)
+        XCTAssertEqual(CapsTapChoice.current(singleTap: esc, toggleEnabled: true), .escape)
+        let noop = ActionMappingEntry(trigger: .singleTapHyper, actionId: "builtin.noop")
+        XCTAssertEqual(CapsTapChoice.current(singleTap: noop, toggleEnabled: true), .nothing)
+        let other = ActionMappingEntry(trigger: .singleTapHyper, actionId: "builtin.backspace")
+        XCTAssertEqual(CapsTapChoice.current(singleTap: other, toggleEnabled: true), .custom)
+        var perApp = esc
+        perApp.bindings = [MappingBinding(when: [.frontmostApp(include: ["com.apple.Terminal"], exclude: [])], actionId: "builtin.noop")]
+        XCTAssertEqual(CapsTapChoice.current(singleTap: perApp, toggleEnabled: true), .custom)
+    }
+
+    func testCommandCaptureWireFormatAndOutputBound() throws {
+        XCTAssertFalse(try YAMLEncoder().encode(ActionConfig.command("date")).contains("capture"))
+        let yaml = try YAMLEncoder().encode(ActionConfig.command("git branch --show-current", capture: true))
+        XCTAssertTrue(yaml.contains("capture: true"))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: yaml),
+                       .command("git branch --show-current", capture: true))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: command\ncommand: date\n"), .command("date"))
+
+        let pipe = Pipe()
+        pipe.fileHandleForWriting.write(Data(repeating: UInt8(ascii: "x"), count: 8192))
+        try pipe.fileHandleForWriting.close()
+        XCTAssertEqual(ActionExecutor.drain(pipe.fileHandleForReading, limit: 1000).count, 1000)
+    }
+
+    func testCapturedCommandIsTerminatedOnTimeout() throws {
+        let done = try ActionExecutor.capture(ActionExecutor.scriptInvocation(.shell, "echo hi"), timeout: 5)
+        XCTAssertFalse(done.timedOut)
+        XCTAssertEqual(done.status, 0)
+        XCTAssertEqual(String(decoding: done.stdout, as: UTF8.self), "hi\n")
+
+        let start = Date()
+        let hung = try ActionExecutor.capture(ActionExecutor.scriptInvocation(.shell, "sleep 30"), timeout: 0.2)
+        XCTAssertTrue(hung.timedOut)
+        XCTAssertNotEqual(hung.status, 0)
+        XCTAssertLessThan(Date().timeIntervalSince(start), 5, "terminated, not waited out")
+    }
+
+    func testRepeatedInputSourceSwitchesAreSuppressed() {
+        let window = InputSourceController.repeatWindow
+        XCTAssertFalse(InputSourceController.isRepeat("com.apple.keylayout.ABC", at: 10, last: nil))
+        XCTAssertTrue(InputSourceController.isRepeat("com.apple.keylayout.ABC", at: 10 + window / 2,
+                                                     last: ("com.apple.keylayout.ABC", 10)))
+        XCTAssertFalse(InputSourceController.isRepeat("com.apple.keylayout.ABC", at: 10 + window,
+                                                      last: ("com.apple.keylayout.ABC", 10)))
+        // A different source always goes through, however quickly it follows.
+        XCTAssertFalse(InputSourceController.isRepeat("com.apple.inputmethod.SCIM.ITABC", at: 10.01,
+                                                      last: ("com.apple.keylayout.ABC", 10)))
+        // And input-source mappings don't re-fire on autorepeat by default.
+        XCTAssertFalse(ActionExecutor.refiresOnRepeat(.inputSource(inputSourceID: "com.apple.keylayout.ABC"), .auto))
+        XCTAssertFalse(ActionExecutor.refiresOnRepeat(.inputSource(inputSourceID: "com.apple.keylayout.ABC"), .never))
+        XCTAssertTrue(ActionExecutor.refiresOnRepeat(.inputSource(inputSourceID: "com.apple.keylayout.ABC"), .onHold))
+    }
+
+    func testEventLocationsDefaultToHidAndRoundTrip() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").tapLocation, .hid)
+        var config = AppConfig()
+        config.tapLocation = .session
+        config.postLocation = .annotatedSession
+        let yaml = try YAMLEncoder().encode(config)
+        XCTAssertTrue(yaml.contains("tap_location: session"))
+        XCTAssertTrue(yaml.contains("post_location: annotated_session"))
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: yaml), config)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "tap_location: kernel\n").tapLocation, .hid)
+        XCTAssertEqual(EventTapLocation.annotatedSession.cgLocation, .cgAnnotatedSessionEventTap)
+    }
+
+    func testOnboardingFlagAndCapsRemapProbe() throws {
+        XCTAssertFalse(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").onboardingCompleted)
+        let yaml = try YAMLEncoder().encode(AppConfig(onboardingCompleted: true))
+        XCTAssertTrue(yaml.contains("onboarding_completed: true"))
+        XCTAssertTrue(try YAMLDecoder().decode(AppConfig.self, from: yaml).onboardingCompleted)
+
+        // `hidutil property --get UserKeyMapping` prints decimal usages.
+        let active = """
+        (
+            {
+            HIDKeyboardModifierMappingDst = 30064771181;
+            HIDKeyboardModifierMappingSrc = 30064771129;
+        },
+            {
+            HIDKeyboardModifierMappingDst = 30064771183;
+            HIDKeyboardModifierMappingSrc = 30064771302;
+        }
+        )
+        """
+        XCTAssertTrue(HidUtil.capsRemapPresent(in: active))
+        XCTAssertFalse(HidUtil.capsRemapPresent(in: "(\n)\n"))
+        XCTAssertFalse(HidUtil.capsRemapPresent(in: "(null)"))
+        // CapsLock mapped somewhere else isn't our remap.
+        XCTAssertFalse(HidUtil.capsRemapPresent(in: """
+        ({ HIDKeyboardModifierMappingDst = 30064771113; HIDKeyboardModifierMappingSrc = 30064771129; })
+        """))
+    }
+
+    /// Raw Caps Lock events are read only when the remap is really missing.
+    func testRawCapsFallbackOnlyWithoutRemap() {
+        XCTAssertFalse(HidUtil.needsRawCapsFallback(applied: true, remapActive: true))
+        XCTAssertTrue(HidUtil.needsRawCapsFallback(applied: false, remapActive: false))
+        XCTAssertTrue(HidUtil.needsRawCapsFallback(applied: false, remapActive: nil))
+        // A rejected user remap keeps the earlier CapsLock → F18.
+        XCTAssertFalse(HidUtil.needsRawCapsFallback(applied: false, remapActive: true))
+        XCTAssertFalse(EngineState().rawCapsFallback)
+    }
+
+    /// A layer left on with no keys for the timeout, and its key up, is stuck;
+    /// a key still held down, or the latching raw fallback, is not.
+    func testTapThreadWorkRunsOnTheTapRunLoop() {
+        var ran = false
+        KeyboardHook.perform(on: nil) { ran = true }
+        XCTAssertTrue(ran, "no tap loop: inline")
+
+        let started = DispatchSemaphore(value: 0)
+        let loop = OSAllocatedUnfairLock<CFRunLoop?>(initialState: nil)
+        let thread = Thread {
+            loop.withLock { $0 = CFRunLoopGetCurrent() }
+            // A source keeps the loop running until it's stopped.
+            let port = Port()
+            RunLoop.current.add(port, forMode: .common)
+            started.signal()
+            CFRunLoopRun()
+        }
+        thread.start()
+        started.wait()
+        let rl = loop.withLock { $0 }
+        let done = expectation(description: "ran on the tap loop")
+        KeyboardHook.perform(on: rl) {
+            XCTAssertTrue(CFRunLoopGetCurrent() === rl)
+            XCTAssertFalse(Thread.isMainThread)
+            done.fulfill()
+        }
+        wait(for: [done], timeout: 2)
+        if let rl { CFRunLoopStop(rl) }
+    }
+
+    func testLayerWatchdogOnlyReleasesAnIdleLayerWithTheKeyUp() {
+        let t0: UInt64 = 1_000_000
+        let late = t0 + EngineConstants.layerStuckTimeoutMs + 1
+        XCTAssertTrue(LayerWatchdog.isStuck(capsDown: true, lastActivityMs: t0, now: late, physicallyDown: false, rawFallback: false))
+        XCTAssertFalse(LayerWatchdog.isStuck(capsDown: true, lastActivityMs: t0, now: t0 + 5_000, physicallyDown: false, rawFallback: false))
+        XCTAssertFalse(LayerWatchdog.isStuck(capsDown: true, lastActivityMs: t0, now: late, physicallyDown: true, rawFallback: false))
+        XCTAssertFalse(LayerWatchdog.isStuck(capsDown: true, lastActivityMs: t0, now: late, physicallyDown: false, rawFallback: true))
+        XCTAssertFalse(LayerWatchdog.isStuck(capsDown: false, lastActivityMs: t0, now: late, physicallyDown: false, rawFallback: false))
+    }
+
+    /// Another layer key is what gets remapped to F18; Caps Lock is then left
+    /// alone, and a user remap can't claim the same key.
+    func testLayerKeyReplacesCapsLockInTheRemap() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "show_hud: true\n").layerKey, .capsLock)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "layer_key: tab\n").layerKey, .capsLock, "unknown → Caps Lock")
+        let yaml = try YAMLEncoder().encode(AppConfig(layerKey: .rightOption))
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: yaml).layerKey, .rightOption)
+
+        let remap = KeyRemap(source: .rightCommand, destination: .f17)
+        let payload = HidUtil.buildPayload(layerKey: .rightOption, extra: [remap, KeyRemap(source: .rightOption, destination: .f13)])
+        XCTAssertTrue(payload.hasPrefix(#"{"UserKeyMapping":[{"HIDKeyboardModifierMappingSrc":0x7000000e6,"HIDKeyboardModifierMappingDst":0x70000006d}"#))
+        XCTAssertFalse(payload.contains("0x700000039"), "Caps Lock stays native")
+        XCTAssertFalse(payload.contains("0x700000068"), "a remap of the layer key is dropped")
+
+        // 0x7000000E6 = 30064771302
+        let rightOption = "({ HIDKeyboardModifierMappingDst = 30064771181; HIDKeyboardModifierMappingSrc = 30064771302; })"
+        XCTAssertTrue(HidUtil.capsRemapPresent(in: rightOption, layerKey: .rightOption))
+        XCTAssertFalse(HidUtil.capsRemapPresent(in: rightOption))
+        XCTAssertFalse(HidUtil.needsRawCapsFallback(applied: false, remapActive: false, layerKey: .rightOption))
+
+        XCTAssertNoThrow(try ConfigStore.validate(layerKey: .capsLock, keyRemaps: [remap]))
+        XCTAssertThrowsError(try ConfigStore.validate(layerKey: .rightCommand, keyRemaps: [remap])) { error in
+            XCTAssertEqual((error as? ConfigError)?.code, .layerKeyInUse)
+        }
+    }
+
+    /// The sandbox only takes actions whose effect stays inside its text field.
+    func testActionSandboxOnlyRunsFieldLocalActions() {
+        XCTAssertTrue(ActionSandbox.isSandboxable(.directional(.wordForward)))
+        XCTAssertTrue(ActionSandbox.isSandboxable(.jump(direction: .down, count: 5)))
+        XCTAssertTrue(ActionSandbox.isSandboxable(.independent(.insertQuotes)))
+        XCTAssertTrue(ActionSandbox.isSandboxable(.wrapPair(open: "(", close: ")", innerSpaces: 0)))
+        XCTAssertTrue(ActionSandbox.isSandboxable(.keyCombo(targetKey: 37, withCtrl: true, withAlt: false,
+                                                           withCmd: false, withTargetShift: false)))
+        // ⌘ combos would reach our own menu (⌘Q, ⌘W).
+        XCTAssertFalse(ActionSandbox.isSandboxable(.keyCombo(targetKey: 12, withCtrl: false, withAlt: false,
+                                                            withCmd: true, withTargetShift: false)))
+        XCTAssertFalse(ActionSandbox.isSandboxable(.independent(.toggleCapsLock)))
+        XCTAssertFalse(ActionSandbox.isSandboxable(.command("true")))
+        XCTAssertFalse(ActionSandbox.isSandboxable(.notify(title: "t", body: "")))
+        XCTAssertEqual((ActionSandbox.sampleText as NSString).substring(from: ActionSandbox.sampleCaret).prefix(4), "over")
+    }
+
+    func testBindableKeysFollowTheTranslationTables() throws {
+        let keys = KeyCodes.bindableKeys
+        XCTAssertEqual(keys.map(\.jsKeycode), keys.map(\.jsKeycode).sorted())
+        XCTAssertEqual(keys.first { $0.jsKeycode == 65 }, BindableKey(jsKeycode: 65, displayName: "A", supported: true))
+        // F18 is the CapsLock remap target: named, listed, never bindable.
+        XCTAssertEqual(keys.first { $0.jsKeycode == 129 }?.supported, false)
+        XCTAssertEqual(keys.first { $0.jsKeycode == 135 }?.displayName, "F24")
+        XCTAssertNil(keys.first { $0.jsKeycode == 200 })
+        // Every supported key round-trips through both tables.
+        for key in keys where key.supported {
+            XCTAssertEqual(KeyCodes.jsToMac(key.jsKeycode).flatMap(KeyCodes.macToJs), key.jsKeycode)
+        }
+        let json = String(decoding: try JSONEncoder().encode(keys[0]), as: UTF8.self)
+        XCTAssertTrue(json.contains("\"js_keycode\":8") && json.contains("\"display_name\":\"Backspace\""))
+    }
+
+    func testLogTailKeepsTheLastLines() {
+        XCTAssertEqual(FileLog.lastLines(of: "a\nb\nc\n", 2), "b\nc")
+        XCTAssertEqual(FileLog.lastLines(of: "a\nb\nc", 2), "b\nc")
+        XCTAssertEqual(FileLog.lastLines(of: "a\nb\n", 10), "a\nb")
+        XCTAssertEqual(FileLog.lastLines(of: "a\n\nb\n", 2), "\nb")
+        XCTAssertEqual(FileLog.lastLines(of: "", 5), "")
+        XCTAssertEqual(FileLog.lastLines(of: "a\nb", 0), "")
+    }
+
+    /// Debug lines cost nothing until a burst turns them on — the message
+    /// isn't even built — and the socket takes the burst length in seconds.
+    func testDebugLogLinesOnlyBuiltDuringABurst() throws {
+        defer { FileLog.shared.setDebugEnabled(false) }
+        var built = 0
+        func message() -> String { built += 1; return "test debug line" }
+        FileLog.shared.setDebugEnabled(false)
+        FileLog.shared.debug(message())
+        XCTAssertEqual(built, 0)
+        FileLog.shared.setDebugEnabled(true)
+        FileLog.shared.debug(message())
+        XCTAssertEqual(built, 1)
+        XCTAssertTrue(FileLog.shared.tail(lines: 1).hasSuffix("[DEBUG] test debug line"))
+
+        let request = try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"debug_logging_burst","params":{"seconds":30}}"#.utf8))
+        XCTAssertEqual(request.method, .debugLoggingBurst)
+        XCTAssertEqual(request.params?.seconds, 30)
+    }
+
+    func testLogRotatesPastTheCapKeepingTwoCopies() throws {
+        let dir = FileManager.default.temporaryDirectory.appendingPathComponent(UUID().uuidString)
+        try FileManager.default.createDirectory(at: dir, withIntermediateDirectories: true)
+        defer { try? FileManager.default.removeItem(at: dir) }
+        let path = dir.appendingPathComponent("test.log").path
+        func write(_ text: String) throws { try text.write(toFile: path, atomically: true, encoding: .utf8) }
+        func read(_ p: String) -> String? { try? String(contentsOfFile: p, encoding: .utf8) }
+
+        try write("small")
+        XCTAssertFalse(FileLog.rotateIfNeeded(path, maxBytes: 10))
+        XCTAssertEqual(read(path), "small")
+
+        for generation in ["first-over-cap", "second-over-cap", "third-over-cap"] {
+            try write(generation)
+            XCTAssertTrue(FileLog.rotateIfNeeded(path, maxBytes: 10))
+            XCTAssertFalse(FileManager.default.fileExists(atPath: path))
+        }
+        XCTAssertEqual(read(path + ".1"), "third-over-cap")
+        XCTAssertEqual(read(path + ".2"), "second-over-cap")
+        XCTAssertFalse(FileManager.default.fileExists(atPath: path + ".3"))
+
+        XCTAssertEqual(AppConfig().logMaxMb, 5)
+        let decoded = try YAMLDecoder().decode(AppConfig.self, from: "log_max_mb: 20\n")
+        XCTAssertEqual(decoded.logMaxMb, 20)
+    }
+
+    func testSelectionTransforms() {
+        XCTAssertEqual(SelectionEditor.transform("Hello wörld", .uppercase), "HELLO WÖRLD")
+        XCTAssertEqual(SelectionEditor.transform("Hello WÖRLD", .lowercase), "hello wörld")
+        XCTAssertEqual(SelectionEditor.transform("  two words \n\t", .trim), "two words")
+        XCTAssertEqual(BuiltinActions.byID("builtin.trim_selection")?.config, .transformSelection(.trim))
+        XCTAssertFalse(ActionExecutor.allowShiftFallback(.transformSelection(.uppercase)))
+    }
+
+    func testRawEscapeModifierPassesTheKeyThrough() {
+        let h: UInt16 = 0x04
+        XCTAssertEqual(ActionExecutor.rawEscapeFlags(keycode: h, activeModifiers: .maskSecondaryFn, escape: .fn), .maskSecondaryFn)
+        XCTAssertNil(ActionExecutor.rawEscapeFlags(keycode: h, activeModifiers: [], escape: .fn))
+        XCTAssertNil(ActionExecutor.rawEscapeFlags(keycode: h, activeModifiers: .maskSecondaryFn, escape: .off))
+        // Arrows carry the Fn flag on their own; that isn't the escape.
+        XCTAssertNil(ActionExecutor.rawEscapeFlags(keycode: KeyCodes.left, activeModifiers: .maskSecondaryFn, escape: .fn))
+        XCTAssertEqual(ActionExecutor.rawEscapeFlags(keycode: KeyCodes.left, activeModifiers: [.maskControl, .maskShift],
+                                                     escape: .control), .maskControl)
+        XCTAssertEqual(AppConfig().rawEscapeModifier, .fn)
+    }
+
+    func testCapsReleaseClassificationBoundaries() {
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 0, didRemap: false, thresholdMs: 200), .shortTap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 199, didRemap: false, thresholdMs: 200), .shortTap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 200, didRemap: false, thresholdMs: 200), .shortTap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 201, didRemap: false, thresholdMs: 200), .suppress)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 5_000, didRemap: false, thresholdMs: 200), .suppress)
+        // A remap during the hold wins, short or long.
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 50, didRemap: true, thresholdMs: 200), .afterRemap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 200, didRemap: true, thresholdMs: 200), .afterRemap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: 900, didRemap: true, thresholdMs: 200), .afterRemap)
+        // Default threshold is the engine's.
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: EngineConstants.capsTapMaxMs, didRemap: false), .shortTap)
+        XCTAssertEqual(ActionExecutor.classifyCapsRelease(heldMs: EngineConstants.capsTapMaxMs + 1, didRemap: false), .suppress)
+    }
+
+    func testDefaultJumpCountsAndCap() throws {
+        let up = Trigger.hyperPlusKey(key: 85, withShift: false)
+        let down = Trigger.hyperPlusKey(key: 68, withShift: false)
+        // The shipped count keeps the built-in ids.
+        let shipped = ConfigStore.defaultMappings(jumpCounts: [.up: 10, .down: 10])
+        XCTAssertEqual(shipped, ConfigStore.defaultMappings())
+        XCTAssertEqual(shipped.first { $0.trigger == up }?.actionId, "builtin.jump_up_10")
+
+        let custom = ConfigStore.defaultMappings(jumpCounts: [.up: 5, .down: 15])
+        XCTAssertEqual(custom.first { $0.trigger == up }?.inlineAction, .jump(direction: .up, count: 5))
+        XCTAssertEqual(custom.first { $0.trigger == down }?.inlineAction, .jump(direction: .down, count: 15))
+        XCTAssertEqual(custom.count, shipped.count)
+        XCTAssertTrue(ConfigStore.isDefaultJump(up))
+        XCTAssertFalse(ConfigStore.isDefaultJump(.hyperPlusKey(key: 72, withShift: false)))
+
+        var config = try YAMLDecoder().decode(AppConfig.self, from: "default_jump_count: 8\ndefault_jump_count_down: 20\n")
+        XCTAssertEqual(config.defaultJumpCount(.up), 8)
+        XCTAssertEqual(config.defaultJumpCount(.down), 20)
+        config = AppConfig()
+        XCTAssertEqual(config.defaultJumpCount(.up), 10)
+
+        XCTAssertNoThrow(try ConfigStore.validate(.jump(direction: .down, count: ConfigStore.maxJumpCount)))
+        XCTAssertThrowsError(try ConfigStore.validate(.jump(direction: .down, count: 10_000)))
+    }
+
+    /// Actions run against a recording emitter post exactly the expected
+    /// keys; sequences land once the post queue drains.
+    func testExecuteEmitsExpectedKeys() {
+        let rec = RecordingEmitter()
+        ActionExecutor.execute(.directional(.wordForward), keyDown: true, activeModifiers: [], emitter: rec)
+        ActionExecutor.execute(.directional(.wordForward), keyDown: false, activeModifiers: [], emitter: rec)
+        XCTAssertEqual(rec.events, [.key(KeyCodes.right, down: true, flags: .maskAlternate),
+                                    .key(KeyCodes.right, down: false, flags: .maskAlternate)])
+
+        rec.reset()
+        ActionExecutor.execute(.jump(direction: .down, count: 3), keyDown: true, activeModifiers: [], emitter: rec)
+        ActionExecutor.execute(.jump(direction: .down, count: 3), keyDown: false, activeModifiers: [], emitter: rec)
+        PostQueue.drain()
+        let tap: [RecordingEmitter.Event] = [.key(KeyCodes.down, down: true, flags: []), .key(KeyCodes.down, down: false, flags: [])]
+        XCTAssertEqual(rec.events, tap + tap + tap)
+
+        rec.reset()
+        ActionExecutor.execute(.keyCombo(targetKey: 86, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: true),
+                               keyDown: true, activeModifiers: .maskAlternate, emitter: rec)
+        XCTAssertEqual(rec.events, [.key(KeyCodes.jsToMac(86)!, down: true, flags: [.maskAlternate, .maskCommand, .maskShift])])
+
+        rec.reset()
+        ActionExecutor.execute(.wrapPair(open: "(", close: ")", innerSpaces: 1), keyDown: true, activeModifiers: [], emitter: rec)
+        PostQueue.drain()
+        XCTAssertEqual(Array(rec.events.prefix(4)), [.text("("), .text(" "), .text(" "), .text(")")])
+        XCTAssertEqual(rec.events.count, 4 + 2 * 2)
+    }
+
+    /// A go-to taps its shortcut, types `line:column` and confirms; a count
+    /// supplies a missing line, and with neither only the field opens.
+    func testGoToTypesLineAfterShortcutAndTakesCount() throws {
+        let vscode = GoToShortcut(targetKey: 71, withCtrl: true)
+        let rec = RecordingEmitter()
+        ActionExecutor.execute(.goTo(line: 12, column: 5, shortcut: vscode), keyDown: true, activeModifiers: .maskShift, emitter: rec)
+        ActionExecutor.execute(.goTo(line: 12, column: 5, shortcut: vscode), keyDown: false, activeModifiers: [], emitter: rec)
+        PostQueue.drain()
+        let g = KeyCodes.jsToMac(71)!
+        XCTAssertEqual(rec.events, [.key(g, down: true, flags: .maskControl), .key(g, down: false, flags: .maskControl),
+                                    .text("1"), .text("2"), .text(":"), .text("5"),
+                                    .key(KeyCodes.return, down: true, flags: []), .key(KeyCodes.return, down: false, flags: [])])
+
+        rec.reset()
+        ActionExecutor.execute(.goTo(line: nil, column: nil), keyDown: true, activeModifiers: [], emitter: rec)
+        PostQueue.drain()
+        let l = KeyCodes.jsToMac(76)!
+        XCTAssertEqual(rec.events, [.key(l, down: true, flags: .maskCommand), .key(l, down: false, flags: .maskCommand)])
+
+        XCTAssertEqual(CountPrefix.fillingLine(.goTo(line: nil, column: 3), count: 123), .goTo(line: 123, column: 3))
+        XCTAssertEqual(CountPrefix.fillingLine(.goTo(line: 7, column: nil), count: 123), .goTo(line: 7, column: nil))
+        XCTAssertEqual(CountPrefix.fillingLine(.goTo(line: nil, column: nil), count: 1), .goTo(line: nil, column: nil))
+        XCTAssertEqual(CountPrefix.fillingLine(.directional(.down), count: 5), .directional(.down))
+
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: go_to\n"), .goTo(line: nil, column: nil))
+        XCTAssertThrowsError(try ConfigStore.validate(.goTo(line: 0, column: nil)))
+        XCTAssertNoThrow(try ConfigStore.validate(.goTo(line: nil, column: 4)))
+    }
+
+    /// Date/time formats are strftime's; a bad `%` code is refused at save,
+    /// and the action types the press's time one character at a time.
+    func testInsertDateTimeFormatsAndValidates() throws {
+        let date = Date(timeIntervalSince1970: 1_700_000_000)  // 2023-11-14 22:13:20 UTC
+        XCTAssertEqual(DateTimeText.render("%Y-%m-%d %H:%M:%S", at: date, utc: true), "2023-11-14 22:13:20")
+        XCTAssertEqual(DateTimeText.render("%a, %d %b %Y", at: date, utc: true), "Tue, 14 Nov 2023")
+        XCTAssertEqual(DateTimeText.render("100%% at %H", at: date, utc: true), "100% at 22")
+        for preset in DateTimeText.presets { XCTAssertNil(DateTimeText.problem(preset), preset) }
+        XCTAssertNotNil(DateTimeText.problem(""))
+        XCTAssertNotNil(DateTimeText.problem("%Q"))
+        XCTAssertNotNil(DateTimeText.problem("50%"))
+        XCTAssertNil(DateTimeText.problem("%Ey week %V"))
+        XCTAssertThrowsError(try ConfigStore.validate(.insertDateTime(format: "%Y-%q")))
+        XCTAssertNoThrow(try ConfigStore.validate(.insertDateTime(format: "%Y")))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: insert_date_time\n"),
+                       .insertDateTime(format: DateTimeText.defaultFormat))
+
+        let rec = RecordingEmitter()
+        ActionExecutor.execute(.insertDateTime(format: "%Y"), keyDown: true, activeModifiers: [], emitter: rec)
+        ActionExecutor.execute(.insertDateTime(format: "%Y"), keyDown: false, activeModifiers: [], emitter: rec)
+        PostQueue.drain()
+        let year = String(Calendar.current.component(.year, from: Date()))
+        XCTAssertEqual(rec.events, year.map { .text(String($0)) })
+    }
+
+    /// Sticky Shift adds Shift to the motions that follow within the
+    /// timeout, and any other chord (or the timeout) drops it.
+    func testStickyShiftExtendsMotionsUntilAnotherChord() {
+        let state = EngineState()
+        let limit = EngineConstants.stickyShiftTimeoutMs
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .directional(.down), now: 1000, state: state), [])
+        XCTAssertTrue(state.toggleStickyShift(now: 1000))
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .directional(.down), now: 1500, state: state), .maskShift)
+        // Each use restarts the timeout.
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .jump(direction: .down, count: 10), now: 1500 + limit, state: state),
+                       .maskShift)
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .independent(.backspace), now: 1600 + limit, state: state), [])
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .directional(.down), now: 1700 + limit, state: state), [])
+
+        XCTAssertTrue(state.toggleStickyShift(now: 10_000))
+        XCTAssertEqual(ActionExecutor.stickyShiftFlags(for: .directional(.left), now: 10_001 + limit, state: state), [])
+        XCTAssertTrue(state.toggleStickyShift(now: 20_000))
+        XCTAssertFalse(state.toggleStickyShift(now: 20_100))
+        XCTAssertEqual(BuiltinActions.byID("builtin.sticky_shift")?.config, .independent(.stickyShift))
+    }
+
+    func testRepeatLastSkipsItselfAndHeldModifiers() throws {
+        XCTAssertTrue(ActionExecutor.isRepeatable(.jump(direction: .down, count: 10)))
+        XCTAssertTrue(ActionExecutor.isRepeatable(.command("true")))
+        XCTAssertFalse(ActionExecutor.isRepeatable(.independent(.repeatLast)))
+        XCTAssertFalse(ActionExecutor.isRepeatable(.independent(.noop)))
+        XCTAssertFalse(ActionExecutor.isRepeatable(.modifierKey(.leftShift)))
+        XCTAssertTrue(CountPrefix.isCountable(.independent(.repeatLast)))
+        XCTAssertEqual(BuiltinActions.byID("builtin.repeat_last")?.config, .independent(.repeatLast))
+        let decoded = try YAMLDecoder().decode(ActionConfig.self, from: "kind: independent\naction: repeat_last\n")
+        XCTAssertEqual(decoded, .independent(.repeatLast))
+    }
+
+    /// A bounced second press is dropped along with its release; a press
+    /// past the window, auto-repeat, and a 0 ms window all pass.
+    func testKeyDebounceDropsChatterOnly() throws {
+        let d = KeyDebounce()
+        func drop(_ key: UInt16, _ down: Bool, at ms: UInt64, repeat isRepeat: Bool = false, window: UInt64 = 30) -> Bool {
+            d.shouldDrop(keycode: key, keyDown: down, isRepeat: isRepeat, now: ms, windowMs: window)
+        }
+        XCTAssertFalse(drop(4, true, at: 1000))
+        XCTAssertFalse(drop(4, false, at: 1010))
+        XCTAssertTrue(drop(4, true, at: 1020))    // chatter
+        XCTAssertTrue(drop(4, false, at: 1025))   // …and its up
+        XCTAssertFalse(drop(4, true, at: 1100))   // a real second press
+        XCTAssertFalse(drop(4, true, at: 1105, repeat: true))
+        XCTAssertTrue(drop(4, true, at: 1110))    // bounce while still held: only the down goes
+        XCTAssertFalse(drop(4, false, at: 1200))  // the real release still arrives
+        XCTAssertFalse(drop(5, true, at: 1201))   // other keys are independent
+        XCTAssertFalse(drop(5, false, at: 1202))
+        XCTAssertFalse(drop(5, true, at: 1203, window: 0))
+
+        XCTAssertEqual(AppConfig().debounceMs, 0)
+        XCTAssertEqual(try YAMLDecoder().decode(AppConfig.self, from: "debounce_ms: 40\n").debounceMs, 40)
+    }
+
+    func testCapsMouseEventsAreOptIn() throws {
+        XCTAssertFalse(AppConfig().capsMouseEvents)
+        XCTAssertTrue(try YAMLDecoder().decode(AppConfig.self, from: "caps_mouse_events: true\n").capsMouseEvents)
+        let keys = KeyboardHook.eventMask(includeMouse: false)
+        XCTAssertEqual(keys & (1 << CGEventType.leftMouseDown.rawValue), 0)
+        XCTAssertNotEqual(KeyboardHook.eventMask(includeMouse: true) & (1 << CGEventType.rightMouseDown.rawValue), 0)
+        XCTAssertEqual(KeyboardHook.eventMask(includeMouse: true) & keys, keys)
+        XCTAssertEqual(CapsMouseButton(.otherMouseDown), .other)
+        XCTAssertNil(CapsMouseButton(.keyDown))
+    }
+
+    func testInjectDelayDefaultsOffAndDecodes() throws {
+        XCTAssertEqual(AppConfig().injectDelayMs, 0)
+        let config = try YAMLDecoder().decode(AppConfig.self, from: "inject_delay_ms: 5\n")
+        XCTAssertEqual(config.injectDelayMs, 5)
+        let roundTrip = try YAMLDecoder().decode(AppConfig.self, from: try YAMLEncoder().encode(config))
+        XCTAssertEqual(roundTrip.injectDelayMs, 5)
+
+        // A queued sequence's pauses stop at its budget; off the queue there are none.
+        XCTAssertEqual(PostQueue.allowedPause(EngineConstants.maxInjectDelayMs), 0)
+        let granted = OSAllocatedUnfairLock(initialState: 0)
+        PostQueue.enqueue {
+            let total = (0..<200).reduce(0) { sum, _ in sum + PostQueue.allowedPause(EngineConstants.maxInjectDelayMs) }
+            granted.withLock { $0 = total }
+        }
+        PostQueue.drain()
+        XCTAssertEqual(granted.withLock { $0 }, EngineConstants.maxInjectPausePerSequenceMs)
+
+        // A go-to's wait for its field is a budgeted pause too.
+        XCTAssertEqual(PostQueue.pause(ActionExecutor.goToFieldDelayMs), 0, "off the queue: no sleep")
+        let slept = OSAllocatedUnfairLock<[Int]>(initialState: [])
+        PostQueue.enqueue {
+            let first = PostQueue.pause(ActionExecutor.goToFieldDelayMs)
+            let second = PostQueue.pause(ActionExecutor.goToFieldDelayMs)
+            slept.withLock { $0 = [first, second] }
+        }
+        PostQueue.drain()
+        XCTAssertEqual(slept.withLock { $0 }, [ActionExecutor.goToFieldDelayMs,
+                                               EngineConstants.maxInjectPausePerSequenceMs - ActionExecutor.goToFieldDelayMs])
+    }
+
+    func testLastOtherAppSkipsOurselvesAndStaysNilUntilAnotherApp() {
+        let own = "me.xueshi.hypercapslock"
+        let us = FrontmostApp(name: "HyperCapslock", bundleID: own)
+        let terminal = FrontmostApp(name: "Terminal", bundleID: "com.apple.Terminal")
+        let bundleless = FrontmostApp(name: "helper", bundleID: nil)
+        // Nothing but ourselves (or an app with no bundle id) seen yet: nothing to offer.
+        XCTAssertNil(FrontmostAppTracker.lastOtherApp(after: us, previous: nil, ownBundleID: own))
+        XCTAssertNil(FrontmostAppTracker.lastOtherApp(after: bundleless, previous: nil, ownBundleID: own))
+        XCTAssertEqual(FrontmostAppTracker.lastOtherApp(after: terminal, previous: nil, ownBundleID: own), terminal)
+        XCTAssertEqual(FrontmostAppTracker.lastOtherApp(after: us, previous: terminal, ownBundleID: own), terminal)
+        XCTAssertEqual(FrontmostAppTracker.lastOtherApp(after: bundleless, previous: terminal, ownBundleID: own), terminal)
+    }
+
+    func testExcludedAppsDedupeAndRemoveIgnoringCase() {
+        var apps = AppState.excludedApps([], adding: "com.valvesoftware.Steam")
+        apps = AppState.excludedApps(apps, adding: "com.apple.Terminal")
+        XCTAssertEqual(AppState.excludedApps(apps, adding: "COM.VALVESOFTWARE.STEAM"), apps)
+        XCTAssertEqual(AppState.excludedApps(apps, removing: "com.valvesoftware.steam"), ["com.apple.Terminal"])
+        XCTAssertEqual(AppState.excludedApps(apps, removing: "com.example.none"), apps)
+
+        defer { EngineState.shared.setExcludedBundleIDs(ConfigStore.shared.appConfig.excludedApps) }
+        EngineState.shared.setExcludedBundleIDs(apps)
+        XCTAssertTrue(EngineState.shared.isExcluded("com.apple.terminal"))
+        XCTAssertFalse(EngineState.shared.isExcluded(nil))
+    }
+
+    func testPostQueueRunsSequencesInOrderAndDrains() {
+        let log = OSAllocatedUnfairLock<[Int]>(initialState: [])
+        for i in 0..<5 {
+            PostQueue.enqueue {
+                if i == 0 { Thread.sleep(forTimeInterval: 0.05) }
+                log.withLock { $0.append(i) }
+            }
+        }
+        PostQueue.drain()
+        XCTAssertEqual(log.withLock { $0 }, [0, 1, 2, 3, 4])
+        PostQueue.drain()  // idle: returns at once
+    }
+
+    /// A key typed while a sequence is posting is queued behind it, not waited
+    /// for: the tap callback returns at once however long the queue is busy.
+    func testTapDefersKeysBehindABusyPostQueueWithoutWaiting() throws {
+        let event = try XCTUnwrap(CGEvent(keyboardEventSource: nil, virtualKey: 4, keyDown: true))
+        XCTAssertFalse(KeyboardHook.deferBehindPostQueue(event) { _ in XCTFail("idle queue: nothing to defer") })
+
+        let busy = DispatchSemaphore(value: 0)
+        PostQueue.enqueue { busy.wait() }
+        let reposted = OSAllocatedUnfairLock<[Int64]>(initialState: [])
+        let start = Date()
+        XCTAssertTrue(KeyboardHook.deferBehindPostQueue(event) { copy in
+            reposted.withLock { $0.append(copy.getIntegerValueField(.keyboardEventKeycode)) }
+        })
+        XCTAssertLessThan(Date().timeIntervalSince(start), 0.05)
+        XCTAssertTrue(reposted.withLock { $0.isEmpty })
+        busy.signal()
+        PostQueue.drain()
+        XCTAssertEqual(reposted.withLock { $0 }, [4])
+        XCTAssertNotEqual(KeyPoster.deferredMagic, KeyPoster.injectedMagic)
+    }
+
+    /// Another process reusing our tag, or our pid posting an untagged event,
+    /// must both be treated as real input.
+    func testInjectedEventsNeedOurTagAndPid() {
+        let magic = KeyPoster.injectedMagic
+        XCTAssertEqual(magic >> 32, 0x4756_4C4E)
+        XCTAssertTrue(KeyPoster.isOwn(userData: magic, sourcePID: 42, magic: magic, ownPID: 42))
+        XCTAssertFalse(KeyPoster.isOwn(userData: magic, sourcePID: 43, magic: magic, ownPID: 42))
+        XCTAssertFalse(KeyPoster.isOwn(userData: 0x4756_4C4E, sourcePID: 42, magic: magic, ownPID: 42))
+        XCTAssertFalse(KeyPoster.isOwn(userData: 0, sourcePID: 42, magic: magic, ownPID: 42))
+    }
+
+    func testMappingInputSourceIDFindsTheSwitchTarget() {
+        let abc = ActionMappingEntry(trigger: .hyperPlusKey(key: 188, withShift: false),
+                                     inlineAction: .inputSource(inputSourceID: "com.apple.keylayout.ABC"))
+        XCTAssertEqual(mappingInputSourceID(abc), "com.apple.keylayout.ABC")
+        let move = ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left")
+        XCTAssertNil(mappingInputSourceID(move))
+    }
+
+    func testInputSourceAvailabilityIsClassifiedOutsideValidation() {
+        let enabled: Set<String> = ["com.apple.keylayout.ABC"]
+        let installed: Set<String> = ["com.apple.keylayout.ABC", "com.apple.keylayout.German"]
+        func classify(_ id: String) -> InputSourceFix.SourceAvailability {
+            InputSourceFix.availability(ofSourceID: id, enabledIDs: enabled, isInstalled: installed.contains)
+        }
+        XCTAssertEqual(classify("com.apple.keylayout.ABC"), .available)
+        XCTAssertEqual(classify("com.apple.keylayout.German"), .installedNotEnabled)
+        XCTAssertEqual(classify("com.apple.keylayout.Nope"), .missing)
+        // Saving stays machine-independent: only an empty id is invalid.
+        XCTAssertNoThrow(try ConfigStore.validate(.inputSource(inputSourceID: "com.apple.keylayout.Nope")))
+        XCTAssertThrowsError(try ConfigStore.validate(.inputSource(inputSourceID: " ")))
+    }
+
+    func testNotifyActionDecodesAndValidates() throws {
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: "kind: notify\ntitle: Focus\n"),
+                       .notify(title: "Focus", body: ""))
+        XCTAssertThrowsError(try ConfigStore.validate(.notify(title: " ", body: "")))
+        XCTAssertNoThrow(try ConfigStore.validate(.notify(title: "", body: "done")))
+        XCTAssertFalse(ActionExecutor.allowShiftFallback(.notify(title: "a", body: "b")))
+    }
+
+    func testScriptActionInvocationAndValidation() throws {
+        let source = "display notification \"it's done\"\nbeep"
+        let apple = ActionExecutor.scriptInvocation(.applescript, source)
+        XCTAssertEqual(apple.executable, "/usr/bin/osascript")
+        XCTAssertEqual(apple.arguments, ["-e", source], "the source is one argument, never re-quoted")
+        XCTAssertEqual(ActionExecutor.scriptInvocation(.shell, "echo hi").arguments, ["-c", "echo hi"])
+        XCTAssertThrowsError(try ConfigStore.validate(.script(language: .applescript, source: " \n ")))
+        XCTAssertFalse(ActionExecutor.allowShiftFallback(.script(language: .shell, source: "true")))
+        XCTAssertThrowsError(try YAMLDecoder().decode(ActionConfig.self, from: "kind: script\nlang: powershell\nsource: x\n"))
+    }
+
+    func testWrapPairWireFormat() throws {
+        let quotes = ActionConfig.wrapPair(open: "\"", close: "\"", innerSpaces: 0)
+        let yaml = try YAMLEncoder().encode(quotes)
+        XCTAssertTrue(yaml.contains("kind: wrap_pair"))
+        XCTAssertFalse(yaml.contains("inner_spaces"))
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: yaml), quotes)
+
+        let braces = ActionConfig.wrapPair(open: "{", close: "}", innerSpaces: 1)
+        XCTAssertEqual(try YAMLDecoder().decode(ActionConfig.self, from: try YAMLEncoder().encode(braces)), braces)
+    }
+
+    func testWrapPairCursorMath() {
+        // The built-in InsertQuotes: six quotes, caret back between the triples.
+        let quotes = ActionExecutor.wrapPairPlan(open: "\"\"\"", close: "\"\"\"", innerSpaces: 0)
+        XCTAssertEqual(quotes.text, "\"\"\"\"\"\"")
+        XCTAssertEqual(quotes.leftArrows, 3)
+
+        let braces = ActionExecutor.wrapPairPlan(open: "{", close: "}", innerSpaces: 1)
+        XCTAssertEqual(braces.text, "{  }")
+        XCTAssertEqual(braces.leftArrows, 2)
+
+        let guillemets = ActionExecutor.wrapPairPlan(open: "« ", close: " »", innerSpaces: 0)
+        XCTAssertEqual(guillemets.leftArrows, 2)
+    }
+
     func testCustomActionRoundTrip() throws {
         let action = Action(id: "ABC-123", name: "Open Calc", config: .command("open -a Calculator"), isBuiltin: false)
         let yaml = try YAMLEncoder().encode([action])
@@ -81,6 +1277,8 @@ final class HyperCapslockTests: XCTestCase {
         XCTAssertEqual(BuiltinActions.byID("builtin.move_left")?.config, .directional(.left))
         XCTAssertEqual(BuiltinActions.byID("builtin.jump_up_10")?.config, .jump(direction: .up, count: 10))
         XCTAssertEqual(BuiltinActions.byID("builtin.insert_quotes")?.config, .independent(.insertQuotes))
+        XCTAssertEqual(BuiltinActions.byID("builtin.line_start")?.config, .directional(.home))
+        XCTAssertEqual(BuiltinActions.byID("builtin.doc_end")?.config, .directional(.docEnd))
         XCTAssertTrue(BuiltinActions.isBuiltinID("builtin.move_left"))
         XCTAssertFalse(BuiltinActions.isBuiltinID("ABC-123"))
     }
@@ -124,6 +1322,60 @@ final class HyperCapslockTests: XCTestCase {
         if case .inputSource = abc?.inlineAction {} else { XCTFail("ABC default should be inline input_source") }
     }
 
+    func testDefaultTableKeysAreNamedAndSlotsUnique() {
+        var slots = Set<String>()
+        let keys = ConfigStore.defaultBuiltinTable.map { ($0.key, $0.withShift) }
+            + ConfigStore.defaultInlineTable.map { ($0.key, $0.withShift) }
+        for (key, shift) in keys {
+            XCTAssertNotEqual(KeyCodes.name(key), "Key\(key)", "default key \(key) has no display name")
+            XCTAssertTrue(slots.insert("\(key)/\(shift)").inserted, "duplicate default slot \(key) shift=\(shift)")
+        }
+        for row in ConfigStore.defaultBuiltinTable {
+            XCTAssertTrue(BuiltinActions.isBuiltinID(row.actionId), "\(row.actionId) is not a built-in")
+        }
+        XCTAssertEqual(ConfigStore.defaultMappings().count, keys.count)
+    }
+
+    func testDefaultMappingLookupForReset() {
+        XCTAssertEqual(ConfigStore.defaultMapping(for: .hyperPlusKey(key: 75, withShift: false))?.actionId, "builtin.move_up")
+        // No default for this slot → a reset removes the mapping.
+        XCTAssertNil(ConfigStore.defaultMapping(for: .hyperPlusKey(key: 75, withShift: true)))
+        XCTAssertNil(ConfigStore.defaultMapping(for: .doubleTapHyper))
+    }
+
+    func testOpenLineAboveIsBuiltinOnCapsShiftO() throws {
+        XCTAssertEqual(BuiltinActions.byID("builtin.open_line_above")?.config, .independent(.openLineAbove))
+        let entry = ConfigStore.defaultMapping(for: .hyperPlusKey(key: 79, withShift: true))
+        XCTAssertEqual(entry?.actionId, "builtin.open_line_above")
+        let data = try JSONEncoder().encode(ActionConfig.independent(.openLineAbove))
+        XCTAssertTrue(String(decoding: data, as: UTF8.self).contains("open_line_above"))
+    }
+
+    func testCommandPaletteListsRunnableBindingsAndFilters() {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        MappingsRegistry.shared.set([
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 186, withShift: false), actionId: "builtin.show_palette"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 78, withShift: false), actionId: "builtin.noop"),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 80, withShift: false), inlineAction: .command("echo hi")),
+        ])
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        let items = CommandPalette.items(ActionExecutor.effectiveMappings(ctx, passthrough: []), ctx: ctx,
+                                         loc: LocalizationManager.shared)
+        XCTAssertEqual(items.map(\.trigger), [.hyperPlusKey(key: 72, withShift: false), .hyperPlusKey(key: 80, withShift: false)])
+        XCTAssertEqual(items[1].title, "echo hi")
+        XCTAssertEqual(CommandPalette.filter(items, query: "ECHO").map(\.action), [.command("echo hi")])
+        XCTAssertEqual(CommandPalette.filter(items, query: "  ").count, 2)
+        XCTAssertEqual(ConfigStore.defaultMapping(for: .hyperPlusKey(key: 186, withShift: false))?.actionId, "builtin.show_palette")
+    }
+
+    func testPanicReleaseIsBuiltinOnCapsEsc() {
+        XCTAssertEqual(BuiltinActions.byID("builtin.release_modifiers")?.config, .independent(.releaseModifiers))
+        let esc = ConfigStore.defaultMappings().first { $0.trigger == .hyperPlusKey(key: 27, withShift: false) }
+        XCTAssertEqual(esc?.actionId, "builtin.release_modifiers")
+    }
+
     // MARK: Per-app scoped mappings (bindings)
 
     func testBindingsRoundTrip() throws {
@@ -213,6 +1465,347 @@ final class HyperCapslockTests: XCTestCase {
         XCTAssertEqual(ActionExecutor.effectiveAction(scopedOnly, RuntimeContext(frontmostBundleID: "com.apple.Safari")), .directional(.right))
     }
 
+    // MARK: Leader-key sequences
+
+    private let goTop = ActionConfig.keyCombo(targetKey: 38, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: false)
+    private let goEnd = ActionConfig.keyCombo(targetKey: 40, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: false)
+
+    func testSequenceTableDecodesAndRoundTrips() throws {
+        let yaml = """
+        71:
+          71: { kind: key_combo, target_key: 38, with_cmd: true }
+          69: { kind: key_combo, target_key: 40, with_cmd: true }
+        """
+        let table = try YAMLDecoder().decode(SequenceTable.self, from: yaml)
+        XCTAssertTrue(table.isLeader(71))
+        XCTAssertFalse(table.isLeader(69))
+        XCTAssertEqual(table.action(leader: 71, followUp: 71), goTop)
+        XCTAssertEqual(table.action(leader: 71, followUp: 69), goEnd)
+        XCTAssertNil(table.action(leader: 71, followUp: 65))
+
+        let again = try YAMLDecoder().decode(SequenceTable.self, from: try YAMLEncoder().encode(table))
+        XCTAssertEqual(again, table)
+    }
+
+    func testSequenceStateResolution() {
+        let table = SequenceTable(leaders: [71: [71: goTop, 69: goEnd]])
+        var state = SequenceState()
+        XCTAssertEqual(state.resolve(followUp: 71, at: 0, table: table, timeoutMs: 800), .idle)
+
+        state.begin(leader: 71, at: 1_000)
+        XCTAssertEqual(state.resolve(followUp: 69, at: 1_800, table: table, timeoutMs: 800), .matched(goEnd))
+        XCTAssertFalse(state.isPending, "a follow-up always ends the sequence")
+
+        // Unmatched follow-up cancels; the next key is handled normally.
+        state.begin(leader: 71, at: 2_000)
+        XCTAssertEqual(state.resolve(followUp: 65, at: 2_100, table: table, timeoutMs: 800), .unmatched)
+        XCTAssertEqual(state.resolve(followUp: 71, at: 2_200, table: table, timeoutMs: 800), .idle)
+
+        // Past the timeout the leader lapses even if the follow-up would match.
+        state.begin(leader: 71, at: 3_000)
+        XCTAssertEqual(state.resolve(followUp: 71, at: 3_801, table: table, timeoutMs: 800), .expired)
+        XCTAssertFalse(state.isPending)
+    }
+
+    // MARK: Hand-written comments survive a re-render
+
+    func testYamlCommentsRoundTrip() throws {
+        let source = """
+        # my layout
+        # (vim-ish)
+
+        mappings:
+        # screenshot shortcut
+        - trigger: hyper_plus_key
+          key: 83
+          action_id: shot
+        - trigger: hyper_plus_key
+          key: 72
+          action_id: builtin.move_left
+        """
+        guard case .mapping(let map)? = try Yams.compose(yaml: source),
+              case .sequence(let seq)? = map["mappings"] else { return XCTFail("bad fixture") }
+        var comments = YamlComments()
+        comments.header = YamlComments.header(of: source)
+        comments.capture(list: "mappings", seq: seq, lines: source.components(separatedBy: "\n"),
+                         key: { ["s", "h"][$0] })
+        XCTAssertEqual(comments.header, ["# my layout", "# (vim-ish)"])
+        XCTAssertEqual(comments.entries["mappings"], ["s": ["# screenshot shortcut"]])
+
+        // Re-rendered with the entries swapped: the note follows its entry.
+        let rendered = "mappings:\n- trigger: hyper_plus_key\n  key: 72\n- trigger: hyper_plus_key\n  key: 83\n"
+        let out = comments.apply(to: rendered, keys: ["mappings": ["h", "s"]])
+        XCTAssertEqual(out, "# my layout\n# (vim-ish)\n\nmappings:\n- trigger: hyper_plus_key\n  key: 72\n"
+                            + "# screenshot shortcut\n- trigger: hyper_plus_key\n  key: 83\n")
+    }
+
+    // MARK: Mapping YAML snapshots
+
+    /// One inline action per `ActionConfig` kind with its exact rendered block
+    /// (under a single-tap trigger). The switch is exhaustive on purpose: a new
+    /// kind doesn't compile until it has a snapshot here.
+    private static func snapshot(_ action: ActionConfig) -> String {
+        switch action {
+        case .directional: return "    kind: directional\n    action: word_forward\n"
+        case .jump: return "    kind: jump\n    direction: down\n    count: 10\n    unit: page\n"
+        case .independent: return "    kind: independent\n    action: open_line_above\n"
+        case .inputSource: return "    kind: input_source\n    input_source_id: com.apple.keylayout.ABC\n"
+        case .command: return "    kind: command\n    command: open -a Safari\n"
+        case .keyCombo:
+            return "    kind: key_combo\n    target_key: 86\n    with_ctrl: false\n    with_alt: false\n"
+                + "    with_cmd: true\n    with_target_shift: true\n"
+        case .openApp: return "    kind: open_app\n    bundle_id: com.apple.Safari\n    app_name: Safari\n"
+        case .modifierKey: return "    kind: hold_modifier\n    modifier: right_command\n"
+        case .wrapPair: return "    kind: wrap_pair\n    open: (\n    close: )\n    inner_spaces: 1\n"
+        case .appControl: return "    kind: app_control\n    command: toggle_pause\n"
+        case .script: return "    kind: script\n    lang: applescript\n    source: beep\n"
+        case .notify: return "    kind: notify\n    title: Focus\n    body: Mode on\n"
+        case .transformSelection: return "    kind: transform_selection\n    op: uppercase\n"
+        case .goTo:
+            return "    kind: go_to\n    line: 42\n    target_key: 71\n    with_ctrl: true\n    with_alt: false\n"
+                + "    with_cmd: false\n    with_target_shift: false\n"
+        case .insertDateTime: return "    kind: insert_date_time\n    format: week %V of %Y\n"
+        }
+    }
+
+    func testMappingYamlSnapshotsForEveryActionKind() throws {
+        let samples: [ActionConfig] = [
+            .directional(.wordForward),
+            .jump(direction: .down, count: 10, unit: .page),
+            .independent(.openLineAbove),
+            .inputSource(inputSourceID: "com.apple.keylayout.ABC"),
+            .command("open -a Safari"),
+            .keyCombo(targetKey: 86, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: true),
+            .openApp(bundleID: "com.apple.Safari", name: "Safari"),
+            .modifierKey(.rightCommand),
+            .wrapPair(open: "(", close: ")", innerSpaces: 1),
+            .appControl(.togglePause),
+            .script(language: .applescript, source: "beep"),
+            .notify(title: "Focus", body: "Mode on"),
+            .transformSelection(.uppercase),
+            .goTo(line: 42, column: nil, shortcut: GoToShortcut(targetKey: 71, withCtrl: true)),
+            .insertDateTime(format: "week %V of %Y"),
+        ]
+        XCTAssertEqual(Set(samples.map(\.kindTag)).count, samples.count, "one sample per kind")
+        for action in samples {
+            let entries = [ActionMappingEntry(trigger: .singleTapHyper, inlineAction: action)]
+            let yaml = try YAMLEncoder().encode(entries)
+            XCTAssertEqual(yaml, "- trigger:\n    kind: single_tap_hyper\n  action:\n" + Self.snapshot(action), action.kindTag)
+            XCTAssertEqual(try YAMLDecoder().decode([ActionMappingEntry].self, from: yaml), entries, action.kindTag)
+        }
+    }
+
+    /// A label and note are real keys of the entry: written only when set,
+    /// read back as written, and shown by the palette in place of the action.
+    func testMappingLabelAndNoteRoundTrip() throws {
+        let trigger = Trigger.hyperPlusKey(key: 83, withShift: false)
+        let labelled = ActionMappingEntry(trigger: trigger, inlineAction: .command("echo saved"),
+                                          label: "Save", note: "Saves the note,\nthen syncs")
+        let yaml = try YAMLEncoder().encode([labelled])
+        XCTAssertTrue(yaml.contains("  label: Save\n"))
+        XCTAssertEqual(try YAMLDecoder().decode([ActionMappingEntry].self, from: yaml), [labelled])
+        let plain = try YAMLEncoder().encode([ActionMappingEntry(trigger: trigger, actionId: "builtin.move_left")])
+        XCTAssertFalse(plain.contains("label") || plain.contains("note"))
+
+        XCTAssertNil(ConfigStore.trimmedText("  \n"))
+        XCTAssertEqual(ConfigStore.trimmedText(" Save "), "Save")
+
+        let items = CommandPalette.items([ActionExecutor.EffectiveMapping(entry: labelled, action: .command("echo saved"),
+                                                                          viaAppRule: false, passthrough: false)],
+                                         ctx: RuntimeContext(frontmostBundleID: nil), loc: LocalizationManager.shared)
+        XCTAssertEqual(items.map(\.title), ["Save"])
+
+        // Clearing the label in the app drops it from the file; other unknown keys stay.
+        let file = "version: 3\nmappings:\n- trigger:\n    kind: hyper_plus_key\n    key: 83\n    with_shift: false\n"
+            + "  action_id: builtin.move_left\n  label: Save\n  x_custom: kept\n"
+        try withScratchStore(file) { open, saved in
+            let store = open()
+            XCTAssertEqual(store.mappings.first?.label, "Save")
+            try store.upsert(trigger: trigger, actionId: "builtin.move_left", inlineAction: nil)
+            XCTAssertFalse(try saved().contains("label"))
+            XCTAssertTrue(try saved().contains("x_custom: kept"))
+        }
+    }
+
+    /// A rejected import leaves the current file's comments and extra keys alone.
+    func testFailedImportKeepsTheLoadedFilesComments() throws {
+        let file = "# my layout\nversion: 3\nx_top: kept\nmappings:\n# left, vim-style\n- trigger:\n    kind: hyper_plus_key\n"
+            + "    key: 72\n    with_shift: false\n  action_id: builtin.move_left\n"
+        let bad = FileManager.default.temporaryDirectory.appendingPathComponent("\(UUID().uuidString).yml")
+        defer { try? FileManager.default.removeItem(at: bad) }
+        try withScratchStore(file) { open, saved in
+            let store = open()
+            // Parses, but has no mappings; then one with an empty command.
+            try "# other\nversion: 3\nmappings: []\n".write(to: bad, atomically: true, encoding: .utf8)
+            XCTAssertThrowsError(try store.importDocument(from: bad.path))
+            try ("version: 3\nmappings:\n- trigger:\n    kind: hyper_plus_key\n    key: 74\n    with_shift: false\n"
+                 + "  action:\n    kind: command\n    command: ' '\n").write(to: bad, atomically: true, encoding: .utf8)
+            XCTAssertThrowsError(try store.importDocument(from: bad.path))
+
+            try store.upsert(trigger: .hyperPlusKey(key: 75, withShift: false), actionId: "builtin.move_up", inlineAction: nil)
+            let out = try saved()
+            XCTAssertTrue(out.hasPrefix("# my layout\n"), out)
+            XCTAssertTrue(out.contains("# left, vim-style\n"))
+            XCTAssertTrue(out.contains("x_top: kept"))
+            XCTAssertFalse(out.contains("# other"))
+        }
+    }
+
+    /// Runs `body` against `action_mappings.yml` = `yaml` in a scratch dir:
+    /// `open` loads a fresh `ConfigStore` over it (a relaunch), `saved` reads
+    /// the file back. The shared registries a load fills are restored after.
+    private func withScratchStore(_ yaml: String,
+                                  _ body: (_ open: () -> ConfigStore, _ saved: () throws -> String) throws -> Void) throws {
+        let dir = FileManager.default.temporaryDirectory.appendingPathComponent(UUID().uuidString)
+        try FileManager.default.createDirectory(at: dir, withIntermediateDirectories: true)
+        let registry = MappingsRegistry.shared.snapshot()
+        defer {
+            MappingsRegistry.shared.set(registry)
+            ActionsRegistry.shared.setCustom(ConfigStore.shared.customActions)
+            KeySequences.shared.setTable(ConfigStore.shared.sequences)
+            try? FileManager.default.removeItem(at: dir)
+        }
+        let url = dir.appendingPathComponent("action_mappings.yml")
+        try yaml.write(to: url, atomically: true, encoding: .utf8)
+        try body({
+            let store = ConfigStore(appDataDir: dir)
+            store.load()
+            return store
+        }, { try String(contentsOf: url, encoding: .utf8) })
+    }
+
+    /// A switched-off mapping is invisible to the engine but keeps its action in the file.
+    func testDisabledMappingPassesThroughButIsKept() throws {
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        let ctx = RuntimeContext(frontmostBundleID: nil)
+        let off = ActionMappingEntry(trigger: .hyperPlusKey(key: 74, withShift: false), actionId: "builtin.move_down",
+                                     enabled: false)
+        let on = ActionMappingEntry(trigger: .hyperPlusKey(key: 75, withShift: false), actionId: "builtin.move_up")
+        // Switching off a Shift or Ctrl variant doesn't hand the press to the plain chord.
+        let shiftOff = ActionMappingEntry(trigger: .hyperPlusKey(key: 75, withShift: true), actionId: "builtin.doc_start",
+                                          enabled: false)
+        let ctrlOff = ActionMappingEntry(trigger: .hyperPlusKey(key: 75, withShift: false, ctrl: true),
+                                         actionId: "builtin.backspace", enabled: false)
+        MappingsRegistry.shared.set([off, on, shiftOff, ctrlOff])
+        func resolve(_ key: UInt16, shift: Bool = false, _ modifiers: CGEventFlags = []) -> ActionMappingEntry? {
+            ActionExecutor.resolveEntry(jsKeycode: key, shiftHeld: shift, modifiers: modifiers, ctx: ctx,
+                                        shiftLayers: .fallback, passthrough: [])
+        }
+        XCTAssertNil(resolve(74))
+        XCTAssertEqual(resolve(75), on)
+        XCTAssertNil(resolve(75, shift: true))
+        XCTAssertNil(resolve(75, .maskControl))
+        // A switched-off plain chord isn't a Shift fallback target either.
+        XCTAssertNil(resolve(74, shift: true))
+
+        let yaml = try YAMLEncoder().encode([off, on])
+        XCTAssertEqual(yaml.components(separatedBy: "enabled: false").count, 2)
+        XCTAssertFalse(yaml.contains("enabled: true"))
+        XCTAssertEqual(try YAMLDecoder().decode([ActionMappingEntry].self, from: yaml), [off, on])
+
+        let request = try JSONDecoder().decode(ControlRequest.self, from: Data(#"{"token":"t","method":"set_binding_enabled","params":{"key":74,"with_shift":false,"enabled":true}}"#.utf8))
+        XCTAssertEqual(request.method, .setBindingEnabled)
+        XCTAssertEqual(request.params?.enabled, true)
+
+        // Off, relaunch, back on: the next launch sees it on (the saved
+        // `enabled: false` mustn't come back from the loaded file).
+        let file = "version: 3\nmappings:\n- trigger:\n    kind: hyper_plus_key\n    key: 74\n    with_shift: false\n"
+            + "  action_id: builtin.move_down\n"
+        try withScratchStore(file) { open, written in
+            try open().setEnabled(trigger: off.trigger, enabled: false)
+            XCTAssertTrue(try written().contains("enabled: false"))
+            let relaunched = open()
+            XCTAssertEqual(relaunched.mappings.first?.enabled, false)
+            try relaunched.setEnabled(trigger: off.trigger, enabled: true)
+            XCTAssertFalse(try written().contains("enabled"))
+            XCTAssertEqual(open().mappings.first?.enabled, true)
+        }
+    }
+
+    /// Strings the renderer has to quote still come back byte-for-byte.
+    func testMappingYamlRoundTripsAwkwardStrings() throws {
+        let entries = [
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 71, withShift: false),
+                               inlineAction: .command("git commit -m 'it''s done' && say \"ok\"")),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 73, withShift: false),
+                               inlineAction: .inputSource(inputSourceID: "com.example.ime: #1 – 中文/ﾃｽﾄ")),
+            ActionMappingEntry(trigger: .hyperPlusKey(key: 79, withShift: true),
+                               inlineAction: .wrapPair(open: "- [", close: "]: ", innerSpaces: 0)),
+            ActionMappingEntry(trigger: .hotkey(key: 120, ctrl: false, alt: false, cmd: false, shift: false),
+                               inlineAction: .openApp(bundleID: "com.example.app", name: "")),
+        ]
+        let yaml = try YAMLEncoder().encode(entries)
+        XCTAssertEqual(try YAMLDecoder().decode([ActionMappingEntry].self, from: yaml), entries)
+    }
+
+    /// Seeded fuzz of command strings through the same chain a save takes —
+    /// encode, compose, serialize under `mappings:`, re-attach comments — then
+    /// a reload. A command runs in a shell, so it must come back exactly.
+    func testFuzzedCommandsSurviveSaveAndReload() throws {
+        struct SplitMix64: RandomNumberGenerator {
+            var state: UInt64
+            mutating func next() -> UInt64 {
+                state &+= 0x9E37_79B9_7F4A_7C15
+                var z = state
+                z = (z ^ (z >> 30)) &* 0xBF58_476D_1CE4_E5B9
+                z = (z ^ (z >> 27)) &* 0x94D0_49BB_1331_11EB
+                return z ^ (z >> 31)
+            }
+        }
+        let pieces = ["a", "Z", "0", "9", " ", "  ", "\t", "\n", "\r\n", "\r", "'", "''", "\"", "\\", ":", ": ", "#", " #",
+                      "-", "- ", "?", "|", ">", "&", "*", "!", "%", "@", "`", "{", "}", "[", "]", ",", "~", "null",
+                      "true", "1.5", "0x1F", "$(date)", "${HOME}", "中文", "é", "🎹", "\u{00A0}", "\u{2028}"]
+        struct Doc: Decodable { var mappings: [ActionMappingEntry] }
+        var rng = SplitMix64(state: 0x4843_4C)
+        var comments = YamlComments()
+        comments.header = ["# fuzz"]
+        for round in 0..<400 {
+            let command = (0..<Int.random(in: 0...12, using: &rng))
+                .map { _ in pieces.randomElement(using: &rng)! }.joined()
+            let entries = [
+                ActionMappingEntry(trigger: .hyperPlusKey(key: 71, withShift: false), inlineAction: .command(command)),
+                ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left"),
+            ]
+            let keys = entries.map { triggerUniqueID($0.trigger) }
+            comments.entries["mappings"] = [keys[1]: ["# after the fuzzed entry"]]
+            guard let seq = try Yams.compose(yaml: try YAMLEncoder().encode(entries)) else { return XCTFail("compose") }
+            let body = try Yams.serialize(node: .mapping(Node.Mapping([(Node("mappings"), seq)])))
+            let saved = comments.apply(to: body, keys: ["mappings": keys])
+            let reloaded = try YAMLDecoder().decode(Doc.self, from: saved).mappings
+            XCTAssertEqual(reloaded, entries, "round \(round): \(command.debugDescription)\n\(saved)")
+            if reloaded != entries { break }
+        }
+    }
+
+    // MARK: Mapping undo/redo history
+
+    func testMappingsHistoryUndoRedo() {
+        let a = [ActionMappingEntry(trigger: .hyperPlusKey(key: 72, withShift: false), actionId: "builtin.move_left")]
+        let b = a + [ActionMappingEntry(trigger: .hyperPlusKey(key: 76, withShift: false), actionId: "builtin.move_right")]
+        var history = MappingsHistory()
+        XCTAssertNil(history.undo(from: a))
+
+        history.record(a)  // a → b
+        XCTAssertEqual(history.undo(from: b), a)
+        XCTAssertEqual(history.redo(from: a), b)
+        XCTAssertNil(history.redo(from: b))
+
+        // A fresh edit after an undo drops the redo branch.
+        _ = history.undo(from: b)
+        history.record(a)
+        XCTAssertFalse(history.canRedo)
+    }
+
+    func testMappingsHistoryIsBounded() {
+        var history = MappingsHistory(limit: 3)
+        for key in UInt16(65)...UInt16(70) {
+            history.record([ActionMappingEntry(trigger: .hyperPlusKey(key: key, withShift: false), actionId: "builtin.noop")])
+        }
+        XCTAssertEqual(history.undoStack.count, 3)
+        XCTAssertEqual(history.undoStack.first?.first?.trigger, .hyperPlusKey(key: 68, withShift: false))
+    }
+
     // MARK: UI — representative action (a noop-default mapping displays its first
     // meaningful per-app rule instead of "Do Nothing").
 
@@ -257,4 +1850,118 @@ final class HyperCapslockTests: XCTestCase {
             trigger: .hyperPlusKey(key: 70, withShift: false), actionId: "builtin.move_left",
             bindings: [MappingBinding(when: term, actionId: "cust-tmux")])).actionId, "builtin.move_left")
     }
+
+    // MARK: Hot-path latency (opt-in: TEST_RUNNER_HC_BENCH=1 xcodebuild test …)
+
+    /// One representative action per `ActionConfig` kind, a 10-line jump among
+    /// them. Keep one per kind when adding a new one.
+    private static let benchActions: [ActionConfig] = [
+        .directional(.wordForward), .jump(direction: .down, count: 10), .independent(.insertQuotes),
+        .inputSource(inputSourceID: "com.apple.keylayout.ABC"), .command("true"),
+        .keyCombo(targetKey: 86, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: true),
+        .openApp(bundleID: "com.apple.Safari", name: "Safari"), .modifierKey(.rightCommand),
+        .wrapPair(open: "(", close: ")", innerSpaces: 1), .appControl(.togglePause),
+        .script(language: .applescript, source: "beep"), .notify(title: "t", body: "b"),
+        .transformSelection(.uppercase), .goTo(line: nil, column: nil), .insertDateTime(format: "%H:%M"),
+    ]
+
+    /// The benchmarks run 10 × a few thousand iterations; keep them out of
+    /// the everyday suite.
+    private func requireBench() throws {
+        try XCTSkipUnless(ProcessInfo.processInfo.environment["HC_BENCH"] != nil, "set HC_BENCH=1 to run benchmarks")
+    }
+
+    /// What the tap callback does before posting anything on a fresh press:
+    /// resolve every default Caps chord (plain and shifted) under a context.
+    func testBenchChordResolution() throws {
+        try requireBench()
+        let saved = MappingsRegistry.shared.snapshot()
+        defer { MappingsRegistry.shared.set(saved) }
+        MappingsRegistry.shared.set(ConfigStore.defaultMappings())
+        let ctx = RuntimeContext(frontmostBundleID: "com.apple.Safari")
+        let keys = (UInt16(48)...90).map { $0 }
+        measure {
+            for _ in 0..<100 {
+                for key in keys {
+                    _ = ActionExecutor.resolveChord(jsKeycode: key, shiftHeld: false, ctx: ctx, shiftLayers: .fallback, passthrough: [])
+                    _ = ActionExecutor.resolveChord(jsKeycode: key, shiftHeld: true, ctx: ctx, shiftLayers: .fallback, passthrough: [])
+                }
+            }
+        }
+    }
+
+    /// `execute` itself with posting recorded instead of sent, for the actions
+    /// that only type (the rest hand off to other threads or processes).
+    func testBenchExecuteWithRecordingEmitter() throws {
+        try requireBench()
+        let typing: [ActionConfig] = [
+            .directional(.wordForward), .jump(direction: .down, count: 10), .independent(.insertQuotes),
+            .keyCombo(targetKey: 86, withCtrl: false, withAlt: false, withCmd: true, withTargetShift: true),
+            .wrapPair(open: "(", close: ")", innerSpaces: 1),
+        ]
+        measure {
+            let rec = RecordingEmitter()
+            for _ in 0..<200 {
+                for action in typing {
+                    ActionExecutor.execute(action, keyDown: true, activeModifiers: [], emitter: rec)
+                    ActionExecutor.execute(action, keyDown: false, activeModifiers: [], emitter: rec)
+                }
+                rec.reset()
+            }
+            PostQueue.drain()
+        }
+    }
+
+    /// The per-action work a chord does on the tap thread besides posting:
+    /// the log line, the HUD parts, the count / repeat checks and, for
+    /// directional moves, the keystroke itself.
+    func testBenchActionPlanning() throws {
+        try requireBench()
+        measure {
+            for _ in 0..<1000 {
+                for action in Self.benchActions {
+                    _ = describeAction(action)
+                    _ = hudParts(action)
+                    _ = CountPrefix.isCountable(action)
+                    _ = ActionExecutor.refiresOnRepeat(action, .auto)
+                    if case .directional(let a) = action {
+                        _ = ActionExecutor.directionalKeystroke(a, activeModifiers: .maskShift, wordMotion: .word)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Test `KeyEmitter`: keeps what would have been posted, taps expanded into
+/// their down/up pairs. Locked, since sequences arrive on the post queue.
+final class RecordingEmitter: KeyEmitter {
+    enum Event: Equatable {
+        case key(UInt16, down: Bool, flags: CGEventFlags)
+        case text(String)
+    }
+
+    private let log = OSAllocatedUnfairLock<[Event]>(initialState: [])
+
+    var events: [Event] { log.withLock { $0 } }
+    func reset() { log.withLock { $0.removeAll() } }
+
+    func post(_ keycode: UInt16, keyDown: Bool, flags: CGEventFlags) {
+        log.withLock { $0.append(.key(keycode, down: keyDown, flags: flags)) }
+    }
+
+    func postTap(_ keycode: UInt16, flags: CGEventFlags) {
+        postTaps(keycode, count: 1, flags: flags)
+    }
+
+    func postTaps(_ keycode: UInt16, count: Int, flags: CGEventFlags) {
+        for _ in 0..<max(count, 0) {
+            post(keycode, keyDown: true, flags: flags)
+            post(keycode, keyDown: false, flags: flags)
+        }
+    }
+
+    func insertString(_ string: String) {
+        log.withLock { $0.append(.text(string)) }
+    }
 }